@@ -0,0 +1,84 @@
+//! Compares the memory used by building a `Value` for a large response with
+//! streaming the same response into a writer.
+//!
+//! This lives apart from the timing benchmarks, since the counting allocator
+//! slows down every allocation it sees. Run it with
+//! `cargo bench --bench allocations`.
+
+#[macro_use]
+extern crate juniper;
+extern crate serde_json;
+
+mod fixtures;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use juniper::{execute, execute_to_writer, EmptyMutation, RootNode, Variables};
+
+use fixtures::{ListQuery, LIST_QUERY};
+
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+fn raise_peak(live: usize) {
+    let mut peak = PEAK.load(Ordering::Relaxed);
+    while live > peak {
+        match PEAK.compare_exchange_weak(peak, live, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => break,
+            Err(current) => peak = current,
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        raise_peak(LIVE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Print the bytes allocated by a single run, and the peak memory use above
+/// what was live before it
+fn report_allocations<F: FnOnce()>(name: &str, f: F) {
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    f();
+
+    println!(
+        "{}: {} bytes allocated, peak {} bytes",
+        name,
+        ALLOCATED.load(Ordering::Relaxed) - allocated,
+        PEAK.load(Ordering::Relaxed) - live
+    );
+}
+
+fn main() {
+    let schema = RootNode::new(ListQuery, EmptyMutation::<()>::new());
+
+    report_allocations("large_list_value", || {
+        let (value, _) = execute(LIST_QUERY, None, &schema, &Variables::new(), &()).unwrap();
+        serde_json::to_writer(io::sink(), &value).unwrap();
+    });
+
+    report_allocations("large_list_streamed", || {
+        execute_to_writer(LIST_QUERY, None, &schema, &Variables::new(), &(), io::sink())
+            .unwrap()
+            .unwrap();
+    });
+}
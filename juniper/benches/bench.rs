@@ -1,12 +1,20 @@
 #[macro_use]
 extern crate bencher;
+#[macro_use]
 extern crate juniper;
+extern crate serde_json;
+
+mod fixtures;
+
+use std::io;
 
 use bencher::Bencher;
 
-use juniper::{execute, EmptyMutation, RootNode, Variables};
+use juniper::{execute, execute_to_writer, EmptyMutation, RootNode, Variables};
 use juniper::tests::model::Database;
 
+use fixtures::{ListQuery, LIST_QUERY};
+
 fn query_type_name(b: &mut Bencher) {
     let database = Database::new();
     let schema = RootNode::new(&database, EmptyMutation::<Database>::new());
@@ -124,5 +132,28 @@ fn introspection_query(b: &mut Bencher) {
     b.iter(|| execute(doc, None, &schema, &Variables::new(), &database));
 }
 
-benchmark_group!(queries, query_type_name, introspection_query);
+fn large_list_value(b: &mut Bencher) {
+    let schema = RootNode::new(ListQuery, EmptyMutation::<()>::new());
+    b.iter(|| {
+        let (value, _) = execute(LIST_QUERY, None, &schema, &Variables::new(), &()).unwrap();
+        serde_json::to_writer(io::sink(), &value).unwrap();
+    });
+}
+
+fn large_list_streamed(b: &mut Bencher) {
+    let schema = RootNode::new(ListQuery, EmptyMutation::<()>::new());
+    b.iter(|| {
+        execute_to_writer(LIST_QUERY, None, &schema, &Variables::new(), &(), io::sink())
+            .unwrap()
+            .unwrap();
+    });
+}
+
+benchmark_group!(
+    queries,
+    query_type_name,
+    introspection_query,
+    large_list_value,
+    large_list_streamed
+);
 benchmark_main!(queries);
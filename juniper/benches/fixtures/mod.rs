@@ -0,0 +1,32 @@
+//! A schema with a large list, shared by the timing and allocation
+//! benchmarks.
+
+pub struct ListQuery;
+
+pub struct Row {
+    id: i32,
+}
+
+graphql_object!(Row: () |&self| {
+    field id() -> i32 {
+        self.id
+    }
+
+    field name() -> String {
+        format!("Row number {}", self.id)
+    }
+
+    field tags() -> Vec<&str> {
+        vec!["first", "second", "third"]
+    }
+});
+
+graphql_object!(ListQuery: () |&self| {
+    // Nullable, so that a failing row only nulls itself and the streamed
+    // response never has to hold more than one row in memory
+    field rows(count: i32) -> Option<Vec<Option<Row>>> {
+        Some((0..count).map(|id| Some(Row { id: id })).collect())
+    }
+});
+
+pub const LIST_QUERY: &str = "{ rows(count: 10000) { id name tags } }";
@@ -6,7 +6,9 @@ use std::slice;
 
 use ordermap::OrderMap;
 
-use executor::Variables;
+use executor::{FieldError, Variables};
+use types::scalars::Upload;
+use value::Value;
 use parser::Spanning;
 
 /// A type literal in the syntax tree
@@ -35,6 +37,10 @@ pub enum Type<'a> {
 ///
 /// Lists and objects variants are _spanned_, i.e. they contain a reference to
 /// their position in the source file, if available.
+///
+/// Uploaded files only occur in variables, where they are put by
+/// `GraphQLRequest::from_multipart_body` or by the caller. They can't be
+/// written in a query or deserialized from JSON.
 #[derive(Clone, PartialEq, Debug)]
 #[allow(missing_docs)]
 pub enum InputValue {
@@ -47,6 +53,7 @@ pub enum InputValue {
     Variable(String),
     List(Vec<Spanning<InputValue>>),
     Object(Vec<(Spanning<String>, Spanning<InputValue>)>),
+    Upload(Upload),
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -110,19 +117,25 @@ pub enum Selection<'a> {
     InlineFragment(Spanning<InlineFragment<'a>>),
 }
 
+/// A directive applied to a selection or definition, e.g. `@skip(if: $flag)`
 #[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
 pub struct Directive<'a> {
     pub name: Spanning<&'a str>,
     pub arguments: Option<Spanning<Arguments<'a>>>,
 }
 
+/// The kind of an operation
 #[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
 pub enum OperationType {
     Query,
     Mutation,
 }
 
+/// A query or mutation in a document
 #[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
 pub struct Operation<'a> {
     pub operation_type: OperationType,
     pub name: Option<Spanning<&'a str>>,
@@ -131,7 +144,9 @@ pub struct Operation<'a> {
     pub selection_set: Vec<Selection<'a>>,
 }
 
+/// A named fragment definition in a document
 #[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
 pub struct Fragment<'a> {
     pub name: Spanning<&'a str>,
     pub type_condition: Spanning<&'a str>,
@@ -139,12 +154,15 @@ pub struct Fragment<'a> {
     pub selection_set: Vec<Selection<'a>>,
 }
 
+/// A top-level definition in a document
 #[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
 pub enum Definition<'a> {
     Operation(Spanning<Operation<'a>>),
     Fragment(Spanning<Fragment<'a>>),
 }
 
+/// A parsed query document
 pub type Document<'a> = Vec<Definition<'a>>;
 
 /// Parse an unstructured input value into a Rust data type.
@@ -156,6 +174,95 @@ pub type Document<'a> = Vec<Definition<'a>>;
 pub trait FromInputValue: Sized {
     /// Performs the conversion.
     fn from_input_value(v: &InputValue) -> Option<Self>;
+
+    /// Performs the conversion, describing why it failed.
+    ///
+    /// The default implementation defers to `from_input_value` and reports
+    /// that a value of this type, by its Rust name, was expected. Containers
+    /// and the convenience macros override this to use the GraphQL name and
+    /// to point out where the offending value is located.
+    fn try_from_input_value(v: &InputValue) -> Result<Self, InputValueError> {
+        Self::from_input_value(v)
+            .ok_or_else(|| InputValueError::expected(short_type_name::<Self>(), v))
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum InputValuePathSegment {
+    Field(String),
+    Element(usize),
+}
+
+/// The name of a Rust type without its module path and type parameters,
+/// e.g. `DogCommand`
+fn short_type_name<T>() -> &'static str {
+    let name = ::std::any::type_name::<T>();
+    let end = name.find('<').unwrap_or(name.len());
+
+    name[..end].rsplit("::").next().unwrap_or(name)
+}
+
+/// Error produced when an input value can not be converted into a Rust type
+///
+/// Carries the path to the offending value inside of lists and input
+/// objects, e.g. `In field "tags": In element #1: Expected "String", found 3`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct InputValueError {
+    path: Vec<InputValuePathSegment>,
+    message: String,
+}
+
+impl InputValueError {
+    /// Construct a new error with the given message
+    pub fn new<T: Into<String>>(message: T) -> InputValueError {
+        InputValueError {
+            path: Vec::new(),
+            message: message.into(),
+        }
+    }
+
+    /// Construct an error saying that a value of the named type was expected
+    pub fn expected(type_name: &str, found: &InputValue) -> InputValueError {
+        InputValueError::new(format!(r#"Expected "{}", found {}"#, type_name, found))
+    }
+
+    /// Mark the error as having occurred inside the named input object field
+    pub fn in_field(mut self, name: &str) -> InputValueError {
+        self.path.insert(0, InputValuePathSegment::Field(name.to_owned()));
+        self
+    }
+
+    /// Mark the error as having occurred inside the list element at `index`
+    pub fn in_element(mut self, index: usize) -> InputValueError {
+        self.path.insert(0, InputValuePathSegment::Element(index));
+        self
+    }
+
+    /// The message describing the error, without the path
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    #[doc(hidden)]
+    pub fn into_argument_error(self, arg_name: &str) -> FieldError {
+        FieldError::new(
+            format!(r#"Invalid value for argument "{}": {}"#, arg_name, self),
+            Value::null(),
+        )
+    }
+}
+
+impl fmt::Display for InputValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for segment in &self.path {
+            try!(match *segment {
+                InputValuePathSegment::Field(ref name) => write!(f, r#"In field "{}": "#, name),
+                InputValuePathSegment::Element(index) => write!(f, "In element #{}: ", index),
+            });
+        }
+
+        write!(f, "{}", self.message)
+    }
 }
 
 /// Losslessly clones a Rust data type into an InputValue.
@@ -192,6 +299,15 @@ impl<'a> Type<'a> {
             _ => false,
         }
     }
+
+    /// Make the outermost type non-null
+    pub fn into_non_null(self) -> Type<'a> {
+        match self {
+            Type::Named(n) => Type::NonNullNamed(n),
+            Type::List(l) => Type::NonNullList(l),
+            t => t,
+        }
+    }
 }
 
 impl<'a> fmt::Display for Type<'a> {
@@ -241,6 +357,11 @@ impl InputValue {
         InputValue::Variable(v.as_ref().to_owned())
     }
 
+    /// Construct an uploaded file value.
+    pub fn upload(u: Upload) -> InputValue {
+        InputValue::Upload(u)
+    }
+
     /// Construct an unlocated list.
     ///
     /// Convenience function to make each `InputValue` in the input vector
@@ -306,6 +427,14 @@ impl InputValue {
         <T as FromInputValue>::from_input_value(self)
     }
 
+    /// Shorthand form of invoking `FromInputValue::try_from_input_value()`.
+    pub fn try_convert<T>(&self) -> Result<T, InputValueError>
+    where
+        T: FromInputValue,
+    {
+        <T as FromInputValue>::try_from_input_value(self)
+    }
+
     /// Does the value represent null?
     pub fn is_null(&self) -> bool {
         match *self {
@@ -406,6 +535,7 @@ impl InputValue {
             (&Enum(ref s1), &Enum(ref s2)) |
             (&Variable(ref s1), &Variable(ref s2)) => s1 == s2,
             (&Boolean(b1), &Boolean(b2)) => b1 == b2,
+            (&Upload(ref u1), &Upload(ref u2)) => u1 == u2,
             (&List(ref l1), &List(ref l2)) => l1.iter()
                 .zip(l2.iter())
                 .all(|(v1, v2)| v1.item.unlocated_eq(&v2.item)),
@@ -421,6 +551,18 @@ impl InputValue {
     }
 }
 
+impl FromInputValue for InputValue {
+    fn from_input_value(v: &InputValue) -> Option<InputValue> {
+        Some(v.clone())
+    }
+}
+
+impl ToInputValue for InputValue {
+    fn to_input_value(&self) -> InputValue {
+        self.clone()
+    }
+}
+
 impl fmt::Display for InputValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -431,6 +573,7 @@ impl fmt::Display for InputValue {
             InputValue::Boolean(v) => write!(f, "{}", v),
             InputValue::Enum(ref v) => write!(f, "{}", v),
             InputValue::Variable(ref v) => write!(f, "${}", v),
+            InputValue::Upload(_) => write!(f, "<upload>"),
             InputValue::List(ref v) => {
                 try!(write!(f, "["));
 
@@ -0,0 +1,434 @@
+//! Builders for GraphQL queries
+//!
+//! Instead of formatting query strings by hand, operations can be put
+//! together from fields, fragments, and `InputValue` arguments. The builders
+//! produce a syntax tree that is printed as valid GraphQL, with strings
+//! escaped properly, or executed directly against a schema.
+//!
+//! ```rust
+//! # use juniper::InputValue;
+//! use juniper::builder::{FieldBuilder, FragmentBuilder, InlineFragmentBuilder, OperationBuilder};
+//!
+//! let query = OperationBuilder::query()
+//!     .name("Hero")
+//!     .variable("episode", "Episode")
+//!     .field(
+//!         FieldBuilder::new("hero")
+//!             .argument("episode", InputValue::variable("episode"))
+//!             .field("name")
+//!             .spread("Appearance")
+//!             .inline_fragment(InlineFragmentBuilder::on("Droid").field("primaryFunction")),
+//!     )
+//!     .field(
+//!         FieldBuilder::new("human")
+//!             .alias("luke")
+//!             .argument("id", "1000")
+//!             .field("name"),
+//!     )
+//!     .fragment(FragmentBuilder::new("Appearance", "Character").field("appearsIn"));
+//!
+//! assert_eq!(query.to_string(), r#"query Hero($episode: Episode) {
+//!   hero(episode: $episode) {
+//!     name
+//!     ...Appearance
+//!     ... on Droid {
+//!       primaryFunction
+//!     }
+//!   }
+//!   luke: human(id: "1000") {
+//!     name
+//!   }
+//! }
+//!
+//! fragment Appearance on Character {
+//!   appearsIn
+//! }
+//! "#);
+//! ```
+
+use std::fmt;
+
+use ast::{Arguments, Definition, Directive, Document, Field, Fragment, FragmentSpread,
+          InlineFragment, InputValue, Operation, OperationType, Selection, ToInputValue,
+          VariableDefinition, VariableDefinitions};
+use executor::{ExecutionError, Variables};
+use parser::{parse_type_source, print_document, Spanning};
+use schema::model::RootNode;
+use types::base::GraphQLType;
+use types::name::Name;
+use value::Value;
+use {execute_document, GraphQLError};
+
+/// Builds a query or mutation, and the fragments it uses
+pub struct OperationBuilder<'a> {
+    operation_type: OperationType,
+    name: Option<&'a str>,
+    variables: Vec<(&'a str, VariableDefinition<'a>)>,
+    directives: Vec<Spanning<Directive<'a>>>,
+    selection_set: Vec<Selection<'a>>,
+    fragments: Vec<Definition<'a>>,
+}
+
+/// Builds a field selection
+pub struct FieldBuilder<'a> {
+    alias: Option<&'a str>,
+    name: &'a str,
+    arguments: Vec<(Spanning<&'a str>, Spanning<InputValue>)>,
+    directives: Vec<Spanning<Directive<'a>>>,
+    selection_set: Vec<Selection<'a>>,
+}
+
+/// Builds a named fragment definition
+pub struct FragmentBuilder<'a> {
+    name: &'a str,
+    type_condition: &'a str,
+    directives: Vec<Spanning<Directive<'a>>>,
+    selection_set: Vec<Selection<'a>>,
+}
+
+/// Builds an inline fragment, optionally restricted to a type
+pub struct InlineFragmentBuilder<'a> {
+    type_condition: Option<&'a str>,
+    directives: Vec<Spanning<Directive<'a>>>,
+    selection_set: Vec<Selection<'a>>,
+}
+
+/// Builds a directive, e.g. `@include(if: $withFriends)`
+pub struct DirectiveBuilder<'a> {
+    name: &'a str,
+    arguments: Vec<(Spanning<&'a str>, Spanning<InputValue>)>,
+}
+
+macro_rules! selection_methods {
+    ($builder:ident) => {
+        impl<'a> $builder<'a> {
+            /// Select a field, either a name or a `FieldBuilder`
+            pub fn field<F: Into<FieldBuilder<'a>>>(mut self, field: F) -> Self {
+                self.selection_set.push(Selection::Field(Spanning::unlocated(
+                    field.into().into_field(),
+                )));
+                self
+            }
+
+            /// Spread the named fragment
+            pub fn spread(self, fragment_name: &'a str) -> Self {
+                self.spread_with(fragment_name, Vec::new())
+            }
+
+            /// Spread the named fragment with directives
+            pub fn spread_with(
+                mut self,
+                fragment_name: &'a str,
+                directives: Vec<DirectiveBuilder<'a>>,
+            ) -> Self {
+                self.selection_set.push(Selection::FragmentSpread(Spanning::unlocated(
+                    FragmentSpread {
+                        name: Spanning::unlocated(valid_name(fragment_name)),
+                        directives: into_directives(
+                            directives.into_iter().map(|d| d.into_directive()).collect(),
+                        ),
+                    },
+                )));
+                self
+            }
+
+            /// Select an inline fragment
+            pub fn inline_fragment(mut self, fragment: InlineFragmentBuilder<'a>) -> Self {
+                self.selection_set.push(Selection::InlineFragment(Spanning::unlocated(
+                    InlineFragment {
+                        type_condition: fragment.type_condition.map(Spanning::unlocated),
+                        directives: into_directives(fragment.directives),
+                        selection_set: fragment.selection_set,
+                    },
+                )));
+                self
+            }
+
+            /// Add a directive
+            pub fn directive(mut self, directive: DirectiveBuilder<'a>) -> Self {
+                self.directives.push(directive.into_directive());
+                self
+            }
+        }
+    };
+}
+
+selection_methods!(OperationBuilder);
+selection_methods!(FieldBuilder);
+selection_methods!(FragmentBuilder);
+selection_methods!(InlineFragmentBuilder);
+
+impl<'a> OperationBuilder<'a> {
+    /// Start building a query
+    pub fn query() -> OperationBuilder<'a> {
+        OperationBuilder::new(OperationType::Query)
+    }
+
+    /// Start building a mutation
+    pub fn mutation() -> OperationBuilder<'a> {
+        OperationBuilder::new(OperationType::Mutation)
+    }
+
+    fn new(operation_type: OperationType) -> OperationBuilder<'a> {
+        OperationBuilder {
+            operation_type: operation_type,
+            name: None,
+            variables: Vec::new(),
+            directives: Vec::new(),
+            selection_set: Vec::new(),
+            fragments: Vec::new(),
+        }
+    }
+
+    /// Set the name of the operation
+    pub fn name(mut self, name: &'a str) -> Self {
+        self.name = Some(valid_name(name));
+        self
+    }
+
+    /// Declare a variable with a type literal, e.g. `"[ID!]!"`
+    ///
+    /// Panics if the type literal can't be parsed.
+    pub fn variable(self, name: &'a str, type_literal: &'a str) -> Self {
+        self.declare_variable(name, type_literal, None)
+    }
+
+    /// Declare a variable with a default value
+    pub fn variable_with_default<T: ToInputValue>(
+        self,
+        name: &'a str,
+        type_literal: &'a str,
+        default: T,
+    ) -> Self {
+        self.declare_variable(name, type_literal, Some(default.to_input_value()))
+    }
+
+    fn declare_variable(
+        mut self,
+        name: &'a str,
+        type_literal: &'a str,
+        default: Option<InputValue>,
+    ) -> Self {
+        let var_type = parse_type_source(type_literal)
+            .unwrap_or_else(|e| panic!("Invalid type literal {:?}: {}", type_literal, e.item));
+
+        self.variables.push((
+            valid_name(name),
+            VariableDefinition {
+                var_type: Spanning::unlocated(var_type),
+                default_value: default.map(Spanning::unlocated),
+            },
+        ));
+        self
+    }
+
+    /// Add a fragment definition to the document
+    pub fn fragment(mut self, fragment: FragmentBuilder<'a>) -> Self {
+        self.fragments.push(Definition::Fragment(Spanning::unlocated(Fragment {
+            name: Spanning::unlocated(fragment.name),
+            type_condition: Spanning::unlocated(fragment.type_condition),
+            directives: into_directives(fragment.directives),
+            selection_set: fragment.selection_set,
+        })));
+        self
+    }
+
+    /// The document containing the operation followed by its fragments
+    #[doc(hidden)]
+    pub fn to_document(&self) -> Document<'a> {
+        let variable_definitions = if self.variables.is_empty() {
+            None
+        } else {
+            Some(Spanning::unlocated(VariableDefinitions {
+                items: self.variables
+                    .iter()
+                    .map(|&(name, ref def)| (Spanning::unlocated(name), def.clone()))
+                    .collect(),
+            }))
+        };
+
+        let mut document = vec![
+            Definition::Operation(Spanning::unlocated(Operation {
+                operation_type: self.operation_type.clone(),
+                name: self.name.map(Spanning::unlocated),
+                variable_definitions: variable_definitions,
+                directives: into_directives(self.directives.clone()),
+                selection_set: self.selection_set.clone(),
+            })),
+        ];
+        document.extend(self.fragments.iter().cloned());
+        document
+    }
+
+    /// Validate and execute the operation in a provided schema
+    ///
+    /// This is equivalent to calling `juniper::execute` with the printed
+    /// operation.
+    pub fn execute<CtxT, QueryT, MutationT>(
+        &self,
+        root_node: &RootNode<QueryT, MutationT>,
+        variables: &Variables,
+        context: &CtxT,
+    ) -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
+    where
+        QueryT: GraphQLType<Context = CtxT>,
+        MutationT: GraphQLType<Context = CtxT>,
+    {
+        execute_document(self.to_document(), None, root_node, variables, context)
+    }
+}
+
+impl<'a> fmt::Display for OperationBuilder<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&print_document(&self.to_document()))
+    }
+}
+
+impl<'a> FieldBuilder<'a> {
+    /// Start building a field selection
+    pub fn new(name: &'a str) -> FieldBuilder<'a> {
+        FieldBuilder {
+            alias: None,
+            name: valid_name(name),
+            arguments: Vec::new(),
+            directives: Vec::new(),
+            selection_set: Vec::new(),
+        }
+    }
+
+    /// Set the response name of the field
+    pub fn alias(mut self, alias: &'a str) -> Self {
+        self.alias = Some(valid_name(alias));
+        self
+    }
+
+    /// Pass an argument to the field
+    ///
+    /// Use `InputValue::variable` to pass a variable, and
+    /// `InputValue::enum_value` for enum values.
+    pub fn argument<T: ToInputValue>(mut self, name: &'a str, value: T) -> Self {
+        self.arguments.push(argument(name, value));
+        self
+    }
+
+    fn into_field(self) -> Field<'a> {
+        Field {
+            alias: self.alias.map(Spanning::unlocated),
+            name: Spanning::unlocated(self.name),
+            arguments: into_arguments(self.arguments),
+            directives: into_directives(self.directives),
+            selection_set: if self.selection_set.is_empty() {
+                None
+            } else {
+                Some(self.selection_set)
+            },
+        }
+    }
+}
+
+impl<'a> From<&'a str> for FieldBuilder<'a> {
+    fn from(name: &'a str) -> FieldBuilder<'a> {
+        FieldBuilder::new(name)
+    }
+}
+
+impl<'a> FragmentBuilder<'a> {
+    /// Start building a fragment on the given type
+    pub fn new(name: &'a str, type_condition: &'a str) -> FragmentBuilder<'a> {
+        FragmentBuilder {
+            name: valid_name(name),
+            type_condition: valid_name(type_condition),
+            directives: Vec::new(),
+            selection_set: Vec::new(),
+        }
+    }
+}
+
+impl<'a> InlineFragmentBuilder<'a> {
+    /// Start building an inline fragment without type condition
+    pub fn new() -> InlineFragmentBuilder<'a> {
+        InlineFragmentBuilder {
+            type_condition: None,
+            directives: Vec::new(),
+            selection_set: Vec::new(),
+        }
+    }
+
+    /// Start building an inline fragment on the given type
+    pub fn on(type_condition: &'a str) -> InlineFragmentBuilder<'a> {
+        InlineFragmentBuilder {
+            type_condition: Some(valid_name(type_condition)),
+            ..InlineFragmentBuilder::new()
+        }
+    }
+}
+
+impl<'a> DirectiveBuilder<'a> {
+    /// Start building a directive
+    pub fn new(name: &'a str) -> DirectiveBuilder<'a> {
+        DirectiveBuilder {
+            name: valid_name(name),
+            arguments: Vec::new(),
+        }
+    }
+
+    /// `@include(if: <value>)`
+    pub fn include<T: ToInputValue>(condition: T) -> DirectiveBuilder<'a> {
+        DirectiveBuilder::new("include").argument("if", condition)
+    }
+
+    /// `@skip(if: <value>)`
+    pub fn skip<T: ToInputValue>(condition: T) -> DirectiveBuilder<'a> {
+        DirectiveBuilder::new("skip").argument("if", condition)
+    }
+
+    /// Pass an argument to the directive
+    pub fn argument<T: ToInputValue>(mut self, name: &'a str, value: T) -> Self {
+        self.arguments.push(argument(name, value));
+        self
+    }
+
+    fn into_directive(self) -> Spanning<Directive<'a>> {
+        Spanning::unlocated(Directive {
+            name: Spanning::unlocated(self.name),
+            arguments: into_arguments(self.arguments),
+        })
+    }
+}
+
+fn valid_name(name: &str) -> &str {
+    if !Name::is_valid(name) {
+        panic!("Invalid GraphQL name: {:?}", name);
+    }
+    name
+}
+
+fn argument<'a, T: ToInputValue>(
+    name: &'a str,
+    value: T,
+) -> (Spanning<&'a str>, Spanning<InputValue>) {
+    (
+        Spanning::unlocated(valid_name(name)),
+        Spanning::unlocated(value.to_input_value()),
+    )
+}
+
+fn into_arguments<'a>(
+    items: Vec<(Spanning<&'a str>, Spanning<InputValue>)>,
+) -> Option<Spanning<Arguments<'a>>> {
+    if items.is_empty() {
+        None
+    } else {
+        Some(Spanning::unlocated(Arguments { items: items }))
+    }
+}
+
+fn into_directives<'a>(
+    directives: Vec<Spanning<Directive<'a>>>,
+) -> Option<Vec<Spanning<Directive<'a>>>> {
+    if directives.is_empty() {
+        None
+    } else {
+        Some(directives)
+    }
+}
@@ -1,6 +1,6 @@
 use std::cmp::Ordering;
 use std::fmt::Display;
-use std::borrow::Cow;
+use std::borrow::{Borrow, Cow};
 use std::collections::HashMap;
 use std::sync::RwLock;
 
@@ -15,8 +15,14 @@ use parser::SourcePosition;
 use schema::meta::{Argument, EnumMeta, EnumValue, Field, InputObjectMeta, InterfaceMeta, ListMeta,
                    MetaType, NullableMeta, ObjectMeta, PlaceholderMeta, ScalarMeta, UnionMeta};
 use schema::model::{RootNode, SchemaType, TypeType};
+use schema::validation::SchemaError;
+#[cfg(feature = "json")]
+use stream::may_become_null;
+use stream::ResponseStream;
 
 use types::base::GraphQLType;
+#[cfg(feature = "parallel")]
+use types::base::resolve_selection_set_in_parallel;
 use types::name::Name;
 
 /// A type registry used to build schemas
@@ -27,12 +33,17 @@ use types::name::Name;
 pub struct Registry<'r> {
     /// Currently registered types
     pub types: FnvHashMap<Name, MetaType<'r>>,
+    /// Errors found while registering types
+    #[doc(hidden)]
+    pub schema_errors: Vec<SchemaError>,
+    rust_types: FnvHashMap<Name, &'static str>,
 }
 
 #[derive(Clone)]
 pub enum FieldPath<'a> {
     Root(SourcePosition),
     Field(&'a str, SourcePosition, &'a FieldPath<'a>),
+    Index(usize, &'a FieldPath<'a>),
 }
 
 /// Query execution engine
@@ -51,6 +62,8 @@ where
     context: &'a CtxT,
     errors: &'a RwLock<Vec<ExecutionError>>,
     field_path: FieldPath<'a>,
+    parent_field: Option<(&'a str, &'a str)>,
+    stream: Option<&'a ResponseStream<'a>>,
 }
 
 /// Error type for errors that occur during query execution
@@ -60,10 +73,22 @@ where
 #[derive(Debug, PartialEq)]
 pub struct ExecutionError {
     location: SourcePosition,
-    path: Vec<String>,
+    path: Vec<PathSegment>,
     error: FieldError,
 }
 
+/// A step on the path to a value in the response
+///
+/// Paths are serialized as a list of strings and integers, e.g.
+/// `["users", 37, "email"]`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PathSegment {
+    /// The response key of a field, i.e. its alias or name
+    Field(String),
+    /// The index of an item in a list
+    Index(usize),
+}
+
 impl Eq for ExecutionError {}
 
 impl PartialOrd for ExecutionError {
@@ -277,7 +302,51 @@ impl<'a, CtxT> Executor<'a, CtxT> {
         info: &T::TypeInfo,
         value: &T,
     ) -> ExecutionResult {
-        Ok(value.resolve(info, self.current_selection_set, self))
+        match self.stream {
+            // The caller needs the complete value
+            Some(stream) => {
+                Ok(stream.suspend(|| value.resolve(info, self.current_selection_set, self)))
+            }
+            None => Ok(value.resolve(info, self.current_selection_set, self)),
+        }
+    }
+
+    /// Resolve the value returned by a field resolver
+    ///
+    /// When streaming the response, the value is written to the response
+    /// stream and `null` is returned in its place.
+    #[doc(hidden)]
+    pub fn resolve_field_value_with_ctx<NewCtxT, T: GraphQLType<Context = NewCtxT>>(
+        &self,
+        info: &T::TypeInfo,
+        value: &T,
+    ) -> ExecutionResult
+    where
+        NewCtxT: FromContext<CtxT>,
+    {
+        match self.stream {
+            Some(stream) if stream.is_active() => {
+                let written = self.replaced_context(
+                    <NewCtxT as FromContext<CtxT>>::from(self.context),
+                ).stream(info, value);
+                stream.set_streamed(written);
+                Ok(Value::null())
+            }
+            _ => self.resolve_with_ctx(info, value),
+        }
+    }
+
+    /// Write a single arbitrary value to the response stream
+    ///
+    /// Returns `false` if the value is `null`, see `GraphQLType::stream`.
+    #[doc(hidden)]
+    pub fn stream<T: GraphQLType<Context = CtxT>>(&self, info: &T::TypeInfo, value: &T) -> bool {
+        value.stream(info, self.current_selection_set, self)
+    }
+
+    #[doc(hidden)]
+    pub fn response_stream(&self) -> &'a ResponseStream<'a> {
+        self.stream.expect("Not streaming the response")
     }
 
     /// Resolve a single arbitrary value into a return value
@@ -311,6 +380,8 @@ impl<'a, CtxT> Executor<'a, CtxT> {
             context: ctx,
             errors: self.errors,
             field_path: self.field_path.clone(),
+            parent_field: self.parent_field,
+            stream: self.stream,
         }
     }
 
@@ -322,19 +393,41 @@ impl<'a, CtxT> Executor<'a, CtxT> {
         location: SourcePosition,
         selection_set: Option<&'a [Selection]>,
     ) -> Executor<CtxT> {
+        let parent_type = self.current_type.innermost_concrete();
+
         Executor {
             fragments: self.fragments,
             variables: self.variables,
             current_selection_set: selection_set,
             current_type: self.schema.make_type(
-                &self.current_type
-                    .innermost_concrete()
+                &parent_type
                     .field_by_name(field_name).expect("Field not found on inner type")
                     .field_type),
             schema: self.schema,
             context: self.context,
             errors: self.errors,
             field_path: FieldPath::Field(field_alias, location, &self.field_path),
+            parent_field: parent_type.name().map(|type_name| (type_name, field_name)),
+            stream: self.stream,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn index_sub_executor(&self, index: usize) -> Executor<CtxT> {
+        Executor {
+            fragments: self.fragments,
+            variables: self.variables,
+            current_selection_set: self.current_selection_set,
+            current_type: self.current_type
+                .list_contents()
+                .expect("Current type is not a list type")
+                .clone(),
+            schema: self.schema,
+            context: self.context,
+            errors: self.errors,
+            field_path: FieldPath::Index(index, &self.field_path),
+            parent_field: self.parent_field,
+            stream: self.stream,
         }
     }
 
@@ -356,6 +449,8 @@ impl<'a, CtxT> Executor<'a, CtxT> {
             context: self.context,
             errors: self.errors,
             field_path: self.field_path.clone(),
+            parent_field: self.parent_field,
+            stream: self.stream,
         }
     }
 
@@ -377,13 +472,18 @@ impl<'a, CtxT> Executor<'a, CtxT> {
         &self.current_type
     }
 
+    #[doc(hidden)]
+    pub fn current_selection_set(&self) -> Option<&'a [Selection<'a>]> {
+        self.current_selection_set
+    }
+
     #[doc(hidden)]
     pub fn variables(&self) -> &'a Variables {
         self.variables
     }
 
     #[doc(hidden)]
-    pub fn fragment_by_name(&self, name: &str) -> Option<&'a Fragment> {
+    pub fn fragment_by_name(&self, name: &str) -> Option<&'a Fragment<'a>> {
         self.fragments.get(name).map(|f| *f)
     }
 
@@ -392,6 +492,61 @@ impl<'a, CtxT> Executor<'a, CtxT> {
         self.field_path.location()
     }
 
+    /// Resolve a value in a non-null position, e.g. a non-null field or an
+    /// item of a list of non-null items
+    ///
+    /// Resolving to `null` is an error. Unless resolving the value already
+    /// reported the error that made it `null`, e.g. in a non-null field of a
+    /// nested object, an error saying that the field can't be null is
+    /// returned.
+    #[doc(hidden)]
+    pub fn resolve_non_null<F>(&self, resolve: F) -> ExecutionResult
+    where
+        F: FnOnce() -> ExecutionResult,
+    {
+        let error_count = self.error_count();
+
+        match resolve() {
+            Ok(Value::Null) if !self.has_errors_since(error_count) => Err(self.non_null_error()),
+            result => result,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn non_null_error(&self) -> FieldError {
+        FieldError::new(
+            match self.parent_field {
+                Some((type_name, field_name)) => format!(
+                    "Cannot return null for non-nullable field {}.{}",
+                    type_name,
+                    field_name
+                ),
+                None => "Cannot return null for non-nullable field".to_owned(),
+            },
+            Value::null(),
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn error_count(&self) -> usize {
+        self.errors.read().unwrap().len()
+    }
+
+    /// Whether an error was reported at or below the current path after the
+    /// first `error_count` errors
+    ///
+    /// Errors of fields resolving concurrently in other branches of the
+    /// response don't count.
+    #[doc(hidden)]
+    pub fn has_errors_since(&self, error_count: usize) -> bool {
+        let mut path = Vec::new();
+        self.field_path.construct_path(&mut path);
+
+        self.errors.read().unwrap()[error_count..]
+            .iter()
+            .any(|e| e.path.starts_with(&path))
+    }
+
     /// Add an error to the execution engine at the current executor location
     pub fn push_error(&self, error: FieldError) {
         let location = self.location().clone();
@@ -414,12 +569,16 @@ impl<'a, CtxT> Executor<'a, CtxT> {
 }
 
 impl<'a> FieldPath<'a> {
-    fn construct_path(&self, acc: &mut Vec<String>) {
+    fn construct_path(&self, acc: &mut Vec<PathSegment>) {
         match *self {
             FieldPath::Root(_) => (),
             FieldPath::Field(name, _, parent) => {
                 parent.construct_path(acc);
-                acc.push(name.to_owned());
+                acc.push(PathSegment::Field(name.to_owned()));
+            }
+            FieldPath::Index(index, parent) => {
+                parent.construct_path(acc);
+                acc.push(PathSegment::Index(index));
             }
         }
     }
@@ -427,16 +586,20 @@ impl<'a> FieldPath<'a> {
     fn location(&self) -> &SourcePosition {
         match *self {
             FieldPath::Root(ref pos) | FieldPath::Field(_, ref pos, _) => pos,
+            FieldPath::Index(_, parent) => parent.location(),
         }
     }
 }
 
 impl ExecutionError {
     #[doc(hidden)]
-    pub fn new(location: SourcePosition, path: &[&str], error: FieldError) -> ExecutionError {
+    pub fn new<P>(location: SourcePosition, path: &[P], error: FieldError) -> ExecutionError
+    where
+        P: Clone + Into<PathSegment>,
+    {
         ExecutionError {
             location: location,
-            path: path.iter().map(|s| (*s).to_owned()).collect(),
+            path: path.iter().cloned().map(Into::into).collect(),
             error: error,
         }
     }
@@ -451,12 +614,25 @@ impl ExecutionError {
         &self.location
     }
 
-    /// The path of fields leading to the field that generated this error
-    pub fn path(&self) -> &[String] {
+    /// The path of fields and list indices leading to the value that
+    /// generated this error
+    pub fn path(&self) -> &[PathSegment] {
         &self.path
     }
 }
 
+impl<'a> From<&'a str> for PathSegment {
+    fn from(name: &'a str) -> PathSegment {
+        PathSegment::Field(name.to_owned())
+    }
+}
+
+impl From<usize> for PathSegment {
+    fn from(index: usize) -> PathSegment {
+        PathSegment::Index(index)
+    }
+}
+
 pub fn execute_validated_query<'a, QueryT, MutationT, CtxT>(
     document: Document,
     operation_name: Option<&str>,
@@ -467,6 +643,128 @@ pub fn execute_validated_query<'a, QueryT, MutationT, CtxT>(
 where
     QueryT: GraphQLType<Context = CtxT>,
     MutationT: GraphQLType<Context = CtxT>,
+{
+    execute_operation(
+        document,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        None,
+        |executor, operation_type| match operation_type {
+            OperationType::Query => executor.resolve_into_value(&root_node.query_info, root_node),
+            OperationType::Mutation => {
+                executor.resolve_into_value(&root_node.mutation_info, &root_node.mutation_type)
+            }
+        },
+    )
+}
+
+/// Execute a validated query, resolving the root fields of a query operation
+/// on the thread pool
+///
+/// Mutations are executed one field after the other, as usual.
+#[cfg(feature = "parallel")]
+pub fn execute_validated_query_in_parallel<'a, QueryT, MutationT, CtxT>(
+    document: Document,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT>,
+    variables: &Variables,
+    context: &CtxT,
+) -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
+where
+    QueryT: GraphQLType<Context = CtxT> + Sync,
+    QueryT::TypeInfo: Sync,
+    MutationT: GraphQLType<Context = CtxT> + Sync,
+    MutationT::TypeInfo: Sync,
+    CtxT: Sync,
+{
+    execute_operation(
+        document,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        None,
+        |executor, operation_type| match operation_type {
+            OperationType::Query => resolve_selection_set_in_parallel(
+                root_node,
+                &root_node.query_info,
+                executor
+                    .current_selection_set
+                    .expect("Operation without a selection set"),
+                executor,
+            ),
+            OperationType::Mutation => {
+                executor.resolve_into_value(&root_node.mutation_info, &root_node.mutation_type)
+            }
+        },
+    )
+}
+
+/// Execute a validated query, writing the start of the response up to and
+/// including its `data` to `stream`
+#[cfg(feature = "json")]
+pub fn execute_validated_query_to_stream<'a, QueryT, MutationT, CtxT>(
+    document: Document,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT>,
+    variables: &Variables,
+    context: &CtxT,
+    stream: &ResponseStream,
+) -> Result<Vec<ExecutionError>, GraphQLError<'a>>
+where
+    QueryT: GraphQLType<Context = CtxT>,
+    MutationT: GraphQLType<Context = CtxT>,
+{
+    let (_, errors) = try!(execute_operation(
+        document,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        Some(stream),
+        |executor, operation_type| {
+            stream.write(b"{\"data\":");
+
+            let is_buffered = may_become_null(executor.current_type(), executor);
+            if is_buffered {
+                stream.begin_buffer();
+            }
+
+            let written = match operation_type {
+                OperationType::Query => executor.stream(&root_node.query_info, root_node),
+                OperationType::Mutation => {
+                    executor.stream(&root_node.mutation_info, &root_node.mutation_type)
+                }
+            };
+
+            if is_buffered {
+                stream.end_buffer(written);
+            }
+
+            if !written {
+                stream.write(b"null");
+            }
+        },
+    ));
+
+    Ok(errors)
+}
+
+fn execute_operation<'a, QueryT, MutationT, CtxT, F, R>(
+    document: Document,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT>,
+    variables: &Variables,
+    context: &CtxT,
+    stream: Option<&ResponseStream>,
+    execute: F,
+) -> Result<(R, Vec<ExecutionError>), GraphQLError<'a>>
+where
+    QueryT: GraphQLType<Context = CtxT>,
+    MutationT: GraphQLType<Context = CtxT>,
+    F: FnOnce(&Executor<CtxT>, OperationType) -> R,
 {
     let mut fragments = vec![];
     let mut operation = None;
@@ -540,14 +838,11 @@ where
             context: context,
             errors: &errors,
             field_path: FieldPath::Root(op.start),
+            parent_field: None,
+            stream: stream,
         };
 
-        value = match op.item.operation_type {
-            OperationType::Query => executor.resolve_into_value(&root_node.query_info, &root_node),
-            OperationType::Mutation => {
-                executor.resolve_into_value(&root_node.mutation_info, &root_node.mutation_type)
-            }
-        };
+        value = execute(&executor, op.item.operation_type.clone());
     }
 
     let mut errors = errors.into_inner().unwrap();
@@ -559,7 +854,11 @@ where
 impl<'r> Registry<'r> {
     /// Construct a new registry
     pub fn new(types: FnvHashMap<Name, MetaType<'r>>) -> Registry<'r> {
-        Registry { types: types }
+        Registry {
+            types: types,
+            schema_errors: Vec::new(),
+            rust_types: FnvHashMap::default(),
+        }
     }
 
     /// Get the `Type` instance for a given GraphQL type
@@ -572,6 +871,7 @@ impl<'r> Registry<'r> {
     {
         if let Some(name) = T::name(info) {
             let validated_name = name.parse::<Name>().unwrap();
+            self.register_rust_type(validated_name.clone(), T::rust_type_name());
             if !self.types.contains_key(name) {
                 self.insert_placeholder(
                     validated_name.clone(),
@@ -586,6 +886,23 @@ impl<'r> Registry<'r> {
         }
     }
 
+    fn register_rust_type(&mut self, name: Name, rust_type: &'static str) {
+        let first = *self.rust_types.entry(name.clone()).or_insert(rust_type);
+
+        if first != rust_type {
+            let name: &str = name.borrow();
+            let error = SchemaError::DuplicateTypeName {
+                name: name.to_owned(),
+                first: first.to_owned(),
+                second: rust_type.to_owned(),
+            };
+
+            if !self.schema_errors.contains(&error) {
+                self.schema_errors.push(error);
+            }
+        }
+    }
+
     /// Create a field with the provided name
     pub fn field<T>(&mut self, name: &str, info: &T::TypeInfo) -> Field<'r>
     where
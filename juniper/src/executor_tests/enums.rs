@@ -86,7 +86,7 @@ fn does_not_accept_string_literals() {
 
     assert_eq!(error, ValidationError(vec![
         RuleError::new(
-            r#"Invalid value for argument "color", expected type "Color!""#,
+            r#"Invalid value for argument "color": Expected "Color", found "RED""#,
             &[SourcePosition::new(18, 0, 18)],
         ),
     ]));
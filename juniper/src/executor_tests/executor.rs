@@ -442,7 +442,7 @@ mod dynamic_context_switching {
 mod propagates_errors_to_nullable_fields {
     use value::Value;
     use schema::model::RootNode;
-    use executor::{ExecutionError, FieldError, FieldResult};
+    use executor::{ExecutionError, FieldError, FieldResult, PathSegment};
     use parser::SourcePosition;
     use types::scalars::EmptyMutation;
 
@@ -612,7 +612,11 @@ mod propagates_errors_to_nullable_fields {
             vec![
                 ExecutionError::new(
                     SourcePosition::new(11, 0, 11),
-                    &["inners", "nonNullableErrorField"],
+                    &[
+                        PathSegment::from("inners"),
+                        PathSegment::from(0),
+                        PathSegment::from("nonNullableErrorField"),
+                    ],
                     FieldError::new("Error for nonNullableErrorField", Value::null()),
                 ),
             ]);
@@ -638,27 +642,47 @@ mod propagates_errors_to_nullable_fields {
             vec![
                 ExecutionError::new(
                     SourcePosition::new(19, 0, 19),
-                    &["nullableInners", "nonNullableErrorField"],
+                    &[
+                        PathSegment::from("nullableInners"),
+                        PathSegment::from(0),
+                        PathSegment::from("nonNullableErrorField"),
+                    ],
                     FieldError::new("Error for nonNullableErrorField", Value::null()),
                 ),
                 ExecutionError::new(
                     SourcePosition::new(19, 0, 19),
-                    &["nullableInners", "nonNullableErrorField"],
+                    &[
+                        PathSegment::from("nullableInners"),
+                        PathSegment::from(1),
+                        PathSegment::from("nonNullableErrorField"),
+                    ],
                     FieldError::new("Error for nonNullableErrorField", Value::null()),
                 ),
                 ExecutionError::new(
                     SourcePosition::new(19, 0, 19),
-                    &["nullableInners", "nonNullableErrorField"],
+                    &[
+                        PathSegment::from("nullableInners"),
+                        PathSegment::from(2),
+                        PathSegment::from("nonNullableErrorField"),
+                    ],
                     FieldError::new("Error for nonNullableErrorField", Value::null()),
                 ),
                 ExecutionError::new(
                     SourcePosition::new(19, 0, 19),
-                    &["nullableInners", "nonNullableErrorField"],
+                    &[
+                        PathSegment::from("nullableInners"),
+                        PathSegment::from(3),
+                        PathSegment::from("nonNullableErrorField"),
+                    ],
                     FieldError::new("Error for nonNullableErrorField", Value::null()),
                 ),
                 ExecutionError::new(
                     SourcePosition::new(19, 0, 19),
-                    &["nullableInners", "nonNullableErrorField"],
+                    &[
+                        PathSegment::from("nullableInners"),
+                        PathSegment::from(4),
+                        PathSegment::from("nonNullableErrorField"),
+                    ],
                     FieldError::new("Error for nonNullableErrorField", Value::null()),
                 ),
             ]);
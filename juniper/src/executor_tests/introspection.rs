@@ -207,7 +207,7 @@ fn interface_introspection() {
     assert_eq!(type_info.get("name"), Some(&Value::string("SampleInterface")));
     assert_eq!(type_info.get("kind"), Some(&Value::string("INTERFACE")));
     assert_eq!(type_info.get("description"), Some(&Value::string("A sample interface")));
-    assert_eq!(type_info.get("interfaces"), Some(&Value::null()));
+    assert_eq!(type_info.get("interfaces"), Some(&Value::list(vec![])));
     assert_eq!(type_info.get("enumValues"), Some(&Value::null()));
     assert_eq!(type_info.get("inputFields"), Some(&Value::null()));
     assert_eq!(type_info.get("ofType"), Some(&Value::null()));
@@ -4,3 +4,9 @@ mod enums;
 mod directives;
 mod executor;
 mod interfaces_unions;
+mod nulls;
+mod validators;
+#[cfg(feature = "json")]
+mod streaming;
+#[cfg(feature = "parallel")]
+mod parallel;
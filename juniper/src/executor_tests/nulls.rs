@@ -0,0 +1,596 @@
+use ordermap::OrderMap;
+
+use executor::{FieldError, PathSegment, Variables};
+use types::dynamic::{DynamicRootNode, FieldDef, ObjectType, SchemaBuilder};
+use value::Value;
+
+// Mirrors the reference implementation's tests for non-null types and list
+// nullability. Every value in `DataType` either resolves to `null` or fails
+// to resolve, depending on the schema.
+
+fn data_schema(throws: bool) -> DynamicRootNode<'static, ()> {
+    let leaf = |name: &str, type_ref: &str| {
+        FieldDef::new(name, type_ref).resolve(move |_, _, _| if throws {
+            Err(FieldError::new("sync", Value::null()))
+        } else {
+            Ok(Value::null())
+        })
+    };
+    let nest = |name: &str, type_ref: &str| {
+        FieldDef::new(name, type_ref)
+            .resolve(|_, _, _| Ok(Value::object(OrderMap::<String, _>::new())))
+    };
+
+    SchemaBuilder::new("DataType")
+        .object(
+            ObjectType::new("DataType")
+                .field(leaf("sync", "String"))
+                .field(leaf("syncNonNull", "String!"))
+                .field(nest("syncNest", "DataType"))
+                .field(nest("syncNonNullNest", "DataType!")),
+        )
+        .build(Value::object(OrderMap::<String, _>::new()))
+        .unwrap()
+}
+
+type ErrorSummary = (usize, usize, Vec<PathSegment>, String);
+
+fn run(schema: &DynamicRootNode<()>, query: &str) -> (Value, Vec<ErrorSummary>) {
+    let (result, errors) = ::execute(query, None, schema, &Variables::new(), &()).unwrap();
+
+    (
+        result,
+        errors
+            .iter()
+            .map(|e| {
+                (
+                    e.location().line(),
+                    e.location().column(),
+                    e.path().to_vec(),
+                    e.error().message().to_owned(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn error(line: usize, column: usize, path: &[&str], message: &str) -> ErrorSummary {
+    (line, column, path_of(path), message.to_owned())
+}
+
+/// Numeric segments are list indices
+fn path_of(segments: &[&str]) -> Vec<PathSegment> {
+    segments
+        .iter()
+        .map(|s| match s.parse() {
+            Ok(index) => PathSegment::Index(index),
+            Err(_) => PathSegment::Field((*s).to_owned()),
+        })
+        .collect()
+}
+
+mod non_null_fields {
+    use value::Value;
+
+    use super::{data_schema, error, run};
+
+    #[test]
+    fn nulls_a_nullable_field_that_returns_null() {
+        assert_eq!(
+            run(&data_schema(false), "query Q { sync }"),
+            (graphql_value!({ "sync": None }), vec![])
+        );
+    }
+
+    #[test]
+    fn nulls_a_nullable_field_that_throws() {
+        assert_eq!(
+            run(&data_schema(true), "query Q { sync }"),
+            (
+                graphql_value!({ "sync": None }),
+                vec![error(0, 10, &["sync"], "sync")]
+            )
+        );
+    }
+
+    #[test]
+    fn nulls_an_object_containing_a_non_null_field_that_returns_null() {
+        assert_eq!(
+            run(&data_schema(false), "query Q { syncNest { syncNonNull } }"),
+            (
+                graphql_value!({ "syncNest": None }),
+                vec![
+                    error(
+                        0,
+                        21,
+                        &["syncNest", "syncNonNull"],
+                        "Cannot return null for non-nullable field DataType.syncNonNull",
+                    ),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn nulls_an_object_containing_a_non_null_field_that_throws() {
+        assert_eq!(
+            run(&data_schema(true), "query Q { syncNest { syncNonNull } }"),
+            (
+                graphql_value!({ "syncNest": None }),
+                vec![error(0, 21, &["syncNest", "syncNonNull"], "sync")]
+            )
+        );
+    }
+
+    static COMPLEX_TREE: &str = r#"
+        query Q {
+          syncNest {
+            sync
+            syncNest { sync }
+          }
+        }"#;
+
+    #[test]
+    fn nulls_a_complex_tree_of_nullable_fields_that_return_null() {
+        assert_eq!(
+            run(&data_schema(false), COMPLEX_TREE),
+            (
+                graphql_value!({
+                    "syncNest": { "sync": None, "syncNest": { "sync": None } },
+                }),
+                vec![]
+            )
+        );
+    }
+
+    #[test]
+    fn nulls_a_complex_tree_of_nullable_fields_that_throw() {
+        assert_eq!(
+            run(&data_schema(true), COMPLEX_TREE),
+            (
+                graphql_value!({
+                    "syncNest": { "sync": None, "syncNest": { "sync": None } },
+                }),
+                vec![
+                    error(3, 12, &["syncNest", "sync"], "sync"),
+                    error(4, 23, &["syncNest", "syncNest", "sync"], "sync"),
+                ]
+            )
+        );
+    }
+
+    static LONG_CHAIN: &str = r#"
+        query Q {
+          syncNest {
+            syncNonNullNest {
+              syncNonNullNest {
+                syncNonNull
+              }
+            }
+          }
+          anotherNest: syncNest {
+            syncNonNullNest {
+              syncNonNullNest {
+                syncNonNullNest {
+                  syncNonNull
+                }
+              }
+            }
+          }
+        }"#;
+
+    #[test]
+    fn nulls_the_first_nullable_object_in_a_chain_of_non_null_fields_that_return_null() {
+        let message = "Cannot return null for non-nullable field DataType.syncNonNull";
+
+        assert_eq!(
+            run(&data_schema(false), LONG_CHAIN),
+            (
+                graphql_value!({ "syncNest": None, "anotherNest": None }),
+                vec![
+                    error(
+                        5,
+                        16,
+                        &["syncNest", "syncNonNullNest", "syncNonNullNest", "syncNonNull"],
+                        message,
+                    ),
+                    error(
+                        13,
+                        18,
+                        &[
+                            "anotherNest",
+                            "syncNonNullNest",
+                            "syncNonNullNest",
+                            "syncNonNullNest",
+                            "syncNonNull",
+                        ],
+                        message,
+                    ),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn nulls_the_first_nullable_object_in_a_chain_of_non_null_fields_that_throw() {
+        assert_eq!(
+            run(&data_schema(true), LONG_CHAIN),
+            (
+                graphql_value!({ "syncNest": None, "anotherNest": None }),
+                vec![
+                    error(
+                        5,
+                        16,
+                        &["syncNest", "syncNonNullNest", "syncNonNullNest", "syncNonNull"],
+                        "sync",
+                    ),
+                    error(
+                        13,
+                        18,
+                        &[
+                            "anotherNest",
+                            "syncNonNullNest",
+                            "syncNonNullNest",
+                            "syncNonNullNest",
+                            "syncNonNull",
+                        ],
+                        "sync",
+                    ),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_errors_of_nullable_siblings_when_nulling_an_object() {
+        let query = r#"
+        query Q {
+          syncNest { sync syncNonNull }
+          other: syncNest { sync }
+        }"#;
+
+        assert_eq!(
+            run(&data_schema(true), query),
+            (
+                graphql_value!({ "syncNest": None, "other": { "sync": None } }),
+                vec![
+                    error(2, 21, &["syncNest", "sync"], "sync"),
+                    error(2, 26, &["syncNest", "syncNonNull"], "sync"),
+                    error(3, 28, &["other", "sync"], "sync"),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn nulls_the_top_level_for_a_non_null_field_that_returns_null() {
+        assert_eq!(
+            run(&data_schema(false), "query Q { syncNonNull }"),
+            (
+                Value::null(),
+                vec![
+                    error(
+                        0,
+                        10,
+                        &["syncNonNull"],
+                        "Cannot return null for non-nullable field DataType.syncNonNull",
+                    ),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn nulls_the_top_level_for_a_non_null_field_that_throws() {
+        assert_eq!(
+            run(&data_schema(true), "query Q { syncNonNull }"),
+            (Value::null(), vec![error(0, 10, &["syncNonNull"], "sync")])
+        );
+    }
+}
+
+mod list_nullability {
+    use schema::meta::EnumValue;
+    use types::dynamic::{EnumType, FieldDef, ObjectType, SchemaBuilder};
+    use value::Value;
+
+    use super::{error, run, ErrorSummary};
+
+    // Lists of a `Digit` enum; "BAD" is not a valid digit and fails to resolve
+    fn check(type_ref: &str, value: Value) -> (Value, Vec<ErrorSummary>) {
+        let schema = SchemaBuilder::<()>::new("Query")
+            .enum_type(EnumType::new(
+                "Digit",
+                &[EnumValue::new("ONE"), EnumValue::new("TWO")],
+            ))
+            .object(ObjectType::new("DataType").field(FieldDef::new("test", type_ref)))
+            .object(ObjectType::new("Query").field(FieldDef::new("nest", "DataType")))
+            .build(graphql_value!({ "nest": { "test": value } }))
+            .unwrap();
+
+        run(&schema, "{ nest { test } }")
+    }
+
+    fn contains_values() -> Value {
+        graphql_value!(["ONE", "TWO"])
+    }
+
+    fn contains_null() -> Value {
+        graphql_value!(["ONE", None, "TWO"])
+    }
+
+    fn contains_error() -> Value {
+        graphql_value!(["ONE", "BAD", "TWO"])
+    }
+
+    fn null_error() -> ErrorSummary {
+        error(
+            0,
+            9,
+            &["nest", "test"],
+            "Cannot return null for non-nullable field DataType.test",
+        )
+    }
+
+    fn null_item_error() -> ErrorSummary {
+        error(
+            0,
+            9,
+            &["nest", "test", "1"],
+            "Cannot return null for non-nullable field DataType.test",
+        )
+    }
+
+    fn invalid_value_error() -> ErrorSummary {
+        error(0, 9, &["nest", "test", "1"], "Invalid value for enum \"Digit\"")
+    }
+
+    #[test]
+    fn nullable_list_of_nullable_items() {
+        assert_eq!(
+            check("[Digit]", contains_values()),
+            (graphql_value!({ "nest": { "test": ["ONE", "TWO"] } }), vec![])
+        );
+        assert_eq!(
+            check("[Digit]", contains_null()),
+            (graphql_value!({ "nest": { "test": ["ONE", None, "TWO"] } }), vec![])
+        );
+        assert_eq!(
+            check("[Digit]", Value::null()),
+            (graphql_value!({ "nest": { "test": None } }), vec![])
+        );
+        assert_eq!(
+            check("[Digit]", contains_error()),
+            (
+                graphql_value!({ "nest": { "test": ["ONE", None, "TWO"] } }),
+                vec![invalid_value_error()]
+            )
+        );
+    }
+
+    #[test]
+    fn non_null_list_of_nullable_items() {
+        assert_eq!(
+            check("[Digit]!", contains_values()),
+            (graphql_value!({ "nest": { "test": ["ONE", "TWO"] } }), vec![])
+        );
+        assert_eq!(
+            check("[Digit]!", contains_null()),
+            (graphql_value!({ "nest": { "test": ["ONE", None, "TWO"] } }), vec![])
+        );
+        assert_eq!(
+            check("[Digit]!", Value::null()),
+            (graphql_value!({ "nest": None }), vec![null_error()])
+        );
+        assert_eq!(
+            check("[Digit]!", contains_error()),
+            (
+                graphql_value!({ "nest": { "test": ["ONE", None, "TWO"] } }),
+                vec![invalid_value_error()]
+            )
+        );
+    }
+
+    #[test]
+    fn nullable_list_of_non_null_items() {
+        assert_eq!(
+            check("[Digit!]", contains_values()),
+            (graphql_value!({ "nest": { "test": ["ONE", "TWO"] } }), vec![])
+        );
+        assert_eq!(
+            check("[Digit!]", contains_null()),
+            (graphql_value!({ "nest": { "test": None } }), vec![null_item_error()])
+        );
+        assert_eq!(
+            check("[Digit!]", Value::null()),
+            (graphql_value!({ "nest": { "test": None } }), vec![])
+        );
+        assert_eq!(
+            check("[Digit!]", contains_error()),
+            (
+                graphql_value!({ "nest": { "test": None } }),
+                vec![invalid_value_error()]
+            )
+        );
+    }
+
+    #[test]
+    fn non_null_list_of_non_null_items() {
+        assert_eq!(
+            check("[Digit!]!", contains_values()),
+            (graphql_value!({ "nest": { "test": ["ONE", "TWO"] } }), vec![])
+        );
+        assert_eq!(
+            check("[Digit!]!", contains_null()),
+            (graphql_value!({ "nest": None }), vec![null_item_error()])
+        );
+        assert_eq!(
+            check("[Digit!]!", Value::null()),
+            (graphql_value!({ "nest": None }), vec![null_error()])
+        );
+        assert_eq!(
+            check("[Digit!]!", contains_error()),
+            (graphql_value!({ "nest": None }), vec![invalid_value_error()])
+        );
+    }
+}
+
+// The same rules applied to Rust containers: `Option<T>` is nullable, while
+// `T`, `Vec<T>`, and `Box<T>` are non-null.
+mod containers {
+    use executor::{FieldResult, PathSegment, Variables};
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+    use value::Value;
+
+    use super::path_of;
+
+    struct Root;
+
+    struct Item(i32);
+
+    graphql_object!(Item: () |&self| {
+        field value() -> FieldResult<i32> {
+            if self.0 == 2 {
+                Err("Two is not allowed")?
+            }
+
+            Ok(self.0)
+        }
+    });
+
+    fn items() -> Vec<Item> {
+        vec![Item(1), Item(2), Item(3)]
+    }
+
+    graphql_object!(Root: () |&self| {
+        field items() -> Vec<Item> { items() }
+        field nullable_items() -> Option<Vec<Option<Item>>> {
+            Some(items().into_iter().map(Some).collect())
+        }
+        field nullable_list() -> Option<Vec<Item>> { Some(items()) }
+        field nullable_entries() -> Vec<Option<Item>> {
+            items().into_iter().map(Some).collect()
+        }
+        field rows() -> Vec<Option<Vec<Option<Item>>>> {
+            vec![Some(items().into_iter().map(Some).collect()), None]
+        }
+        field grid() -> Vec<Option<Vec<Item>>> { vec![Some(items()), Some(items())] }
+        field maybe_item() -> Option<Item> { Some(Item(2)) }
+        field boxed_item() -> Box<Item> { Box::new(Item(2)) }
+        field nest() -> Option<Root> { Some(Root) }
+    });
+
+    fn run(query: &str) -> (Value, Vec<(Vec<PathSegment>, String)>) {
+        let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+        let (result, errors) = ::execute(query, None, &schema, &Variables::new(), &()).unwrap();
+
+        (
+            result,
+            errors
+                .iter()
+                .map(|e| (e.path().to_vec(), e.error().message().to_owned()))
+                .collect(),
+        )
+    }
+
+    fn two_is_not_allowed(path: &[&str]) -> Vec<(Vec<PathSegment>, String)> {
+        vec![(path_of(path), "Two is not allowed".to_owned())]
+    }
+
+    #[test]
+    fn nulls_the_item_in_a_list_of_nullable_items() {
+        assert_eq!(
+            run("{ nullableItems { value } nullableEntries { value } }"),
+            (
+                graphql_value!({
+                    "nullableItems": [{ "value": 1 }, None, { "value": 3 }],
+                    "nullableEntries": [{ "value": 1 }, None, { "value": 3 }],
+                }),
+                vec![
+                    (
+                        path_of(&["nullableItems", "1", "value"]),
+                        "Two is not allowed".to_owned(),
+                    ),
+                    (
+                        path_of(&["nullableEntries", "1", "value"]),
+                        "Two is not allowed".to_owned(),
+                    ),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn indexes_items_of_nested_lists() {
+        assert_eq!(
+            run("{ rows { value } }"),
+            (
+                graphql_value!({
+                    "rows": [[{ "value": 1 }, None, { "value": 3 }], None],
+                }),
+                two_is_not_allowed(&["rows", "0", "1", "value"])
+            )
+        );
+        assert_eq!(
+            run("{ grid { value } }"),
+            (
+                graphql_value!({ "grid": [None, None] }),
+                vec![
+                    (
+                        path_of(&["grid", "0", "1", "value"]),
+                        "Two is not allowed".to_owned(),
+                    ),
+                    (
+                        path_of(&["grid", "1", "1", "value"]),
+                        "Two is not allowed".to_owned(),
+                    ),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn nulls_a_nullable_list_of_non_null_items() {
+        assert_eq!(
+            run("{ nullableList { value } }"),
+            (
+                graphql_value!({ "nullableList": None }),
+                two_is_not_allowed(&["nullableList", "1", "value"])
+            )
+        );
+    }
+
+    #[test]
+    fn nulls_the_parent_of_a_non_null_list_of_non_null_items() {
+        assert_eq!(
+            run("{ nest { items { value } } }"),
+            (
+                graphql_value!({ "nest": None }),
+                two_is_not_allowed(&["nest", "items", "1", "value"])
+            )
+        );
+        assert_eq!(
+            run("{ items { value } }"),
+            (Value::null(), two_is_not_allowed(&["items", "1", "value"]))
+        );
+    }
+
+    #[test]
+    fn nulls_options_and_the_parent_of_boxes() {
+        assert_eq!(
+            run("{ maybeItem { value } }"),
+            (
+                graphql_value!({ "maybeItem": None }),
+                two_is_not_allowed(&["maybeItem", "value"])
+            )
+        );
+        assert_eq!(
+            run("{ nest { boxedItem { value } } }"),
+            (
+                graphql_value!({ "nest": None }),
+                two_is_not_allowed(&["nest", "boxedItem", "value"])
+            )
+        );
+    }
+}
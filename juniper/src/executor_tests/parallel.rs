@@ -0,0 +1,202 @@
+use std::rc::Rc;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use rayon::ThreadPoolBuilder;
+use serde_json;
+
+use executor::{Context, FieldResult, Variables};
+use schema::model::RootNode;
+
+/// Lets a number of fields wait for each other, which only succeeds if they
+/// are resolved at the same time
+struct Rendezvous {
+    arrived: Mutex<usize>,
+    all_arrived: Condvar,
+    count: usize,
+}
+
+impl Rendezvous {
+    fn new(count: usize) -> Rendezvous {
+        Rendezvous {
+            arrived: Mutex::new(0),
+            all_arrived: Condvar::new(),
+            count: count,
+        }
+    }
+
+    fn meet(&self) -> bool {
+        let mut arrived = self.arrived.lock().unwrap();
+        *arrived += 1;
+        self.all_arrived.notify_all();
+
+        while *arrived < self.count {
+            let (guard, timeout) = self.all_arrived
+                .wait_timeout(arrived, Duration::from_secs(5))
+                .unwrap();
+            arrived = guard;
+
+            if timeout.timed_out() {
+                return *arrived >= self.count;
+            }
+        }
+
+        true
+    }
+}
+
+struct TestContext {
+    rendezvous: Rendezvous,
+    log: Mutex<Vec<String>>,
+}
+
+impl Context for TestContext {}
+
+impl TestContext {
+    fn new(count: usize) -> TestContext {
+        TestContext {
+            rendezvous: Rendezvous::new(count),
+            log: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn record(&self, entry: &str) {
+        self.log.lock().unwrap().push(entry.to_owned());
+    }
+}
+
+struct Query;
+struct Mutation;
+
+// Types below the root fields don't have to be `Sync`
+struct Item {
+    id: Rc<i32>,
+}
+
+graphql_object!(Item: TestContext |&self| {
+    field id() -> i32 { *self.id }
+
+    field met(&executor) -> bool { executor.context().rendezvous.meet() }
+});
+
+graphql_object!(Query: TestContext |&self| {
+    field met(&executor) -> bool { executor.context().rendezvous.meet() }
+
+    field items(count: i32) -> Vec<Item> {
+        (0..count).map(|id| Item { id: Rc::new(id) }).collect()
+    }
+
+    field slow(id: i32, millis: i32) -> i32 {
+        thread::sleep(Duration::from_millis(millis as u64));
+        id
+    }
+
+    field failing() -> FieldResult<Option<i32>> {
+        Err("Failed")?
+    }
+});
+
+graphql_object!(Mutation: TestContext |&self| {
+    field step(&executor, name: String) -> Vec<Item> {
+        let context = executor.context();
+
+        context.record(&format!("start {}", name));
+        thread::sleep(Duration::from_millis(20));
+        context.record(&format!("end {}", name));
+
+        vec![Item { id: Rc::new(0) }, Item { id: Rc::new(1) }]
+    }
+});
+
+fn run(query: &str, context: &TestContext) -> (String, Vec<String>) {
+    let schema = RootNode::new(Query, Mutation);
+    let pool = ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+
+    let (result, errors) = pool.install(|| {
+        ::execute_parallel(query, None, &schema, &Variables::new(), context)
+            .expect("Execution failed")
+    });
+
+    (
+        serde_json::to_string(&result).unwrap(),
+        errors
+            .iter()
+            .map(|e| e.error().message().to_owned())
+            .collect(),
+    )
+}
+
+#[test]
+fn resolves_sibling_fields_concurrently() {
+    let context = TestContext::new(3);
+
+    assert_eq!(
+        run("{ a: met b: met ... on Query { c: met } }", &context),
+        (r#"{"a":true,"b":true,"c":true}"#.to_owned(), vec![])
+    );
+}
+
+#[test]
+fn resolves_each_root_field_on_its_own_thread() {
+    let context = TestContext::new(2);
+
+    // The item, which isn't `Sync`, is resolved on the thread of its root
+    // field while the other root field waits for it
+    assert_eq!(
+        run("{ met items(count: 1) { id met } }", &context),
+        (
+            r#"{"met":true,"items":[{"id":0,"met":true}]}"#.to_owned(),
+            vec![]
+        )
+    );
+}
+
+#[test]
+fn keeps_the_order_of_keys() {
+    let context = TestContext::new(1);
+
+    // Earlier fields take longer, so they finish last
+    let (result, errors) = run(
+        r#"
+        {
+            first: slow(id: 1, millis: 60)
+            ...Rest
+            failing
+            items(count: 2) { id }
+        }
+
+        fragment Rest on Query {
+            second: slow(id: 2, millis: 30)
+            ... on Query { third: slow(id: 3, millis: 0) }
+        }
+        "#,
+        &context,
+    );
+
+    assert_eq!(
+        result,
+        r#"{"first":1,"second":2,"third":3,"failing":null,"items":[{"id":0},{"id":1}]}"#
+    );
+    assert_eq!(errors, vec!["Failed".to_owned()]);
+}
+
+#[test]
+fn resolves_mutation_root_fields_serially() {
+    let context = TestContext::new(1);
+
+    let (result, errors) = run(
+        r#"mutation { a: step(name: "a") { id } b: step(name: "b") { id } }"#,
+        &context,
+    );
+
+    assert_eq!(
+        result,
+        r#"{"a":[{"id":0},{"id":1}],"b":[{"id":0},{"id":1}]}"#
+    );
+    assert_eq!(errors, Vec::<String>::new());
+    assert_eq!(
+        *context.log.lock().unwrap(),
+        vec!["start a", "end a", "start b", "end b"]
+    );
+}
@@ -0,0 +1,242 @@
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use serde_json;
+
+use executor::{Context, FieldResult, Variables};
+use schema::model::RootNode;
+use tests::model::Database;
+use types::scalars::EmptyMutation;
+use GraphQLError;
+
+struct Root;
+
+struct Item {
+    id: i32,
+}
+
+graphql_object!(Item: () |&self| {
+    field id() -> i32 {
+        self.id
+    }
+
+    field value() -> FieldResult<String> {
+        if self.id == 2 {
+            Err("Item 2 is broken")?
+        } else {
+            Ok(format!("item {}", self.id))
+        }
+    }
+
+    field nested() -> Option<Item> {
+        Some(Item { id: self.id + 1 })
+    }
+
+    field count() -> Option<i32> {
+        if self.id == 2 { None } else { Some(self.id) }
+    }
+});
+
+graphql_object!(Root: () |&self| {
+    field items(count: i32) -> Vec<Item> {
+        (1..count + 1).map(|id| Item { id: id }).collect()
+    }
+
+    field nullable_items(count: i32) -> Vec<Option<Item>> {
+        (1..count + 1).map(|id| Some(Item { id: id })).collect()
+    }
+
+    field item(id: i32) -> Option<Item> {
+        Some(Item { id: id })
+    }
+
+    field non_null_item(id: i32) -> Item {
+        Item { id: id }
+    }
+});
+
+/// Returns the streamed response and the serialized result of `execute`
+fn run<QueryT, CtxT>(
+    schema: &RootNode<QueryT, EmptyMutation<CtxT>>,
+    context: &CtxT,
+    query: &str,
+) -> (String, String)
+where
+    QueryT: ::GraphQLType<Context = CtxT>,
+{
+    let vars = Variables::new();
+
+    let mut streamed = Vec::new();
+    ::execute_to_writer(query, None, schema, &vars, context, &mut streamed)
+        .expect("Execution failed")
+        .expect("Writing failed");
+
+    let (value, errors) = ::execute(query, None, schema, &vars, context).unwrap();
+    let mut expected = format!(r#"{{"data":{}"#, serde_json::to_string(&value).unwrap());
+    if !errors.is_empty() {
+        expected += &format!(r#","errors":{}"#, serde_json::to_string(&errors).unwrap());
+    }
+    expected += "}";
+
+    (String::from_utf8(streamed).unwrap(), expected)
+}
+
+fn assert_streams_like_execute(query: &str) {
+    let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+    let (streamed, expected) = run(&schema, &(), query);
+
+    assert_eq!(streamed, expected);
+}
+
+#[test]
+fn streams_fields_in_query_order() {
+    let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+    let (streamed, expected) = run(
+        &schema,
+        &(),
+        r#"{ second: item(id: 3) { value id } first: item(id: 1) { __typename, nested { id } } }"#,
+    );
+
+    assert_eq!(
+        streamed,
+        r#"{"data":{"second":{"value":"item 3","id":3},"first":{"__typename":"Item","nested":{"id":2}}}}"#
+    );
+    assert_eq!(streamed, expected);
+}
+
+#[test]
+fn streams_star_wars_queries() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, EmptyMutation::<Database>::new());
+
+    for query in &[
+        r#"{ hero { id name friends { name appearsIn } } }"#,
+        r#"{ human(id: "1000") { ...HumanFields } droid(id: "2001") { name primaryFunction } }
+           fragment HumanFields on Human { name homePlanet }"#,
+        r#"{ hero { name ... on Droid { primaryFunction } } }"#,
+        r#"{ __schema { queryType { name } types { name kind } } }"#,
+    ] {
+        let (streamed, expected) = run(&schema, &database, query);
+        assert_eq!(streamed, expected);
+    }
+}
+
+#[test]
+fn nulls_nullable_fields_that_fail() {
+    assert_streams_like_execute(r#"{ items(count: 3) { id count } item(id: 2) { count } }"#);
+}
+
+#[test]
+fn propagates_nulls_to_the_nullable_parent() {
+    assert_streams_like_execute(r#"{ item(id: 1) { id nested { id value } } }"#);
+}
+
+#[test]
+fn nulls_non_null_lists_with_failing_items() {
+    let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+    let (streamed, expected) = run(
+        &schema,
+        &(),
+        r#"{ item(id: 1) { id } items(count: 3) { value } }"#,
+    );
+
+    assert_eq!(
+        streamed,
+        r#"{"data":null,"errors":[{"message":"Item 2 is broken","locations":[{"line":1,"column":40}],"path":["items",1,"value"]}]}"#
+    );
+    assert_eq!(streamed, expected);
+}
+
+#[test]
+fn nulls_failing_items_in_lists_of_nullable_items() {
+    assert_streams_like_execute(r#"{ nullableItems(count: 3) { id value } }"#);
+}
+
+#[test]
+fn nulls_data_when_a_non_null_root_field_fails() {
+    assert_streams_like_execute(r#"{ item(id: 1) { id } nonNullItem(id: 2) { value } }"#);
+}
+
+#[test]
+fn merges_fields_with_the_same_response_name() {
+    assert_streams_like_execute(
+        r#"{ item(id: 1) { nested { id } } item(id: 1) { id nested { value } } }"#,
+    );
+}
+
+#[test]
+fn skips_excluded_fields() {
+    assert_streams_like_execute(
+        r#"{ item(id: 1) { id @skip(if: true) value ... @include(if: false) { count } } }"#,
+    );
+}
+
+#[test]
+fn returns_query_errors_without_writing() {
+    let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+    let mut streamed = Vec::new();
+
+    let result = ::execute_to_writer(
+        r#"{ item(id: 1) { unknown } }"#,
+        None,
+        &schema,
+        &Variables::new(),
+        &(),
+        &mut streamed,
+    );
+
+    match result {
+        Err(GraphQLError::ValidationError(_)) => (),
+        _ => panic!("Expected a validation error"),
+    }
+    assert!(streamed.is_empty());
+}
+
+/// A writer whose output can be inspected by resolvers during execution
+#[derive(Clone)]
+struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for SharedOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Context for SharedOutput {}
+
+struct ProgressRoot;
+
+graphql_object!(ProgressRoot: SharedOutput |&self| {
+    field bytes_written(&executor) -> Option<i32> {
+        Some(executor.context().0.lock().unwrap().len() as i32)
+    }
+
+    field item(id: i32) -> Option<Item> {
+        Some(Item { id: id })
+    }
+});
+
+#[test]
+fn writes_fields_before_later_fields_resolve() {
+    let schema = RootNode::new(ProgressRoot, EmptyMutation::<SharedOutput>::new());
+    let output = SharedOutput(Arc::new(Mutex::new(Vec::new())));
+
+    ::execute_to_writer(
+        r#"{ first: bytesWritten item(id: 1) { id } second: bytesWritten }"#,
+        None,
+        &schema,
+        &Variables::new(),
+        &output,
+        output.clone(),
+    ).expect("Execution failed")
+        .expect("Writing failed");
+
+    assert_eq!(
+        String::from_utf8(output.0.lock().unwrap().clone()).unwrap(),
+        r#"{"data":{"first":17,"item":{"id":1},"second":45}}"#
+    );
+}
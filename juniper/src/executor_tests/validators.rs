@@ -0,0 +1,149 @@
+use ast::InputValue;
+use executor::{PathSegment, Variables};
+use schema::model::RootNode;
+use value::Value;
+use GraphQLError::ValidationError;
+use validation::RuleError;
+use parser::SourcePosition;
+use types::scalars::EmptyMutation;
+
+struct TestType;
+
+graphql_input_object!(
+    #[derive(Debug)]
+    struct NewUser {
+        name: String where [min_length(1), max_length(8)],
+        tags: Option<Vec<String>> as "Lowercase tags" where [max_length(2), pattern("^[a-z]+$")],
+    }
+);
+
+graphql_object!(TestType: () |&self| {
+    field rate(stars: i32 as "Number of stars" where [min(1), max(5)]) -> Option<String> {
+        Some(format!("{} stars", stars))
+    }
+
+    field sign_up(user: NewUser) -> Option<String> {
+        Some(format!("{:?}", user))
+    }
+});
+
+fn run(query: &str, vars: Variables) -> (Value, Vec<::ExecutionError>) {
+    let schema = RootNode::new(TestType, EmptyMutation::<()>::new());
+
+    ::execute(query, None, &schema, &vars, &()).expect("Execution failed")
+}
+
+#[test]
+fn accepts_valid_values() {
+    let (result, errs) = run(
+        r#"{ rate(stars: 5) signUp(user: { name: "ada", tags: ["math"] }) }"#,
+        Variables::new(),
+    );
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        graphql_value!({
+            "rate": "5 stars",
+            "signUp": "NewUser { name: \"ada\", tags: Some([\"math\"]) }",
+        })
+    );
+}
+
+#[test]
+fn rejects_invalid_literals_during_validation() {
+    let schema = RootNode::new(TestType, EmptyMutation::<()>::new());
+
+    let error = ::execute(
+        r#"{ rate(stars: 9) }"#,
+        None,
+        &schema,
+        &Variables::new(),
+        &(),
+    ).unwrap_err();
+
+    assert_eq!(
+        error,
+        ValidationError(vec![
+            RuleError::new(
+                r#"Invalid value for argument "stars": Expected a value of at most 5, found 9"#,
+                &[SourcePosition::new(14, 0, 14)],
+            ),
+        ])
+    );
+}
+
+#[test]
+fn rejects_invalid_variables_during_execution() {
+    let vars = vec![("stars".to_owned(), InputValue::int(0))]
+        .into_iter()
+        .collect();
+
+    let (result, errs) = run(r#"query q($stars: Int!) { rate(stars: $stars) }"#, vars);
+
+    assert_eq!(result, graphql_value!({ "rate": None }));
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].path(), &[PathSegment::Field("rate".to_owned())]);
+    assert_eq!(
+        errs[0].error().message(),
+        r#"Invalid value for argument "stars": Expected a value of at least 1, found 0"#
+    );
+}
+
+#[test]
+fn reports_the_path_of_invalid_input_fields() {
+    let vars = vec![
+        (
+            "user".to_owned(),
+            InputValue::object(
+                vec![
+                    ("name", InputValue::string("ada")),
+                    (
+                        "tags",
+                        InputValue::list(vec![InputValue::string("ok"), InputValue::string("NO")]),
+                    ),
+                ].into_iter()
+                    .collect(),
+            ),
+        ),
+    ].into_iter()
+        .collect();
+
+    let (result, errs) = run(r#"query q($user: NewUser!) { signUp(user: $user) }"#, vars);
+
+    assert_eq!(result, graphql_value!({ "signUp": None }));
+    assert_eq!(errs.len(), 1);
+    assert_eq!(
+        errs[0].error().message(),
+        r#"Invalid value for argument "user": In field "tags": In element #1: Expected a value matching "^[a-z]+$", found "NO""#
+    );
+}
+
+#[test]
+fn describes_conversion_errors() {
+    let list = InputValue::list(vec![InputValue::int(1), InputValue::string("two")]);
+
+    assert_eq!(list.convert::<Vec<i32>>(), None);
+    assert_eq!(
+        list.try_convert::<Vec<i32>>().unwrap_err().to_string(),
+        r#"In element #1: Expected "Int", found "two""#
+    );
+
+    let user = InputValue::object(
+        vec![("tags", InputValue::list(vec![InputValue::int(3)]))]
+            .into_iter()
+            .collect(),
+    );
+
+    let error = user.try_convert::<NewUser>().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        r#"In field "name": Expected "String", found null"#
+    );
+    assert_eq!(error.message(), r#"Expected "String", found null"#);
+
+    assert_eq!(
+        InputValue::int(3).try_convert::<NewUser>().unwrap_err().to_string(),
+        r#"Expected "NewUser", found 3"#
+    );
+}
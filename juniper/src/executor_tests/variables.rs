@@ -849,7 +849,7 @@ fn does_not_allow_missing_required_field() {
 
     assert_eq!(error, ValidationError(vec![
         RuleError::new(
-            r#"Invalid value for argument "arg", expected type "ExampleInputObject!""#,
+            r#"Invalid value for argument "arg": In field "b": Expected "Int!", found null"#,
             &[SourcePosition::new(20, 0, 20)],
         ),
     ]));
@@ -866,7 +866,7 @@ fn does_not_allow_null_in_required_field() {
 
     assert_eq!(error, ValidationError(vec![
         RuleError::new(
-            r#"Invalid value for argument "arg", expected type "ExampleInputObject!""#,
+            r#"Invalid value for argument "arg": In field "b": Expected "Int!", found null"#,
             &[SourcePosition::new(20, 0, 20)],
         ),
     ]));
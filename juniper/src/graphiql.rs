@@ -1,67 +1,277 @@
-//! Utility module to generate a GraphiQL interface
+//! Utility module to generate a GraphiQL or GraphQL Playground interface
+
+use ordermap::OrderMap;
+use serde_json;
 
 /// Generate the HTML source to show a GraphiQL interface
+///
+/// This uses the default configuration, see `GraphiQLConfig` to customize
+/// the page.
 pub fn graphiql_source(graphql_endpoint_url: &str) -> String {
-    let stylesheet_source = r#"
+    GraphiQLConfig::new(graphql_endpoint_url).source()
+}
+
+/// Configuration of the GraphiQL page
+///
+/// ```rust
+/// # use juniper::graphiql::GraphiQLConfig;
+/// let html = GraphiQLConfig::new("/graphql")
+///     .title("Star Wars API")
+///     .header("Authorization", "Bearer <token>")
+///     .default_query("{ hero { name } }")
+///     .source();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphiQLConfig {
+    endpoint_url: String,
+    subscriptions_url: Option<String>,
+    title: String,
+    default_query: Option<String>,
+    headers: OrderMap<String, String>,
+    react_url: String,
+    react_dom_url: String,
+    graphiql_js_url: String,
+    graphiql_css_url: String,
+}
+
+impl GraphiQLConfig {
+    /// Configure a page sending queries to the endpoint, loading its assets
+    /// from unpkg.com
+    pub fn new(graphql_endpoint_url: &str) -> GraphiQLConfig {
+        GraphiQLConfig {
+            endpoint_url: graphql_endpoint_url.to_owned(),
+            subscriptions_url: None,
+            title: "GraphQL".to_owned(),
+            default_query: None,
+            headers: OrderMap::new(),
+            react_url: "https://unpkg.com/react@17.0.2/umd/react.production.min.js".to_owned(),
+            react_dom_url: "https://unpkg.com/react-dom@17.0.2/umd/react-dom.production.min.js"
+                .to_owned(),
+            graphiql_js_url: "https://unpkg.com/graphiql@2.4.7/graphiql.min.js".to_owned(),
+            graphiql_css_url: "https://unpkg.com/graphiql@2.4.7/graphiql.min.css".to_owned(),
+        }
+    }
+
+    /// Set the WebSocket URL subscriptions are sent to
+    pub fn subscriptions_url(mut self, url: &str) -> GraphiQLConfig {
+        self.subscriptions_url = Some(url.to_owned());
+        self
+    }
+
+    /// Set the title of the page
+    pub fn title(mut self, title: &str) -> GraphiQLConfig {
+        self.title = title.to_owned();
+        self
+    }
+
+    /// Set the query shown when the editor is opened for the first time
+    pub fn default_query(mut self, query: &str) -> GraphiQLConfig {
+        self.default_query = Some(query.to_owned());
+        self
+    }
+
+    /// Add a header to the headers editor, which is sent with every request
+    pub fn header(mut self, name: &str, value: &str) -> GraphiQLConfig {
+        self.headers.insert(name.to_owned(), value.to_owned());
+        self
+    }
+
+    /// Load React from the URL instead of unpkg.com
+    pub fn react_url(mut self, url: &str) -> GraphiQLConfig {
+        self.react_url = url.to_owned();
+        self
+    }
+
+    /// Load ReactDOM from the URL instead of unpkg.com
+    pub fn react_dom_url(mut self, url: &str) -> GraphiQLConfig {
+        self.react_dom_url = url.to_owned();
+        self
+    }
+
+    /// Load the GraphiQL script from the URL instead of unpkg.com
+    pub fn graphiql_js_url(mut self, url: &str) -> GraphiQLConfig {
+        self.graphiql_js_url = url.to_owned();
+        self
+    }
+
+    /// Load the GraphiQL stylesheet from the URL instead of unpkg.com
+    pub fn graphiql_css_url(mut self, url: &str) -> GraphiQLConfig {
+        self.graphiql_css_url = url.to_owned();
+        self
+    }
+
+    /// Generate the HTML source of the page
+    pub fn source(&self) -> String {
+        let headers = if self.headers.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string_pretty(&self.headers).expect("Headers are valid JSON"))
+        };
+
+        format!(r#"
+<!DOCTYPE html>
+<html>
+<head>
+    <title>{title}</title>
     <style>
-        html, body, #app {
+        html, body, #app {{
             height: 100%;
             margin: 0;
             overflow: hidden;
             width: 100%;
-        }
+        }}
     </style>
-    "#;
-    let fetcher_source = r#"
+    <link rel="stylesheet" type="text/css" href="{graphiql_css_url}">
+</head>
+<body>
+    <div id="app"></div>
+
+    <script src="{react_url}"></script>
+    <script src="{react_dom_url}"></script>
+    <script src="{graphiql_js_url}"></script>
     <script>
-        function graphQLFetcher(params) {
-            return fetch(GRAPHQL_URL, {
-                method: 'post',
-                headers: {
-                    'Accept': 'application/json',
-                    'Content-Type': 'application/json',
-                },
-                body: JSON.stringify(params)
-            }).then(function (response) {
-                return response.text();
-            }).then(function (body) {
-                try {
-                    return JSON.parse(body);
-                } catch (error) {
-                    return body;
-                }
-            });
-        }
+        var fetcher = GraphiQL.createFetcher({{
+            url: {endpoint_url},
+            subscriptionUrl: {subscriptions_url},
+        }});
         ReactDOM.render(
-            React.createElement(GraphiQL, {
-                fetcher: graphQLFetcher,
-            }),
+            React.createElement(GraphiQL, {{
+                fetcher: fetcher,
+                defaultQuery: {default_query},
+                headers: {headers},
+                isHeadersEditorEnabled: true,
+            }}),
             document.querySelector('#app'));
     </script>
-    "#;
+</body>
+</html>
+"#,
+            title = escape_html(&self.title),
+            graphiql_css_url = escape_html(&self.graphiql_css_url),
+            react_url = escape_html(&self.react_url),
+            react_dom_url = escape_html(&self.react_dom_url),
+            graphiql_js_url = escape_html(&self.graphiql_js_url),
+            endpoint_url = script_value(&self.endpoint_url),
+            subscriptions_url = script_value(&self.subscriptions_url),
+            default_query = script_value(&self.default_query),
+            headers = script_value(&headers))
+    }
+}
 
+/// Generate the HTML source to show a GraphQL Playground interface
+pub fn playground_source(
+    graphql_endpoint_url: &str,
+    subscriptions_endpoint_url: Option<&str>,
+) -> String {
     format!(r#"
 <!DOCTYPE html>
 <html>
 <head>
-    <title>GraphQL</title>
-    {stylesheet_source}
-    <link rel="stylesheet" type="text/css" href="//cdnjs.cloudflare.com/ajax/libs/graphiql/0.10.2/graphiql.css">
+    <meta charset="utf-8" />
+    <meta name="viewport" content="user-scalable=no, initial-scale=1.0, minimum-scale=1.0, maximum-scale=1.0, minimal-ui">
+    <title>GraphQL Playground</title>
+    <link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/static/css/index.css" />
+    <link rel="shortcut icon" href="https://cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/favicon.png" />
+    <script src="https://cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/static/js/middleware.js"></script>
 </head>
 <body>
-    <div id="app"></div>
-
-    <script src="//cdnjs.cloudflare.com/ajax/libs/fetch/2.0.3/fetch.js"></script>
-    <script src="//cdnjs.cloudflare.com/ajax/libs/react/15.6.0/react.js"></script>
-    <script src="//cdnjs.cloudflare.com/ajax/libs/react/15.6.0/react-dom.js"></script>
-    <script src="//cdnjs.cloudflare.com/ajax/libs/graphiql/0.10.2/graphiql.js"></script>
-    <script>var GRAPHQL_URL = '{graphql_url}';</script>
-    {fetcher_source}
+    <div id="root"></div>
+    <script>
+        window.addEventListener('load', function (event) {{
+            GraphQLPlayground.init(document.getElementById('root'), {{
+                endpoint: {endpoint_url},
+                subscriptionEndpoint: {subscriptions_url},
+            }});
+        }});
+    </script>
 </body>
 </html>
 "#,
-        graphql_url = graphql_endpoint_url,
-        stylesheet_source = stylesheet_source,
-        fetcher_source = fetcher_source)
+        endpoint_url = script_value(&graphql_endpoint_url),
+        subscriptions_url = script_value(&subscriptions_endpoint_url))
+}
+
+/// Escape text for use in HTML content and quoted attributes
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Encode a value as a JavaScript literal that can't close the surrounding
+/// `<script>` element
+fn script_value<T: ::serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value)
+        .expect("Value is valid JSON")
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+        .replace('\u{2028}', "\\u2028")
+        .replace('\u{2029}', "\\u2029")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{graphiql_source, playground_source, GraphiQLConfig};
+
+    #[test]
+    fn test_default_graphiql_source() {
+        let source = graphiql_source("/graphql");
+
+        assert!(source.contains(r#"url: "/graphql","#));
+        assert!(source.contains("subscriptionUrl: null,"));
+        assert!(source.contains("<title>GraphQL</title>"));
+        assert!(source.contains(r#"<script src="https://unpkg.com/graphiql@2.4.7/graphiql.min.js">"#));
+    }
+
+    #[test]
+    fn test_configured_graphiql_source() {
+        let source = GraphiQLConfig::new("/graphql")
+            .subscriptions_url("ws://localhost/subscriptions")
+            .title("Star Wars")
+            .default_query("{ hero { name } }")
+            .header("Authorization", "Bearer token")
+            .graphiql_js_url("/assets/graphiql.js")
+            .source();
+
+        assert!(source.contains(r#"subscriptionUrl: "ws://localhost/subscriptions","#));
+        assert!(source.contains("<title>Star Wars</title>"));
+        assert!(source.contains(r#"defaultQuery: "{ hero { name } }","#));
+        assert!(source.contains(r#"headers: "{\n  \"Authorization\": \"Bearer token\"\n}","#));
+        assert!(source.contains(r#"<script src="/assets/graphiql.js">"#));
+        assert!(!source.contains("unpkg.com/graphiql@2.4.7/graphiql.min.js"));
+    }
+
+    #[test]
+    fn test_escapes_injected_values() {
+        let source = GraphiQLConfig::new("/graphql?a=1&b='</script><script>alert(1)</script>")
+            .title("</title><script>alert(1)</script>")
+            .graphiql_css_url(r#"/style.css"><script>alert(1)</script>"#)
+            .source();
+
+        assert!(!source.contains("<script>alert(1)"));
+        assert!(source.contains("<title>&lt;/title&gt;&lt;script&gt;alert(1)&lt;/script&gt;</title>"));
+        assert!(source.contains(r#"href="/style.css&quot;&gt;&lt;script&gt;"#));
+        assert!(source.contains(r#"url: "/graphql?a=1\u0026b='\u003c/script\u003e"#));
+    }
+
+    #[test]
+    fn test_playground_source() {
+        let source = playground_source("/graphql", Some("ws://localhost/</script>"));
 
+        assert!(source.contains(r#"endpoint: "/graphql","#));
+        assert!(source.contains(r#"subscriptionEndpoint: "ws://localhost/\u003c/script\u003e","#));
+        assert!(playground_source("/graphql", None).contains("subscriptionEndpoint: null,"));
+    }
 }
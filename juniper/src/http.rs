@@ -1,19 +1,23 @@
 //! Utilities for building HTTP endpoints in a library-agnostic manner
 
+use std::fmt;
+use std::str;
+
+use ordermap::OrderMap;
 use serde::ser;
 use serde::ser::SerializeMap;
+use serde_json;
 
-use {GraphQLError, GraphQLType, RootNode, Value, Variables};
-use ast::InputValue;
+use {GraphQLError, GraphQLType, RootNode, Upload, Value, Variables};
+use ast::{Definition, InputValue, OperationType};
 use executor::ExecutionError;
+use parser::parse_document_source;
 
 /// The expected structure of the decoded JSON document for either POST or GET requests.
 ///
-/// For POST, you can use Serde to deserialize the incoming JSON data directly
-/// into this struct - it derives Deserialize for exactly this reason.
-///
-/// For GET, you will need to parse the query string and exctract "query",
-/// "operationName", and "variables" manually.
+/// Use `from_query_string` for GET requests, and `from_post_body` for POST
+/// requests with an `application/json` or `application/graphql` body. You
+/// can also use Serde to deserialize JSON data directly into this struct.
 #[derive(Deserialize, Clone, Serialize)]
 pub struct GraphQLRequest {
     query: String,
@@ -53,6 +57,248 @@ impl GraphQLRequest {
         }
     }
 
+    /// Build a request from the query string of a GET request
+    ///
+    /// The query string holds the `query`, and optionally the
+    /// `operationName` and JSON-encoded `variables`, e.g.
+    /// `query=query($id:String!){human(id:$id){name}}&variables={"id":"1000"}`.
+    /// GET requests must not have side effects, so requests for mutations are
+    /// refused with `MutationOverGet`.
+    pub fn from_query_string(query_string: &str) -> Result<GraphQLRequest, GraphQLRequestError> {
+        let mut query = None;
+        let mut operation_name = None;
+        let mut variables = None;
+
+        for pair in query_string.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = match pair.find('=') {
+                Some(i) => (&pair[..i], &pair[i + 1..]),
+                None => (pair, ""),
+            };
+            let key = try!(percent_decode(key));
+            let value = try!(percent_decode(value));
+
+            let slot = match key.as_str() {
+                "query" => &mut query,
+                "operationName" => &mut operation_name,
+                "variables" => &mut variables,
+                _ => continue,
+            };
+
+            if slot.is_some() {
+                return Err(GraphQLRequestError::DuplicateParameter(key));
+            }
+            *slot = Some(value);
+        }
+
+        let variables = match variables {
+            Some(ref v) if !v.is_empty() => Some(try!(
+                serde_json::from_str::<InputValue>(v)
+                    .map_err(|e| GraphQLRequestError::InvalidVariables(e.to_string()))
+            )),
+            _ => None,
+        };
+
+        let request = try!(GraphQLRequest::checked(
+            try!(query.ok_or(GraphQLRequestError::MissingQuery)),
+            operation_name.and_then(|n| if n.is_empty() { None } else { Some(n) }),
+            variables,
+        ));
+
+        if request.operation_type() == Some(OperationType::Mutation) {
+            return Err(GraphQLRequestError::MutationOverGet);
+        }
+
+        Ok(request)
+    }
+
+    /// Build a request from an `application/json` body
+    pub fn from_json_body(body: &str) -> Result<GraphQLRequest, GraphQLRequestError> {
+        let request = try!(
+            serde_json::from_str::<GraphQLRequest>(body)
+                .map_err(|e| GraphQLRequestError::InvalidJson(e.to_string()))
+        );
+
+        GraphQLRequest::checked(request.query, request.operation_name, request.variables)
+    }
+
+    /// Build a request from an `application/graphql` body, which holds only
+    /// the query
+    pub fn from_graphql_body(body: &str) -> Result<GraphQLRequest, GraphQLRequestError> {
+        GraphQLRequest::checked(body.to_owned(), None, None)
+    }
+
+    /// Build a request from the body of a POST request, depending on the
+    /// value of its `Content-Type` header
+    pub fn from_post_body(
+        content_type: Option<&str>,
+        body: &str,
+    ) -> Result<GraphQLRequest, GraphQLRequestError> {
+        let media_type = content_type.map(|t| {
+            t.split(';').next().unwrap_or("").trim().to_ascii_lowercase()
+        });
+
+        match media_type.as_ref().map(|t| t.as_str()) {
+            Some("application/json") => GraphQLRequest::from_json_body(body),
+            Some("application/graphql") => GraphQLRequest::from_graphql_body(body),
+            _ => Err(GraphQLRequestError::UnsupportedContentType(
+                content_type.map(|t| t.to_owned()),
+            )),
+        }
+    }
+
+    /// Build a request from a `multipart/form-data` body following the
+    /// GraphQL multipart request specification
+    ///
+    /// The `operations` part holds the JSON request, with nulls in place of
+    /// the files. The `map` part maps the name of each file part to the
+    /// paths of those nulls, e.g. `{"0": ["variables.avatar"]}`. Each file
+    /// becomes an `Upload` argument when the request is executed. Batched
+    /// operations aren't supported.
+    ///
+    /// `limits.max_request_size` is checked before the body is parsed, but
+    /// only after it has been read into memory. Integrations must stop
+    /// reading the body once it exceeds the limit instead of relying on this
+    /// check.
+    pub fn from_multipart_body(
+        content_type: &str,
+        body: &[u8],
+        limits: &UploadLimits,
+    ) -> Result<GraphQLRequest, GraphQLRequestError> {
+        if body.len() > limits.max_request_size {
+            return Err(GraphQLRequestError::RequestTooLarge);
+        }
+
+        let mut params = content_type.split(';');
+        let media_type = params.next().unwrap_or("").trim().to_ascii_lowercase();
+
+        if media_type != "multipart/form-data" {
+            return Err(GraphQLRequestError::UnsupportedContentType(
+                Some(content_type.to_owned()),
+            ));
+        }
+
+        let boundary = try!(
+            params
+                .filter_map(|p| {
+                    let mut pair = p.splitn(2, '=');
+                    match (pair.next(), pair.next()) {
+                        (Some(k), Some(v)) if k.trim().eq_ignore_ascii_case("boundary") => {
+                            Some(v.trim().trim_matches('"'))
+                        }
+                        _ => None,
+                    }
+                })
+                .next()
+                .filter(|b| !b.is_empty())
+                .ok_or_else(|| multipart_error("Missing boundary"))
+        );
+
+        let parts = try!(parse_multipart(body, boundary));
+
+        let find_part = |name: &str| parts.iter().find(|p| p.name == name);
+
+        let operations = try!(
+            find_part("operations").ok_or_else(|| multipart_error("Missing operations"))
+        );
+        let operations = try!(
+            str::from_utf8(operations.content)
+                .map_err(|_| multipart_error("Operations must be UTF-8"))
+        );
+        if operations.trim_left().starts_with('[') {
+            return Err(multipart_error("Batched operations are not supported"));
+        }
+        let mut request = try!(GraphQLRequest::from_json_body(operations));
+
+        let map = try!(find_part("map").ok_or_else(|| multipart_error("Missing map")));
+        let map = try!(
+            serde_json::from_slice::<OrderMap<String, Vec<String>>>(map.content)
+                .map_err(|e| multipart_error(&format!("Invalid map: {}", e)))
+        );
+
+        // Only the parts referenced by the map are files
+        if map.len() > limits.max_files {
+            return Err(GraphQLRequestError::TooManyFiles);
+        }
+
+        for (name, paths) in map {
+            let part = try!(
+                find_part(&name)
+                    .ok_or_else(|| multipart_error(&format!("Missing file \"{}\"", name)))
+            );
+            if part.content.len() > limits.max_file_size {
+                return Err(GraphQLRequestError::FileTooLarge(name));
+            }
+
+            let upload = InputValue::upload(Upload::new(
+                part.filename.clone(),
+                part.content_type.clone(),
+                part.content.to_vec(),
+            ));
+
+            for path in paths {
+                let segments = path.split('.').collect::<Vec<_>>();
+
+                let inserted = match (segments.split_first(), request.variables.as_mut()) {
+                    (Some((&"variables", rest)), Some(variables)) => {
+                        insert_upload(variables, rest, &upload)
+                    }
+                    _ => false,
+                };
+
+                if !inserted {
+                    return Err(multipart_error(&format!("Invalid path \"{}\"", path)));
+                }
+            }
+        }
+
+        Ok(request)
+    }
+
+    fn checked(
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<InputValue>,
+    ) -> Result<GraphQLRequest, GraphQLRequestError> {
+        if query.trim().is_empty() {
+            return Err(GraphQLRequestError::MissingQuery);
+        }
+
+        match variables {
+            None | Some(InputValue::Null) | Some(InputValue::Object(_)) => (),
+            Some(_) => {
+                return Err(GraphQLRequestError::InvalidVariables(
+                    "Variables must be an object".to_owned(),
+                ))
+            }
+        }
+
+        Ok(GraphQLRequest::new(query, operation_name, variables))
+    }
+
+    /// The type of the operation the request executes, if the query can be
+    /// parsed and the operation can be found
+    fn operation_type(&self) -> Option<OperationType> {
+        let document = match parse_document_source(&self.query) {
+            Ok(document) => document,
+            Err(_) => return None,
+        };
+
+        let mut operations = document.into_iter().filter_map(|def| match def {
+            Definition::Operation(op) => Some(op.item),
+            Definition::Fragment(_) => None,
+        });
+
+        match self.operation_name() {
+            Some(name) => operations
+                .find(|op| op.name.as_ref().map(|n| n.item) == Some(name))
+                .map(|op| op.operation_type),
+            None => match (operations.next(), operations.next()) {
+                (Some(op), None) => Some(op.operation_type),
+                _ => None,
+            },
+        }
+    }
+
     /// Execute a GraphQL request using the specified schema and context
     ///
     /// This is a simple wrapper around the `execute` function exposed at the
@@ -76,11 +322,421 @@ impl GraphQLRequest {
     }
 }
 
+/// An HTTP request that doesn't hold a valid GraphQL request
+///
+/// The error implements Serialize, so you can send it as the JSON body of
+/// the response, with the status code from `status_code`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphQLRequestError {
+    /// The request doesn't contain a query
+    MissingQuery,
+    /// A parameter occurs more than once in the query string
+    DuplicateParameter(String),
+    /// The query string isn't properly percent-encoded UTF-8
+    InvalidEncoding,
+    /// The body isn't a valid JSON request
+    InvalidJson(String),
+    /// The variables aren't a JSON object
+    InvalidVariables(String),
+    /// The POST body has a content type other than `application/json` and
+    /// `application/graphql`
+    UnsupportedContentType(Option<String>),
+    /// The operation of a GET request is a mutation
+    MutationOverGet,
+    /// The multipart body doesn't follow the GraphQL multipart request
+    /// specification
+    InvalidMultipart(String),
+    /// The body is larger than allowed by `UploadLimits`
+    RequestTooLarge,
+    /// The multipart body has more files than allowed by `UploadLimits`
+    TooManyFiles,
+    /// The named part of the multipart body is larger than allowed by
+    /// `UploadLimits`
+    FileTooLarge(String),
+    /// Reading the body from the connection failed
+    UnreadableBody(String),
+    /// The body isn't valid UTF-8
+    InvalidBodyEncoding,
+}
+
+impl GraphQLRequestError {
+    /// The HTTP status code to respond with
+    ///
+    /// This is `405 Method Not Allowed` for mutations over GET,
+    /// `413 Payload Too Large` for bodies exceeding the limits, and
+    /// `400 Bad Request` otherwise.
+    pub fn status_code(&self) -> u16 {
+        match *self {
+            GraphQLRequestError::MutationOverGet => 405,
+            GraphQLRequestError::RequestTooLarge |
+            GraphQLRequestError::TooManyFiles |
+            GraphQLRequestError::FileTooLarge(_) => 413,
+            _ => 400,
+        }
+    }
+}
+
+impl fmt::Display for GraphQLRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GraphQLRequestError::MissingQuery => write!(f, "Missing query"),
+            GraphQLRequestError::DuplicateParameter(ref name) => {
+                write!(f, "Parameter \"{}\" occurs more than once", name)
+            }
+            GraphQLRequestError::InvalidEncoding => write!(f, "Invalid query string encoding"),
+            GraphQLRequestError::InvalidJson(ref message) => {
+                write!(f, "Invalid JSON body: {}", message)
+            }
+            GraphQLRequestError::InvalidVariables(ref message) => {
+                write!(f, "Invalid variables: {}", message)
+            }
+            GraphQLRequestError::UnsupportedContentType(Some(ref content_type)) => {
+                write!(f, "Unsupported content type \"{}\"", content_type)
+            }
+            GraphQLRequestError::UnsupportedContentType(None) => write!(f, "Missing content type"),
+            GraphQLRequestError::MutationOverGet => {
+                write!(f, "Mutations can only be executed with POST requests")
+            }
+            GraphQLRequestError::InvalidMultipart(ref message) => {
+                write!(f, "Invalid multipart request: {}", message)
+            }
+            GraphQLRequestError::RequestTooLarge => write!(f, "Request body is too large"),
+            GraphQLRequestError::TooManyFiles => write!(f, "Too many files"),
+            GraphQLRequestError::FileTooLarge(ref name) => {
+                write!(f, "File \"{}\" is too large", name)
+            }
+            GraphQLRequestError::UnreadableBody(ref message) => {
+                write!(f, "Could not read the request body: {}", message)
+            }
+            GraphQLRequestError::InvalidBodyEncoding => {
+                write!(f, "The request body must be UTF-8")
+            }
+        }
+    }
+}
+
+impl ser::Serialize for GraphQLRequestError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut error = OrderMap::new();
+        error.insert("message", self.to_string());
+
+        let mut map = try!(serializer.serialize_map(Some(1)));
+        try!(map.serialize_key("errors"));
+        try!(map.serialize_value(&[error]));
+        map.end()
+    }
+}
+
+fn percent_decode(s: &str) -> Result<String, GraphQLRequestError> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let hex = try!(
+                    s.get(i + 1..i + 3)
+                        .filter(|h| h.chars().all(|c| c.is_digit(16)))
+                        .and_then(|h| u8::from_str_radix(h, 16).ok())
+                        .ok_or(GraphQLRequestError::InvalidEncoding)
+                );
+                decoded.push(hex);
+                i += 2;
+            }
+            b => decoded.push(b),
+        }
+        i += 1;
+    }
+
+    String::from_utf8(decoded).map_err(|_| GraphQLRequestError::InvalidEncoding)
+}
+
+/// Limits for the body and the files of a request
+#[derive(Debug, Clone, PartialEq)]
+pub struct UploadLimits {
+    /// The maximum size of the whole request body in bytes
+    pub max_request_size: usize,
+    /// The maximum number of files in a request
+    pub max_files: usize,
+    /// The maximum size of a single file in bytes
+    pub max_file_size: usize,
+}
+
+impl Default for UploadLimits {
+    /// Allows bodies of up to 100 MiB with up to 10 files of 10 MiB each
+    fn default() -> UploadLimits {
+        UploadLimits {
+            max_request_size: 100 * 1024 * 1024,
+            max_files: 10,
+            max_file_size: 10 * 1024 * 1024,
+        }
+    }
+}
+
+struct MultipartPart<'a> {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+    content: &'a [u8],
+}
+
+fn multipart_error(message: &str) -> GraphQLRequestError {
+    GraphQLRequestError::InvalidMultipart(message.to_owned())
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn parse_multipart<'a>(
+    body: &'a [u8],
+    boundary: &str,
+) -> Result<Vec<MultipartPart<'a>>, GraphQLRequestError> {
+    let delimiter = format!("--{}", boundary).into_bytes();
+    let separator = [&b"\r\n"[..], &delimiter].concat();
+
+    let mut rest = if body.starts_with(&delimiter) {
+        &body[delimiter.len()..]
+    } else {
+        let start = try!(
+            find_bytes(body, &separator).ok_or_else(|| multipart_error("Missing boundary"))
+        );
+        &body[start + separator.len()..]
+    };
+
+    let mut parts = Vec::new();
+
+    loop {
+        if rest.starts_with(b"--") {
+            return Ok(parts);
+        }
+        if !rest.starts_with(b"\r\n") {
+            return Err(multipart_error("Malformed boundary"));
+        }
+        rest = &rest[2..];
+
+        let headers_end = try!(
+            find_bytes(rest, b"\r\n\r\n").ok_or_else(|| multipart_error("Malformed part headers"))
+        );
+        let headers = try!(
+            str::from_utf8(&rest[..headers_end])
+                .map_err(|_| multipart_error("Malformed part headers"))
+        );
+        rest = &rest[headers_end + 4..];
+
+        let content_end = try!(
+            find_bytes(rest, &separator).ok_or_else(|| multipart_error("Missing closing boundary"))
+        );
+        let content = &rest[..content_end];
+        rest = &rest[content_end + separator.len()..];
+
+        let mut name = None;
+        let mut filename = None;
+        let mut content_type = None;
+
+        for header in headers.split("\r\n") {
+            let mut pair = header.splitn(2, ':');
+            let (key, value) = match (pair.next(), pair.next()) {
+                (Some(k), Some(v)) => (k.trim().to_ascii_lowercase(), v.trim()),
+                _ => return Err(multipart_error("Malformed part headers")),
+            };
+
+            if key == "content-disposition" {
+                for (param, value) in header_params(value) {
+                    match param.to_ascii_lowercase().as_str() {
+                        "name" => name = Some(value),
+                        "filename" => filename = Some(value),
+                        _ => (),
+                    }
+                }
+            } else if key == "content-type" {
+                content_type = Some(value.to_owned());
+            }
+        }
+
+        parts.push(MultipartPart {
+            name: try!(name.ok_or_else(|| multipart_error("Part without a name"))),
+            filename: filename,
+            content_type: content_type,
+            content: content,
+        });
+    }
+}
+
+/// The parameters of a header value like `form-data; name="file"`, where
+/// values may be quoted
+fn header_params(value: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut chars = value.chars().peekable();
+
+    // Skip the value before the first parameter
+    while chars.next().map_or(false, |c| c != ';') {}
+
+    loop {
+        let name = chars
+            .by_ref()
+            .take_while(|&c| c != '=')
+            .collect::<String>()
+            .trim()
+            .to_owned();
+        if name.is_empty() {
+            return params;
+        }
+
+        while chars.peek() == Some(&' ') {
+            chars.next();
+        }
+
+        let mut param_value = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => param_value.extend(chars.next()),
+                    c => param_value.push(c),
+                }
+            }
+            while chars.next().map_or(false, |c| c != ';') {}
+        } else {
+            param_value = chars
+                .by_ref()
+                .take_while(|&c| c != ';')
+                .collect::<String>()
+                .trim()
+                .to_owned();
+        }
+
+        params.push((name, param_value));
+    }
+}
+
+/// Replace the null at the given path in the variables with an upload
+fn insert_upload(value: &mut InputValue, path: &[&str], upload: &InputValue) -> bool {
+    match path.split_first() {
+        None => if value.is_null() {
+            *value = upload.clone();
+            true
+        } else {
+            false
+        },
+        Some((segment, rest)) => match *value {
+            InputValue::Object(ref mut fields) => fields
+                .iter_mut()
+                .find(|&&mut (ref key, _)| key.item == *segment)
+                .map_or(false, |&mut (_, ref mut v)| insert_upload(&mut v.item, rest, upload)),
+            InputValue::List(ref mut items) => segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| items.get_mut(i))
+                .map_or(false, |v| insert_upload(&mut v.item, rest, upload)),
+            _ => false,
+        },
+    }
+}
+
+/// The media types a GraphQL response can be sent as, following the
+/// GraphQL over HTTP specification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseContentType {
+    /// `application/graphql-response+json`, where request errors are sent
+    /// with a `4xx` status code
+    GraphQLResponseJson,
+    /// `application/json`, where all responses are sent with `200 OK` for
+    /// compatibility with older clients
+    Json,
+}
+
+impl ResponseContentType {
+    /// Pick the content type from the value of the `Accept` header
+    ///
+    /// Requests without an `Accept` header, or accepting either type only
+    /// through wildcards, get `application/json`. Returns `None` if neither
+    /// type is acceptable, which should be answered with `406 Not
+    /// Acceptable`.
+    pub fn negotiate(accept: Option<&str>) -> Option<ResponseContentType> {
+        let accept = match accept {
+            Some(accept) if !accept.trim().is_empty() => accept,
+            _ => return Some(ResponseContentType::Json),
+        };
+
+        let graphql_response = accepted_quality(accept, "application/graphql-response+json");
+        let json = accepted_quality(accept, "application/json");
+
+        match (graphql_response, json) {
+            (None, None) => None,
+            (Some(_), None) => Some(ResponseContentType::GraphQLResponseJson),
+            (None, Some(_)) => Some(ResponseContentType::Json),
+            (Some((q1, specificity)), Some((q2, _))) => {
+                if q1 > q2 || (q1 == q2 && specificity == 2) {
+                    Some(ResponseContentType::GraphQLResponseJson)
+                } else {
+                    Some(ResponseContentType::Json)
+                }
+            }
+        }
+    }
+
+    /// The value of the `Content-Type` header
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ResponseContentType::GraphQLResponseJson => "application/graphql-response+json",
+            ResponseContentType::Json => "application/json",
+        }
+    }
+}
+
+/// The quality and specificity of the most specific media range in the
+/// `Accept` header matching the media type, if it's acceptable
+fn accepted_quality(accept: &str, media_type: &str) -> Option<(f32, u8)> {
+    let main_type = media_type.split('/').next().unwrap_or("");
+    let mut best: Option<(f32, u8)> = None;
+
+    for range in accept.split(',') {
+        let mut params = range.split(';');
+        let range_type = params.next().unwrap_or("").trim().to_ascii_lowercase();
+
+        let specificity = if range_type == media_type {
+            2
+        } else if range_type == format!("{}/*", main_type) {
+            1
+        } else if range_type == "*/*" {
+            0
+        } else {
+            continue;
+        };
+
+        let quality = params
+            .filter_map(|p| {
+                let mut pair = p.splitn(2, '=');
+                match (pair.next(), pair.next()) {
+                    (Some(k), Some(v)) if k.trim().eq_ignore_ascii_case("q") => {
+                        v.trim().parse::<f32>().ok()
+                    }
+                    _ => None,
+                }
+            })
+            .next()
+            .unwrap_or(1.0);
+
+        if best.map_or(true, |(_, s)| specificity > s) {
+            best = Some((quality, specificity));
+        }
+    }
+
+    best.filter(|&(quality, _)| quality > 0.0)
+}
+
 /// Simple wrapper around the result from executing a GraphQL query
 ///
 /// This struct implements Serialize, so you can simply serialize this
-/// to JSON and send it over the wire. Use the `is_ok` method to determine
-/// whether to send a 200 or 400 HTTP status code.
+/// to JSON and send it over the wire. Use `status_and_content_type` to pick
+/// the HTTP status code and `Content-Type` header of the response.
 pub struct GraphQLResponse<'a>(Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>);
 
 impl<'a> GraphQLResponse<'a> {
@@ -91,6 +747,31 @@ impl<'a> GraphQLResponse<'a> {
     pub fn is_ok(&self) -> bool {
         self.0.is_ok()
     }
+
+    /// The HTTP status code to send the response with as the given content
+    /// type
+    ///
+    /// Requests that fail before execution, e.g. because the query is
+    /// invalid, get `400 Bad Request` as `application/graphql-response+json`.
+    /// Everything else gets `200 OK`, even if there are execution errors.
+    pub fn status_code(&self, content_type: ResponseContentType) -> u16 {
+        match (content_type, &self.0) {
+            (ResponseContentType::GraphQLResponseJson, &Err(_)) => 400,
+            _ => 200,
+        }
+    }
+
+    /// The HTTP status code and `Content-Type` header to send the response
+    /// with, given the value of the request's `Accept` header
+    ///
+    /// If the client accepts neither JSON content type, this is
+    /// `406 Not Acceptable` with `application/json`.
+    pub fn status_and_content_type(&self, accept: Option<&str>) -> (u16, &'static str) {
+        match ResponseContentType::negotiate(accept) {
+            Some(content_type) => (self.status_code(content_type), content_type.as_str()),
+            None => (406, ResponseContentType::Json.as_str()),
+        }
+    }
 }
 
 impl<'a> ser::Serialize for GraphQLResponse<'a> {
@@ -136,6 +817,13 @@ pub mod tests {
     pub trait HTTPIntegration {
         fn get(&self, url: &str) -> TestResponse;
         fn post(&self, url: &str, body: &str) -> TestResponse;
+
+        /// Send a POST request with an `application/graphql` body
+        ///
+        /// Integrations returning `None` skip the tests of such requests.
+        fn post_graphql(&self, _url: &str, _body: &str) -> Option<TestResponse> {
+            None
+        }
     }
 
     pub fn run_http_test_suite<T: HTTPIntegration>(integration: &T) {
@@ -152,6 +840,24 @@ pub mod tests {
 
         println!("  - test_simple_post");
         test_simple_post(integration);
+
+        println!("  - test_post_with_operation_name");
+        test_post_with_operation_name(integration);
+
+        println!("  - test_graphql_post");
+        test_graphql_post(integration);
+
+        println!("  - test_get_without_query");
+        test_get_without_query(integration);
+
+        println!("  - test_get_with_invalid_variables");
+        test_get_with_invalid_variables(integration);
+
+        println!("  - test_mutation_over_get");
+        test_mutation_over_get(integration);
+
+        println!("  - test_invalid_json_post");
+        test_invalid_json_post(integration);
     }
 
     fn unwrap_json_response(response: &TestResponse) -> Json {
@@ -244,4 +950,91 @@ pub mod tests {
                 .expect("Invalid JSON constant in test")
         );
     }
+
+    fn test_post_with_operation_name<T: HTTPIntegration>(integration: &T) {
+        let response = integration.post(
+            "/",
+            r#"{
+                "query": "query Luke($id: String!) { human(id: $id) { name } } query Hero { hero { name } }",
+                "operationName": "Luke",
+                "variables": {"id": "1000"}
+            }"#,
+        );
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{"data": {"human": {"name": "Luke Skywalker"}}}"#)
+                .expect("Invalid JSON constant in test")
+        );
+    }
+
+    fn test_graphql_post<T: HTTPIntegration>(integration: &T) {
+        let response = match integration.post_graphql("/", "{hero{name}}") {
+            Some(response) => response,
+            None => {
+                println!("    skipped, the integration doesn't send application/graphql");
+                return;
+            }
+        };
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+                .expect("Invalid JSON constant in test")
+        );
+    }
+
+    fn test_get_without_query<T: HTTPIntegration>(integration: &T) {
+        let response = integration.get("/?operationName=Hero");
+
+        assert_eq!(response.status_code, 400);
+        assert_eq!(response.content_type, "application/json");
+        assert_error_message(&response, "Missing query");
+    }
+
+    fn test_get_with_invalid_variables<T: HTTPIntegration>(integration: &T) {
+        let response = integration.get("/?query={hero{name}}&variables=[1,2]");
+
+        assert_eq!(response.status_code, 400);
+        assert_eq!(response.content_type, "application/json");
+        assert_error_message(&response, "Invalid variables: Variables must be an object");
+    }
+
+    fn test_mutation_over_get<T: HTTPIntegration>(integration: &T) {
+        let response = integration.get("/?query=mutation%20{%20hero%20{%20name%20}%20}");
+
+        assert_eq!(response.status_code, 405);
+        assert_eq!(response.content_type, "application/json");
+        assert_error_message(&response, "Mutations can only be executed with POST requests");
+    }
+
+    fn test_invalid_json_post<T: HTTPIntegration>(integration: &T) {
+        let response = integration.post("/", r#"{"query": "#);
+
+        assert_eq!(response.status_code, 400);
+        assert_eq!(response.content_type, "application/json");
+    }
+
+    fn assert_error_message(response: &TestResponse, message: &str) {
+        assert_eq!(
+            unwrap_json_response(response),
+            json_error(message)
+        );
+    }
+
+    fn json_error(message: &str) -> Json {
+        let mut error = serde_json::Map::new();
+        error.insert("message".to_owned(), Json::String(message.to_owned()));
+
+        let mut response = serde_json::Map::new();
+        response.insert("errors".to_owned(), Json::Array(vec![Json::Object(error)]));
+
+        Json::Object(response)
+    }
 }
@@ -6,13 +6,43 @@
 |-------------------------|------------------------|-------------------------------------------|
 | `DateTime<FixedOffset>` | RFC3339 string         |                                           |
 | `DateTime<Utc>`         | RFC3339 string         |                                           |
-| `NaiveDate`             | RFC3339 string         |                                           |
+| `DateTime<Local>`       | RFC3339 string         | Input is converted to the local timezone. |
+| `NaiveDate`             | RFC3339 full-date      | e.g. `2014-11-28`, input can also be a    |
+|                         |                        | full RFC3339 string.                      |
+| `NaiveTime`             | RFC3339 partial-time   | e.g. `14:30:00.25`                        |
 | `NaiveDateTime`         | float (unix timestamp) | JSON numbers (i.e. IEEE doubles) are not  |
 |                         |                        | precise enough for nanoseconds.           |
-|                         |                        | Values will be truncated to microsecond   |
-|                         |                        | resolution.                               |
+|                         |                        | Values are truncated to whole seconds.    |
+| `Duration`              | ISO 8601 string        | e.g. `P1DT2H30M`, without years or months |
+|                         |                        | since their length varies.                |
+
+# Features
+
+With the `chrono-naive-rfc3339` feature, `NaiveDateTime` is serialized as an
+RFC3339 string without an offset instead, e.g. `2014-11-28T21:00:09.005`,
+which keeps nanosecond precision.
+
+With the `chrono-scalar-names` feature, the types are named after the
+[GraphQL Scalars](https://www.graphql-scalars.com) specifications. It enables
+`chrono-naive-rfc3339`.
+
+| Rust Type               | Default name          | `chrono-scalar-names` |
+|-------------------------|-----------------------|-----------------------|
+| `DateTime<FixedOffset>` | `DateTimeFixedOffset` | `DateTime`            |
+| `DateTime<Utc>`         | `DateTimeUtc`         | `DateTime`            |
+| `DateTime<Local>`       | `DateTimeLocal`       | `DateTime`            |
+| `NaiveDate`             | `NaiveDate`           | `Date`                |
+| `NaiveTime`             | `NaiveTime`           | `Time`                |
+| `NaiveDateTime`         | `NaiveDateTime`       | `LocalDateTime`       |
+| `Duration`              | `Duration`            | `Duration`            |
+
+Since the `DateTime` types share a name, a schema can only use one of them
+with `chrono-scalar-names`.
 
 */
+use std::fmt::Write;
+
+use chrono::Duration;
 use chrono::prelude::*;
 
 use ::Value;
@@ -20,8 +50,32 @@ use ::Value;
 #[doc(hidden)]
 pub static RFC3339_FORMAT: &'static str = "%Y-%m-%dT%H:%M:%S%.f%:z";
 static RFC3339_PARSE_FORMAT: &'static str = "%+";
+static FULL_DATE_FORMAT: &'static str = "%Y-%m-%d";
+static PARTIAL_TIME_FORMAT: &'static str = "%H:%M:%S%.f";
+#[cfg(feature = "chrono-naive-rfc3339")]
+static NAIVE_DATETIME_FORMAT: &'static str = "%Y-%m-%dT%H:%M:%S%.f";
+
+#[cfg(not(feature = "chrono-scalar-names"))]
+mod names {
+    pub static DATETIME_FIXED_OFFSET: &'static str = "DateTimeFixedOffset";
+    pub static DATETIME_UTC: &'static str = "DateTimeUtc";
+    pub static DATETIME_LOCAL: &'static str = "DateTimeLocal";
+    pub static NAIVE_DATE: &'static str = "NaiveDate";
+    pub static NAIVE_TIME: &'static str = "NaiveTime";
+    pub static NAIVE_DATETIME: &'static str = "NaiveDateTime";
+}
+
+#[cfg(feature = "chrono-scalar-names")]
+mod names {
+    pub static DATETIME_FIXED_OFFSET: &'static str = "DateTime";
+    pub static DATETIME_UTC: &'static str = "DateTime";
+    pub static DATETIME_LOCAL: &'static str = "DateTime";
+    pub static NAIVE_DATE: &'static str = "Date";
+    pub static NAIVE_TIME: &'static str = "Time";
+    pub static NAIVE_DATETIME: &'static str = "LocalDateTime";
+}
 
-graphql_scalar!(DateTime<FixedOffset> as "DateTimeFixedOffset" {
+graphql_scalar!(DateTime<FixedOffset> as (names::DATETIME_FIXED_OFFSET) {
     description: "DateTime"
 
     resolve(&self) -> Value {
@@ -34,7 +88,7 @@ graphql_scalar!(DateTime<FixedOffset> as "DateTimeFixedOffset" {
     }
 });
 
-graphql_scalar!(DateTime<Utc> as "DateTimeUtc" {
+graphql_scalar!(DateTime<Utc> as (names::DATETIME_UTC) {
     description: "DateTime"
 
     resolve(&self) -> Value {
@@ -47,27 +101,58 @@ graphql_scalar!(DateTime<Utc> as "DateTimeUtc" {
     }
 });
 
+graphql_scalar!(DateTime<Local> as (names::DATETIME_LOCAL) {
+    description: "DateTime"
+
+    resolve(&self) -> Value {
+        Value::string(self.to_rfc3339())
+    }
+
+    from_input_value(v: &InputValue) -> Option<DateTime<Local>> {
+        v.as_string_value()
+         .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+         .map(|d| d.with_timezone(&Local))
+    }
+});
+
 // Don't use `Date` as the docs say:
 // "[Date] should be considered ambiguous at best, due to the "
 // inherent lack of precision required for the time zone resolution.
 // For serialization and deserialization uses, it is best to use
 // `NaiveDate` instead."
-graphql_scalar!(NaiveDate {
+graphql_scalar!(NaiveDate as (names::NAIVE_DATE) {
     description: "NaiveDate"
 
     resolve(&self) -> Value {
-        Value::string(self.format(RFC3339_FORMAT).to_string())
+        Value::string(self.format(FULL_DATE_FORMAT).to_string())
     }
 
     from_input_value(v: &InputValue) -> Option<NaiveDate> {
+        v.as_string_value().and_then(|s| {
+            NaiveDate::parse_from_str(s, FULL_DATE_FORMAT)
+                .or_else(|_| NaiveDate::parse_from_str(s, RFC3339_PARSE_FORMAT))
+                .ok()
+        })
+    }
+});
+
+graphql_scalar!(NaiveTime as (names::NAIVE_TIME) {
+    description: "NaiveTime"
+
+    resolve(&self) -> Value {
+        Value::string(self.format(PARTIAL_TIME_FORMAT).to_string())
+    }
+
+    from_input_value(v: &InputValue) -> Option<NaiveTime> {
         v.as_string_value()
-         .and_then(|s| NaiveDate::parse_from_str(s, RFC3339_PARSE_FORMAT).ok())
+         .and_then(|s| NaiveTime::parse_from_str(s, PARTIAL_TIME_FORMAT).ok())
     }
 });
 
 /// JSON numbers (i.e. IEEE doubles) are not precise enough for nanosecond
-/// datetimes. Values will be truncated to microsecond resolution.
-graphql_scalar!(NaiveDateTime {
+/// datetimes. Values are truncated to whole seconds.
+#[cfg(not(feature = "chrono-naive-rfc3339"))]
+graphql_scalar!(NaiveDateTime as (names::NAIVE_DATETIME) {
     description: "NaiveDateTime"
 
     resolve(&self) -> Value {
@@ -80,10 +165,168 @@ graphql_scalar!(NaiveDateTime {
     }
 });
 
+#[cfg(feature = "chrono-naive-rfc3339")]
+graphql_scalar!(NaiveDateTime as (names::NAIVE_DATETIME) {
+    description: "NaiveDateTime"
+
+    resolve(&self) -> Value {
+        Value::string(self.format(NAIVE_DATETIME_FORMAT).to_string())
+    }
+
+    from_input_value(v: &InputValue) -> Option<NaiveDateTime> {
+        v.as_string_value()
+         .and_then(|s| NaiveDateTime::parse_from_str(s, NAIVE_DATETIME_FORMAT).ok())
+    }
+});
+
+graphql_scalar!(Duration {
+    description: "Duration"
+
+    resolve(&self) -> Value {
+        Value::string(format_duration(self))
+    }
+
+    from_input_value(v: &InputValue) -> Option<Duration> {
+        v.as_string_value().and_then(parse_duration)
+    }
+});
+
+/// Format a duration in ISO 8601, e.g. `P1DT2H30M4.5S`, with days as the
+/// largest unit
+fn format_duration(duration: &Duration) -> String {
+    let negative = *duration < Duration::zero();
+    let duration = if negative { -*duration } else { *duration };
+
+    let seconds = duration.num_seconds();
+    let nanos = (duration - Duration::seconds(seconds))
+        .num_nanoseconds()
+        .unwrap_or(0);
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        seconds % 86_400 / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+    );
+
+    let mut s = String::from(if negative { "-P" } else { "P" });
+
+    if days > 0 {
+        write!(s, "{}D", days).unwrap();
+    }
+    if days == 0 || hours > 0 || minutes > 0 || seconds > 0 || nanos > 0 {
+        s.push('T');
+
+        if hours > 0 {
+            write!(s, "{}H", hours).unwrap();
+        }
+        if minutes > 0 {
+            write!(s, "{}M", minutes).unwrap();
+        }
+        if seconds > 0 || nanos > 0 || (hours == 0 && minutes == 0) {
+            write!(s, "{}", seconds).unwrap();
+
+            if nanos > 0 {
+                write!(s, ".{}", format!("{:09}", nanos).trim_end_matches('0')).unwrap();
+            }
+
+            s.push('S');
+        }
+    }
+
+    s
+}
+
+/// Parse an ISO 8601 duration in weeks, days, hours, minutes, and seconds
+///
+/// Years and months are refused since their length varies.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let (negative, s) = match s.chars().next() {
+        Some('-') => (true, &s[1..]),
+        Some('+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+
+    if !s.starts_with('P') {
+        return None;
+    }
+
+    let mut parts = s[1..].splitn(2, 'T');
+    let date = parts.next().unwrap_or("");
+    let time = parts.next();
+
+    if time == Some("") || (date.is_empty() && time.is_none()) {
+        return None;
+    }
+
+    let mut seconds = 0;
+    let mut nanos = 0;
+
+    add_duration_components(date, &[('W', 604_800), ('D', 86_400)], &mut seconds, &mut nanos)?;
+    if let Some(time) = time {
+        add_duration_components(
+            time,
+            &[('H', 3600), ('M', 60), ('S', 1)],
+            &mut seconds,
+            &mut nanos,
+        )?;
+    }
+
+    // `Duration` holds milliseconds in an `i64`
+    if seconds > i64::max_value() / 1000 - 1 {
+        return None;
+    }
+
+    let duration = Duration::seconds(seconds) + Duration::nanoseconds(nanos);
+    Some(if negative { -duration } else { duration })
+}
+
+/// Add up components like `2H30M` in the order of the units, where only
+/// seconds can have a fraction
+fn add_duration_components(
+    part: &str,
+    units: &[(char, i64)],
+    seconds: &mut i64,
+    nanos: &mut i64,
+) -> Option<()> {
+    let mut units = units.iter();
+    let mut rest = part;
+
+    while !rest.is_empty() {
+        let end = rest.find(|c: char| !c.is_ascii_digit() && c != '.' && c != ',')?;
+        let unit = rest[end..].chars().next()?;
+        let &(_, unit_seconds) = units.find(|&&(u, _)| u == unit)?;
+
+        let mut number = rest[..end].splitn(2, |c| c == '.' || c == ',');
+        rest = &rest[end + unit.len_utf8()..];
+
+        let whole = number.next().unwrap_or("");
+        if whole.is_empty() || !whole.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let whole = whole.parse::<i64>().ok()?.checked_mul(unit_seconds)?;
+        *seconds = seconds.checked_add(whole)?;
+
+        if let Some(fraction) = number.next() {
+            let valid = unit_seconds == 1 && !fraction.is_empty() && fraction.len() <= 9
+                && fraction.chars().all(|c| c.is_ascii_digit());
+
+            if !valid {
+                return None;
+            }
+
+            *nanos = format!("{:0<9}", fraction).parse().ok()?;
+        }
+    }
+
+    Some(())
+}
+
 #[cfg(test)]
 mod test {
+    use chrono::Duration;
     use chrono::prelude::*;
     use super::RFC3339_PARSE_FORMAT;
+    use {GraphQLType, ToInputValue};
 
     fn datetime_fixedoffset_test(raw: &'static str) {
         let input = ::InputValue::String(raw.to_string());
@@ -169,6 +412,15 @@ mod test {
     }
 
     #[test]
+    fn naivedate_round_trip() {
+        let date = NaiveDate::from_ymd(2014, 11, 28);
+
+        assert_eq!(date.to_input_value(), ::InputValue::string("2014-11-28"));
+        assert_eq!(from_string("2014-11-28"), Some(date));
+    }
+
+    #[test]
+    #[cfg(not(feature = "chrono-naive-rfc3339"))]
     fn naivedatetime_from_input_value() {
         let raw = 1_000_000_000_f64;
         let input = ::InputValue::Float(raw);
@@ -179,4 +431,120 @@ mod test {
         assert_eq!(parsed, expected);
         assert_eq!(raw, expected.timestamp() as f64);
     }
+
+    #[test]
+    #[cfg(feature = "chrono-naive-rfc3339")]
+    fn naivedatetime_rfc3339_round_trip() {
+        let datetime = NaiveDate::from_ymd(2014, 11, 28).and_hms_nano(21, 0, 9, 5_000_001);
+        let resolved = ::InputValue::string("2014-11-28T21:00:09.005000001");
+
+        assert_eq!(datetime.to_input_value(), resolved);
+        assert_eq!(
+            from_string::<NaiveDateTime>("2014-11-28T21:00:09.005000001"),
+            Some(datetime)
+        );
+        assert_eq!(
+            from_string::<NaiveDateTime>("2014-11-28T21:00:09"),
+            Some(NaiveDate::from_ymd(2014, 11, 28).and_hms(21, 0, 9))
+        );
+        assert_eq!(from_string::<NaiveDateTime>("2014-11-28T21:00:09Z"), None);
+    }
+
+    fn from_string<T: ::FromInputValue>(raw: &str) -> Option<T> {
+        T::from_input_value(&::InputValue::string(raw))
+    }
+
+    #[test]
+    fn datetime_local_from_input_value() {
+        let parsed: DateTime<Local> = from_string("2014-11-28T21:00:09.05+09:00").unwrap();
+        let expected = DateTime::parse_from_rfc3339("2014-11-28T21:00:09.05+09:00").unwrap();
+
+        assert_eq!(parsed, expected);
+        assert_eq!(from_string::<DateTime<Local>>("2014-11-28"), None);
+    }
+
+    #[test]
+    fn naivetime_round_trip() {
+        assert_eq!(
+            NaiveTime::from_hms(14, 30, 0).to_input_value(),
+            ::InputValue::string("14:30:00")
+        );
+        assert_eq!(
+            NaiveTime::from_hms_milli(14, 30, 0, 250).to_input_value(),
+            ::InputValue::string("14:30:00.250")
+        );
+
+        assert_eq!(from_string("14:30:00"), Some(NaiveTime::from_hms(14, 30, 0)));
+        assert_eq!(
+            from_string("14:30:00.25"),
+            Some(NaiveTime::from_hms_milli(14, 30, 0, 250))
+        );
+        assert_eq!(from_string::<NaiveTime>("14:30"), None);
+        assert_eq!(from_string::<NaiveTime>("25:00:00"), None);
+    }
+
+    #[test]
+    fn duration_to_iso8601() {
+        let cases = vec![
+            (Duration::zero(), "PT0S"),
+            (Duration::days(2), "P2D"),
+            (Duration::minutes(90), "PT1H30M"),
+            (Duration::milliseconds(1500), "PT1.5S"),
+            (Duration::nanoseconds(1), "PT0.000000001S"),
+            (Duration::days(1) + Duration::seconds(3), "P1DT3S"),
+            (-Duration::seconds(3725), "-PT1H2M5S"),
+        ];
+
+        for (duration, expected) in cases {
+            assert_eq!(duration.to_input_value(), ::InputValue::string(expected));
+        }
+    }
+
+    #[test]
+    fn duration_from_iso8601() {
+        let cases = vec![
+            ("PT0S", Duration::zero()),
+            ("P2W", Duration::weeks(2)),
+            ("P1DT12H", Duration::hours(36)),
+            ("PT90M", Duration::minutes(90)),
+            ("PT1.5S", Duration::milliseconds(1500)),
+            ("PT0,25S", Duration::milliseconds(250)),
+            ("-PT1H2M5S", -Duration::seconds(3725)),
+            ("+P1D", Duration::days(1)),
+        ];
+
+        for (raw, expected) in cases {
+            assert_eq!(from_string(raw), Some(expected), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn duration_from_invalid_iso8601() {
+        let cases = vec![
+            "", "P", "PT", "P1DT", "1D", "P1Y", "P1M", "PT1.5H", "PT1S2M", "P1D1W", "PT-1S",
+            "PT1.S", "PT1.0000000001S", "P99999999999999999999D", "P106751991167301D",
+        ];
+
+        for raw in cases {
+            assert_eq!(from_string::<Duration>(raw), None, "{}", raw);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "chrono-scalar-names"))]
+    fn default_names() {
+        assert_eq!(<DateTime<Local> as GraphQLType>::name(&()), Some("DateTimeLocal"));
+        assert_eq!(<NaiveTime as GraphQLType>::name(&()), Some("NaiveTime"));
+        assert_eq!(<Duration as GraphQLType>::name(&()), Some("Duration"));
+    }
+
+    #[test]
+    #[cfg(feature = "chrono-scalar-names")]
+    fn graphql_scalars_names() {
+        assert_eq!(<DateTime<Utc> as GraphQLType>::name(&()), Some("DateTime"));
+        assert_eq!(<NaiveDate as GraphQLType>::name(&()), Some("Date"));
+        assert_eq!(<NaiveTime as GraphQLType>::name(&()), Some("Time"));
+        assert_eq!(<NaiveDateTime as GraphQLType>::name(&()), Some("LocalDateTime"));
+        assert_eq!(<Duration as GraphQLType>::name(&()), Some("Duration"));
+    }
 }
@@ -0,0 +1,319 @@
+use ordermap::OrderMap;
+use serde_json::{self, Number, Value as Json};
+
+use ::{Executor, FromInputValue, GraphQLType, InputValue, InputValueError, Registry, Selection,
+       ToInputValue, Value};
+use schema::meta::MetaType;
+
+/// Integers in the `i32` range become `Int`, all other numbers `Float`, which
+/// is exact for integers up to 2^53.
+impl From<Json> for Value {
+    fn from(json: Json) -> Value {
+        match json {
+            Json::Null => Value::null(),
+            Json::Bool(b) => Value::boolean(b),
+            Json::Number(n) => match small_int(&n) {
+                Some(i) => Value::int(i),
+                None => Value::float(n.as_f64().expect("JSON numbers are finite")),
+            },
+            Json::String(s) => Value::string(s),
+            Json::Array(l) => Value::list(l.into_iter().map(Value::from).collect()),
+            Json::Object(o) => Value::object(
+                o.into_iter()
+                    .map(|(k, v)| (k, Value::from(v)))
+                    .collect::<OrderMap<_, _>>(),
+            ),
+        }
+    }
+}
+
+/// Floats holding integers outside the `i32` range become integers again, so
+/// converting JSON to a `Value` and back is lossless for integers up to 2^53.
+/// Non-finite floats, which JSON can't represent, become `null`.
+impl From<Value> for Json {
+    fn from(value: Value) -> Json {
+        match value {
+            Value::Null => Json::Null,
+            Value::Int(i) => Json::Number(i.into()),
+            Value::Float(f) => json_from_float(f),
+            Value::String(s) => Json::String(s),
+            Value::Boolean(b) => Json::Bool(b),
+            Value::List(l) => Json::Array(l.into_iter().map(Json::from).collect()),
+            Value::Object(o) => Json::Object(o.into_iter().map(|(k, v)| (k, Json::from(v))).collect()),
+        }
+    }
+}
+
+/// Numbers are converted like for `Value`.
+impl From<Json> for InputValue {
+    fn from(json: Json) -> InputValue {
+        match json {
+            Json::Null => InputValue::null(),
+            Json::Bool(b) => InputValue::boolean(b),
+            Json::Number(n) => match small_int(&n) {
+                Some(i) => InputValue::int(i),
+                None => InputValue::float(n.as_f64().expect("JSON numbers are finite")),
+            },
+            Json::String(s) => InputValue::string(s),
+            Json::Array(l) => InputValue::list(l.into_iter().map(InputValue::from).collect()),
+            Json::Object(o) => InputValue::object(
+                o.into_iter()
+                    .map(|(k, v)| (k, InputValue::from(v)))
+                    .collect::<OrderMap<_, _>>(),
+            ),
+        }
+    }
+}
+
+/// Floats are converted like for `Value`, and enum values become strings.
+/// Variables and uploads, which have no JSON representation, become `null`,
+/// like when serializing an `InputValue`.
+impl From<InputValue> for Json {
+    fn from(value: InputValue) -> Json {
+        match value {
+            InputValue::Null | InputValue::Variable(_) | InputValue::Upload(_) => Json::Null,
+            InputValue::Int(i) => Json::Number(i.into()),
+            InputValue::Float(f) => json_from_float(f),
+            InputValue::String(s) | InputValue::Enum(s) => Json::String(s),
+            InputValue::Boolean(b) => Json::Bool(b),
+            InputValue::List(l) => Json::Array(l.into_iter().map(|v| Json::from(v.item)).collect()),
+            InputValue::Object(o) => Json::Object(
+                o.into_iter()
+                    .map(|(k, v)| (k.item, Json::from(v.item)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+fn json_from_float(f: f64) -> Json {
+    let max_exact = (1u64 << 53) as f64;
+    let is_int = f.fract() == 0.0 && f.abs() <= max_exact;
+
+    if is_int && (f < i32::min_value() as f64 || f > i32::max_value() as f64) {
+        Json::Number((f as i64).into())
+    } else {
+        Number::from_f64(f).map_or(Json::Null, Json::Number)
+    }
+}
+
+fn small_int(n: &Number) -> Option<i32> {
+    n.as_i64()
+        .filter(|&i| i >= i32::min_value() as i64 && i <= i32::max_value() as i64)
+        .map(|i| i as i32)
+}
+
+/// Converts literals and variables to JSON, but not enum values, since
+/// `{kind: FOO}` is most likely a typo for `{kind: "FOO"}`.
+fn json_from_input_value(v: &InputValue) -> Option<Json> {
+    match *v {
+        InputValue::Null => Some(Json::Null),
+        InputValue::Int(i) => Some(Json::Number(i.into())),
+        InputValue::Float(f) => Number::from_f64(f).map(Json::Number),
+        InputValue::String(ref s) => Some(Json::String(s.clone())),
+        InputValue::Boolean(b) => Some(Json::Bool(b)),
+        InputValue::List(ref l) => l.iter()
+            .map(|v| json_from_input_value(&v.item))
+            .collect::<Option<Vec<_>>>()
+            .map(Json::Array),
+        InputValue::Object(ref o) => o.iter()
+            .map(|&(ref k, ref v)| json_from_input_value(&v.item).map(|v| (k.item.clone(), v)))
+            .collect::<Option<serde_json::Map<_, _>>>()
+            .map(Json::Object),
+        InputValue::Enum(_) | InputValue::Variable(_) | InputValue::Upload(_) => None,
+    }
+}
+
+// Implemented without `graphql_scalar!`, which can't build a scalar that
+// takes lists and objects
+impl GraphQLType for Json {
+    type Context = ();
+    type TypeInfo = ();
+
+    fn name(_: &()) -> Option<&str> {
+        Some("JSON")
+    }
+
+    fn meta<'r>(info: &(), registry: &mut Registry<'r>) -> MetaType<'r> {
+        registry
+            .build_scalar_type::<Self>(info)
+            .description("Arbitrary JSON, given as a literal or variable of any shape")
+            .accept_lists_and_objects()
+            .into_meta()
+    }
+
+    fn resolve(&self, _: &(), _: Option<&[Selection]>, _: &Executor<Self::Context>) -> Value {
+        Value::from(self.clone())
+    }
+}
+
+impl ToInputValue for Json {
+    fn to_input_value(&self) -> InputValue {
+        InputValue::from(self.clone())
+    }
+}
+
+impl FromInputValue for Json {
+    fn from_input_value(v: &InputValue) -> Option<Json> {
+        json_from_input_value(v)
+    }
+
+    fn try_from_input_value(v: &InputValue) -> Result<Json, InputValueError> {
+        json_from_input_value(v).ok_or_else(|| InputValueError::expected("JSON", v))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{self, Value as Json};
+
+    use executor::Variables;
+    use parser::SourcePosition;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+    use validation::RuleError;
+    use {GraphQLError, InputValue, Value};
+
+    fn json(s: &str) -> Json {
+        serde_json::from_str(s).expect("Invalid JSON constant in test")
+    }
+
+    #[test]
+    fn json_value_conversions_round_trip() {
+        let original = json(
+            r#"{"int": -12, "big": 5000000000, "float": 1.5, "string": "a", "bool": true,
+                "null": null, "list": [1, [2, {"a": []}]]}"#,
+        );
+
+        let value = Value::from(original.clone());
+        assert_eq!(value.as_object_value().unwrap().get("int"), Some(&Value::int(-12)));
+        assert_eq!(
+            value.as_object_value().unwrap().get("big"),
+            Some(&Value::float(5000000000.0))
+        );
+        assert_eq!(Json::from(value), original);
+
+        let input = InputValue::from(original.clone());
+        assert_eq!(input.to_object_value().unwrap().get("int"), Some(&&InputValue::int(-12)));
+        assert_eq!(Json::from(input), original);
+    }
+
+    #[test]
+    fn json_from_non_json_values() {
+        assert_eq!(Json::from(Value::float(::std::f64::NAN)), Json::Null);
+        assert_eq!(Json::from(InputValue::enum_value("FOO")), Json::String("FOO".to_owned()));
+        assert_eq!(Json::from(InputValue::variable("foo")), Json::Null);
+    }
+
+    struct Query;
+
+    /// A scalar that would take any value
+    struct Anything;
+
+    graphql_scalar!(Anything {
+        resolve(&self) -> Value {
+            Value::null()
+        }
+
+        from_input_value(_v: &InputValue) -> Option<Anything> {
+            Some(Anything)
+        }
+    });
+
+    graphql_object!(Query: () |&self| {
+        field echo(value: Json) -> Json { value }
+
+        field anything(value: Anything) -> bool { true }
+
+        field settings() -> Json {
+            json(r#"{"theme": "dark", "flags": {"beta": true}, "limits": [1, 2.5]}"#)
+        }
+    });
+
+    fn run(query: &str, vars: Variables) -> Json {
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+        let (result, errors) = ::execute(query, None, &schema, &vars, &()).expect("Execution failed");
+
+        assert_eq!(errors, vec![]);
+        Json::from(result)
+    }
+
+    #[test]
+    fn json_scalar_output() {
+        assert_eq!(
+            run("{ settings }", Variables::new()),
+            json(r#"{"settings": {"theme": "dark", "flags": {"beta": true}, "limits": [1, 2.5]}}"#)
+        );
+    }
+
+    #[test]
+    fn json_scalar_from_literals() {
+        assert_eq!(
+            run(r#"{ echo(value: {a: [1, "two", null], b: {c: false}}) }"#, Variables::new()),
+            json(r#"{"echo": {"a": [1, "two", null], "b": {"c": false}}}"#)
+        );
+        assert_eq!(
+            run(r#"{ echo(value: "plain") }"#, Variables::new()),
+            json(r#"{"echo": "plain"}"#)
+        );
+    }
+
+    #[test]
+    fn json_scalar_from_variables() {
+        let mut vars = Variables::new();
+        vars.insert(
+            "value".to_owned(),
+            InputValue::from(json(r#"{"nested": [{"deep": 1.25}]}"#)),
+        );
+
+        assert_eq!(
+            run("query($value: JSON!) { echo(value: $value) }", vars),
+            json(r#"{"echo": {"nested": [{"deep": 1.25}]}}"#)
+        );
+    }
+
+    #[test]
+    fn json_scalar_from_literals_with_variables() {
+        let mut vars = Variables::new();
+        vars.insert("x".to_owned(), InputValue::from(json(r#"{"deep": [1, true]}"#)));
+
+        assert_eq!(
+            run("query($x: JSON) { echo(value: {a: $x, b: [$x, 2]}) }", vars),
+            json(r#"{"echo": {"a": {"deep": [1, true]}, "b": [{"deep": [1, true]}, 2]}}"#)
+        );
+    }
+
+    #[test]
+    fn json_scalar_rejects_enum_literals() {
+        assert!(
+            ::execute(
+                "{ echo(value: {kind: FOO}) }",
+                None,
+                &RootNode::new(Query, EmptyMutation::<()>::new()),
+                &Variables::new(),
+                &(),
+            ).is_err()
+        );
+    }
+
+    #[test]
+    fn other_scalars_reject_lists_and_objects() {
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+
+        assert!(::execute("{ anything(value: [1]) }", None, &schema, &Variables::new(), &()).is_err());
+        assert!(::execute("{ anything(value: {a: 1}) }", None, &schema, &Variables::new(), &()).is_err());
+
+        let mut vars = Variables::new();
+        vars.insert("value".to_owned(), InputValue::from(json(r#"{"a": 1}"#)));
+
+        assert_eq!(
+            ::execute("query($value: Anything) { anything(value: $value) }", None, &schema, &vars, &()),
+            Err(GraphQLError::ValidationError(vec![
+                RuleError::new(
+                    r#"Variable "$value" got invalid value. Expected "Anything", found object."#,
+                    &[SourcePosition::new(6, 0, 6)],
+                ),
+            ]))
+        );
+    }
+}
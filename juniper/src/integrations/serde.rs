@@ -6,7 +6,7 @@ use std::fmt;
 
 use {GraphQLError, Value};
 use ast::InputValue;
-use executor::ExecutionError;
+use executor::{ExecutionError, PathSegment};
 use parser::{ParseError, SourcePosition, Spanning};
 use validation::RuleError;
 
@@ -36,6 +36,18 @@ impl ser::Serialize for ExecutionError {
     }
 }
 
+impl ser::Serialize for PathSegment {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *self {
+            PathSegment::Field(ref name) => serializer.serialize_str(name),
+            PathSegment::Index(index) => serializer.serialize_u64(index as u64),
+        }
+    }
+}
+
 impl<'a> ser::Serialize for GraphQLError<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -155,7 +167,9 @@ impl ser::Serialize for InputValue {
         S: ser::Serializer,
     {
         match *self {
-            InputValue::Null | InputValue::Variable(_) => serializer.serialize_unit(),
+            InputValue::Null | InputValue::Variable(_) | InputValue::Upload(_) => {
+                serializer.serialize_unit()
+            }
             InputValue::Int(v) => serializer.serialize_i64(v as i64),
             InputValue::Float(v) => serializer.serialize_f64(v),
             InputValue::String(ref v) | InputValue::Enum(ref v) => serializer.serialize_str(v),
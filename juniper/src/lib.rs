@@ -100,32 +100,49 @@ the [`graphql_object!`][3] macro.
 Juniper has [built-in integrations][object_integrations] for converting existing object types to
 GraphQL objects for popular crates.
 
+## Parallel execution
+
+With the `parallel` feature, `execute_parallel` resolves the root fields of
+queries on [rayon][rayon]'s thread pool, or on the pool it is called in with
+`ThreadPool::install`. Everything below a root field is resolved on the same
+thread. The root fields of mutations still run one after the other, and the
+order of keys in the response stays the same. Only the root types, their type
+infos, and the context have to be `Sync`.
+
 ## Integrating with web servers
 
 The most obvious usecase is to expose the GraphQL schema over an HTTP endpoint.
 To support this, Juniper offers additional crates that integrate with popular web frameworks.
 
+* [juniper_hyper][juniper_hyper]: Handlers for [Hyper][Hyper]
 * [juniper_iron][juniper_iron]: Handlers for [Iron][Iron]
 * [juniper_rocket][juniper_rocket]: Handlers for [Rocket][Rocket]
 
 [1]: http://graphql.org
 [3]: macro.graphql_object!.html
+[Hyper]: https://hyper.rs
+[juniper_hyper]: https://docs.rs/juniper_hyper
 [Iron]: http://ironframework.io
 [Rocket]: https://rocket.rs
 [object_integrations]: integrations/index.html
+[rayon]: https://docs.rs/rayon
 
 */
 #![warn(missing_docs)]
 
 extern crate serde;
+#[cfg(feature = "json")]
 #[macro_use]
 extern crate serde_derive;
 
-#[cfg(any(test, feature = "expose-test-schema"))]
+#[cfg(any(test, feature = "json"))]
 extern crate serde_json;
 
 extern crate fnv;
+#[macro_use]
+extern crate lazy_static;
 extern crate ordermap;
+extern crate regex;
 
 #[cfg(any(test, feature = "chrono"))]
 extern crate chrono;
@@ -136,7 +153,14 @@ extern crate url;
 #[cfg(any(test, feature = "uuid"))]
 extern crate uuid;
 
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
 use std::borrow::Cow;
+#[cfg(feature = "json")]
+use std::io;
+#[cfg(feature = "json")]
+use std::sync::Mutex;
 
 #[macro_use]
 mod value;
@@ -148,11 +172,17 @@ mod types;
 mod schema;
 mod validation;
 mod executor;
+mod stream;
 // This needs to be public until docs have support for private modules:
 // https://github.com/rust-lang/cargo/issues/1520
 pub mod integrations;
+#[cfg(feature = "json")]
 pub mod graphiql;
+#[cfg(feature = "json")]
 pub mod http;
+#[cfg(feature = "json")]
+pub mod websocket;
+pub mod builder;
 #[macro_use]
 mod result_ext;
 
@@ -167,18 +197,33 @@ mod executor_tests;
 use parser::{parse_document_source, ParseError, Spanning};
 use validation::{validate_input_values, visit_all_rules, ValidatorContext};
 use executor::execute_validated_query;
-
-pub use ast::{FromInputValue, InputValue, Selection, ToInputValue, Type};
+#[cfg(feature = "parallel")]
+use executor::execute_validated_query_in_parallel;
+#[cfg(feature = "json")]
+use executor::execute_validated_query_to_stream;
+#[cfg(feature = "json")]
+use stream::ResponseStream;
+use ast::Document;
+
+pub use ast::{FromInputValue, InputValue, InputValueError, Selection, ToInputValue, Type};
 pub use value::Value;
 pub use types::base::{Arguments, GraphQLType, TypeKind};
+#[doc(hidden)]
+pub use types::base::stream_selection_set;
 pub use executor::{Context, ExecutionError, ExecutionResult, Executor, FieldError, FieldResult,
-                   FromContext, IntoResolvable, Registry, Variables};
+                   FromContext, IntoResolvable, PathSegment, Registry, Variables};
 pub use validation::RuleError;
-pub use types::scalars::{EmptyMutation, ID};
-pub use schema::model::RootNode;
+pub use types::scalars::{EmptyMutation, ID, Upload};
+pub use schema::model::{RootNode, SchemaType};
+pub use schema::validation::SchemaError;
 pub use result_ext::ResultExt;
 
 pub use schema::meta;
+pub use schema::diff;
+pub use types::dynamic;
+
+#[doc(hidden)]
+pub use types::name::cached_type_name;
 
 /// An error that prevented query execution
 #[derive(Debug, PartialEq)]
@@ -202,28 +247,165 @@ pub fn execute<'a, CtxT, QueryT, MutationT>(
 where
     QueryT: GraphQLType<Context = CtxT>,
     MutationT: GraphQLType<Context = CtxT>,
+{
+    let document = try!(parse_and_validate(document_source, root_node, variables));
+
+    execute_validated_query(document, operation_name, root_node, variables, context)
+}
+
+/// Execute a query in a provided schema, resolving the root fields of queries
+/// on the thread pool
+///
+/// This works like `execute`, except that root query fields resolve
+/// concurrently on rayon's thread pool. Run it inside `ThreadPool::install`
+/// to use another pool.
+#[cfg(feature = "parallel")]
+pub fn execute_parallel<'a, CtxT, QueryT, MutationT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT>,
+    variables: &Variables,
+    context: &CtxT,
+) -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
+where
+    QueryT: GraphQLType<Context = CtxT> + Sync,
+    QueryT::TypeInfo: Sync,
+    MutationT: GraphQLType<Context = CtxT> + Sync,
+    MutationT::TypeInfo: Sync,
+    CtxT: Sync,
+{
+    let document = try!(parse_and_validate(document_source, root_node, variables));
+
+    execute_validated_query_in_parallel(document, operation_name, root_node, variables, context)
+}
+
+/// Execute a query in a provided schema, writing the JSON response to
+/// `writer` while fields are resolved
+///
+/// This avoids building the complete result as a `Value` before serializing
+/// it. The response has the same format as a serialized `GraphQLResponse`,
+/// with fields in the order of the query. Only the parts of the response that
+/// might still be replaced by `null` when a non-null field fails are buffered.
+///
+/// Parse and validation errors are returned before anything is written. The
+/// inner result reports errors writing to `writer`, which is best wrapped in
+/// an `io::BufWriter`.
+#[cfg(feature = "json")]
+pub fn execute_to_writer<'a, CtxT, QueryT, MutationT, W>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT>,
+    variables: &Variables,
+    context: &CtxT,
+    writer: W,
+) -> Result<io::Result<()>, GraphQLError<'a>>
+where
+    QueryT: GraphQLType<Context = CtxT>,
+    MutationT: GraphQLType<Context = CtxT>,
+    W: io::Write + Send,
+{
+    let document = try!(parse_and_validate(document_source, root_node, variables));
+
+    let output = Mutex::new(writer);
+    let stream = ResponseStream::new(&output);
+
+    let mut errors = try!(execute_validated_query_to_stream(
+        document,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        &stream,
+    ));
+    errors.sort();
+
+    if !errors.is_empty() {
+        stream.write(b",\"errors\":");
+        stream.write_json(&errors);
+    }
+    stream.write(b"}");
+
+    Ok(stream.finish().and_then(|()| output.into_inner().unwrap().flush()))
+}
+
+/// Validate a query against a schema without executing it
+///
+/// This runs the same rules as `execute`, except for the checks of variable
+/// values. Together with `SchemaType::from_introspection`, it checks queries
+/// against a schema that's served elsewhere:
+///
+/// ```rust,ignore
+/// let schema = SchemaType::from_introspection(&introspection_result).unwrap();
+///
+/// juniper::validate_query("{ hero { name } }", &schema).unwrap();
+/// ```
+pub fn validate_query<'a>(document_source: &'a str, schema: &SchemaType) -> Result<(), GraphQLError<'a>> {
+    let document = try!(parse_document_source(document_source));
+
+    validate_rules(&document, schema)
+}
+
+fn parse_and_validate<'a, QueryT, MutationT>(
+    document_source: &'a str,
+    root_node: &RootNode<QueryT, MutationT>,
+    variables: &Variables,
+) -> Result<Document<'a>, GraphQLError<'a>>
+where
+    QueryT: GraphQLType,
+    MutationT: GraphQLType,
 {
     let document = try!(parse_document_source(document_source));
+    try!(validate_document(&document, root_node, variables));
+
+    Ok(document)
+}
+
+fn execute_document<'a, CtxT, QueryT, MutationT>(
+    document: Document<'a>,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT>,
+    variables: &Variables,
+    context: &CtxT,
+) -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
+where
+    QueryT: GraphQLType<Context = CtxT>,
+    MutationT: GraphQLType<Context = CtxT>,
+{
+    try!(validate_document(&document, root_node, variables));
+
+    execute_validated_query(document, operation_name, root_node, variables, context)
+}
 
+fn validate_document<'a, QueryT, MutationT>(
+    document: &Document<'a>,
+    root_node: &RootNode<QueryT, MutationT>,
+    variables: &Variables,
+) -> Result<(), GraphQLError<'a>>
+where
+    QueryT: GraphQLType,
+    MutationT: GraphQLType,
+{
     {
-        let errors = validate_input_values(variables, &document, &root_node.schema);
+        let errors = validate_input_values(variables, document, &root_node.schema);
 
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
         }
     }
 
-    {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
-        visit_all_rules(&mut ctx, &document);
+    validate_rules(document, &root_node.schema)
+}
 
-        let errors = ctx.into_errors();
-        if !errors.is_empty() {
-            return Err(GraphQLError::ValidationError(errors));
-        }
+fn validate_rules<'a>(document: &Document<'a>, schema: &SchemaType) -> Result<(), GraphQLError<'a>> {
+    let mut ctx = ValidatorContext::new(schema, document);
+    visit_all_rules(&mut ctx, document);
+
+    let errors = ctx.into_errors();
+    if !errors.is_empty() {
+        return Err(GraphQLError::ValidationError(errors));
     }
 
-    execute_validated_query(document, operation_name, root_node, variables, context)
+    Ok(())
 }
 
 impl<'a> From<Spanning<ParseError<'a>>> for GraphQLError<'a> {
@@ -269,3 +451,102 @@ fn test_to_camel_case() {
     assert_eq!(&to_camel_case("a")[..], "a");
     assert_eq!(&to_camel_case("")[..], "");
 }
+
+#[doc(hidden)]
+pub fn to_snake_case(s: &str) -> String {
+    let chars = s.chars().collect::<Vec<_>>();
+    let mut dest = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map_or(false, |n| n.is_lowercase());
+
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower)
+            {
+                dest.push('_');
+            }
+        }
+
+        dest.extend(c.to_lowercase());
+    }
+
+    dest
+}
+
+#[test]
+fn test_to_snake_case() {
+    assert_eq!(&to_snake_case("test")[..], "test");
+    assert_eq!(&to_snake_case("first_second")[..], "first_second");
+    assert_eq!(&to_snake_case("firstSecond")[..], "first_second");
+    assert_eq!(&to_snake_case("NewHope")[..], "new_hope");
+    assert_eq!(&to_snake_case("HTTPServer")[..], "http_server");
+    assert_eq!(&to_snake_case("version2Id")[..], "version2_id");
+    assert_eq!(&to_snake_case("")[..], "");
+}
+
+/// Naming policy applied to the field and argument names generated by the
+/// convenience macros
+///
+/// The macros accept the policy as a string in a `rename_all` item, e.g.
+/// `rename_all: "snake_case"`.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenameRule {
+    /// Use the Rust identifier unchanged
+    None,
+    /// `fieldName`, the GraphQL convention and the default for fields
+    CamelCase,
+    /// `field_name`
+    SnakeCase,
+    /// `FIELD_NAME`
+    ScreamingSnakeCase,
+}
+
+impl RenameRule {
+    /// Look up a policy by the name used in `rename_all`
+    pub fn from_name(name: &str) -> Option<RenameRule> {
+        match name {
+            "none" => Some(RenameRule::None),
+            "camelCase" => Some(RenameRule::CamelCase),
+            "snake_case" => Some(RenameRule::SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Some(RenameRule::ScreamingSnakeCase),
+            _ => None,
+        }
+    }
+
+    /// Convert a Rust identifier to the name exposed in the schema
+    pub fn apply<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        match *self {
+            RenameRule::None => Cow::Borrowed(s),
+            RenameRule::CamelCase => to_camel_case(s),
+            RenameRule::SnakeCase => Cow::Owned(to_snake_case(s)),
+            RenameRule::ScreamingSnakeCase => Cow::Owned(to_snake_case(s).to_uppercase()),
+        }
+    }
+
+    /// Enum variants are in PascalCase, so they are split into words before
+    /// applying the rule
+    pub fn apply_to_variant<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        match *self {
+            RenameRule::None => Cow::Borrowed(s),
+            _ => Cow::Owned(self.apply(&to_snake_case(s)).into_owned()),
+        }
+    }
+}
+
+#[test]
+fn test_rename_rule() {
+    assert_eq!(&RenameRule::None.apply("user_name")[..], "user_name");
+    assert_eq!(&RenameRule::CamelCase.apply("user_name")[..], "userName");
+    assert_eq!(&RenameRule::CamelCase.apply("_test")[..], "Test");
+    assert_eq!(&RenameRule::CamelCase.apply("HTTPServer")[..], "HTTPServer");
+    assert_eq!(&RenameRule::SnakeCase.apply("user_name")[..], "user_name");
+    assert_eq!(&RenameRule::SnakeCase.apply("NewHope")[..], "new_hope");
+    assert_eq!(&RenameRule::ScreamingSnakeCase.apply("user_name")[..], "USER_NAME");
+    assert_eq!(&RenameRule::ScreamingSnakeCase.apply("NewHope")[..], "NEW_HOPE");
+    assert_eq!(&RenameRule::CamelCase.apply_to_variant("NewHope")[..], "newHope");
+    assert_eq!(&RenameRule::None.apply_to_variant("NewHope")[..], "NewHope");
+    assert_eq!(RenameRule::from_name("snake_case"), Some(RenameRule::SnakeCase));
+    assert_eq!(RenameRule::from_name("kebab-case"), None);
+}
@@ -5,54 +5,65 @@ macro_rules! __graphql__args {
     ( @as_expr, $e:expr) => { $e };
     ( @as_pattern, $p:pat) => { $p };
 
-    ( @assign_arg_vars, $args:ident, $executorvar:ident, , $($rest:tt)* ) => {
-        __graphql__args!(@assign_arg_vars, $args, $executorvar, $($rest)*);
+    ( @assign_arg_vars, $args:ident, $executorvar:ident, $renamevar:ident, , $($rest:tt)* ) => {
+        __graphql__args!(@assign_arg_vars, $args, $executorvar, $renamevar, $($rest)*);
     };
 
-    ( @assign_arg_vars, $args:ident, $executorvar:ident, ) => {
+    ( @assign_arg_vars, $args:ident, $executorvar:ident, $renamevar:ident, ) => {
         ();
     };
 
     (
         @assign_arg_vars,
-        $args:ident, $executorvar:ident, &$exec:ident $($rest:tt)*
+        $args:ident, $executorvar:ident, $renamevar:ident, &$exec:ident $($rest:tt)*
     ) => {
         let __graphql__args!(@as_pattern, $exec) = &$executorvar;
-        __graphql__args!(@assign_arg_vars, $args, $executorvar, $($rest)*);
+        __graphql__args!(@assign_arg_vars, $args, $executorvar, $renamevar, $($rest)*);
     };
 
     (
         @assign_arg_vars,
-        $args:ident, $executorvar:ident,
-        $name:ident $(= $default:tt)* : $ty:ty $(as $desc:tt)*, $($rest:tt)*
+        $args:ident, $executorvar:ident, $renamevar:ident,
+        $name:ident $(= $default:tt)* : $ty:ty $(as $desc:tt)* $(where $validators:tt)*,
+        $($rest:tt)*
     ) => {
-        let $name: $ty = $args
-            .get(&$crate::to_camel_case(stringify!($name)))
-            .expect("Argument missing - validation must have failed");
-        __graphql__args!(@assign_arg_vars, $args, $executorvar, $($rest)*);
+        __graphql__args!(@assign_arg_var, $args, $renamevar, $name, $ty);
+        __graphql__args!(@assign_arg_vars, $args, $executorvar, $renamevar, $($rest)*);
     };
 
     (
         @assign_arg_vars,
-        $args:ident, $executorvar:ident,
-        $name:ident  $(= $default:tt)* : $ty:ty $(as $desc:expr)*
+        $args:ident, $executorvar:ident, $renamevar:ident,
+        $name:ident $(= $default:tt)* : $ty:ty $(as $desc:tt)* $(where $validators:tt)*
     ) => {
-        let $name: $ty = $args
-            .get(&$crate::to_camel_case(stringify!($name)))
-            .expect("Argument missing - validation must have failed");
+        __graphql__args!(@assign_arg_var, $args, $renamevar, $name, $ty);
     };
 
-    ( @apply_args, $reg:expr, $base:expr, $info:expr, ( ) ) => {
+    ( @assign_arg_var, $args:ident, $renamevar:ident, $name:ident, $ty:ty ) => {
+        let $name: $ty = match $args.try_get(&$renamevar.apply(stringify!($name))) {
+            Ok(Some(v)) => v,
+            Ok(None) => panic!("Argument missing - validation must have failed"),
+            Err(e) => return Err(e.into_argument_error(&$renamevar.apply(stringify!($name)))),
+        };
+    };
+
+    // Calls .validator() on $val for each validator in the list
+    ( @apply_validators, [ $( $vname:ident ( $($varg:tt)* ) ),* ], $val:expr ) => {
+        $val $( .validator($crate::meta::Validator::$vname($($varg)*)) )*
+    };
+
+    ( @apply_args, $reg:expr, $rename:expr, $base:expr, $info:expr, ( ) ) => {
         $base
     };
 
     (
         @apply_args,
-        $reg:expr, $base:expr, $info:expr, ( , $( $rest:tt )* )
+        $reg:expr, $rename:expr, $base:expr, $info:expr, ( , $( $rest:tt )* )
     ) => {
         __graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $base,
             $info,
             ( $($rest)* ))
@@ -60,11 +71,12 @@ macro_rules! __graphql__args {
 
     (
         @apply_args,
-        $reg:expr, $base:expr, $info:expr, ( &executor $( $rest:tt )* )
+        $reg:expr, $rename:expr, $base:expr, $info:expr, ( &executor $( $rest:tt )* )
     ) => {
         __graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $base,
             $info,
             ( $($rest)* ))
@@ -72,22 +84,61 @@ macro_rules! __graphql__args {
 
     (
         @apply_args,
-        $reg:expr, $base:expr, $info:expr, ( $name:ident = $default:tt : $t:ty )
+        $reg:expr, $rename:expr, $base:expr, $info:expr,
+        ( $name:ident = $default:tt : $t:ty $(as $desc:tt)* where $validators:tt $( $rest:tt )* )
+    ) => {
+        __graphql__args!(
+            @apply_args,
+            $reg,
+            $rename,
+            $base.argument(__graphql__args!(
+                @apply_validators,
+                $validators,
+                $reg.arg_with_default::<$t>(
+                    &$rename.apply(stringify!($name)),
+                    &__graphql__args!(@as_expr, $default), $info)
+                $(.description($desc))*)),
+            $info,
+            ( $($rest)* ))
+    };
+
+    (
+        @apply_args,
+        $reg:expr, $rename:expr, $base:expr, $info:expr,
+        ( $name:ident : $t:ty $(as $desc:tt)* where $validators:tt $( $rest:tt )* )
+    ) => {
+        __graphql__args!(
+            @apply_args,
+            $reg,
+            $rename,
+            $base.argument(__graphql__args!(
+                @apply_validators,
+                $validators,
+                $reg.arg::<$t>(&$rename.apply(stringify!($name)), $info)
+                $(.description($desc))*)),
+            $info,
+            ( $($rest)* ))
+    };
+
+    (
+        @apply_args,
+        $reg:expr, $rename:expr, $base:expr, $info:expr, ( $name:ident = $default:tt : $t:ty )
     ) => {
         $base.argument($reg.arg_with_default::<$t>(
-            &$crate::to_camel_case(stringify!($name)),
+            &$rename.apply(stringify!($name)),
             &__graphql__args!(@as_expr, $default), $info))
     };
 
     (
         @apply_args,
-        $reg:expr, $base:expr, $info:expr, ( $name:ident = $default:tt : $t:ty , $( $rest:tt )* )
+        $reg:expr, $rename:expr, $base:expr, $info:expr, ( $name:ident = $default:tt : $t:ty , $( $rest:tt )* )
     ) => {
         __graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $base.argument($reg.arg_with_default::<$t>(
-                &$crate::to_camel_case(stringify!($name)),
+                &$rename.apply(stringify!($name)),
                 &__graphql__args!(@as_expr, $default), $info)),
             $info,
             ( $($rest)* ))
@@ -95,14 +146,15 @@ macro_rules! __graphql__args {
 
     (
         @apply_args,
-        $reg:expr, $base:expr, $info:expr,
+        $reg:expr, $rename:expr, $base:expr, $info:expr,
         ( $name:ident = $default:tt : $t:ty as $desc:tt $( $rest:tt )* )
     ) => {
         __graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $base.argument($reg.arg_with_default::<$t>(
-                &$crate::to_camel_case(stringify!($name)),
+                &$rename.apply(stringify!($name)),
                 &__graphql__args!(@as_expr, $default), $info)
                 .description($desc)),
             $info,
@@ -111,35 +163,37 @@ macro_rules! __graphql__args {
 
     (
         @apply_args,
-        $reg:expr, $base:expr, $info:expr, ( $name:ident : $t:ty )
+        $reg:expr, $rename:expr, $base:expr, $info:expr, ( $name:ident : $t:ty )
     ) => {
         $base.argument($reg.arg::<$t>(
-            &$crate::to_camel_case(stringify!($name)), $info))
+            &$rename.apply(stringify!($name)), $info))
     };
 
     (
         @apply_args,
-        $reg:expr, $base:expr, $info:expr, ( $name:ident : $t:ty , $( $rest:tt )* )
+        $reg:expr, $rename:expr, $base:expr, $info:expr, ( $name:ident : $t:ty , $( $rest:tt )* )
     ) => {
         __graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $base.argument($reg.arg::<$t>(
-                &$crate::to_camel_case(stringify!($name)), $info)),
+                &$rename.apply(stringify!($name)), $info)),
             $info,
             ( $($rest)* ))
     };
 
     (
         @apply_args,
-        $reg:expr, $base:expr, $info:expr, ( $name:ident : $t:ty as $desc:tt $( $rest:tt )* )
+        $reg:expr, $rename:expr, $base:expr, $info:expr, ( $name:ident : $t:ty as $desc:tt $( $rest:tt )* )
     ) => {
         __graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $base.argument(
                 $reg.arg::<$t>(
-                    &$crate::to_camel_case(stringify!($name)), $info)
+                    &$rename.apply(stringify!($name)), $info)
                 .description($desc)),
             $info,
             ( $($rest)* ))
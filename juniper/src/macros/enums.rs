@@ -102,6 +102,14 @@ macro_rules! graphql_enum {
                     _ => None,
                 }
             }
+
+            fn try_from_input_value(
+                v: &$crate::InputValue,
+            ) -> Result<$name, $crate::InputValueError> {
+                <$name as $crate::FromInputValue>::from_input_value(v).ok_or_else(|| {
+                    $crate::InputValueError::expected(graphql_enum!(@as_expr, $outname), v)
+                })
+            }
         }
 
         impl $crate::ToInputValue for $name {
@@ -55,6 +55,10 @@ macro_rules! __graphql__build_field_matches {
         __graphql__build_field_matches!($resolveargs, $acc, $( $rest )*);
     };
 
+    ( $resolveargs:tt, $acc:tt, rename_all : $value:tt $( $rest:tt )*) => {
+        __graphql__build_field_matches!($resolveargs, $acc, $( $rest )*);
+    };
+
     ( $resolveargs:tt, $acc:tt, instance_resolvers : | $execvar:pat | $resolvers:tt $( $rest:tt )*) => {
         __graphql__build_field_matches!($resolveargs, $acc, $( $rest )*);
     };
@@ -64,22 +68,23 @@ macro_rules! __graphql__build_field_matches {
     };
 
     (
-        ($outname:tt, $selfvar:ident, $fieldvar:ident, $argsvar:ident, $executorvar:ident),
+        ($outname:tt, $selfvar:ident, $fieldvar:ident, $argsvar:ident, $executorvar:ident, $renamevar:ident),
         ( $( ( $name:ident; ( $($args:tt)* ); $t:ty; $body:block ) )* ),
     ) => {
         $(
-            if $fieldvar == &$crate::to_camel_case(stringify!($name)) {
+            if $fieldvar == &$renamevar.apply(stringify!($name)) {
+                __graphql__args!(
+                    @assign_arg_vars,
+                    $argsvar, $executorvar, $renamevar, $($args)*
+                );
+
                 let result: $t = (||{
-                    __graphql__args!(
-                        @assign_arg_vars,
-                        $argsvar, $executorvar, $($args)*
-                    );
                     $body
                 })();
 
                 return ($crate::IntoResolvable::into(result, $executorvar.context())).and_then(
                     |res| match res {
-                        Some((ctx, r)) => $executorvar.replaced_context(ctx).resolve_with_ctx(&(), &r),
+                        Some((ctx, r)) => $executorvar.replaced_context(ctx).resolve_field_value_with_ctx(&(), &r),
                         None => Ok($crate::Value::null()),
                     })
             }
@@ -87,3 +92,63 @@ macro_rules! __graphql__build_field_matches {
         panic!("Field {} not found on type {}", $fieldvar, $outname);
     };
 }
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __graphql__rename_rule {
+    // Rules are matched here so that an unknown rule fails to compile
+    ( @from_name, "none" ) => { $crate::RenameRule::None };
+    ( @from_name, "camelCase" ) => { $crate::RenameRule::CamelCase };
+    ( @from_name, "snake_case" ) => { $crate::RenameRule::SnakeCase };
+    ( @from_name, "SCREAMING_SNAKE_CASE" ) => { $crate::RenameRule::ScreamingSnakeCase };
+    ( @from_name, $rule:tt ) => {
+        compile_error!(concat!(
+            "Unknown rename_all rule ", stringify!($rule), ", expected one of \"none\", ",
+            "\"camelCase\", \"snake_case\", or \"SCREAMING_SNAKE_CASE\""
+        ))
+    };
+
+    // field deprecated <reason> <name>(...) -> <type> as <description> { ... }
+    ( field deprecated $_reason:tt $name:ident $args:tt -> $t:ty as $desc:tt $body:block $( $rest:tt )* ) => {
+        __graphql__rename_rule!($( $rest )*)
+    };
+
+    // field deprecated <reason> <name>(...) -> <type> { ... }
+    ( field deprecated $_reason:tt $name:ident $args:tt -> $t:ty $body:block $( $rest:tt )* ) => {
+        __graphql__rename_rule!($( $rest )*)
+    };
+
+    // field <name>(...) -> <type> as <description> { ... }
+    ( field $name:ident $args:tt -> $t:ty as $desc:tt $body:block $( $rest:tt )* ) => {
+        __graphql__rename_rule!($( $rest )*)
+    };
+
+    // field <name>(...) -> <type> { ... }
+    ( field $name:ident $args:tt -> $t:ty $body:block $( $rest:tt )* ) => {
+        __graphql__rename_rule!($( $rest )*)
+    };
+
+    ( description : $value:tt $( $rest:tt )* ) => {
+        __graphql__rename_rule!($( $rest )*)
+    };
+
+    ( interfaces : $value:tt $( $rest:tt )* ) => {
+        __graphql__rename_rule!($( $rest )*)
+    };
+
+    ( instance_resolvers : | $execvar:pat | $resolvers:tt $( $rest:tt )* ) => {
+        __graphql__rename_rule!($( $rest )*)
+    };
+
+    ( , $( $rest:tt )* ) => {
+        __graphql__rename_rule!($( $rest )*)
+    };
+
+    ( rename_all : $value:tt $( $rest:tt )* ) => {
+        __graphql__rename_rule!(@from_name, $value)
+    };
+
+    () => {
+        $crate::RenameRule::CamelCase
+    };
+}
@@ -29,6 +29,11 @@ and arguments.
 If you want to expose the struct under a different name than the Rust
 type, you can write `struct Coordinates as "MyCoordinates" { ...`.
 
+Field names are converted to camel case by default. To use another naming
+policy, put `rename_all: "snake_case"` before the struct declaration; the
+supported rules are `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`,
+and `"none"`.
+
 You can specify *default values* for input object fields; the syntax
 is similar to argument default values:
 
@@ -44,6 +49,22 @@ graphql_input_object!(
 # fn main() { }
 ```
 
+Fields can be constrained with `where [...]` after the type and description,
+using the same validators as field arguments:
+
+```rust
+# #[macro_use] extern crate juniper;
+#
+graphql_input_object!(
+    struct NewUser {
+        name: String where [min_length(1), max_length(50)],
+        age: Option<i32> as "Age in years" where [min(0)],
+    }
+);
+
+# fn main() { }
+```
+
 */
 #[macro_export]
 macro_rules! graphql_input_object {
@@ -55,23 +76,31 @@ macro_rules! graphql_input_object {
     ( @apply_description, , $val:expr ) => { $val };
     ( @apply_description, $descr:tt , $val:expr ) => { $val.description($descr) };
 
-    // Generate the FromInputValue::from method body, provided a
-    // HashMap<&str, &InputValue> in $var
+    // Calls .validator() on $val for each validator in the list
+    ( @apply_validators, , $val:expr ) => { $val };
+    ( @apply_validators, [ $( $vname:ident ( $($varg:tt)* ) ),* ], $val:expr ) => {
+        $val $( .validator($crate::meta::Validator::$vname($($varg)*)) )*
+    };
+
+    // Generate the FromInputValue::try_from_input_value method body, provided
+    // a HashMap<&str, &InputValue> in $var
     (
         @generate_from_input_value,
-        $name:tt, $var:tt,
-        ( $($field_name:ident $(= $default:tt)* : $field_type:ty $(as $descr:tt)* $(,)* ),* )
+        $name:tt, $var:tt, $rename:tt,
+        ( $($field_name:ident $(= $default:tt)* : $field_type:ty $(as $descr:tt)* $(where $validators:tt)* $(,)* ),* )
     ) => {
-        Some($name {
+        Ok($name {
             $( $field_name: {
-                let n = $crate::to_camel_case(stringify!($field_name));
+                let n = $rename.apply(stringify!($field_name));
                 let v: Option<&&$crate::InputValue> = $var.get(&n[..]);
 
-                match v {
-                    $( Some(&&$crate::InputValue::Null) | None if true => $default, )*
-                        Some(v) => $crate::FromInputValue::from_input_value(v).unwrap(),
-                        _ => $crate::FromInputValue::from_input_value(&$crate::InputValue::null()).unwrap()
-                }
+                let value = match v {
+                    $( Some(&&$crate::InputValue::Null) | None if true => Ok($default), )*
+                        Some(v) => $crate::FromInputValue::try_from_input_value(v),
+                        _ => $crate::FromInputValue::try_from_input_value(&$crate::InputValue::null())
+                };
+
+                try!(value.map_err(|e: $crate::InputValueError| e.in_field(&n)))
             } ),*
         })
     };
@@ -79,12 +108,15 @@ macro_rules! graphql_input_object {
     // Generate the ToInputValue::To method body, provided self in $self
     (
         @generate_to_input_value,
-        $name:tt, $selfvar:tt,
-        ( $($field_name:ident $(= $default:tt)* : $field_type:ty $(as $descr:tt)* $(,)* ),* )
+        $name:tt, $selfvar:tt, $rename:tt,
+        ( $($field_name:ident $(= $default:tt)* : $field_type:ty $(as $descr:tt)* $(where $validators:tt)* $(,)* ),* )
     ) => {
         $crate::InputValue::object(vec![
             $(
-                ($crate::to_camel_case(stringify!($field_name)), $selfvar.$field_name.to_input_value())
+                (
+                    $rename.apply(stringify!($field_name)),
+                    $selfvar.$field_name.to_input_value()
+                )
             ),*
         ].into_iter().collect())
     };
@@ -93,7 +125,7 @@ macro_rules! graphql_input_object {
     (
         @generate_struct_fields,
         ( $($meta:tt)* ), ( $($pubmod:tt)* ), $name:tt,
-        ( $($field_name:ident $(= $default:tt)* : $field_type:ty $(as $descr:tt)* $(,)* ),* )
+        ( $($field_name:ident $(= $default:tt)* : $field_type:ty $(as $descr:tt)* $(where $validators:tt)* $(,)* ),* )
     ) => {
         $($meta)* $($pubmod)* struct $name {
             $( $field_name: $field_type, )*
@@ -103,42 +135,48 @@ macro_rules! graphql_input_object {
     // Generate single field meta for field with default value
     (
         @generate_single_meta_field,
-        $reg:tt,
-        ( $field_name:ident = $default:tt : $field_type:ty $(as $descr:tt)* )
+        $reg:tt, $rename:tt,
+        ( $field_name:ident = $default:tt : $field_type:ty $(as $descr:tt)* $(where $validators:tt)* )
     ) => {
         graphql_input_object!(
-            @apply_description,
-            $($descr)*,
-            $reg.arg_with_default::<$field_type>(
-                &$crate::to_camel_case(stringify!($field_name)),
-                &$default, &()))
+            @apply_validators,
+            $($validators)*,
+            graphql_input_object!(
+                @apply_description,
+                $($descr)*,
+                $reg.arg_with_default::<$field_type>(
+                    &$rename.apply(stringify!($field_name)),
+                    &$default, &())))
     };
 
     // Generate single field meta for field without default value
     (
         @generate_single_meta_field,
-        $reg:tt,
-        ( $field_name:ident : $field_type:ty $(as $descr:tt)* )
+        $reg:tt, $rename:tt,
+        ( $field_name:ident : $field_type:ty $(as $descr:tt)* $(where $validators:tt)* )
     ) => {
         graphql_input_object!(
-            @apply_description,
-            $($descr)*,
-            $reg.arg::<$field_type>(
-                &$crate::to_camel_case(stringify!($field_name)), &()))
+            @apply_validators,
+            $($validators)*,
+            graphql_input_object!(
+                @apply_description,
+                $($descr)*,
+                $reg.arg::<$field_type>(
+                    &$rename.apply(stringify!($field_name)), &())))
     };
 
     // Generate the input field meta list, i.e. &[Argument] for
     (
         @generate_meta_fields,
-        $reg:tt,
-        ( $($field_name:ident $(= $default:tt)* : $field_type:ty $(as $descr:tt)* $(,)* ),* )
+        $reg:tt, $rename:tt,
+        ( $($field_name:ident $(= $default:tt)* : $field_type:ty $(as $descr:tt)* $(where $validators:tt)* $(,)* ),* )
     ) => {
         &[
             $(
                 graphql_input_object!(
                     @generate_single_meta_field,
-                    $reg,
-                    ( $field_name $(= $default)* : $field_type $(as $descr)* )
+                    $reg, $rename,
+                    ( $field_name $(= $default)* : $field_type $(as $descr)* $(where $validators)* )
                 )
             ),*
         ]
@@ -148,12 +186,12 @@ macro_rules! graphql_input_object {
     // struct $name { ... }
     (
         @parse,
-        ( $_ignore1:tt, $_ignore2:tt, $_ignore3:tt, $_ignore4:tt, $_ignore5:tt, $descr:tt ),
+        ( $_ignore1:tt, $_ignore2:tt, $_ignore3:tt, $_ignore4:tt, $_ignore5:tt, $descr:tt, $rename:tt ),
         $(#[$meta:meta])* struct $name:ident { $($fields:tt)* } $($rest:tt)*
     ) => {
         graphql_input_object!(
             @parse,
-            ( ( $(#[$meta])* ), ( ), $name, (stringify!($name)), ($($fields)*), $descr ),
+            ( ( $(#[$meta])* ), ( ), $name, (stringify!($name)), ($($fields)*), $descr, $rename ),
             $($rest)*
         );
     };
@@ -162,12 +200,12 @@ macro_rules! graphql_input_object {
     // pub struct $name { ... }
     (
         @parse,
-        ( $_ignore1:tt, $_ignore2:tt, $_ignore3:tt, $_ignore4:tt, $_ignore5:tt, $descr:tt ),
+        ( $_ignore1:tt, $_ignore2:tt, $_ignore3:tt, $_ignore4:tt, $_ignore5:tt, $descr:tt, $rename:tt ),
         $(#[$meta:meta])* pub struct $name:ident { $($fields:tt)* } $($rest:tt)*
     ) => {
         graphql_input_object!(
             @parse,
-            ( ( $(#[$meta])* ), ( pub ), $name, (stringify!($name)), ($($fields)*), $descr ),
+            ( ( $(#[$meta])* ), ( pub ), $name, (stringify!($name)), ($($fields)*), $descr, $rename ),
             $($rest)*
         );
     };
@@ -176,12 +214,12 @@ macro_rules! graphql_input_object {
     // struct $name as "GraphQLName" { ... }
     (
         @parse,
-        ( $_ignore1:tt, $_ignore2:tt, $_ignore3:tt, $_ignore4:tt, $_ignore5:tt, $descr:tt ),
+        ( $_ignore1:tt, $_ignore2:tt, $_ignore3:tt, $_ignore4:tt, $_ignore5:tt, $descr:tt, $rename:tt ),
         $(#[$meta:meta])* struct $name:ident as $outname:tt { $($fields:tt)* } $($rest:tt)*
     ) => {
         graphql_input_object!(
             @parse,
-            ( ( $($meta)* ), ( ), $name, $outname, ($($fields)*), $descr ),
+            ( ( $($meta)* ), ( ), $name, $outname, ($($fields)*), $descr, $rename ),
             $($rest)*
         );
     };
@@ -190,12 +228,12 @@ macro_rules! graphql_input_object {
     // pub struct $name as "GraphQLName" { ... }
     (
         @parse,
-        ( $_ignore1:tt, $_ignore2:tt, $_ignore3:tt, $_ignore4:tt, $_ignore5:tt, $descr:tt ),
+        ( $_ignore1:tt, $_ignore2:tt, $_ignore3:tt, $_ignore4:tt, $_ignore5:tt, $descr:tt, $rename:tt ),
         $(#[$meta:meta])* pub struct $name:ident as $outname:tt { $($fields:tt)* } $($rest:tt)*
     ) => {
         graphql_input_object!(
             @parse,
-            ( ( $($meta)* ), ( pub ), $name, $outname, ($($fields)*), $descr ),
+            ( ( $($meta)* ), ( pub ), $name, $outname, ($($fields)*), $descr, $rename ),
             $($rest)*
         );
     };
@@ -203,12 +241,25 @@ macro_rules! graphql_input_object {
     // description: <description>
     (
         @parse,
-        ( $meta:tt, $pubmod:tt, $name:tt, $outname:tt, $fields:tt, $_ignore:tt ),
+        ( $meta:tt, $pubmod:tt, $name:tt, $outname:tt, $fields:tt, $_ignore:tt, $rename:tt ),
         description: $descr:tt $($rest:tt)*
     ) => {
         graphql_input_object!(
             @parse,
-            ( $meta, $pubmod, $name, $outname, $fields, $descr ),
+            ( $meta, $pubmod, $name, $outname, $fields, $descr, $rename ),
+            $($rest)*
+        );
+    };
+
+    // rename_all: <rule>
+    (
+        @parse,
+        ( $meta:tt, $pubmod:tt, $name:tt, $outname:tt, $fields:tt, $descr:tt, $_ignore:tt ),
+        rename_all: $rename:tt $($rest:tt)*
+    ) => {
+        graphql_input_object!(
+            @parse,
+            ( $meta, $pubmod, $name, $outname, $fields, $descr, $rename ),
             $($rest)*
         );
     };
@@ -216,49 +267,60 @@ macro_rules! graphql_input_object {
     // No more data to parse, generate the struct and impls
     (
         @parse,
-        ( $meta:tt, $pubmod:tt, $name:tt, $outname:tt, $fields:tt, $descr:tt ),
+        ( $meta:tt, $pubmod:tt, $name:tt, $outname:tt, $fields:tt, $descr:tt, $rename:tt ),
     ) => {
         graphql_input_object!(@generate_struct_fields, $meta, $pubmod, $name, $fields);
 
-        impl $crate::FromInputValue for $name {
-            fn from_input_value(value: &$crate::InputValue) -> Option<$name> {
-                if let Some(obj) = value.to_object_value() {
-                    graphql_input_object!(@generate_from_input_value, $name, obj, $fields)
+        // The rule is resolved once, so that an unknown rule is only reported once
+        const _: () = {
+            const __GRAPHQL_RENAME_RULE: $crate::RenameRule = __graphql__rename_rule!(@from_name, $rename);
+
+            impl $crate::FromInputValue for $name {
+                fn from_input_value(value: &$crate::InputValue) -> Option<$name> {
+                    <$name as $crate::FromInputValue>::try_from_input_value(value).ok()
                 }
-                else {
-                   None
+
+                fn try_from_input_value(
+                    value: &$crate::InputValue,
+                ) -> Result<$name, $crate::InputValueError> {
+                    if let Some(obj) = value.to_object_value() {
+                        graphql_input_object!(@generate_from_input_value, $name, obj, __GRAPHQL_RENAME_RULE, $fields)
+                    }
+                    else {
+                        Err($crate::InputValueError::expected($outname, value))
+                    }
                 }
             }
-        }
 
-        impl $crate::ToInputValue for $name {
-            fn to_input_value(&self) -> $crate::InputValue {
-                graphql_input_object!(@generate_to_input_value, $name, self, $fields)
+            impl $crate::ToInputValue for $name {
+                fn to_input_value(&self) -> $crate::InputValue {
+                    graphql_input_object!(@generate_to_input_value, $name, self, __GRAPHQL_RENAME_RULE, $fields)
+                }
             }
-        }
 
-        impl $crate::GraphQLType for $name {
-            type Context = ();
-            type TypeInfo = ();
+            impl $crate::GraphQLType for $name {
+                type Context = ();
+                type TypeInfo = ();
 
-            fn name(_: &()) -> Option<&str> {
-                Some($outname)
-            }
+                fn name(_: &()) -> Option<&str> {
+                    Some($outname)
+                }
 
-            fn meta<'r>(_: &(), registry: &mut $crate::Registry<'r>) -> $crate::meta::MetaType<'r> {
-                let fields = graphql_input_object!(@generate_meta_fields, registry, $fields);
-                graphql_input_object!(
-                    @maybe_apply, $descr, description,
-                    registry.build_input_object_type::<$name>(&(), fields)).into_meta()
+                fn meta<'r>(_: &(), registry: &mut $crate::Registry<'r>) -> $crate::meta::MetaType<'r> {
+                    let fields = graphql_input_object!(@generate_meta_fields, registry, __GRAPHQL_RENAME_RULE, $fields);
+                    graphql_input_object!(
+                        @maybe_apply, $descr, description,
+                        registry.build_input_object_type::<$name>(&(), fields)).into_meta()
+                }
             }
-        }
+        };
     };
 
     // Entry point: parse calls starting with a struct declaration
     ( $(#[$meta:meta])* struct $($items:tt)* ) => {
         graphql_input_object!(
             @parse,
-            ( ( ), ( ), None, None, None, None ),
+            ( ( ), ( ), None, None, None, None, "camelCase" ),
             $(#[$meta])* struct $($items)*
         );
     };
@@ -267,7 +329,7 @@ macro_rules! graphql_input_object {
     ( $(#[$meta:meta])* pub struct $($items:tt)* ) => {
         graphql_input_object!(
             @parse,
-            ( ( ), ( ), None, None, None, None ),
+            ( ( ), ( ), None, None, None, None, "camelCase" ),
             $(#[$meta])* pub struct $($items)*
         );
     };
@@ -276,8 +338,17 @@ macro_rules! graphql_input_object {
     ( description: $($items:tt)* ) => {
         graphql_input_object!(
             @parse,
-            ( ( ), ( ), None, None, None, None ),
+            ( ( ), ( ), None, None, None, None, "camelCase" ),
             description: $($items)*
         );
     };
+
+    // Entry point: parse calls starting with the naming policy
+    ( rename_all: $($items:tt)* ) => {
+        graphql_input_object!(
+            @parse,
+            ( ( ), ( ), None, None, None, None, "camelCase" ),
+            rename_all: $($items)*
+        );
+    };
 }
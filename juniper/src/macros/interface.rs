@@ -13,8 +13,14 @@ type name as well as downcast the general type to the actual concrete type.
 ## Syntax
 
 See the documentation for [`graphql_object!`][1] on the general item and type
-syntax. `graphql_interface!` requires an additional `instance_resolvers` item,
-and does _not_ support the `interfaces` item.
+syntax. `graphql_interface!` requires an additional `instance_resolvers` item.
+
+The `interfaces` item declares the interfaces this interface implements. Types
+implementing this interface must list those interfaces as well:
+
+```rust,ignore
+interfaces: [&Node],
+```
 
 `instance_resolvers` is a match like structure used to resolve the concrete
 instance type of the interface. It starts with a context argument and continues
@@ -93,97 +99,124 @@ macro_rules! graphql_interface {
     // field deprecated <reason> <name>(...) -> <type> as <description> { ... }
     (
         @ gather_meta,
-        ($reg:expr, $acc:expr, $info:expr, $descr:expr),
+        ($reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr),
         field deprecated $reason:tt $name:ident $args:tt -> $t:ty as $desc:tt $body:block $( $rest:tt )*
     ) => {
         $acc.push(__graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $reg.field_convert::<$t, _, Self::Context>(
-                &$crate::to_camel_case(stringify!($name)), $info)
+                &$rename.apply(stringify!($name)), $info)
                 .description($desc)
                 .deprecated($reason),
             $info,
             $args));
 
-        graphql_interface!(@ gather_meta, ($reg, $acc, $info, $descr), $( $rest )*);
+        graphql_interface!(@ gather_meta, ($reg, $acc, $info, $rename, $descr, $ifaces), $( $rest )*);
     };
 
     // field deprecated <reason> <name>(...) -> <type> { ... }
     (
         @ gather_meta,
-        ($reg:expr, $acc:expr, $info:expr, $descr:expr),
+        ($reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr),
         field deprecated $reason:tt $name:ident $args:tt -> $t:ty $body:block $( $rest:tt )*
     ) => {
         $acc.push(__graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $reg.field_convert::<$t, _, Self::Context>(
-                &$crate::to_camel_case(stringify!($name)), $info)
+                &$rename.apply(stringify!($name)), $info)
                 .deprecated($reason),
             $info,
             $args));
 
-        graphql_interface!(@ gather_meta, ($reg, $acc, $info, $descr), $( $rest )*);
+        graphql_interface!(@ gather_meta, ($reg, $acc, $info, $rename, $descr, $ifaces), $( $rest )*);
     };
 
     // field <name>(...) -> <type> as <description> { ... }
     (
         @gather_meta,
-        ($reg:expr, $acc:expr, $info:expr, $descr:expr),
+        ($reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr),
         field $name:ident $args:tt -> $t:ty as $desc:tt $body:block $( $rest:tt )*
     ) => {
         $acc.push(__graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $reg.field_convert::<$t, _, Self::Context>(
-                &$crate::to_camel_case(stringify!($name)), $info)
+                &$rename.apply(stringify!($name)), $info)
                 .description($desc),
             $info,
             $args));
 
-        graphql_interface!(@ gather_meta, ($reg, $acc, $info, $descr), $( $rest )*);
+        graphql_interface!(@ gather_meta, ($reg, $acc, $info, $rename, $descr, $ifaces), $( $rest )*);
     };
 
     // field <name>(...) -> <type> { ... }
     (
         @ gather_meta,
-        ($reg:expr, $acc:expr, $info:expr, $descr:expr),
+        ($reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr),
         field $name:ident $args:tt -> $t:ty $body:block $( $rest:tt )*
     ) => {
         $acc.push(__graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $reg.field_convert::<$t, _, Self::Context>(
-                &$crate::to_camel_case(stringify!($name)), $info),
+                &$rename.apply(stringify!($name)), $info),
             $info,
             $args));
 
-        graphql_interface!(@ gather_meta, ($reg, $acc, $info, $descr), $( $rest )*);
+        graphql_interface!(@ gather_meta, ($reg, $acc, $info, $rename, $descr, $ifaces), $( $rest )*);
     };
 
     // description: <description>
     (
         @ gather_meta,
-        ($reg:expr, $acc:expr, $info:expr, $descr:expr),
+        ($reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr),
         description : $value:tt $( $rest:tt )*
     ) => {
         $descr = Some(graphql_interface!(@as_expr, $value));
 
-        graphql_interface!(@gather_meta, ($reg, $acc, $info, $descr), $( $rest )*)
+        graphql_interface!(@gather_meta, ($reg, $acc, $info, $rename, $descr, $ifaces), $( $rest )*)
+    };
+
+    // interfaces: [...]
+    (
+        @ gather_meta,
+        ($reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr),
+        interfaces : $value:tt $( $rest:tt )*
+    ) => {
+        graphql_interface!(@assign_interfaces, $reg, $ifaces, $value);
+
+        graphql_interface!(@gather_meta, ($reg, $acc, $info, $rename, $descr, $ifaces), $( $rest )*)
+    };
+
+    ( @assign_interfaces, $reg:expr, $tgt:expr, [ $($t:ty,)* ] ) => {
+        $tgt = Some(vec![
+            $($reg.get_type::<$t>(&())),*
+        ]);
+    };
+
+    ( @assign_interfaces, $reg:expr, $tgt:expr, [ $($t:ty),* ] ) => {
+        $tgt = Some(vec![
+            $($reg.get_type::<$t>(&())),*
+        ]);
     };
 
     // instance_resolvers: | <ctxtvar> | [...]
     (
         @ gather_meta,
-        ($reg:expr, $acc:expr, $info:expr, $descr:expr),
+        ($reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr),
         instance_resolvers : | $ctxtvar:pat | { $( $srctype:ty => $resolver:expr ),* $(,)* } $( $rest:tt )*
     ) => {
         $(
             let _ = $reg.get_type::<$srctype>(&());
         )*
 
-            graphql_interface!(@gather_meta, ($reg, $acc, $info, $descr), $( $rest )*)
+            graphql_interface!(@gather_meta, ($reg, $acc, $info, $rename, $descr, $ifaces), $( $rest )*)
     };
 
     // instance_resolvers: | <ctxtvar> | [...]
@@ -227,73 +260,89 @@ macro_rules! graphql_interface {
     ( @ $mfn:ident, $buildargs:tt, ) => {};
 
     (
-        ( $($lifetime:tt),* ) $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
+        ( $($lifetime:tt)* ) $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
             $( $items:tt )*
         }
     ) => {
-        graphql_interface!(@as_item, impl<$($lifetime)*> $crate::GraphQLType for $name {
-            type Context = $ctxt;
-            type TypeInfo = ();
-
-            fn name(_: &()) -> Option<&str> {
-                Some($outname)
-            }
+        // The rule is resolved once, so that an unknown rule is only reported once
+        const _: () = {
+            const __GRAPHQL_RENAME_RULE: $crate::RenameRule = __graphql__rename_rule!($($items)*);
 
-            #[allow(unused_assignments)]
-            #[allow(unused_mut)]
-            fn meta<'r>(info: &(), registry: &mut $crate::Registry<'r>) -> $crate::meta::MetaType<'r> {
-                let mut fields = Vec::new();
-                let mut description = None;
-                graphql_interface!(@ gather_meta, (registry, fields, info, description), $($items)*);
-                let mut mt = registry.build_interface_type::<$name>(&(), &fields);
+            graphql_interface!(@as_item, impl<$($lifetime)*> $crate::GraphQLType for $name {
+                type Context = $ctxt;
+                type TypeInfo = ();
 
-                if let Some(description) = description {
-                    mt = mt.description(description);
+                fn name(_: &()) -> Option<&str> {
+                    Some($outname)
                 }
 
-                mt.into_meta()
-            }
+                #[allow(unused_assignments)]
+                #[allow(unused_mut)]
+                #[allow(unused_variables)]
+                fn meta<'r>(info: &(), registry: &mut $crate::Registry<'r>) -> $crate::meta::MetaType<'r> {
+                    let mut fields = Vec::new();
+                    let mut description = None;
+                    let mut interfaces: Option<Vec<$crate::Type>> = None;
+                    let rename = __GRAPHQL_RENAME_RULE;
+                    graphql_interface!(
+                        @ gather_meta,
+                        (registry, fields, info, rename, description, interfaces),
+                        $($items)*);
+                    let mut mt = registry.build_interface_type::<$name>(&(), &fields);
+
+                    if let Some(description) = description {
+                        mt = mt.description(description);
+                    }
+
+                    if let Some(interfaces) = interfaces {
+                        mt = mt.interfaces(&interfaces);
+                    }
+
+                    mt.into_meta()
+                }
 
-            #[allow(unused_variables)]
-            #[allow(unused_mut)]
-            fn resolve_field(&$mainself, info: &(), field: &str, args: &$crate::Arguments, mut executor: &$crate::Executor<Self::Context>) -> $crate::ExecutionResult {
-                __graphql__build_field_matches!(
-                    ($outname, $mainself, field, args, executor),
-                    (),
-                    $($items)*);
-            }
+                #[allow(unused_variables)]
+                #[allow(unused_mut)]
+                fn resolve_field(&$mainself, info: &(), field: &str, args: &$crate::Arguments, mut executor: &$crate::Executor<Self::Context>) -> $crate::ExecutionResult {
+                    let rename = __GRAPHQL_RENAME_RULE;
+                    __graphql__build_field_matches!(
+                        ($outname, $mainself, field, args, executor, rename),
+                        (),
+                        $($items)*);
+                }
 
-            fn concrete_type_name(&$mainself, context: &Self::Context) -> String {
-                graphql_interface!(
-                    @ concrete_type_name,
-                    ($outname, context, $ctxt),
-                    $($items)*);
-            }
+                fn concrete_type_name(&$mainself, context: &Self::Context) -> String {
+                    graphql_interface!(
+                        @ concrete_type_name,
+                        ($outname, context, $ctxt),
+                        $($items)*);
+                }
 
-            fn resolve_into_type(
-                &$mainself,
-                _: &(),
-                type_name: &str,
-                _: Option<&[$crate::Selection]>,
-                executor: &$crate::Executor<Self::Context>,
-            )
-                -> $crate::ExecutionResult
-            {
-                graphql_interface!(
-                    @ resolve_into_type,
-                    ($outname, type_name, executor, $ctxt),
-                    $($items)*);
-            }
-        });
+                fn resolve_into_type(
+                    &$mainself,
+                    _: &(),
+                    type_name: &str,
+                    _: Option<&[$crate::Selection]>,
+                    executor: &$crate::Executor<Self::Context>,
+                )
+                    -> $crate::ExecutionResult
+                {
+                    graphql_interface!(
+                        @ resolve_into_type,
+                        ($outname, type_name, executor, $ctxt),
+                        $($items)*);
+                }
+            });
+        };
     };
 
     (
-        <$($lifetime:tt),*> $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
+        <$($lifetime:tt $(: $bound:path)*),*> $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
             $( $items:tt )*
         }
     ) => {
         graphql_interface!(
-            ($($lifetime),*) $name : $ctxt as $outname | &$mainself | { $( $items )* });
+            ($($lifetime $(: $bound)*),*) $name : $ctxt as $outname | &$mainself | { $( $items )* });
     };
 
     (
@@ -66,7 +66,7 @@ graphql_object!(User: () |&self| {
 ## Generics and lifetimes
 
 You can expose generic or pointer types by prefixing the type with the necessary
-generic parameters:
+generic parameters. Type parameters can have trait bounds:
 
 ```rust
 # #[macro_use] extern crate juniper;
@@ -78,7 +78,7 @@ graphql_object!(<'a> &'a SomeTrait: () as "SomeTrait" |&self| {
 
 struct GenericType<T> { items: Vec<T> }
 
-graphql_object!(<T> GenericType<T>: () as "GenericType" |&self| {
+graphql_object!(<T: Clone> GenericType<T>: () as "GenericType" |&self| {
     field count() -> i32 { self.items.len() as i32 }
 });
 
@@ -184,6 +184,16 @@ Informs the schema that the type implements the specified interfaces. This needs
 to be _GraphQL_ interfaces, not necessarily Rust traits. The Rust types do not
 need to have any connection, only what's exposed in the schema matters.
 
+### Naming policy
+
+```text
+rename_all: "snake_case"
+```
+
+Controls how field and argument names are exposed in the schema. Supported
+rules are `"camelCase"` (the default), `"snake_case"`, `"SCREAMING_SNAKE_CASE"`,
+and `"none"`, which keeps the Rust names as they are.
+
 ### Fields
 
 ```text
@@ -194,7 +204,8 @@ field deprecated "Reason" name(args...) -> Type as "Field description" { }
 ```
 
 Defines a field on the object. The name is converted to camel case, e.g.
-`user_name` is exposed as `userName`. The `as "Field description"` adds the
+`user_name` is exposed as `userName`, unless a different naming policy is set
+with `rename_all`. The `as "Field description"` adds the
 string as documentation on the field.
 
 ### Field arguments
@@ -205,6 +216,7 @@ arg_name: ArgType
 arg_name = default_value: ArgType
 arg_name: ArgType as "Argument description"
 arg_name = default_value: ArgType as "Argument description"
+arg_name: ArgType as "Argument description" where [min(1), max(10)]
 ```
 
 Field arguments can take many forms. If the field needs access to the executor
@@ -226,6 +238,12 @@ arg_name: Option<i32>       -- optional, None if unspecified
 arg_name = 123: i32         -- optional, "123" if unspecified
 ```
 
+Arguments can be constrained by appending `where [...]` with a list of
+[validators][2]: `min(n)` and `max(n)` for numbers, `min_length(n)` and
+`max_length(n)` for strings and lists, and `pattern("regex")` for strings.
+Values breaking a constraint are rejected when validating the query, or, for
+variables, when resolving the field.
+
 Due to some syntactical limitations in the macros, you must parentesize more
 complex default value expressions:
 
@@ -235,6 +253,7 @@ arg_name = ("default".to_owned()): String
 ```
 
 [1]: struct.Executor.html
+[2]: meta/enum.Validator.html
 
 */
 #[macro_export]
@@ -245,109 +264,122 @@ macro_rules! graphql_object {
     // field deprecated <reason> <name>(...) -> <type> as <description> { ... }
     (
         @gather_object_meta,
-        $reg:expr, $acc:expr, $info:expr, $descr:expr, $ifaces:expr,
+        $reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr,
         field deprecated $reason:tt $name:ident $args:tt -> $t:ty as $desc:tt $body:block $( $rest:tt )*
     ) => {
         $acc.push(__graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $reg.field_convert::<$t, _, Self::Context>(
-                &$crate::to_camel_case(stringify!($name)), $info)
+                &$rename.apply(stringify!($name)), $info)
                 .description($desc)
                 .deprecated($reason),
             $info,
             $args));
 
-        graphql_object!(@gather_object_meta, $reg, $acc, $info, $descr, $ifaces, $( $rest )*);
+        graphql_object!(@gather_object_meta, $reg, $acc, $info, $rename, $descr, $ifaces, $( $rest )*);
     };
 
     // field deprecated <reason> <name>(...) -> <type> { ... }
     (
         @gather_object_meta,
-        $reg:expr, $acc:expr, $info:expr, $descr:expr, $ifaces:expr,
+        $reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr,
         field deprecated $reason:tt $name:ident $args:tt -> $t:ty $body:block $( $rest:tt )*
     ) => {
         $acc.push(__graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $reg.field_convert::<$t, _, Self::Context>(
-                &$crate::to_camel_case(stringify!($name)), $info)
+                &$rename.apply(stringify!($name)), $info)
                 .deprecated($reason),
             $info,
             $args));
 
-        graphql_object!(@gather_object_meta, $reg, $acc, $info, $descr, $ifaces, $( $rest )*);
+        graphql_object!(@gather_object_meta, $reg, $acc, $info, $rename, $descr, $ifaces, $( $rest )*);
     };
 
     // field <name>(...) -> <type> as <description> { ... }
     (
         @gather_object_meta,
-        $reg:expr, $acc:expr, $info:expr, $descr:expr, $ifaces:expr,
+        $reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr,
         field $name:ident $args:tt -> $t:ty as $desc:tt $body:block $( $rest:tt )*
     ) => {
         $acc.push(__graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $reg.field_convert::<$t, _, Self::Context>(
-                &$crate::to_camel_case(stringify!($name)), $info)
+                &$rename.apply(stringify!($name)), $info)
                 .description($desc),
             $info,
             $args));
 
-        graphql_object!(@gather_object_meta, $reg, $acc, $info, $descr, $ifaces, $( $rest )*);
+        graphql_object!(@gather_object_meta, $reg, $acc, $info, $rename, $descr, $ifaces, $( $rest )*);
     };
 
     // field <name>(...) -> <type> { ... }
     (
         @gather_object_meta,
-        $reg:expr, $acc:expr, $info:expr, $descr:expr, $ifaces:expr,
+        $reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr,
         field $name:ident $args:tt -> $t:ty $body:block $( $rest:tt )*
     ) => {
         $acc.push(__graphql__args!(
             @apply_args,
             $reg,
+            $rename,
             $reg.field_convert::<$t, _, Self::Context>(
-                &$crate::to_camel_case(stringify!($name)), $info),
+                &$rename.apply(stringify!($name)), $info),
             $info,
             $args));
 
-        graphql_object!(@gather_object_meta, $reg, $acc, $info, $descr, $ifaces, $( $rest )*);
+        graphql_object!(@gather_object_meta, $reg, $acc, $info, $rename, $descr, $ifaces, $( $rest )*);
     };
 
     // description: <description>
     (
         @gather_object_meta,
-        $reg:expr, $acc:expr, $info:expr, $descr:expr, $ifaces:expr,
+        $reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr,
         description : $value:tt $( $rest:tt )*
     ) => {
         $descr = Some(graphql_object!(@as_expr, $value));
 
-        graphql_object!(@gather_object_meta, $reg, $acc, $info, $descr, $ifaces, $( $rest )*)
+        graphql_object!(@gather_object_meta, $reg, $acc, $info, $rename, $descr, $ifaces, $( $rest )*)
+    };
+
+    // rename_all: <rule>
+    (
+        @gather_object_meta,
+        $reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr,
+        rename_all : $value:tt $( $rest:tt )*
+    ) => {
+        graphql_object!(@gather_object_meta, $reg, $acc, $info, $rename, $descr, $ifaces, $( $rest )*)
     };
 
     // interfaces: [...]
     (
         @gather_object_meta,
-        $reg:expr, $acc:expr, $info:expr, $descr:expr, $ifaces:expr,
+        $reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr,
         interfaces : $value:tt $( $rest:tt )*
     ) => {
         graphql_object!(@assign_interfaces, $reg, $ifaces, $value);
 
-        graphql_object!(@gather_object_meta, $reg, $acc, $info, $descr, $ifaces, $( $rest )*)
+        graphql_object!(@gather_object_meta, $reg, $acc, $info, $rename, $descr, $ifaces, $( $rest )*)
     };
 
     // eat commas
     (
         @gather_object_meta,
-        $reg:expr, $acc:expr, $info:expr, $descr:expr, $ifaces:expr, , $( $rest:tt )*
+        $reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr, , $( $rest:tt )*
     ) => {
-        graphql_object!(@gather_object_meta, $reg, $acc, $info, $descr, $ifaces, $( $rest )*)
+        graphql_object!(@gather_object_meta, $reg, $acc, $info, $rename, $descr, $ifaces, $( $rest )*)
     };
 
     // base case
     (
         @gather_object_meta,
-        $reg:expr, $acc:expr, $info:expr, $descr:expr, $ifaces:expr,
+        $reg:expr, $acc:expr, $info:expr, $rename:expr, $descr:expr, $ifaces:expr,
     ) => {};
 
     ( @assign_interfaces, $reg:expr, $tgt:expr, [ $($t:ty,)* ] ) => {
@@ -366,67 +398,86 @@ macro_rules! graphql_object {
         ( $($lifetime:tt)* );
         $name:ty; $ctxt:ty; $outname:expr; $mainself:ident; $($items:tt)*
     ) => {
-        graphql_object!(@as_item, impl<$($lifetime)*> $crate::GraphQLType for $name {
-            type Context = $ctxt;
-            type TypeInfo = ();
-
-            fn name(_: &()) -> Option<&str> {
-                Some($outname)
-            }
-
-            #[allow(unused_assignments)]
-            #[allow(unused_mut)]
-            fn meta<'r>(info: &(), registry: &mut $crate::Registry<'r>) -> $crate::meta::MetaType<'r> {
-                let mut fields = Vec::new();
-                let mut description = None;
-                let mut interfaces: Option<Vec<$crate::Type>> = None;
-                graphql_object!(
-                    @gather_object_meta,
-                    registry, fields, info, description, interfaces, $($items)*
-                );
-                let mut mt = registry.build_object_type::<$name>(info, &fields);
-
-                if let Some(description) = description {
-                    mt = mt.description(description);
+        // The rule is resolved once, so that an unknown rule is only reported once
+        const _: () = {
+            const __GRAPHQL_RENAME_RULE: $crate::RenameRule = __graphql__rename_rule!($($items)*);
+
+            graphql_object!(@as_item, impl<$($lifetime)*> $crate::GraphQLType for $name {
+                type Context = $ctxt;
+                type TypeInfo = ();
+
+                fn name(_: &()) -> Option<&str> {
+                    Some($outname)
                 }
 
-                if let Some(interfaces) = interfaces {
-                    mt = mt.interfaces(&interfaces);
+                #[allow(unused_assignments)]
+                #[allow(unused_mut)]
+                #[allow(unused_variables)]
+                fn meta<'r>(info: &(), registry: &mut $crate::Registry<'r>) -> $crate::meta::MetaType<'r> {
+                    let mut fields = Vec::new();
+                    let mut description = None;
+                    let mut interfaces: Option<Vec<$crate::Type>> = None;
+                    let rename = __GRAPHQL_RENAME_RULE;
+                    graphql_object!(
+                        @gather_object_meta,
+                        registry, fields, info, rename, description, interfaces, $($items)*
+                    );
+                    let mut mt = registry.build_object_type::<$name>(info, &fields);
+
+                    if let Some(description) = description {
+                        mt = mt.description(description);
+                    }
+
+                    if let Some(interfaces) = interfaces {
+                        mt = mt.interfaces(&interfaces);
+                    }
+
+                    mt.into_meta()
                 }
 
-                mt.into_meta()
-            }
-
-            fn concrete_type_name(&self, _: &Self::Context) -> String {
-                $outname.to_owned()
-            }
-
-            #[allow(unused_variables)]
-            #[allow(unused_mut)]
-            fn resolve_field(
-                &$mainself,
-                info: &(),
-                field: &str,
-                args: &$crate::Arguments,
-                executor: &$crate::Executor<Self::Context>
-            )
-                -> $crate::ExecutionResult
-            {
-                __graphql__build_field_matches!(
-                    ($outname, $mainself, field, args, executor),
-                    (),
-                    $($items)*);
-            }
-        });
+                fn concrete_type_name(&self, _: &Self::Context) -> String {
+                    $outname.to_owned()
+                }
+
+                #[allow(unused_variables)]
+                #[allow(unused_mut)]
+                fn resolve_field(
+                    &$mainself,
+                    info: &(),
+                    field: &str,
+                    args: &$crate::Arguments,
+                    executor: &$crate::Executor<Self::Context>
+                )
+                    -> $crate::ExecutionResult
+                {
+                    let rename = __GRAPHQL_RENAME_RULE;
+                    __graphql__build_field_matches!(
+                        ($outname, $mainself, field, args, executor, rename),
+                        (),
+                        $($items)*);
+                }
+
+                fn stream(
+                    &$mainself,
+                    info: &(),
+                    selection_set: Option<&[$crate::Selection]>,
+                    executor: &$crate::Executor<Self::Context>
+                )
+                    -> bool
+                {
+                    $crate::stream_selection_set($mainself, info, selection_set, executor)
+                }
+            });
+        };
     };
 
     (
-        <$( $lifetime:tt ),*> $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
+        <$( $lifetime:tt $(: $bound:path)* ),*> $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
             $( $items:tt )*
         }
     ) => {
         graphql_object!(
-            ( $($lifetime),* ); $name; $ctxt; $outname; $mainself; $( $items )*);
+            ( $($lifetime $(: $bound)*),* ); $name; $ctxt; $outname; $mainself; $( $items )*);
     };
 
     (
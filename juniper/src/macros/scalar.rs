@@ -97,6 +97,14 @@ macro_rules! graphql_scalar {
             fn from_input_value($fiv_arg: &$crate::InputValue) -> $fiv_result {
                 $fiv_body
             }
+
+            fn try_from_input_value(
+                v: &$crate::InputValue,
+            ) -> Result<$name, $crate::InputValueError> {
+                <$name as $crate::FromInputValue>::from_input_value(v).ok_or_else(|| {
+                    $crate::InputValueError::expected(graphql_scalar!(@as_expr, $outname), v)
+                })
+            }
         }
     };
 
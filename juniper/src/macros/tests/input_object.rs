@@ -1,6 +1,6 @@
 use ordermap::OrderMap;
 
-use ast::{FromInputValue, InputValue};
+use ast::{FromInputValue, InputValue, ToInputValue};
 use executor::Variables;
 use value::Value;
 use schema::model::RootNode;
@@ -85,6 +85,15 @@ graphql_input_object!(
     }
 );
 
+graphql_input_object!(
+    rename_all: "SCREAMING_SNAKE_CASE"
+
+    struct RenameScreaming {
+        field_one: String,
+        field_two = 456: i32,
+    }
+);
+
 graphql_object!(Root: () |&self| {
     field test_field(
         a1: DefaultName,
@@ -98,6 +107,7 @@ graphql_object!(Root: () |&self| {
         a9: NamedPublicWithDescription,
         a10: NamedPublic,
         a11: FieldWithDefaults,
+        a12: RenameScreaming,
     ) -> i32 {
         0
     }
@@ -473,3 +483,77 @@ fn field_with_defaults_introspection() {
         ].into_iter().collect())));
     });
 }
+
+#[test]
+fn rename_all_introspection() {
+    let doc = r#"
+    {
+        __type(name: "RenameScreaming") {
+            name
+            description
+            inputFields {
+                name
+                description
+                type {
+                    name
+                }
+                defaultValue
+            }
+        }
+    }
+    "#;
+
+    run_type_info_query(doc, |type_info, fields| {
+        assert_eq!(type_info.get("name"), Some(&Value::string("RenameScreaming")));
+
+        assert_eq!(fields.len(), 2);
+
+        assert!(fields.contains(&Value::object(vec![
+            ("name", Value::string("FIELD_ONE")),
+            ("description", Value::null()),
+            ("type", Value::object(vec![
+                ("name", Value::null()),
+            ].into_iter().collect())),
+            ("defaultValue", Value::null()),
+        ].into_iter().collect())));
+
+        assert!(fields.contains(&Value::object(vec![
+            ("name", Value::string("FIELD_TWO")),
+            ("description", Value::null()),
+            ("type", Value::object(vec![
+                ("name", Value::string("Int")),
+            ].into_iter().collect())),
+            ("defaultValue", Value::string("456")),
+        ].into_iter().collect())));
+    });
+}
+
+#[test]
+fn rename_all_input_value() {
+    let iv = InputValue::object(
+        vec![
+        ("FIELD_ONE", InputValue::string("number one")),
+    ].into_iter()
+            .collect(),
+    );
+
+    let dv: Option<RenameScreaming> = FromInputValue::from_input_value(&iv);
+
+    assert!(dv.is_some());
+
+    let dv = dv.unwrap();
+
+    assert_eq!(dv.field_one, "number one");
+    assert_eq!(dv.field_two, 456);
+
+    assert_eq!(
+        dv.to_input_value(),
+        InputValue::object(
+            vec![
+                ("FIELD_ONE", InputValue::string("number one")),
+                ("FIELD_TWO", InputValue::int(456)),
+            ].into_iter()
+                .collect()
+        )
+    );
+}
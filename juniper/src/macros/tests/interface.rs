@@ -10,7 +10,7 @@ use types::scalars::EmptyMutation;
 
 Syntax to validate:
 
-* Order of items: fields, description, instance resolvers
+* Order of items: fields, description, interfaces, instance resolvers
 * Optional Generics/lifetimes
 * Custom name vs. default name
 * Optional commas between items
@@ -41,6 +41,8 @@ struct CommasOnMeta;
 
 struct ResolversWithTrailingComma;
 
+struct WithInterfaces;
+
 struct Root;
 
 graphql_object!(Concrete: () |&self| {
@@ -113,6 +115,14 @@ graphql_interface!(ResolversWithTrailingComma: () |&self| {
     field simple() -> i32 { 0 }
 });
 
+graphql_interface!(WithInterfaces: () |&self| {
+    field simple() -> i32 { 0 }
+
+    interfaces: [CustomName]
+
+    instance_resolvers: |_| { Concrete => Some(Concrete) }
+});
+
 graphql_object!(<'a> Root: () as "Root" |&self| {
     field custom_name() -> CustomName { CustomName {} }
 
@@ -130,6 +140,8 @@ graphql_object!(<'a> Root: () as "Root" |&self| {
         ResolversWithTrailingComma {}
     }
 
+    field with_interfaces() -> WithInterfaces { WithInterfaces {} }
+
 });
 
 
@@ -145,6 +157,9 @@ where
             fields(includeDeprecated: true) {
                 name
             }
+            interfaces {
+                name
+            }
         }
     }
     "#;
@@ -284,3 +299,22 @@ fn introspect_resolvers_with_trailing_comma() {
         ].into_iter().collect())));
     });
 }
+
+#[test]
+fn introspect_with_interfaces() {
+    run_type_info_query("WithInterfaces", |object, fields| {
+        assert_eq!(object.get("name"), Some(&Value::string("WithInterfaces")));
+        assert_eq!(
+            object.get("interfaces"),
+            Some(&Value::list(vec![
+                Value::object(vec![
+                    ("name", Value::string("ACustomNamedInterface")),
+                ].into_iter().collect()),
+            ]))
+        );
+
+        assert!(fields.contains(&Value::object(vec![
+            ("name", Value::string("simple")),
+        ].into_iter().collect())));
+    });
+}
@@ -5,7 +5,7 @@ use ast::InputValue;
 use value::Value;
 use schema::model::RootNode;
 use types::scalars::EmptyMutation;
-use executor::{FieldResult, Context};
+use executor::{FieldResult, Context, Variables};
 
 /*
 
@@ -40,6 +40,8 @@ struct InterfacesFirst;
 struct CommasWithTrailing;
 struct CommasOnMeta;
 
+struct RenameSnakeCase;
+
 struct Root;
 
 graphql_object!(CustomName: () as "ACustomNamedType" |&self| {
@@ -104,6 +106,14 @@ graphql_object!(CommasOnMeta: () |&self| {
     field simple() -> i32 { 0 }
 });
 
+graphql_object!(RenameSnakeCase: () |&self| {
+    field user_name() -> &str { "bob" }
+
+    field double_it(some_value: i32) -> i32 { some_value * 2 }
+
+    rename_all: "snake_case"
+});
+
 struct InnerContext;
 impl Context for InnerContext {}
 
@@ -132,6 +142,8 @@ graphql_object!(<'a> Root: InnerContext as "Root" |&self| {
     field commas_with_trailing() -> CommasWithTrailing { CommasWithTrailing {} }
     field commas_on_meta() -> CommasOnMeta { CommasOnMeta {} }
 
+    field rename_snake_case() -> RenameSnakeCase { RenameSnakeCase {} }
+
     field ctx_switcher() -> CtxSwitcher { CtxSwitcher {} }
 });
 
@@ -375,3 +387,47 @@ fn introspect_ctx_switch() {
         })));
     });
 }
+
+#[test]
+fn introspect_rename_snake_case() {
+    run_type_info_query("RenameSnakeCase", |object, fields| {
+        assert_eq!(object.get("name"), Some(&Value::string("RenameSnakeCase")));
+
+        assert!(fields.contains(&graphql_value!({
+            "name": "user_name",
+            "type": { "kind": "NON_NULL", "name": None, "ofType": { "kind": "SCALAR", "name": "String" } }
+        })));
+
+        assert!(fields.contains(&graphql_value!({
+            "name": "double_it",
+            "type": { "kind": "NON_NULL", "name": None, "ofType": { "kind": "SCALAR", "name": "Int" } }
+        })));
+    });
+}
+
+#[test]
+fn resolve_rename_snake_case() {
+    let doc = r#"
+    {
+        renameSnakeCase {
+            user_name
+            double_it(some_value: 21)
+        }
+    }
+    "#;
+    let schema = RootNode::new(Root {}, EmptyMutation::<InnerContext>::new());
+
+    let (result, errs) =
+        ::execute(doc, None, &schema, &Variables::new(), &InnerContext).expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        graphql_value!({
+            "renameSnakeCase": {
+                "user_name": "bob",
+                "double_it": 42,
+            }
+        })
+    );
+}
@@ -101,7 +101,7 @@ macro_rules! graphql_union {
     ( @ $mfn:ident, $args:tt, ) => {};
 
     (
-        ( $($lifetime:tt),* ) $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
+        ( $($lifetime:tt)* ) $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
             $( $items:tt )*
         }
     ) => {
@@ -153,12 +153,12 @@ macro_rules! graphql_union {
     };
 
     (
-        <$($lifetime:tt),*> $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
+        <$($lifetime:tt $(: $bound:path)*),*> $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
             $( $items:tt )*
         }
     ) => {
         graphql_union!(
-            ($($lifetime),*) $name : $ctxt as $outname | &$mainself | { $( $items )* });
+            ($($lifetime $(: $bound)*),*) $name : $ctxt as $outname | &$mainself | { $( $items )* });
     };
 
     (
@@ -15,6 +15,19 @@ pub fn parse_document_source(s: &str) -> UnlocatedParseResult<Document> {
     parse_document(&mut parser)
 }
 
+#[doc(hidden)]
+pub fn parse_type_source(s: &str) -> UnlocatedParseResult<Type> {
+    let mut lexer = Lexer::new(s);
+    let mut parser = try!(Parser::new(&mut lexer).map_err(|s| s.map(ParseError::LexerError)));
+    let parsed_type = try!(parse_type(&mut parser));
+
+    if parser.peek().item != Token::EndOfFile {
+        return Err(try!(parser.next()).map(ParseError::UnexpectedToken));
+    }
+
+    Ok(parsed_type.item)
+}
+
 fn parse_document<'a>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Document<'a>> {
     let mut defs = Vec::new();
 
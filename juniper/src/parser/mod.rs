@@ -5,12 +5,20 @@ mod lexer;
 mod parser;
 mod value;
 mod document;
+mod printer;
+mod normalizer;
 
 #[cfg(test)]
 mod tests;
 
-pub use self::document::parse_document_source;
+pub use self::document::{parse_document_source, parse_type_source};
+pub use self::value::parse_value_source;
+pub use self::printer::{print_document, print_value};
+pub use self::normalizer::{normalize_document, normalize_query};
 
 pub use self::parser::{OptionParseResult, ParseError, ParseResult, Parser, UnlocatedParseResult};
 pub use self::lexer::{Lexer, LexerError, Token};
 pub use self::utils::{SourcePosition, Spanning};
+
+// The document AST, for tools working on parsed queries
+pub use ast::{Definition, Directive, Document, Fragment, Operation, OperationType};
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use ast::{Arguments, Definition, Directive, Document, Fragment, InlineFragment, InputValue,
+          Operation, Selection};
+use parser::{parse_document_source, print_document, ParseError, Spanning};
+
+/// Normalize a document to the shape of its operations
+///
+/// Two queries that only differ in formatting, field order, aliases, fragment
+/// structure, or the values passed to them normalize to the same document:
+///
+/// * fragment spreads are replaced by inline fragments, and fragment
+///   definitions are dropped,
+/// * inline fragments without type condition or directives are merged into
+///   the surrounding selection set,
+/// * aliases and variable definitions are removed,
+/// * every argument value is replaced by a variable named like the argument,
+/// * fields, inline fragments, arguments, and directives are sorted by name.
+///
+/// The result is meant for logging and comparing queries, e.g. as the key of
+/// a persisted query whitelist. It generally isn't valid for execution since
+/// its variables are not defined.
+pub fn normalize_document<'a>(document: &Document<'a>) -> Document<'a> {
+    let fragments = document
+        .iter()
+        .filter_map(|d| match *d {
+            Definition::Fragment(ref f) => Some((f.item.name.item, &f.item)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    let normalizer = Normalizer {
+        fragments: fragments,
+    };
+
+    document
+        .iter()
+        .filter_map(|d| match *d {
+            Definition::Operation(ref op) => Some(Definition::Operation(Spanning {
+                item: Operation {
+                    operation_type: op.item.operation_type.clone(),
+                    name: op.item.name.clone(),
+                    variable_definitions: None,
+                    directives: normalizer.directives(&op.item.directives),
+                    selection_set: normalizer.selection_set(&op.item.selection_set, &mut vec![]),
+                },
+                start: op.start.clone(),
+                end: op.end.clone(),
+            })),
+            Definition::Fragment(_) => None,
+        })
+        .collect()
+}
+
+/// Parse a query and print its normalized form
+///
+/// See `normalize_document` for the normalization rules.
+///
+/// ```rust
+/// use juniper::parser::normalize_query;
+///
+/// assert_eq!(
+///     normalize_query(r#"{ me: user(id: "4") { name ...F } } fragment F on User { email }"#)
+///         .unwrap(),
+///     normalize_query(r#"query { user(id: $userId) { ... on User { email }, name } }"#)
+///         .unwrap()
+/// );
+/// ```
+pub fn normalize_query(source: &str) -> Result<String, Spanning<ParseError>> {
+    let document = try!(parse_document_source(source));
+
+    Ok(print_document(&normalize_document(&document)))
+}
+
+struct Normalizer<'a: 'd, 'd> {
+    fragments: HashMap<&'a str, &'d Fragment<'a>>,
+}
+
+impl<'a, 'd> Normalizer<'a, 'd> {
+    fn selection_set(
+        &self,
+        selection_set: &[Selection<'a>],
+        visited: &mut Vec<&'a str>,
+    ) -> Vec<Selection<'a>> {
+        let mut result = Vec::new();
+        self.collect_selections(selection_set, visited, &mut result);
+
+        result.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+        result
+    }
+
+    fn collect_selections(
+        &self,
+        selection_set: &[Selection<'a>],
+        visited: &mut Vec<&'a str>,
+        result: &mut Vec<Selection<'a>>,
+    ) {
+        for selection in selection_set {
+            match *selection {
+                Selection::Field(ref field) => {
+                    let mut field = field.clone();
+
+                    field.item.alias = None;
+                    field.item.arguments = self.arguments(&field.item.arguments);
+                    field.item.directives = self.directives(&field.item.directives);
+                    field.item.selection_set = field
+                        .item
+                        .selection_set
+                        .map(|s| self.selection_set(&s, visited));
+
+                    result.push(Selection::Field(field));
+                }
+                Selection::FragmentSpread(ref spread) => {
+                    let name = spread.item.name.item;
+
+                    // Unknown fragments are kept as spreads, and cycles are
+                    // cut at the first repeated fragment
+                    let fragment = match self.fragments.get(name) {
+                        Some(fragment) => fragment,
+                        None => {
+                            result.push(selection.clone());
+                            continue;
+                        }
+                    };
+                    if visited.contains(&name) {
+                        continue;
+                    }
+
+                    visited.push(name);
+                    let selection_set = self.selection_set(&fragment.selection_set, visited);
+                    visited.pop();
+
+                    result.push(Selection::InlineFragment(Spanning {
+                        item: InlineFragment {
+                            type_condition: Some(fragment.type_condition.clone()),
+                            directives: self.directives(&spread.item.directives),
+                            selection_set: selection_set,
+                        },
+                        start: spread.start.clone(),
+                        end: spread.end.clone(),
+                    }));
+                }
+                Selection::InlineFragment(ref fragment) => {
+                    if fragment.item.type_condition.is_none() &&
+                        fragment.item.directives.is_none()
+                    {
+                        self.collect_selections(&fragment.item.selection_set, visited, result);
+                        continue;
+                    }
+
+                    result.push(Selection::InlineFragment(Spanning {
+                        item: InlineFragment {
+                            type_condition: fragment.item.type_condition.clone(),
+                            directives: self.directives(&fragment.item.directives),
+                            selection_set: self.selection_set(
+                                &fragment.item.selection_set,
+                                visited,
+                            ),
+                        },
+                        start: fragment.start.clone(),
+                        end: fragment.end.clone(),
+                    }));
+                }
+            }
+        }
+    }
+
+    fn arguments(
+        &self,
+        arguments: &Option<Spanning<Arguments<'a>>>,
+    ) -> Option<Spanning<Arguments<'a>>> {
+        arguments.as_ref().map(|arguments| {
+            let mut items = arguments
+                .item
+                .iter()
+                .map(|&(ref name, ref value)| {
+                    (
+                        name.clone(),
+                        Spanning {
+                            item: InputValue::variable(name.item),
+                            start: value.start.clone(),
+                            end: value.end.clone(),
+                        },
+                    )
+                })
+                .collect::<Vec<_>>();
+            items.sort_by(|a, b| a.0.item.cmp(b.0.item));
+
+            Spanning {
+                item: Arguments { items: items },
+                start: arguments.start.clone(),
+                end: arguments.end.clone(),
+            }
+        })
+    }
+
+    fn directives(
+        &self,
+        directives: &Option<Vec<Spanning<Directive<'a>>>>,
+    ) -> Option<Vec<Spanning<Directive<'a>>>> {
+        directives.as_ref().map(|directives| {
+            let mut directives = directives
+                .iter()
+                .map(|d| Spanning {
+                    item: Directive {
+                        name: d.item.name.clone(),
+                        arguments: self.arguments(&d.item.arguments),
+                    },
+                    start: d.start.clone(),
+                    end: d.end.clone(),
+                })
+                .collect::<Vec<_>>();
+            directives.sort_by(|a, b| a.item.name.item.cmp(b.item.name.item));
+            directives
+        })
+    }
+}
+
+/// Fields sort before fragments, by name and then by the names of their
+/// arguments; fragments sort by type condition
+fn sort_key<'a>(selection: &Selection<'a>) -> (u8, &'a str, String) {
+    match *selection {
+        Selection::Field(ref f) => (
+            0,
+            f.item.name.item,
+            f.item
+                .arguments
+                .as_ref()
+                .map(|a| {
+                    a.item
+                        .iter()
+                        .map(|&(ref k, _)| k.item)
+                        .collect::<Vec<_>>()
+                        .join(",")
+                })
+                .unwrap_or_default(),
+        ),
+        Selection::InlineFragment(ref f) => (
+            1,
+            f.item.type_condition.as_ref().map(|c| c.item).unwrap_or(""),
+            String::new(),
+        ),
+        Selection::FragmentSpread(ref s) => (2, s.item.name.item, String::new()),
+    }
+}
@@ -0,0 +1,265 @@
+use std::fmt::Write;
+
+use ast::{Arguments, Definition, Directive, Document, Fragment, InputValue, Operation,
+          OperationType, Selection, VariableDefinitions};
+use parser::Spanning;
+
+/// Print a document as GraphQL source text
+///
+/// Definitions are separated by blank lines, and selection sets are indented
+/// by two spaces. Anonymous queries without variables or directives are
+/// printed in the shorthand form `{ ... }`.
+///
+/// ```rust
+/// use juniper::parser::{parse_document_source, print_document};
+///
+/// let document = parse_document_source("query Q{user(id:4){name}}").unwrap();
+///
+/// assert_eq!(
+///     print_document(&document),
+///     "query Q {\n  user(id: 4) {\n    name\n  }\n}\n"
+/// );
+/// ```
+pub fn print_document(document: &Document) -> String {
+    let mut printer = Printer {
+        out: String::new(),
+        indent: 0,
+    };
+
+    for (i, definition) in document.iter().enumerate() {
+        if i > 0 {
+            printer.out.push('\n');
+        }
+
+        match *definition {
+            Definition::Operation(ref op) => printer.operation(&op.item),
+            Definition::Fragment(ref fragment) => printer.fragment(&fragment.item),
+        }
+
+        printer.out.push('\n');
+    }
+
+    printer.out
+}
+
+/// Print a value as a GraphQL literal
+///
+/// Unlike the `Display` implementation of `InputValue`, strings are escaped
+/// and floats always contain a decimal point or exponent.
+pub fn print_value(value: &InputValue) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+struct Printer {
+    out: String,
+    indent: usize,
+}
+
+impl Printer {
+    fn operation(&mut self, op: &Operation) {
+        let is_shorthand = op.operation_type == OperationType::Query && op.name.is_none() &&
+            op.variable_definitions.is_none() && op.directives.is_none();
+
+        if !is_shorthand {
+            self.out.push_str(match op.operation_type {
+                OperationType::Query => "query",
+                OperationType::Mutation => "mutation",
+            });
+
+            if let Some(ref name) = op.name {
+                self.out.push(' ');
+                self.out.push_str(name.item);
+            }
+
+            if let Some(ref defs) = op.variable_definitions {
+                self.variable_definitions(&defs.item);
+            }
+
+            self.directives(&op.directives);
+            self.out.push(' ');
+        }
+
+        self.selection_set(&op.selection_set);
+    }
+
+    fn fragment(&mut self, fragment: &Fragment) {
+        self.out.push_str("fragment ");
+        self.out.push_str(fragment.name.item);
+        self.out.push_str(" on ");
+        self.out.push_str(fragment.type_condition.item);
+        self.directives(&fragment.directives);
+        self.out.push(' ');
+        self.selection_set(&fragment.selection_set);
+    }
+
+    fn variable_definitions(&mut self, defs: &VariableDefinitions) {
+        if defs.items.is_empty() {
+            return;
+        }
+
+        self.out.push('(');
+
+        for (i, &(ref name, ref def)) in defs.items.iter().enumerate() {
+            if i > 0 {
+                self.out.push_str(", ");
+            }
+
+            write!(self.out, "${}: {}", name.item, def.var_type.item).unwrap();
+
+            if let Some(ref default) = def.default_value {
+                self.out.push_str(" = ");
+                write_value(&mut self.out, &default.item);
+            }
+        }
+
+        self.out.push(')');
+    }
+
+    fn selection_set(&mut self, selection_set: &[Selection]) {
+        self.out.push_str("{\n");
+        self.indent += 1;
+
+        for selection in selection_set {
+            self.write_indent();
+
+            match *selection {
+                Selection::Field(Spanning { item: ref field, .. }) => {
+                    if let Some(ref alias) = field.alias {
+                        self.out.push_str(alias.item);
+                        self.out.push_str(": ");
+                    }
+
+                    self.out.push_str(field.name.item);
+                    self.arguments(&field.arguments);
+                    self.directives(&field.directives);
+
+                    if let Some(ref selection_set) = field.selection_set {
+                        self.out.push(' ');
+                        self.selection_set(selection_set);
+                    }
+                }
+                Selection::FragmentSpread(Spanning { item: ref spread, .. }) => {
+                    self.out.push_str("...");
+                    self.out.push_str(spread.name.item);
+                    self.directives(&spread.directives);
+                }
+                Selection::InlineFragment(Spanning { item: ref fragment, .. }) => {
+                    self.out.push_str("...");
+
+                    if let Some(ref type_condition) = fragment.type_condition {
+                        self.out.push_str(" on ");
+                        self.out.push_str(type_condition.item);
+                    }
+
+                    self.directives(&fragment.directives);
+                    self.out.push(' ');
+                    self.selection_set(&fragment.selection_set);
+                }
+            }
+
+            self.out.push('\n');
+        }
+
+        self.indent -= 1;
+        self.write_indent();
+        self.out.push('}');
+    }
+
+    fn arguments(&mut self, arguments: &Option<Spanning<Arguments>>) {
+        if let Some(ref arguments) = *arguments {
+            if arguments.item.items.is_empty() {
+                return;
+            }
+
+            self.out.push('(');
+
+            for (i, &(ref name, ref value)) in arguments.item.iter().enumerate() {
+                if i > 0 {
+                    self.out.push_str(", ");
+                }
+
+                self.out.push_str(name.item);
+                self.out.push_str(": ");
+                write_value(&mut self.out, &value.item);
+            }
+
+            self.out.push(')');
+        }
+    }
+
+    fn directives(&mut self, directives: &Option<Vec<Spanning<Directive>>>) {
+        if let Some(ref directives) = *directives {
+            for directive in directives {
+                self.out.push_str(" @");
+                self.out.push_str(directive.item.name.item);
+                self.arguments(&directive.item.arguments);
+            }
+        }
+    }
+
+    fn write_indent(&mut self) {
+        for _ in 0..self.indent {
+            self.out.push_str("  ");
+        }
+    }
+}
+
+fn write_value(out: &mut String, value: &InputValue) {
+    match *value {
+        InputValue::Null | InputValue::Upload(_) => out.push_str("null"),
+        InputValue::Int(v) => write!(out, "{}", v).unwrap(),
+        InputValue::Float(v) => write!(out, "{:?}", v).unwrap(),
+        InputValue::String(ref v) => write_string(out, v),
+        InputValue::Boolean(v) => write!(out, "{}", v).unwrap(),
+        InputValue::Enum(ref v) => out.push_str(v),
+        InputValue::Variable(ref v) => write!(out, "${}", v).unwrap(),
+        InputValue::List(ref items) => {
+            out.push('[');
+
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_value(out, &item.item);
+            }
+
+            out.push(']');
+        }
+        InputValue::Object(ref fields) => {
+            out.push('{');
+
+            for (i, &(ref key, ref value)) in fields.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&key.item);
+                out.push_str(": ");
+                write_value(out, &value.item);
+            }
+
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if c < ' ' => write!(out, "\\u{:04X}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+}
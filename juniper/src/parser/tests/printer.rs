@@ -0,0 +1,154 @@
+use ast::InputValue;
+use parser::{normalize_query, parse_document_source, parse_value_source, print_document,
+             print_value};
+
+fn print(s: &str) -> String {
+    print_document(&parse_document_source(s).expect(&format!("Parse error on input {:#?}", s)))
+}
+
+#[test]
+fn prints_shorthand_queries() {
+    assert_eq!(
+        print("{node(id:4){id,name}}"),
+        "{\n  node(id: 4) {\n    id\n    name\n  }\n}\n"
+    );
+}
+
+#[test]
+fn prints_all_definitions() {
+    let printed = print(
+        r#"
+        query Hero($episode: Episode = JEDI, $withFriends: Boolean!, $ids: [ID!]) @live {
+            hero(episode: $episode) {
+                heroName: name
+                ...Friends @include(if: $withFriends)
+                ... on Droid { primaryFunction }
+                ... @skip(if: false) { id }
+            }
+        }
+
+        mutation { like(input: {id: "1", tags: ["a", "b"], score: 1.5, reason: null}) }
+
+        fragment Friends on Character { friends { name } }
+        "#,
+    );
+
+    assert_eq!(
+        printed,
+        r#"query Hero($episode: Episode = JEDI, $withFriends: Boolean!, $ids: [ID!]) @live {
+  hero(episode: $episode) {
+    heroName: name
+    ...Friends @include(if: $withFriends)
+    ... on Droid {
+      primaryFunction
+    }
+    ... @skip(if: false) {
+      id
+    }
+  }
+}
+
+mutation {
+  like(input: {id: "1", tags: ["a", "b"], score: 1.5, reason: null})
+}
+
+fragment Friends on Character {
+  friends {
+    name
+  }
+}
+"#
+    );
+
+    assert_eq!(print(&printed), printed);
+}
+
+#[test]
+fn prints_values_that_parse_back() {
+    for source in &[
+        r#""quote \" backslash \\ newline \n tab \t unicode \u0001 é""#,
+        "1.0",
+        "-2.5e-7",
+        "[1, [true, ENUM], {a: {b: null}}]",
+    ] {
+        let value = parse_value_source(source).unwrap();
+        let printed = print_value(&value);
+
+        assert_eq!(parse_value_source(&printed).unwrap(), value);
+    }
+
+    assert_eq!(print_value(&InputValue::float(1.0)), "1.0");
+    assert_eq!(
+        print_value(&InputValue::string("say \"hi\"\n")),
+        r#""say \"hi\"\n""#
+    );
+}
+
+#[test]
+fn normalizes_query_shapes() {
+    assert_eq!(
+        normalize_query(
+            r#"
+            query Hero($episode: Episode = JEDI) {
+                hero(episode: $episode) {
+                    ...Friends @include(if: true)
+                    heroName: name
+                    ... { id, appearsIn }
+                    ... on Droid { primaryFunction }
+                }
+                human(name: "Luke", id: "1000") { homePlanet }
+            }
+
+            fragment Friends on Character { friends(first: 10) { name } }
+            "#,
+        ).unwrap(),
+        r#"query Hero {
+  hero(episode: $episode) {
+    appearsIn
+    id
+    name
+    ... on Character @include(if: $if) {
+      friends(first: $first) {
+        name
+      }
+    }
+    ... on Droid {
+      primaryFunction
+    }
+  }
+  human(id: $id, name: $name) {
+    homePlanet
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn normalizes_equivalent_queries_to_the_same_text() {
+    let expected = normalize_query(r#"{ user(id: 1, name: "x") { email name } }"#).unwrap();
+
+    for source in &[
+        r#"{ user(name: "other", id: 2) { name, email } }"#,
+        r#"{ me: user(id: $id, name: $name) { n: name ... { email } } }"#,
+    ] {
+        assert_eq!(normalize_query(source).unwrap(), expected);
+    }
+
+    // Fragments keep their type condition, as it might narrow the type
+    assert_eq!(
+        normalize_query(r#"{ user(id: 3) { ...Name, email } } fragment Name on User { name }"#)
+            .unwrap(),
+        "{\n  user(id: $id) {\n    email\n    ... on User {\n      name\n    }\n  }\n}\n"
+    );
+}
+
+#[test]
+fn normalizes_recursive_fragments() {
+    assert_eq!(
+        normalize_query(
+            r#"{ ...A } fragment A on Query { a ...B } fragment B on Query { b ...A ...Unknown }"#,
+        ).unwrap(),
+        "{\n  ... on Query {\n    a\n    ... on Query {\n      b\n      ...Unknown\n    }\n  }\n}\n"
+    );
+}
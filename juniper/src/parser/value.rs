@@ -1,6 +1,19 @@
 use ast::InputValue;
 
-use parser::{ParseError, ParseResult, Parser, Spanning, Token};
+use parser::{Lexer, ParseError, ParseResult, Parser, Spanning, Token, UnlocatedParseResult};
+
+#[doc(hidden)]
+pub fn parse_value_source(s: &str) -> UnlocatedParseResult<InputValue> {
+    let mut lexer = Lexer::new(s);
+    let mut parser = try!(Parser::new(&mut lexer).map_err(|s| s.map(ParseError::LexerError)));
+    let value = try!(parse_value_literal(&mut parser, true));
+
+    if parser.peek().item != Token::EndOfFile {
+        return Err(try!(parser.next()).map(ParseError::UnexpectedToken));
+    }
+
+    Ok(value.item)
+}
 
 pub fn parse_value_literal<'a>(
     parser: &mut Parser<'a>,
@@ -0,0 +1,806 @@
+//! Comparing schemas to detect breaking changes
+//!
+//! `diff_schemas` compares two schemas built from Rust types, while
+//! `diff_introspection` compares the result of an introspection query, e.g.
+//! one saved from the previous deployment, against a schema. Both return the
+//! list of changes between the old and the new schema, each classified by
+//! how it affects existing clients:
+//!
+//! ```rust
+//! # #[macro_use] extern crate juniper;
+//! use juniper::{EmptyMutation, SchemaType};
+//! use juniper::diff::{diff_schemas, Criticality};
+//!
+//! struct Query;
+//!
+//! graphql_object!(Query: () |&self| {
+//!     field version() -> i32 { 1 }
+//! });
+//!
+//! # fn main() {
+//! let old = SchemaType::new::<Query, EmptyMutation<()>>(&(), &());
+//! let new = SchemaType::new::<Query, EmptyMutation<()>>(&(), &());
+//!
+//! let changes = diff_schemas(&old, &new);
+//! assert!(changes.iter().all(|c| c.criticality != Criticality::Breaking));
+//! # }
+//! ```
+
+use std::fmt;
+
+use fnv::FnvHashMap;
+
+use ast::{InputValue, Type};
+use schema::meta::{Argument, EnumValue, Field, MetaType, UnionMeta};
+use schema::introspection::read_types;
+pub use schema::introspection::IntrospectionError;
+use schema::model::SchemaType;
+use types::base::TypeKind;
+
+/// How a change affects existing clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Criticality {
+    /// The change can not break any existing query
+    Safe,
+    /// Existing queries remain valid, but clients may see values they do not
+    /// expect, e.g. a new enum value
+    Dangerous,
+    /// Existing queries may fail to validate or execute
+    Breaking,
+}
+
+/// A single difference between two schemas
+///
+/// Fields, arguments, input fields, and enum values are referred to by their
+/// coordinates, e.g. `Query.hero` for a field, `Query.hero(episode:)` for an
+/// argument, and `Episode.JEDI` for an enum value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    /// A type was added
+    TypeAdded {
+        /// The name of the type
+        name: String,
+    },
+    /// A type was removed
+    TypeRemoved {
+        /// The name of the type
+        name: String,
+    },
+    /// A type changed its kind, e.g. from an object to an interface
+    TypeKindChanged {
+        /// The name of the type
+        name: String,
+        /// The old kind
+        from: TypeKind,
+        /// The new kind
+        to: TypeKind,
+    },
+    /// A field was added to an object or interface
+    FieldAdded {
+        /// The field
+        path: String,
+    },
+    /// A field was removed from an object or interface
+    FieldRemoved {
+        /// The field
+        path: String,
+    },
+    /// A field changed its type
+    FieldTypeChanged {
+        /// The field
+        path: String,
+        /// The old type
+        from: String,
+        /// The new type
+        to: String,
+    },
+    /// An argument was added to a field
+    ArgumentAdded {
+        /// The argument
+        path: String,
+        /// Whether the argument is non-null without a default value
+        required: bool,
+    },
+    /// An argument was removed from a field
+    ArgumentRemoved {
+        /// The argument
+        path: String,
+    },
+    /// An argument changed its type
+    ArgumentTypeChanged {
+        /// The argument
+        path: String,
+        /// The old type
+        from: String,
+        /// The new type
+        to: String,
+    },
+    /// An argument changed its default value
+    ArgumentDefaultChanged {
+        /// The argument
+        path: String,
+        /// The old default value
+        from: Option<String>,
+        /// The new default value
+        to: Option<String>,
+    },
+    /// A field was added to an input object
+    InputFieldAdded {
+        /// The input field
+        path: String,
+        /// Whether the input field is non-null without a default value
+        required: bool,
+    },
+    /// A field was removed from an input object
+    InputFieldRemoved {
+        /// The input field
+        path: String,
+    },
+    /// An input field changed its type
+    InputFieldTypeChanged {
+        /// The input field
+        path: String,
+        /// The old type
+        from: String,
+        /// The new type
+        to: String,
+    },
+    /// An input field changed its default value
+    InputFieldDefaultChanged {
+        /// The input field
+        path: String,
+        /// The old default value
+        from: Option<String>,
+        /// The new default value
+        to: Option<String>,
+    },
+    /// An input object started or stopped being a `@oneOf` input object
+    OneOfChanged {
+        /// The name of the input object
+        name: String,
+        /// Whether the input object is now a `@oneOf` input object
+        one_of: bool,
+    },
+    /// A value was added to an enum
+    EnumValueAdded {
+        /// The enum value
+        path: String,
+    },
+    /// A value was removed from an enum
+    EnumValueRemoved {
+        /// The enum value
+        path: String,
+    },
+    /// A member type was added to a union
+    UnionMemberAdded {
+        /// The name of the union
+        union_name: String,
+        /// The name of the member type
+        member: String,
+    },
+    /// A member type was removed from a union
+    UnionMemberRemoved {
+        /// The name of the union
+        union_name: String,
+        /// The name of the member type
+        member: String,
+    },
+    /// An object or interface started implementing an interface
+    InterfaceAdded {
+        /// The name of the implementing object or interface
+        object: String,
+        /// The name of the interface
+        interface: String,
+    },
+    /// An object or interface stopped implementing an interface
+    InterfaceRemoved {
+        /// The name of the implementing object or interface
+        object: String,
+        /// The name of the interface
+        interface: String,
+    },
+}
+
+/// A change between two schemas along with its classification
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaChange {
+    /// How the change affects existing clients
+    pub criticality: Criticality,
+    /// What changed
+    pub kind: ChangeKind,
+}
+
+/// Compare two schemas
+///
+/// The changes are ordered by type name.
+pub fn diff_schemas(old: &SchemaType, new: &SchemaType) -> Vec<SchemaChange> {
+    diff_types(&old.concrete_type_list(), &new.concrete_type_list())
+}
+
+/// Compare an introspection result with a schema
+///
+/// `old` is the response to an introspection query, either the full response
+/// with a `data` key or only its data. Parse JSON into an `InputValue` to
+/// compare against a schema served elsewhere, or convert a `Value` returned
+/// by `juniper::execute` with `ToInputValue::to_input_value`.
+pub fn diff_introspection(
+    old: &InputValue,
+    new: &SchemaType,
+) -> Result<Vec<SchemaChange>, IntrospectionError> {
+    let old_types = try!(read_types(old));
+
+    Ok(diff_types(
+        &old_types.iter().collect::<Vec<_>>(),
+        &new.concrete_type_list(),
+    ))
+}
+
+impl SchemaChange {
+    fn new(criticality: Criticality, kind: ChangeKind) -> SchemaChange {
+        SchemaChange {
+            criticality: criticality,
+            kind: kind,
+        }
+    }
+
+    /// Returns true if the change may break existing clients
+    pub fn is_breaking(&self) -> bool {
+        self.criticality == Criticality::Breaking
+    }
+}
+
+impl fmt::Display for Criticality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Criticality::Safe => "safe",
+            Criticality::Dangerous => "dangerous",
+            Criticality::Breaking => "breaking",
+        })
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}", self.criticality, self.kind)
+    }
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::ChangeKind::*;
+
+        match *self {
+            TypeAdded { ref name } => write!(f, "Type \"{}\" was added", name),
+            TypeRemoved { ref name } => write!(f, "Type \"{}\" was removed", name),
+            TypeKindChanged {
+                ref name,
+                ref from,
+                ref to,
+            } => write!(
+                f,
+                "Type \"{}\" changed from {} to {}",
+                name,
+                kind_name(from),
+                kind_name(to)
+            ),
+            FieldAdded { ref path } => write!(f, "Field \"{}\" was added", path),
+            FieldRemoved { ref path } => write!(f, "Field \"{}\" was removed", path),
+            FieldTypeChanged {
+                ref path,
+                ref from,
+                ref to,
+            } => write!(
+                f,
+                "Field \"{}\" changed type from \"{}\" to \"{}\"",
+                path,
+                from,
+                to
+            ),
+            ArgumentAdded { ref path, required } => write!(
+                f,
+                "{} argument \"{}\" was added",
+                if required { "Required" } else { "Optional" },
+                path
+            ),
+            ArgumentRemoved { ref path } => write!(f, "Argument \"{}\" was removed", path),
+            ArgumentTypeChanged {
+                ref path,
+                ref from,
+                ref to,
+            } => write!(
+                f,
+                "Argument \"{}\" changed type from \"{}\" to \"{}\"",
+                path,
+                from,
+                to
+            ),
+            ArgumentDefaultChanged {
+                ref path,
+                ref from,
+                ref to,
+            } => write!(
+                f,
+                "Argument \"{}\" changed default value from {} to {}",
+                path,
+                default_name(from),
+                default_name(to)
+            ),
+            InputFieldAdded { ref path, required } => write!(
+                f,
+                "{} input field \"{}\" was added",
+                if required { "Required" } else { "Optional" },
+                path
+            ),
+            InputFieldRemoved { ref path } => write!(f, "Input field \"{}\" was removed", path),
+            InputFieldTypeChanged {
+                ref path,
+                ref from,
+                ref to,
+            } => write!(
+                f,
+                "Input field \"{}\" changed type from \"{}\" to \"{}\"",
+                path,
+                from,
+                to
+            ),
+            InputFieldDefaultChanged {
+                ref path,
+                ref from,
+                ref to,
+            } => write!(
+                f,
+                "Input field \"{}\" changed default value from {} to {}",
+                path,
+                default_name(from),
+                default_name(to)
+            ),
+            OneOfChanged { ref name, one_of } => write!(
+                f,
+                "Input object \"{}\" {} a oneOf input object",
+                name,
+                if one_of { "is now" } else { "is no longer" }
+            ),
+            EnumValueAdded { ref path } => write!(f, "Enum value \"{}\" was added", path),
+            EnumValueRemoved { ref path } => write!(f, "Enum value \"{}\" was removed", path),
+            UnionMemberAdded {
+                ref union_name,
+                ref member,
+            } => write!(f, "Type \"{}\" was added to union \"{}\"", member, union_name),
+            UnionMemberRemoved {
+                ref union_name,
+                ref member,
+            } => write!(
+                f,
+                "Type \"{}\" was removed from union \"{}\"",
+                member,
+                union_name
+            ),
+            InterfaceAdded {
+                ref object,
+                ref interface,
+            } => write!(
+                f,
+                "Type \"{}\" now implements interface \"{}\"",
+                object,
+                interface
+            ),
+            InterfaceRemoved {
+                ref object,
+                ref interface,
+            } => write!(
+                f,
+                "Type \"{}\" no longer implements interface \"{}\"",
+                object,
+                interface
+            ),
+        }
+    }
+}
+
+fn kind_name(kind: &TypeKind) -> &'static str {
+    match *kind {
+        TypeKind::Scalar => "SCALAR",
+        TypeKind::Object => "OBJECT",
+        TypeKind::Interface => "INTERFACE",
+        TypeKind::Union => "UNION",
+        TypeKind::Enum => "ENUM",
+        TypeKind::InputObject => "INPUT_OBJECT",
+        TypeKind::List => "LIST",
+        TypeKind::NonNull => "NON_NULL",
+    }
+}
+
+fn default_name(value: &Option<String>) -> &str {
+    value.as_ref().map(|v| &v[..]).unwrap_or("none")
+}
+
+/// Collect the named types visible through introspection
+fn named_types<'t, 'a>(types: &[&'t MetaType<'a>]) -> FnvHashMap<&'t str, &'t MetaType<'a>> {
+    types
+        .iter()
+        .filter_map(|t| t.name().map(|name| (name, *t)))
+        .filter(|&(name, _)| !name.starts_with("__") && name != "_EmptyMutation")
+        .collect()
+}
+
+fn diff_types(old: &[&MetaType], new: &[&MetaType]) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    let old_types = named_types(old);
+    let new_types = named_types(new);
+
+    let mut names = old_types.keys().chain(new_types.keys()).collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+
+    for name in names {
+        let (old_type, new_type) = match (old_types.get(name), new_types.get(name)) {
+            (Some(old_type), Some(new_type)) => (*old_type, *new_type),
+            (Some(_), None) => {
+                changes.push(SchemaChange::new(
+                    Criticality::Breaking,
+                    ChangeKind::TypeRemoved {
+                        name: name.to_string(),
+                    },
+                ));
+                continue;
+            }
+            (None, Some(_)) => {
+                changes.push(SchemaChange::new(
+                    Criticality::Safe,
+                    ChangeKind::TypeAdded {
+                        name: name.to_string(),
+                    },
+                ));
+                continue;
+            }
+            (None, None) => continue,
+        };
+
+        match (old_type, new_type) {
+            (&MetaType::Object(ref old_object), &MetaType::Object(ref new_object)) => {
+                diff_fields(name, &old_object.fields, &new_object.fields, &mut changes);
+                diff_interfaces(
+                    name,
+                    &old_object.interface_names,
+                    &new_object.interface_names,
+                    &mut changes,
+                );
+            }
+            (&MetaType::Interface(ref old_interface), &MetaType::Interface(ref new_interface)) => {
+                diff_fields(
+                    name,
+                    &old_interface.fields,
+                    &new_interface.fields,
+                    &mut changes,
+                );
+                diff_interfaces(
+                    name,
+                    &old_interface.interface_names,
+                    &new_interface.interface_names,
+                    &mut changes,
+                );
+            }
+            (&MetaType::Union(ref old_union), &MetaType::Union(ref new_union)) => {
+                diff_union_members(old_union, new_union, &mut changes);
+            }
+            (&MetaType::Enum(ref old_enum), &MetaType::Enum(ref new_enum)) => {
+                diff_enum_values(name, &old_enum.values, &new_enum.values, &mut changes);
+            }
+            (&MetaType::InputObject(ref old_input), &MetaType::InputObject(ref new_input)) => {
+                diff_input_values(
+                    name,
+                    &old_input.input_fields,
+                    &new_input.input_fields,
+                    false,
+                    &mut changes,
+                );
+
+                // Values providing several fields are rejected by a oneOf
+                // input object, while the reverse accepts everything
+                if old_input.is_one_of != new_input.is_one_of {
+                    changes.push(SchemaChange::new(
+                        if new_input.is_one_of {
+                            Criticality::Breaking
+                        } else {
+                            Criticality::Safe
+                        },
+                        ChangeKind::OneOfChanged {
+                            name: name.to_string(),
+                            one_of: new_input.is_one_of,
+                        },
+                    ));
+                }
+            }
+            (&MetaType::Scalar(_), &MetaType::Scalar(_)) => {}
+            _ => changes.push(SchemaChange::new(
+                Criticality::Breaking,
+                ChangeKind::TypeKindChanged {
+                    name: name.to_string(),
+                    from: old_type.type_kind(),
+                    to: new_type.type_kind(),
+                },
+            )),
+        }
+    }
+
+    changes
+}
+
+fn diff_fields(type_name: &str, old: &[Field], new: &[Field], changes: &mut Vec<SchemaChange>) {
+    // Meta fields like `__typename` are not part of the type's definition
+    let old = old.iter().filter(|f| !f.name.starts_with("__"));
+    let new = new.iter().filter(|f| !f.name.starts_with("__"));
+
+    for old_field in old.clone() {
+        let path = format!("{}.{}", type_name, old_field.name);
+
+        let new_field = match new.clone().find(|f| f.name == old_field.name) {
+            Some(field) => field,
+            None => {
+                changes.push(SchemaChange::new(
+                    Criticality::Breaking,
+                    ChangeKind::FieldRemoved { path: path },
+                ));
+                continue;
+            }
+        };
+
+        if old_field.field_type != new_field.field_type {
+            let criticality = if is_safe_output_change(&old_field.field_type, &new_field.field_type)
+            {
+                Criticality::Safe
+            } else {
+                Criticality::Breaking
+            };
+
+            changes.push(SchemaChange::new(
+                criticality,
+                ChangeKind::FieldTypeChanged {
+                    path: path.clone(),
+                    from: old_field.field_type.to_string(),
+                    to: new_field.field_type.to_string(),
+                },
+            ));
+        }
+
+        let no_arguments = Vec::new();
+        diff_input_values(
+            &path,
+            old_field.arguments.as_ref().unwrap_or(&no_arguments),
+            new_field.arguments.as_ref().unwrap_or(&no_arguments),
+            true,
+            changes,
+        );
+    }
+
+    for new_field in new {
+        if !old.clone().any(|f| f.name == new_field.name) {
+            changes.push(SchemaChange::new(
+                Criticality::Safe,
+                ChangeKind::FieldAdded {
+                    path: format!("{}.{}", type_name, new_field.name),
+                },
+            ));
+        }
+    }
+}
+
+fn diff_input_values(
+    owner: &str,
+    old: &[Argument],
+    new: &[Argument],
+    is_arguments: bool,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let path_of = |name: &str| if is_arguments {
+        format!("{}({}:)", owner, name)
+    } else {
+        format!("{}.{}", owner, name)
+    };
+
+    for old_value in old {
+        let path = path_of(&old_value.name);
+
+        let new_value = match new.iter().find(|v| v.name == old_value.name) {
+            Some(value) => value,
+            None => {
+                changes.push(SchemaChange::new(
+                    Criticality::Breaking,
+                    if is_arguments {
+                        ChangeKind::ArgumentRemoved { path: path }
+                    } else {
+                        ChangeKind::InputFieldRemoved { path: path }
+                    },
+                ));
+                continue;
+            }
+        };
+
+        if old_value.arg_type != new_value.arg_type {
+            let criticality = if is_safe_input_change(&old_value.arg_type, &new_value.arg_type) {
+                Criticality::Safe
+            } else {
+                Criticality::Breaking
+            };
+            let from = old_value.arg_type.to_string();
+            let to = new_value.arg_type.to_string();
+
+            changes.push(SchemaChange::new(
+                criticality,
+                if is_arguments {
+                    ChangeKind::ArgumentTypeChanged {
+                        path: path.clone(),
+                        from: from,
+                        to: to,
+                    }
+                } else {
+                    ChangeKind::InputFieldTypeChanged {
+                        path: path.clone(),
+                        from: from,
+                        to: to,
+                    }
+                },
+            ));
+        }
+
+        let old_default = old_value.default_value.as_ref().map(|v| v.to_string());
+        let new_default = new_value.default_value.as_ref().map(|v| v.to_string());
+
+        if old_default != new_default {
+            changes.push(SchemaChange::new(
+                Criticality::Dangerous,
+                if is_arguments {
+                    ChangeKind::ArgumentDefaultChanged {
+                        path: path,
+                        from: old_default,
+                        to: new_default,
+                    }
+                } else {
+                    ChangeKind::InputFieldDefaultChanged {
+                        path: path,
+                        from: old_default,
+                        to: new_default,
+                    }
+                },
+            ));
+        }
+    }
+
+    for new_value in new {
+        if old.iter().any(|v| v.name == new_value.name) {
+            continue;
+        }
+
+        let path = path_of(&new_value.name);
+        let required = new_value.arg_type.is_non_null() && new_value.default_value.is_none();
+        let criticality = if required {
+            Criticality::Breaking
+        } else {
+            Criticality::Dangerous
+        };
+
+        changes.push(SchemaChange::new(
+            criticality,
+            if is_arguments {
+                ChangeKind::ArgumentAdded {
+                    path: path,
+                    required: required,
+                }
+            } else {
+                ChangeKind::InputFieldAdded {
+                    path: path,
+                    required: required,
+                }
+            },
+        ));
+    }
+}
+
+fn diff_enum_values(
+    enum_name: &str,
+    old: &[EnumValue],
+    new: &[EnumValue],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_value in old {
+        if !new.iter().any(|v| v.name == old_value.name) {
+            changes.push(SchemaChange::new(
+                Criticality::Breaking,
+                ChangeKind::EnumValueRemoved {
+                    path: format!("{}.{}", enum_name, old_value.name),
+                },
+            ));
+        }
+    }
+
+    for new_value in new {
+        if !old.iter().any(|v| v.name == new_value.name) {
+            changes.push(SchemaChange::new(
+                Criticality::Dangerous,
+                ChangeKind::EnumValueAdded {
+                    path: format!("{}.{}", enum_name, new_value.name),
+                },
+            ));
+        }
+    }
+}
+
+fn diff_union_members(old: &UnionMeta, new: &UnionMeta, changes: &mut Vec<SchemaChange>) {
+    for member in &old.of_type_names {
+        if !new.of_type_names.contains(member) {
+            changes.push(SchemaChange::new(
+                Criticality::Breaking,
+                ChangeKind::UnionMemberRemoved {
+                    union_name: old.name.to_string(),
+                    member: member.clone(),
+                },
+            ));
+        }
+    }
+
+    for member in &new.of_type_names {
+        if !old.of_type_names.contains(member) {
+            changes.push(SchemaChange::new(
+                Criticality::Dangerous,
+                ChangeKind::UnionMemberAdded {
+                    union_name: new.name.to_string(),
+                    member: member.clone(),
+                },
+            ));
+        }
+    }
+}
+
+fn diff_interfaces(
+    type_name: &str,
+    old: &[String],
+    new: &[String],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for interface in old {
+        if !new.contains(interface) {
+            changes.push(SchemaChange::new(
+                Criticality::Breaking,
+                ChangeKind::InterfaceRemoved {
+                    object: type_name.to_owned(),
+                    interface: interface.clone(),
+                },
+            ));
+        }
+    }
+
+    for interface in new {
+        if !old.contains(interface) {
+            changes.push(SchemaChange::new(
+                Criticality::Dangerous,
+                ChangeKind::InterfaceAdded {
+                    object: type_name.to_owned(),
+                    interface: interface.clone(),
+                },
+            ));
+        }
+    }
+}
+
+/// A field's type may only become stricter, i.e. non-null where it was nullable
+fn is_safe_output_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (&Type::Named(ref old_name), &Type::Named(ref new_name)) |
+        (&Type::Named(ref old_name), &Type::NonNullNamed(ref new_name)) |
+        (&Type::NonNullNamed(ref old_name), &Type::NonNullNamed(ref new_name)) => {
+            old_name == new_name
+        }
+        (&Type::List(ref old_inner), &Type::List(ref new_inner)) |
+        (&Type::List(ref old_inner), &Type::NonNullList(ref new_inner)) |
+        (&Type::NonNullList(ref old_inner), &Type::NonNullList(ref new_inner)) => {
+            is_safe_output_change(old_inner, new_inner)
+        }
+        _ => false,
+    }
+}
+
+/// An input's type may only become more lenient, i.e. nullable where it was
+/// non-null
+fn is_safe_input_change(old: &Type, new: &Type) -> bool {
+    is_safe_output_change(new, old)
+}
@@ -0,0 +1,477 @@
+//! Reading the result of an introspection query back into schema metadata
+
+use std::borrow::Cow;
+use std::fmt;
+
+use ordermap::OrderMap;
+
+use ast::{InputValue, InputValueError, Type};
+use parser::parse_value_source;
+use schema::meta::{Argument, EnumMeta, EnumValue, Field, InputObjectMeta, InterfaceMeta,
+                   MetaType, ObjectMeta, ScalarMeta, UnionMeta};
+use schema::model::{DirectiveLocation, DirectiveType};
+use types::name::Name;
+use types::scalars::ID;
+
+/// An introspection result that could not be read
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntrospectionError {
+    /// Where in the result the problem was found, e.g. `__schema.types[3]`
+    pub path: String,
+    /// A description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for IntrospectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// The parts of a schema described by an introspection result
+pub struct IntrospectedSchema {
+    pub query_type_name: String,
+    pub mutation_type_name: Option<String>,
+    pub types: Vec<(Name, MetaType<'static>)>,
+    pub directives: Vec<DirectiveType<'static>>,
+}
+
+/// Read the types of an introspection result
+pub fn read_types(value: &InputValue) -> Result<Vec<MetaType<'static>>, IntrospectionError> {
+    let (schema_path, schema) = try!(schema_object(value));
+
+    read_type_list(&schema_path, &schema)
+}
+
+/// Read an introspection result, and check that all types it refers to are
+/// part of it
+pub fn read_schema(value: &InputValue) -> Result<IntrospectedSchema, IntrospectionError> {
+    let (schema_path, schema) = try!(schema_object(value));
+    let types = try!(read_type_list(&schema_path, &schema));
+
+    let root_type_name = |key: &str| -> Result<Option<String>, IntrospectionError> {
+        let path = join_path(&schema_path, key);
+
+        match schema.get(key) {
+            None | Some(&&InputValue::Null) => Ok(None),
+            Some(value) => {
+                let name = try!(string_field(&path, &try!(object_at(&path, value)), "name"));
+                Ok(Some(name.to_owned()))
+            }
+        }
+    };
+
+    let query_type_name = match try!(root_type_name("queryType")) {
+        Some(name) => name,
+        None => return Err(error(&schema_path, "Missing field \"queryType\"")),
+    };
+    let mutation_type_name = try!(root_type_name("mutationType"));
+    let directives = try!(read_list(&schema_path, &schema, "directives", read_directive));
+
+    let type_exists = |name: &str| types.iter().any(|t| t.name() == Some(name));
+    let check = |path: String, name: &str| if type_exists(name) {
+        Ok(())
+    } else {
+        Err(error(&path, format!("Unknown type \"{}\"", name)))
+    };
+
+    try!(check(join_path(&schema_path, "queryType"), &query_type_name));
+    if let Some(ref name) = mutation_type_name {
+        try!(check(join_path(&schema_path, "mutationType"), name));
+    }
+
+    let types_path = join_path(&schema_path, "types");
+    for (i, t) in types.iter().enumerate() {
+        let path = format!("{}[{}]", types_path, i);
+
+        match *t {
+            MetaType::Object(ObjectMeta {
+                ref fields,
+                ref interface_names,
+                ..
+            }) |
+            MetaType::Interface(InterfaceMeta {
+                ref fields,
+                ref interface_names,
+                ..
+            }) => {
+                for (j, field) in fields.iter().enumerate() {
+                    let field_path = format!("{}.fields[{}]", path, j);
+                    try!(check(
+                        join_path(&field_path, "type"),
+                        field.field_type.innermost_name()
+                    ));
+
+                    for (k, arg) in field.arguments.iter().flat_map(|a| a).enumerate() {
+                        try!(check(
+                            format!("{}.args[{}].type", field_path, k),
+                            arg.arg_type.innermost_name()
+                        ));
+                    }
+                }
+                for (j, name) in interface_names.iter().enumerate() {
+                    try!(check(format!("{}.interfaces[{}]", path, j), name));
+                }
+            }
+            MetaType::Union(UnionMeta {
+                ref of_type_names, ..
+            }) => for (j, name) in of_type_names.iter().enumerate() {
+                try!(check(format!("{}.possibleTypes[{}]", path, j), name));
+            },
+            MetaType::InputObject(InputObjectMeta {
+                ref input_fields, ..
+            }) => for (j, field) in input_fields.iter().enumerate() {
+                try!(check(
+                    format!("{}.inputFields[{}].type", path, j),
+                    field.arg_type.innermost_name()
+                ));
+            },
+            _ => (),
+        }
+    }
+
+    let mut named_types = Vec::new();
+    for (i, t) in types.into_iter().enumerate() {
+        let path = format!("{}[{}].name", types_path, i);
+        let name = try!(
+            t.name()
+                .ok_or_else(|| error(&path, "Type without a name"))
+                .and_then(|name| name.parse::<Name>().map_err(|e| error(&path, e.to_string())))
+        );
+        named_types.push((name, t));
+    }
+
+    Ok(IntrospectedSchema {
+        query_type_name: query_type_name,
+        mutation_type_name: mutation_type_name,
+        types: named_types,
+        directives: directives,
+    })
+}
+
+fn schema_object(
+    value: &InputValue,
+) -> Result<(String, OrderMap<&str, &InputValue>), IntrospectionError> {
+    let root = try!(object_at("", value));
+    let (path, root) = match root.get("data") {
+        Some(data) => ("data".to_owned(), try!(object_at("data", data))),
+        None => (String::new(), root),
+    };
+
+    let schema_path = join_path(&path, "__schema");
+    let schema = try!(object_at(&schema_path, try!(require(&path, &root, "__schema"))));
+
+    Ok((schema_path, schema))
+}
+
+fn read_type_list(
+    schema_path: &str,
+    schema: &OrderMap<&str, &InputValue>,
+) -> Result<Vec<MetaType<'static>>, IntrospectionError> {
+    let types_path = join_path(schema_path, "types");
+    let types = try!(list_at(&types_path, try!(require(schema_path, schema, "types"))));
+
+    types
+        .iter()
+        .enumerate()
+        .map(|(i, t)| read_type(&format!("{}[{}]", types_path, i), t))
+        .collect()
+}
+
+fn read_type(path: &str, value: &InputValue) -> Result<MetaType<'static>, IntrospectionError> {
+    let object = try!(object_at(path, value));
+    let kind = try!(string_field(path, &object, "kind"));
+    let name = Cow::Owned(try!(string_field(path, &object, "name")).to_owned());
+    let description = try!(optional_string_field(path, &object, "description"));
+
+    let meta_type = match kind {
+        "SCALAR" => {
+            // Values of custom scalars can't be checked without their
+            // implementation, so they accept anything
+            let mut meta = match &*name {
+                "Int" => ScalarMeta::new::<i32>(name.clone()),
+                "Float" => ScalarMeta::new::<f64>(name.clone()),
+                "String" => ScalarMeta::new::<String>(name.clone()),
+                "Boolean" => ScalarMeta::new::<bool>(name.clone()),
+                "ID" => ScalarMeta::new::<ID>(name.clone()),
+                _ => ScalarMeta {
+                    name: name.clone(),
+                    description: None,
+                    try_parse_fn: Box::new(|_| Ok(())),
+                    accepts_lists_and_objects: false,
+                },
+            };
+            meta.description = description;
+            MetaType::Scalar(meta)
+        }
+        "OBJECT" => MetaType::Object(ObjectMeta {
+            name: name,
+            description: description,
+            fields: try!(read_list(path, &object, "fields", read_field)),
+            interface_names: try!(read_list(path, &object, "interfaces", read_type_ref))
+                .iter()
+                .map(|t| t.innermost_name().to_owned())
+                .collect(),
+        }),
+        "INTERFACE" => MetaType::Interface(InterfaceMeta {
+            name: name,
+            description: description,
+            fields: try!(read_list(path, &object, "fields", read_field)),
+            interface_names: try!(read_list(path, &object, "interfaces", read_type_ref))
+                .iter()
+                .map(|t| t.innermost_name().to_owned())
+                .collect(),
+        }),
+        "UNION" => MetaType::Union(UnionMeta {
+            name: name,
+            description: description,
+            of_type_names: try!(read_list(path, &object, "possibleTypes", read_type_ref))
+                .iter()
+                .map(|t| t.innermost_name().to_owned())
+                .collect(),
+        }),
+        "ENUM" => {
+            let values = try!(read_list(path, &object, "enumValues", read_enum_value));
+            let names = values.iter().map(|v| v.name.clone()).collect::<Vec<_>>();
+            let enum_name = name.to_string();
+
+            MetaType::Enum(EnumMeta {
+                name: name,
+                description: description,
+                values: values,
+                try_parse_fn: Box::new(move |v: &InputValue| {
+                    match v.as_enum_value().or_else(|| v.as_string_value()) {
+                        Some(value) if names.iter().any(|n| n == value) => Ok(()),
+                        _ => Err(InputValueError::expected(&enum_name, v)),
+                    }
+                }),
+            })
+        }
+        "INPUT_OBJECT" => {
+            let input_object_name = name.to_string();
+
+            MetaType::InputObject(InputObjectMeta {
+                name: name,
+                description: description,
+                input_fields: try!(read_list(path, &object, "inputFields", read_input_value)),
+                is_one_of: try!(optional_bool_field(path, &object, "isOneOf")).unwrap_or(false),
+                try_parse_fn: Box::new(move |v: &InputValue| match v.to_object_value() {
+                    Some(_) => Ok(()),
+                    None => Err(InputValueError::expected(&input_object_name, v)),
+                }),
+            })
+        }
+        _ => return Err(error(path, format!("Unknown type kind \"{}\"", kind))),
+    };
+
+    Ok(meta_type)
+}
+
+fn read_field(path: &str, value: &InputValue) -> Result<Field<'static>, IntrospectionError> {
+    let object = try!(object_at(path, value));
+    let arguments = try!(read_list(path, &object, "args", read_input_value));
+
+    Ok(Field {
+        name: try!(string_field(path, &object, "name")).to_owned(),
+        description: try!(optional_string_field(path, &object, "description")),
+        arguments: if arguments.is_empty() {
+            None
+        } else {
+            Some(arguments)
+        },
+        field_type: try!(read_type_ref(
+            &join_path(path, "type"),
+            try!(require(path, &object, "type"))
+        )),
+        deprecation_reason: try!(optional_string_field(path, &object, "deprecationReason")),
+    })
+}
+
+fn read_input_value(path: &str, value: &InputValue) -> Result<Argument<'static>, IntrospectionError> {
+    let object = try!(object_at(path, value));
+    let default_value = match try!(optional_string_field(path, &object, "defaultValue")) {
+        Some(source) => Some(try!(parse_value_source(&source).map_err(|e| {
+            error(
+                &join_path(path, "defaultValue"),
+                format!("Invalid value \"{}\": {:?}", source, e.item),
+            )
+        }))),
+        None => None,
+    };
+
+    Ok(Argument {
+        name: try!(string_field(path, &object, "name")).to_owned(),
+        description: try!(optional_string_field(path, &object, "description")),
+        arg_type: try!(read_type_ref(
+            &join_path(path, "type"),
+            try!(require(path, &object, "type"))
+        )),
+        default_value: default_value,
+        validators: Vec::new(),
+    })
+}
+
+fn read_directive(path: &str, value: &InputValue) -> Result<DirectiveType<'static>, IntrospectionError> {
+    let object = try!(object_at(path, value));
+
+    // Locations in the type system, e.g. `FIELD_DEFINITION`, don't matter
+    // when validating queries and are left out
+    let locations = try!(read_list(path, &object, "locations", |path, value| {
+        match value.as_enum_value().or_else(|| value.as_string_value()) {
+            Some(location) => Ok(read_directive_location(location)),
+            None => Err(error(path, "Expected a directive location")),
+        }
+    }));
+
+    let mut directive = DirectiveType::new(
+        try!(string_field(path, &object, "name")),
+        &locations.into_iter().flat_map(|l| l).collect::<Vec<_>>(),
+        &try!(read_list(path, &object, "args", read_input_value)),
+    );
+    directive.description = try!(optional_string_field(path, &object, "description"));
+
+    Ok(directive)
+}
+
+fn read_directive_location(location: &str) -> Option<DirectiveLocation> {
+    match location {
+        "QUERY" => Some(DirectiveLocation::Query),
+        "MUTATION" => Some(DirectiveLocation::Mutation),
+        "FIELD" => Some(DirectiveLocation::Field),
+        "FRAGMENT_DEFINITION" => Some(DirectiveLocation::FragmentDefinition),
+        "FRAGMENT_SPREAD" => Some(DirectiveLocation::FragmentSpread),
+        "INLINE_FRAGMENT" => Some(DirectiveLocation::InlineFragment),
+        "INPUT_OBJECT" => Some(DirectiveLocation::InputObject),
+        _ => None,
+    }
+}
+
+fn read_enum_value(path: &str, value: &InputValue) -> Result<EnumValue, IntrospectionError> {
+    let object = try!(object_at(path, value));
+
+    Ok(EnumValue {
+        name: try!(string_field(path, &object, "name")).to_owned(),
+        description: try!(optional_string_field(path, &object, "description")),
+        deprecation_reason: try!(optional_string_field(path, &object, "deprecationReason")),
+    })
+}
+
+fn read_type_ref(path: &str, value: &InputValue) -> Result<Type<'static>, IntrospectionError> {
+    let object = try!(object_at(path, value));
+    let of_type = |object: &OrderMap<&str, &InputValue>| {
+        read_type_ref(
+            &join_path(path, "ofType"),
+            try!(require(path, object, "ofType")),
+        )
+    };
+
+    match try!(string_field(path, &object, "kind")) {
+        "NON_NULL" => match try!(of_type(&object)) {
+            Type::Named(name) => Ok(Type::NonNullNamed(name)),
+            Type::List(inner) => Ok(Type::NonNullList(inner)),
+            _ => Err(error(path, "Non-null type wraps another non-null type")),
+        },
+        "LIST" => Ok(Type::List(Box::new(try!(of_type(&object))))),
+        _ => Ok(Type::Named(Cow::Owned(
+            try!(string_field(path, &object, "name")).to_owned(),
+        ))),
+    }
+}
+
+fn read_list<T, F>(
+    path: &str,
+    object: &OrderMap<&str, &InputValue>,
+    key: &str,
+    read: F,
+) -> Result<Vec<T>, IntrospectionError>
+where
+    F: Fn(&str, &InputValue) -> Result<T, IntrospectionError>,
+{
+    let path = join_path(path, key);
+
+    match object.get(key) {
+        None | Some(&&InputValue::Null) => Ok(Vec::new()),
+        Some(value) => try!(list_at(&path, value))
+            .iter()
+            .enumerate()
+            .map(|(i, item)| read(&format!("{}[{}]", path, i), item))
+            .collect(),
+    }
+}
+
+fn object_at<'v>(
+    path: &str,
+    value: &'v InputValue,
+) -> Result<OrderMap<&'v str, &'v InputValue>, IntrospectionError> {
+    value
+        .to_object_value()
+        .ok_or_else(|| error(path, "Expected an object"))
+}
+
+fn list_at<'v>(path: &str, value: &'v InputValue) -> Result<Vec<&'v InputValue>, IntrospectionError> {
+    value
+        .to_list_value()
+        .ok_or_else(|| error(path, "Expected a list"))
+}
+
+fn require<'v>(
+    path: &str,
+    object: &OrderMap<&str, &'v InputValue>,
+    key: &str,
+) -> Result<&'v InputValue, IntrospectionError> {
+    object
+        .get(key)
+        .cloned()
+        .ok_or_else(|| error(path, format!("Missing field \"{}\"", key)))
+}
+
+fn string_field<'v>(
+    path: &str,
+    object: &OrderMap<&str, &'v InputValue>,
+    key: &str,
+) -> Result<&'v str, IntrospectionError> {
+    try!(require(path, object, key))
+        .as_string_value()
+        .ok_or_else(|| error(&join_path(path, key), "Expected a string"))
+}
+
+fn optional_string_field(
+    path: &str,
+    object: &OrderMap<&str, &InputValue>,
+    key: &str,
+) -> Result<Option<String>, IntrospectionError> {
+    match object.get(key) {
+        None | Some(&&InputValue::Null) => Ok(None),
+        Some(value) => value
+            .as_string_value()
+            .map(|s| Some(s.to_owned()))
+            .ok_or_else(|| error(&join_path(path, key), "Expected a string")),
+    }
+}
+
+fn optional_bool_field(
+    path: &str,
+    object: &OrderMap<&str, &InputValue>,
+    key: &str,
+) -> Result<Option<bool>, IntrospectionError> {
+    match object.get(key) {
+        None | Some(&&InputValue::Null) => Ok(None),
+        Some(&&InputValue::Boolean(b)) => Ok(Some(b)),
+        Some(_) => Err(error(&join_path(path, key), "Expected a boolean")),
+    }
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn error<S: Into<String>>(path: &str, message: S) -> IntrospectionError {
+    IntrospectionError {
+        path: path.to_owned(),
+        message: message.into(),
+    }
+}
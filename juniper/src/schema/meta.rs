@@ -3,7 +3,9 @@
 use std::borrow::Cow;
 use std::fmt;
 
-use ast::{FromInputValue, InputValue, Type};
+use regex::Regex;
+
+use ast::{FromInputValue, InputValue, InputValueError, Type};
 use types::base::TypeKind;
 
 /// Scalar type metadata
@@ -13,7 +15,9 @@ pub struct ScalarMeta<'a> {
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
-    pub try_parse_fn: Box<Fn(&InputValue) -> bool + Send + Sync>,
+    pub try_parse_fn: Box<Fn(&InputValue) -> Result<(), InputValueError> + Send + Sync>,
+    #[doc(hidden)]
+    pub accepts_lists_and_objects: bool,
 }
 
 /// List type metadata
@@ -52,7 +56,7 @@ pub struct EnumMeta<'a> {
     #[doc(hidden)]
     pub values: Vec<EnumValue>,
     #[doc(hidden)]
-    pub try_parse_fn: Box<Fn(&InputValue) -> bool + Send + Sync>,
+    pub try_parse_fn: Box<Fn(&InputValue) -> Result<(), InputValueError> + Send + Sync>,
 }
 
 /// Interface type metadata
@@ -64,6 +68,8 @@ pub struct InterfaceMeta<'a> {
     pub description: Option<String>,
     #[doc(hidden)]
     pub fields: Vec<Field<'a>>,
+    #[doc(hidden)]
+    pub interface_names: Vec<String>,
 }
 
 /// Union type metadata
@@ -86,7 +92,9 @@ pub struct InputObjectMeta<'a> {
     #[doc(hidden)]
     pub input_fields: Vec<Argument<'a>>,
     #[doc(hidden)]
-    pub try_parse_fn: Box<Fn(&InputValue) -> bool + Send + Sync>,
+    pub is_one_of: bool,
+    #[doc(hidden)]
+    pub try_parse_fn: Box<Fn(&InputValue) -> Result<(), InputValueError> + Send + Sync>,
 }
 
 /// A placeholder for not-yet-registered types
@@ -148,6 +156,29 @@ pub struct Argument<'a> {
     pub arg_type: Type<'a>,
     #[doc(hidden)]
     pub default_value: Option<InputValue>,
+    #[doc(hidden)]
+    pub validators: Vec<Validator>,
+}
+
+/// A declarative constraint on the value of an argument or input field
+///
+/// Validators are checked against literals when the query is validated, and
+/// against the final values - including variables - before a field is
+/// resolved. Null values always pass. `Min`, `Max`, and `Pattern` apply to
+/// each element of a list, while the length constraints apply to strings and
+/// to lists as a whole.
+#[derive(Debug, Clone)]
+pub enum Validator {
+    /// The number must be at least this large
+    Min(f64),
+    /// The number must be at most this large
+    Max(f64),
+    /// The string or list must have at least this many items
+    MinLength(usize),
+    /// The string or list must have at most this many items
+    MaxLength(usize),
+    /// The string must match the regular expression
+    Pattern(Regex),
 }
 
 /// Metadata for a single value in an enum
@@ -280,10 +311,10 @@ impl<'a> MetaType<'a> {
     /// Access the input value parse function, if applicable
     ///
     /// An input value parse function is a function that takes an `InputValue` instance and returns
-    /// `true` if it can be parsed as the provided type.
+    /// an error describing why it can't be parsed as the provided type, if it can't.
     ///
     /// Only scalars, enums, and input objects have parse functions.
-    pub fn input_value_parse_fn(&self) -> Option<&Box<Fn(&InputValue) -> bool + Send + Sync>> {
+    pub fn input_value_parse_fn(&self) -> Option<&Box<Fn(&InputValue) -> Result<(), InputValueError> + Send + Sync>> {
         match *self {
             MetaType::Scalar(ScalarMeta {
                 ref try_parse_fn, ..
@@ -345,10 +376,22 @@ impl<'a> ScalarMeta<'a> {
         ScalarMeta {
             name: name,
             description: None,
-            try_parse_fn: Box::new(|v: &InputValue| <T as FromInputValue>::from_input_value(v).is_some()),
+            try_parse_fn: Box::new(|v: &InputValue| {
+                <T as FromInputValue>::try_from_input_value(v).map(|_| ())
+            }),
+            accepts_lists_and_objects: false,
         }
     }
 
+    /// Let the scalar take list and object values, like the `JSON` scalar
+    ///
+    /// Other scalars are never given lists or objects, even if their
+    /// `from_input_value` would accept them.
+    pub fn accept_lists_and_objects(mut self) -> ScalarMeta<'a> {
+        self.accepts_lists_and_objects = true;
+        self
+    }
+
     /// Set the description for the given scalar type
     ///
     /// If a description already was set prior to calling this method, it will be overwritten.
@@ -431,7 +474,9 @@ impl<'a> EnumMeta<'a> {
             name: name,
             description: None,
             values: values.to_vec(),
-            try_parse_fn: Box::new(|v: &InputValue| <T as FromInputValue>::from_input_value(v).is_some()),
+            try_parse_fn: Box::new(|v: &InputValue| {
+                <T as FromInputValue>::try_from_input_value(v).map(|_| ())
+            }),
         }
     }
 
@@ -456,6 +501,7 @@ impl<'a> InterfaceMeta<'a> {
             name: name,
             description: None,
             fields: fields.to_vec(),
+            interface_names: vec![],
         }
     }
 
@@ -467,6 +513,18 @@ impl<'a> InterfaceMeta<'a> {
         self
     }
 
+    /// Set the interfaces this interface implements
+    ///
+    /// If a list of interfaces already was provided prior to calling this method, they will be
+    /// overwritten.
+    pub fn interfaces(mut self, interfaces: &[Type<'a>]) -> InterfaceMeta<'a> {
+        self.interface_names = interfaces
+            .iter()
+            .map(|t| t.innermost_name().to_owned())
+            .collect();
+        self
+    }
+
     /// Wrap this interface type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a> {
         MetaType::Interface(self)
@@ -510,7 +568,10 @@ impl<'a> InputObjectMeta<'a> {
             name: name,
             description: None,
             input_fields: input_fields.to_vec(),
-            try_parse_fn: Box::new(|v: &InputValue| <T as FromInputValue>::from_input_value(v).is_some()),
+            is_one_of: false,
+            try_parse_fn: Box::new(|v: &InputValue| {
+                <T as FromInputValue>::try_from_input_value(v).map(|_| ())
+            }),
         }
     }
 
@@ -522,6 +583,14 @@ impl<'a> InputObjectMeta<'a> {
         self
     }
 
+    /// Mark the type as a `@oneOf` input object
+    ///
+    /// Values of the type must provide exactly one field, which may not be null.
+    pub fn one_of(mut self) -> InputObjectMeta<'a> {
+        self.is_one_of = true;
+        self
+    }
+
     /// Wrap this union type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a> {
         MetaType::InputObject(self)
@@ -570,6 +639,7 @@ impl<'a> Argument<'a> {
             description: None,
             arg_type: arg_type,
             default_value: None,
+            validators: Vec::new(),
         }
     }
 
@@ -588,6 +658,117 @@ impl<'a> Argument<'a> {
         self.default_value = Some(default_value);
         self
     }
+
+    /// Add a validator to the argument
+    ///
+    /// Validators are checked in the order they were added.
+    pub fn validator(mut self, validator: Validator) -> Argument<'a> {
+        self.validators.push(validator);
+        self
+    }
+}
+
+impl Validator {
+    /// Require numbers to be at least `min`
+    pub fn min<T: Into<f64>>(min: T) -> Validator {
+        Validator::Min(min.into())
+    }
+
+    /// Require numbers to be at most `max`
+    pub fn max<T: Into<f64>>(max: T) -> Validator {
+        Validator::Max(max.into())
+    }
+
+    /// Require strings and lists to have at least `min` items
+    pub fn min_length(min: usize) -> Validator {
+        Validator::MinLength(min)
+    }
+
+    /// Require strings and lists to have at most `max` items
+    pub fn max_length(max: usize) -> Validator {
+        Validator::MaxLength(max)
+    }
+
+    /// Require strings to match the regular expression
+    ///
+    /// Panics if the pattern is not a valid regular expression.
+    pub fn pattern(pattern: &str) -> Validator {
+        match Regex::new(pattern) {
+            Ok(regex) => Validator::Pattern(regex),
+            Err(e) => panic!("Invalid validator pattern \"{}\": {}", pattern, e),
+        }
+    }
+
+    /// Check a value against the validator
+    pub fn validate(&self, value: &InputValue) -> Result<(), InputValueError> {
+        match (self, value) {
+            (_, &InputValue::Null) | (_, &InputValue::Variable(_)) => Ok(()),
+            (&Validator::MinLength(min), v) => match input_length(v) {
+                Some(len) if len < min => Err(InputValueError::new(format!(
+                    "Expected a length of at least {}, found {}",
+                    min,
+                    len
+                ))),
+                _ => Ok(()),
+            },
+            (&Validator::MaxLength(max), v) => match input_length(v) {
+                Some(len) if len > max => Err(InputValueError::new(format!(
+                    "Expected a length of at most {}, found {}",
+                    max,
+                    len
+                ))),
+                _ => Ok(()),
+            },
+            (_, &InputValue::List(ref items)) => {
+                for (i, item) in items.iter().enumerate() {
+                    try!(self.validate(&item.item).map_err(|e| e.in_element(i)));
+                }
+                Ok(())
+            }
+            (&Validator::Min(min), v) => match input_number(v) {
+                Some(n) if n < min => Err(InputValueError::new(format!(
+                    "Expected a value of at least {}, found {}",
+                    min,
+                    v
+                ))),
+                _ => Ok(()),
+            },
+            (&Validator::Max(max), v) => match input_number(v) {
+                Some(n) if n > max => Err(InputValueError::new(format!(
+                    "Expected a value of at most {}, found {}",
+                    max,
+                    v
+                ))),
+                _ => Ok(()),
+            },
+            (&Validator::Pattern(ref regex), &InputValue::String(ref s)) => if regex.is_match(s) {
+                Ok(())
+            } else {
+                Err(InputValueError::new(format!(
+                    "Expected a value matching \"{}\", found {}",
+                    regex.as_str(),
+                    value
+                )))
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+fn input_length(value: &InputValue) -> Option<usize> {
+    match *value {
+        InputValue::String(ref s) => Some(s.chars().count()),
+        InputValue::List(ref items) => Some(items.len()),
+        _ => None,
+    }
+}
+
+fn input_number(value: &InputValue) -> Option<f64> {
+    match *value {
+        InputValue::Int(i) => Some(i as f64),
+        InputValue::Float(f) => Some(f),
+        _ => None,
+    }
 }
 
 impl EnumValue {
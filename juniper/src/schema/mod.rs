@@ -1,3 +1,6 @@
 pub mod model;
 pub mod schema;
 pub mod meta;
+pub mod validation;
+pub mod diff;
+mod introspection;
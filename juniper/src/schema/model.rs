@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt;
 
 use fnv::FnvHashMap;
@@ -5,8 +6,11 @@ use fnv::FnvHashMap;
 use types::base::GraphQLType;
 use types::name::Name;
 use executor::{Context, Registry};
-use ast::Type;
-use schema::meta::{Argument, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta, UnionMeta};
+use ast::{InputValue, Type};
+use schema::introspection::{read_schema, IntrospectionError};
+use schema::meta::{Argument, Field, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta,
+                   UnionMeta};
+use schema::validation::{validate_types, SchemaError};
 
 /// Root query node of a schema
 ///
@@ -31,6 +35,7 @@ pub struct SchemaType<'a> {
     query_type_name: String,
     mutation_type_name: Option<String>,
     directives: FnvHashMap<String, DirectiveType<'a>>,
+    registration_errors: Vec<SchemaError>,
 }
 
 impl<'a> Context for SchemaType<'a> {}
@@ -57,6 +62,7 @@ pub enum DirectiveLocation {
     FragmentDefinition,
     FragmentSpread,
     InlineFragment,
+    InputObject,
 }
 
 impl<'a, QueryT, MutationT> RootNode<'a, QueryT, MutationT>
@@ -81,23 +87,54 @@ where
     /// Construct a new root node from query and mutation nodes,
     /// while also providing type info objects for the query and
     /// mutation types.
+    ///
+    /// In debug builds, the schema is validated, and this function panics
+    /// listing all errors if the schema is invalid. Types without fields are
+    /// only reported by `validate`.
     pub fn new_with_info(
         query_obj: QueryT,
         mutation_obj: MutationT,
         query_info: QueryT::TypeInfo,
         mutation_info: MutationT::TypeInfo,
     ) -> RootNode<'a, QueryT, MutationT> {
+        let schema = SchemaType::new::<QueryT, MutationT>(&query_info, &mutation_info);
+
+        if cfg!(debug_assertions) {
+            let errors = schema
+                .validate()
+                .iter()
+                .filter(|e| match **e {
+                    SchemaError::EmptyFields { .. } => false,
+                    _ => true,
+                })
+                .map(|e| format!("  * {}", e))
+                .collect::<Vec<_>>();
+
+            if !errors.is_empty() {
+                panic!("Invalid schema:\n{}", errors.join("\n"));
+            }
+        }
+
         RootNode {
             query_type: query_obj,
             mutation_type: mutation_obj,
-            schema: SchemaType::new::<QueryT, MutationT>(&query_info, &mutation_info),
+            schema: schema,
             query_info: query_info,
             mutation_info: mutation_info,
         }
     }
+
+    /// Check the schema against the type system rules of the specification
+    ///
+    /// Returns all violations found, or an empty list if the schema is
+    /// valid. This check is run when constructing the root node.
+    pub fn validate(&self) -> Vec<SchemaError> {
+        self.schema.validate()
+    }
 }
 
 impl<'a> SchemaType<'a> {
+    /// Build the schema for the given query and mutation root types
     pub fn new<QueryT, MutationT>(
         query_info: &QueryT::TypeInfo,
         mutation_info: &MutationT::TypeInfo,
@@ -106,74 +143,123 @@ impl<'a> SchemaType<'a> {
         QueryT: GraphQLType,
         MutationT: GraphQLType,
     {
-        let mut directives = FnvHashMap::default();
-        let query_type_name: String;
-        let mutation_type_name: String;
-
-        let mut registry = Registry::new(FnvHashMap::default());
-        query_type_name = registry
-            .get_type::<QueryT>(query_info)
-            .innermost_name()
-            .to_owned();
-        mutation_type_name = registry
-            .get_type::<MutationT>(mutation_info)
-            .innermost_name()
-            .to_owned();
-
-        registry.get_type::<SchemaType>(&());
-        directives.insert("skip".to_owned(), DirectiveType::new_skip(&mut registry));
-        directives.insert(
-            "include".to_owned(),
-            DirectiveType::new_include(&mut registry),
-        );
-
-        let mut meta_fields = vec![
-            registry.field::<SchemaType>("__schema", &()),
-            registry
-                .field::<TypeType>("__type", &())
-                .argument(registry.arg::<String>("name", &())),
-        ];
-
-        if let Some(root_type) = registry.types.get_mut(&query_type_name) {
-            if let MetaType::Object(ObjectMeta { ref mut fields, .. }) = *root_type {
-                fields.append(&mut meta_fields);
-            } else {
-                panic!("Root type is not an object");
-            }
-        } else {
-            panic!("Root type not found");
-        }
+        let schema = build_schema_type::<QueryT, MutationT>(query_info, mutation_info);
 
-        for meta_type in registry.types.values() {
+        for meta_type in schema.types.values() {
             if let MetaType::Placeholder(PlaceholderMeta { ref of_type }) = *meta_type {
                 panic!("Type {:?} is still a placeholder type", of_type);
             }
         }
 
-        SchemaType {
-            types: registry.types,
-            query_type_name: query_type_name,
-            mutation_type_name: if &mutation_type_name != "_EmptyMutation" {
-                Some(mutation_type_name)
-            } else {
-                None
-            },
-            directives: directives,
+        schema
+    }
+
+    /// Build a schema from the result of an introspection query
+    ///
+    /// `value` is the response to an introspection query, either the full
+    /// response with a `data` key or only its data, as read by
+    /// `diff::diff_introspection`. The schema can't execute queries, but it
+    /// describes the types, fields, arguments, and directives of the remote
+    /// schema for validating queries against it offline.
+    ///
+    /// Values of custom scalars can't be checked against their
+    /// implementation, so any literal is valid for them. The `skip`,
+    /// `include`, and `oneOf` directives are added if the result doesn't
+    /// contain them.
+    pub fn from_introspection(value: &InputValue) -> Result<SchemaType<'static>, IntrospectionError> {
+        let introspected = try!(read_schema(value));
+
+        let mut types = introspected
+            .types
+            .into_iter()
+            .collect::<FnvHashMap<_, _>>();
+
+        // The meta fields are only part of the query type if the
+        // introspection types are
+        if types.contains_key("__Schema") && types.contains_key("__Type") {
+            if let Some(&mut MetaType::Object(ObjectMeta { ref mut fields, .. })) =
+                types.get_mut(&introspected.query_type_name)
+            {
+                if !fields.iter().any(|f| f.name == "__schema") {
+                    fields.push(Field {
+                        name: "__schema".to_owned(),
+                        description: None,
+                        arguments: None,
+                        field_type: Type::NonNullNamed(Cow::Borrowed("__Schema")),
+                        deprecation_reason: None,
+                    });
+                    fields.push(Field {
+                        name: "__type".to_owned(),
+                        description: None,
+                        arguments: Some(vec![
+                            Argument::new("name", Type::NonNullNamed(Cow::Borrowed("String"))),
+                        ]),
+                        field_type: Type::Named(Cow::Borrowed("__Type")),
+                        deprecation_reason: None,
+                    });
+                }
+            }
+        }
+
+        let mut directives = introspected
+            .directives
+            .into_iter()
+            .map(|d| (d.name.clone(), d))
+            .collect::<FnvHashMap<_, _>>();
+        for name in &["skip", "include"] {
+            directives.entry((*name).to_owned()).or_insert_with(|| {
+                DirectiveType::new(
+                    name,
+                    &[
+                        DirectiveLocation::Field,
+                        DirectiveLocation::FragmentSpread,
+                        DirectiveLocation::InlineFragment,
+                    ],
+                    &[
+                        Argument::new("if", Type::NonNullNamed(Cow::Borrowed("Boolean"))),
+                    ],
+                )
+            });
         }
+        directives
+            .entry("oneOf".to_owned())
+            .or_insert_with(DirectiveType::new_one_of);
+
+        Ok(SchemaType {
+            types: types,
+            query_type_name: introspected.query_type_name,
+            mutation_type_name: introspected.mutation_type_name,
+            directives: directives,
+            registration_errors: Vec::new(),
+        })
     }
 
+    /// Check the schema against the type system rules of the specification
+    ///
+    /// Returns all violations found, or an empty list if the schema is
+    /// valid.
+    pub fn validate(&self) -> Vec<SchemaError> {
+        let mut errors = self.registration_errors.clone();
+        errors.extend(validate_types(self));
+        errors
+    }
+
+    /// Register a directive with the schema
     pub fn add_directive(&mut self, directive: DirectiveType<'a>) {
         self.directives.insert(directive.name.clone(), directive);
     }
 
+    /// Get a type by name, wrapped for introspection
     pub fn type_by_name(&self, name: &str) -> Option<TypeType> {
         self.types.get(name).map(|t| TypeType::Concrete(t))
     }
 
+    /// Get the meta type with the given name
     pub fn concrete_type_by_name(&self, name: &str) -> Option<&MetaType> {
         self.types.get(name)
     }
 
+    /// Get the query root type, wrapped for introspection
     pub fn query_type(&self) -> TypeType {
         TypeType::Concrete(
             self.types
@@ -182,12 +268,14 @@ impl<'a> SchemaType<'a> {
         )
     }
 
+    /// Get the meta type of the query root
     pub fn concrete_query_type(&self) -> &MetaType {
         self.types
             .get(&self.query_type_name)
             .expect("Query type does not exist in schema")
     }
 
+    /// Get the mutation root type, if any, wrapped for introspection
     pub fn mutation_type(&self) -> Option<TypeType> {
         if let Some(ref mutation_type_name) = self.mutation_type_name {
             Some(
@@ -199,6 +287,7 @@ impl<'a> SchemaType<'a> {
         }
     }
 
+    /// Get the meta type of the mutation root, if any
     pub fn concrete_mutation_type(&self) -> Option<&MetaType> {
         self.mutation_type_name.as_ref().map(|name| {
             self.concrete_type_by_name(name)
@@ -206,14 +295,17 @@ impl<'a> SchemaType<'a> {
         })
     }
 
+    /// List all types in the schema, wrapped for introspection
     pub fn type_list(&self) -> Vec<TypeType> {
         self.types.values().map(|t| TypeType::Concrete(t)).collect()
     }
 
+    /// List the meta types of all types in the schema
     pub fn concrete_type_list(&self) -> Vec<&MetaType> {
         self.types.values().collect()
     }
 
+    /// Resolve an AST type against the types in the schema
     pub fn make_type(&self, t: &Type) -> TypeType {
         match *t {
             Type::NonNullNamed(ref n) => TypeType::NonNull(Box::new(
@@ -227,29 +319,36 @@ impl<'a> SchemaType<'a> {
         }
     }
 
+    /// List all directives in the schema
     pub fn directive_list(&self) -> Vec<&DirectiveType> {
         self.directives.values().collect()
     }
 
+    /// Get a directive by name
     pub fn directive_by_name(&self, name: &str) -> Option<&DirectiveType> {
         self.directives.get(name)
     }
 
+    /// Check whether two types can apply to the same object
     pub fn type_overlap(&self, t1: &MetaType, t2: &MetaType) -> bool {
         if (t1 as *const MetaType) == (t2 as *const MetaType) {
             return true;
         }
 
         match (t1.is_abstract(), t2.is_abstract()) {
-            (true, true) => self.possible_types(t1)
-                .iter()
-                .any(|t| self.is_possible_type(t2, t)),
+            (true, true) => {
+                self.is_possible_type(t1, t2) || self.is_possible_type(t2, t1)
+                    || self.possible_types(t1)
+                        .iter()
+                        .any(|t| self.is_possible_type(t2, t))
+            }
             (true, false) => self.is_possible_type(t1, t2),
             (false, true) => self.is_possible_type(t2, t1),
             (false, false) => false,
         }
     }
 
+    /// List the object types an abstract type can resolve to
     pub fn possible_types(&self, t: &MetaType) -> Vec<&MetaType> {
         match *t {
             MetaType::Union(UnionMeta {
@@ -272,12 +371,25 @@ impl<'a> SchemaType<'a> {
         }
     }
 
+    /// Check whether a type is a possible type of an abstract type
     pub fn is_possible_type(&self, abstract_type: &MetaType, possible_type: &MetaType) -> bool {
+        if let (
+            &MetaType::Interface(InterfaceMeta { ref name, .. }),
+            &MetaType::Interface(InterfaceMeta {
+                ref interface_names,
+                ..
+            }),
+        ) = (abstract_type, possible_type)
+        {
+            return interface_names.iter().any(|iname| iname == name);
+        }
+
         self.possible_types(abstract_type).into_iter().any(|t| {
             (t as *const MetaType) == (possible_type as *const MetaType)
         })
     }
 
+    /// Check whether `sub_type` can be used where `super_type` is expected
     pub fn is_subtype<'b>(&self, sub_type: &Type<'b>, super_type: &Type<'b>) -> bool {
         use ast::Type::*;
 
@@ -300,6 +412,7 @@ impl<'a> SchemaType<'a> {
         }
     }
 
+    /// Check whether the named type is a subtype of the other named type
     pub fn is_named_subtype(&self, sub_type_name: &str, super_type_name: &str) -> bool {
         if sub_type_name == super_type_name {
             true
@@ -314,6 +427,68 @@ impl<'a> SchemaType<'a> {
     }
 }
 
+/// Build a schema without checking for unresolved placeholder types, which
+/// dynamic schemas report as validation errors instead
+pub fn build_schema_type<'a, QueryT, MutationT>(
+    query_info: &QueryT::TypeInfo,
+    mutation_info: &MutationT::TypeInfo,
+) -> SchemaType<'a>
+where
+    QueryT: GraphQLType,
+    MutationT: GraphQLType,
+{
+    let mut directives = FnvHashMap::default();
+    let query_type_name: String;
+    let mutation_type_name: String;
+
+    let mut registry = Registry::new(FnvHashMap::default());
+    query_type_name = registry
+        .get_type::<QueryT>(query_info)
+        .innermost_name()
+        .to_owned();
+    mutation_type_name = registry
+        .get_type::<MutationT>(mutation_info)
+        .innermost_name()
+        .to_owned();
+
+    registry.get_type::<SchemaType>(&());
+    directives.insert("skip".to_owned(), DirectiveType::new_skip(&mut registry));
+    directives.insert(
+        "include".to_owned(),
+        DirectiveType::new_include(&mut registry),
+    );
+    directives.insert("oneOf".to_owned(), DirectiveType::new_one_of());
+
+    let mut meta_fields = vec![
+        registry.field::<SchemaType>("__schema", &()),
+        registry
+            .field::<TypeType>("__type", &())
+            .argument(registry.arg::<String>("name", &())),
+    ];
+
+    if let Some(root_type) = registry.types.get_mut(&query_type_name) {
+        if let MetaType::Object(ObjectMeta { ref mut fields, .. }) = *root_type {
+            fields.append(&mut meta_fields);
+        } else {
+            panic!("Root type is not an object");
+        }
+    } else {
+        panic!("Root type not found");
+    }
+
+    SchemaType {
+        types: registry.types,
+        query_type_name: query_type_name,
+        mutation_type_name: if &mutation_type_name != "_EmptyMutation" {
+            Some(mutation_type_name)
+        } else {
+            None
+        },
+        directives: directives,
+        registration_errors: registry.schema_errors,
+    }
+}
+
 impl<'a> TypeType<'a> {
     pub fn to_concrete(&self) -> Option<&'a MetaType> {
         match *self {
@@ -383,6 +558,13 @@ impl<'a> DirectiveType<'a> {
         )
     }
 
+    fn new_one_of() -> DirectiveType<'a> {
+        Self::new("oneOf", &[DirectiveLocation::InputObject], &[]).description(
+            "Indicates that exactly one field of an input object must be given, and \
+             that it must not be null",
+        )
+    }
+
     pub fn description(mut self, description: &str) -> DirectiveType<'a> {
         self.description = Some(description.to_owned());
         self
@@ -398,6 +580,7 @@ impl fmt::Display for DirectiveLocation {
             DirectiveLocation::FragmentDefinition => "fragment definition",
             DirectiveLocation::FragmentSpread => "fragment spread",
             DirectiveLocation::InlineFragment => "inline fragment",
+            DirectiveLocation::InputObject => "input object",
         })
     }
 }
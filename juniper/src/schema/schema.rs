@@ -1,4 +1,5 @@
-use types::base::{Arguments, GraphQLType, TypeKind};
+use ast::Selection;
+use types::base::{stream_selection_set, Arguments, GraphQLType, TypeKind};
 use executor::{ExecutionResult, Executor, Registry};
 
 use schema::meta::{Argument, EnumMeta, EnumValue, Field, InputObjectMeta, InterfaceMeta, MetaType,
@@ -21,6 +22,10 @@ where
         QueryT::meta(info, registry)
     }
 
+    fn rust_type_name() -> &'static str {
+        QueryT::rust_type_name()
+    }
+
     fn resolve_field(
         &self,
         info: &QueryT::TypeInfo,
@@ -41,6 +46,15 @@ where
             _ => self.query_type.resolve_field(info, field, args, executor),
         }
     }
+
+    fn stream(
+        &self,
+        info: &QueryT::TypeInfo,
+        selection_set: Option<&[Selection]>,
+        executor: &Executor<CtxT>,
+    ) -> bool {
+        stream_selection_set(self, info, selection_set, executor)
+    }
 }
 
 graphql_object!(<'a> SchemaType<'a>: SchemaType<'a> as "__Schema" |&self| {
@@ -120,9 +134,18 @@ graphql_object!(<'a> TypeType<'a>: SchemaType<'a> as "__Type" |&self| {
         }
     }
 
+    field is_one_of() -> Option<bool> {
+        match *self {
+            TypeType::Concrete(&MetaType::InputObject(InputObjectMeta { is_one_of, .. })) =>
+                Some(is_one_of),
+            _ => None,
+        }
+    }
+
     field interfaces(&executor) -> Option<Vec<TypeType>> {
         match *self {
-            TypeType::Concrete(&MetaType::Object(ObjectMeta { ref interface_names, .. })) => {
+            TypeType::Concrete(&MetaType::Object(ObjectMeta { ref interface_names, .. })) |
+            TypeType::Concrete(&MetaType::Interface(InterfaceMeta { ref interface_names, .. })) => {
                 let schema = executor.context();
                 Some(interface_names
                     .iter()
@@ -289,4 +312,5 @@ graphql_enum!(DirectiveLocation as "__DirectiveLocation" {
     DirectiveLocation::FragmentDefinition => "FRAGMENT_DEFINITION",
     DirectiveLocation::FragmentSpread => "FRAGMENT_SPREAD",
     DirectiveLocation::InlineFragment => "INLINE_FRAGMENT",
+    DirectiveLocation::InputObject => "INPUT_OBJECT",
 });
@@ -0,0 +1,698 @@
+//! Type system validation for schemas
+
+use std::collections::HashSet;
+use std::fmt;
+
+use schema::meta::{Argument, Field, InterfaceMeta, MetaType};
+use schema::model::SchemaType;
+
+/// Types defined by the introspection system, which may use reserved names
+const INTROSPECTION_TYPES: &[&str] = &[
+    "__Schema",
+    "__Type",
+    "__TypeKind",
+    "__Field",
+    "__InputValue",
+    "__EnumValue",
+    "__Directive",
+    "__DirectiveLocation",
+];
+
+/// A violation of the type system rules found in a schema
+///
+/// Fields, arguments, and input fields are referred to by their coordinates,
+/// e.g. `Query.hero` for a field and `Query.hero(episode:)` for an argument.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaError {
+    /// Two different Rust types were registered with the same GraphQL name
+    DuplicateTypeName {
+        /// The GraphQL type name
+        name: String,
+        /// The Rust type registered first
+        first: String,
+        /// The conflicting Rust type
+        second: String,
+    },
+    /// A type was referenced while being registered, but never defined
+    UnresolvedPlaceholder {
+        /// The name of the type
+        name: String,
+    },
+    /// A type reference of a dynamically defined field or argument could not
+    /// be parsed
+    InvalidTypeReference {
+        /// The field, argument, or input field
+        path: String,
+        /// The unparsable type reference
+        type_ref: String,
+    },
+    /// The root type of an operation is not an object type
+    InvalidRootType {
+        /// The operation, `query` or `mutation`
+        operation: String,
+        /// The name of the root type
+        name: String,
+    },
+    /// A type, field, or argument refers to a type not in the schema
+    UnknownType {
+        /// The referring type, field, or argument
+        path: String,
+        /// The name of the missing type
+        name: String,
+    },
+    /// A name starting with `__` is used outside of the introspection system
+    ReservedName {
+        /// The type, field, argument, or enum value using the name
+        path: String,
+    },
+    /// Two fields of a type have the same name
+    DuplicateField {
+        /// The duplicated field
+        path: String,
+    },
+    /// Two arguments of a field, or two fields of an input object, have the
+    /// same name
+    DuplicateArgument {
+        /// The duplicated argument or input field
+        path: String,
+    },
+    /// A field has an input object type
+    InputTypeInOutputPosition {
+        /// The field
+        path: String,
+        /// The name of the input object type
+        name: String,
+    },
+    /// An argument or input field has an object, interface, or union type
+    OutputTypeInInputPosition {
+        /// The argument or input field
+        path: String,
+        /// The name of the output type
+        name: String,
+    },
+    /// An object, interface, or input object has no fields
+    EmptyFields {
+        /// The name of the type
+        name: String,
+    },
+    /// An enum has no values
+    EmptyEnum {
+        /// The name of the enum
+        name: String,
+    },
+    /// Two values of an enum have the same name
+    DuplicateEnumValue {
+        /// The duplicated enum value
+        path: String,
+    },
+    /// An enum value is named `true`, `false`, or `null`
+    InvalidEnumValue {
+        /// The enum value
+        path: String,
+    },
+    /// A union has no member types
+    EmptyUnion {
+        /// The name of the union
+        name: String,
+    },
+    /// A member of a union is not an object type
+    InvalidUnionMember {
+        /// The name of the union
+        union_name: String,
+        /// The name of the member type
+        member: String,
+    },
+    /// A field of a oneOf input object is non-null or has a default value
+    InvalidOneOfField {
+        /// The input field
+        path: String,
+    },
+    /// An object or interface claims to implement a type that is not an
+    /// interface
+    NotAnInterface {
+        /// The name of the object or interface
+        object: String,
+        /// The name of the claimed interface
+        interface: String,
+    },
+    /// An interface implements itself, directly or through other interfaces
+    SelfImplementation {
+        /// The name of the interface
+        name: String,
+    },
+    /// A type implements an interface, but not the interfaces that one
+    /// implements in turn
+    MissingTransitiveInterface {
+        /// The name of the object or interface
+        name: String,
+        /// The name of the interface that must also be implemented
+        interface: String,
+        /// The implemented interface that requires it
+        via: String,
+    },
+    /// An object lacks a field of an interface it implements
+    MissingInterfaceField {
+        /// The name of the interface
+        interface: String,
+        /// The missing field, e.g. `Human.name`
+        path: String,
+    },
+    /// An object field's type is not a subtype of the interface field's type
+    InvalidInterfaceFieldType {
+        /// The name of the interface
+        interface: String,
+        /// The object field
+        path: String,
+        /// The type of the interface field
+        expected: String,
+        /// The type of the object field
+        found: String,
+    },
+    /// An object field lacks an argument of the interface field
+    MissingInterfaceArgument {
+        /// The name of the interface
+        interface: String,
+        /// The missing argument
+        path: String,
+    },
+    /// An object field's argument has a different type than the interface
+    /// field's argument
+    InvalidInterfaceArgumentType {
+        /// The name of the interface
+        interface: String,
+        /// The object field's argument
+        path: String,
+        /// The type of the interface field's argument
+        expected: String,
+        /// The type of the object field's argument
+        found: String,
+    },
+    /// An object field has a non-null argument without a default value that
+    /// is not defined by the interface
+    RequiredArgumentNotInInterface {
+        /// The name of the interface
+        interface: String,
+        /// The additional argument
+        path: String,
+    },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::SchemaError::*;
+
+        match *self {
+            DuplicateTypeName {
+                ref name,
+                ref first,
+                ref second,
+            } => write!(
+                f,
+                "Type name \"{}\" is used by both `{}` and `{}`",
+                name,
+                first,
+                second
+            ),
+            UnresolvedPlaceholder { ref name } => {
+                write!(f, "Type \"{}\" was referenced but never defined", name)
+            }
+            InvalidTypeReference {
+                ref path,
+                ref type_ref,
+            } => write!(f, "\"{}\" has invalid type \"{}\"", path, type_ref),
+            InvalidRootType {
+                ref operation,
+                ref name,
+            } => write!(
+                f,
+                "The {} root type \"{}\" is not an object type",
+                operation,
+                name
+            ),
+            UnknownType { ref path, ref name } => {
+                write!(f, "\"{}\" refers to unknown type \"{}\"", path, name)
+            }
+            ReservedName { ref path } => write!(
+                f,
+                "\"{}\" uses a name starting with \"__\", which is reserved for introspection",
+                path
+            ),
+            DuplicateField { ref path } => write!(f, "Field \"{}\" is defined twice", path),
+            DuplicateArgument { ref path } => write!(f, "\"{}\" is defined twice", path),
+            InputTypeInOutputPosition { ref path, ref name } => write!(
+                f,
+                "Field \"{}\" has input object type \"{}\", but fields must have output types",
+                path,
+                name
+            ),
+            OutputTypeInInputPosition { ref path, ref name } => write!(
+                f,
+                "\"{}\" has output type \"{}\", but arguments and input fields must have \
+                 input types",
+                path,
+                name
+            ),
+            EmptyFields { ref name } => write!(f, "Type \"{}\" has no fields", name),
+            EmptyEnum { ref name } => write!(f, "Enum \"{}\" has no values", name),
+            DuplicateEnumValue { ref path } => write!(f, "Enum value \"{}\" is defined twice", path),
+            InvalidEnumValue { ref path } => write!(
+                f,
+                "Enum value \"{}\" can not be named true, false, or null",
+                path
+            ),
+            EmptyUnion { ref name } => write!(f, "Union \"{}\" has no member types", name),
+            InvalidUnionMember {
+                ref union_name,
+                ref member,
+            } => write!(
+                f,
+                "Union \"{}\" can only contain object types, but contains \"{}\"",
+                union_name,
+                member
+            ),
+            NotAnInterface {
+                ref object,
+                ref interface,
+            } => write!(
+                f,
+                "Type \"{}\" implements \"{}\", which is not an interface",
+                object,
+                interface
+            ),
+            InvalidOneOfField { ref path } => write!(
+                f,
+                "OneOf input field \"{}\" must be nullable and have no default value",
+                path
+            ),
+            SelfImplementation { ref name } => {
+                write!(f, "Interface \"{}\" cannot implement itself", name)
+            }
+            MissingTransitiveInterface {
+                ref name,
+                ref interface,
+                ref via,
+            } => write!(
+                f,
+                "Type \"{}\" must also implement \"{}\", which is implemented by \"{}\"",
+                name,
+                interface,
+                via
+            ),
+            MissingInterfaceField {
+                ref interface,
+                ref path,
+            } => write!(
+                f,
+                "Field \"{}\" is required by interface \"{}\", but not defined",
+                path,
+                interface
+            ),
+            InvalidInterfaceFieldType {
+                ref interface,
+                ref path,
+                ref expected,
+                ref found,
+            } => write!(
+                f,
+                "Field \"{}\" has type \"{}\", which is not a subtype of \"{}\" required by \
+                 interface \"{}\"",
+                path,
+                found,
+                expected,
+                interface
+            ),
+            MissingInterfaceArgument {
+                ref interface,
+                ref path,
+            } => write!(
+                f,
+                "Argument \"{}\" is required by interface \"{}\", but not defined",
+                path,
+                interface
+            ),
+            InvalidInterfaceArgumentType {
+                ref interface,
+                ref path,
+                ref expected,
+                ref found,
+            } => write!(
+                f,
+                "Argument \"{}\" has type \"{}\", but interface \"{}\" expects \"{}\"",
+                path,
+                found,
+                interface,
+                expected
+            ),
+            RequiredArgumentNotInInterface {
+                ref interface,
+                ref path,
+            } => write!(
+                f,
+                "Argument \"{}\" is required, but not defined by interface \"{}\"",
+                path,
+                interface
+            ),
+        }
+    }
+}
+
+/// Check all types of `schema` against the type validation rules
+pub fn validate_types(schema: &SchemaType) -> Vec<SchemaError> {
+    let mut errors = Vec::new();
+    let query_type_name = schema.concrete_query_type().name().map(str::to_owned);
+
+    let mut types = schema.concrete_type_list();
+    types.sort_by_key(|t| t.name().map(str::to_owned));
+
+    for meta_type in types {
+        match *meta_type {
+            MetaType::Placeholder(ref placeholder) => {
+                errors.push(SchemaError::UnresolvedPlaceholder {
+                    name: placeholder.of_type.innermost_name().to_owned(),
+                });
+            }
+            MetaType::Scalar(ref scalar) => check_type_name(&scalar.name, &mut errors),
+            MetaType::Object(ref object) => {
+                check_type_name(&object.name, &mut errors);
+
+                // `EmptyMutation` stands for a missing mutation type
+                if object.name != "_EmptyMutation" {
+                    check_not_empty(&object.name, &object.fields, &mut errors);
+                }
+
+                let is_query = query_type_name.as_ref().map(|n| &n[..]) == Some(&object.name[..]);
+                check_fields(schema, &object.name, &object.fields, is_query, &mut errors);
+                check_interfaces(
+                    schema,
+                    &object.name,
+                    &object.fields,
+                    &object.interface_names,
+                    &mut errors,
+                );
+            }
+            MetaType::Interface(ref interface) => {
+                check_type_name(&interface.name, &mut errors);
+                check_not_empty(&interface.name, &interface.fields, &mut errors);
+                check_fields(schema, &interface.name, &interface.fields, false, &mut errors);
+                check_interfaces(
+                    schema,
+                    &interface.name,
+                    &interface.fields,
+                    &interface.interface_names,
+                    &mut errors,
+                );
+            }
+            MetaType::Union(ref union) => {
+                check_type_name(&union.name, &mut errors);
+
+                if union.of_type_names.is_empty() {
+                    errors.push(SchemaError::EmptyUnion {
+                        name: union.name.to_string(),
+                    });
+                }
+
+                for member in &union.of_type_names {
+                    match schema.concrete_type_by_name(member) {
+                        None => errors.push(SchemaError::UnknownType {
+                            path: union.name.to_string(),
+                            name: member.clone(),
+                        }),
+                        Some(&MetaType::Object(_)) => {}
+                        Some(_) => errors.push(SchemaError::InvalidUnionMember {
+                            union_name: union.name.to_string(),
+                            member: member.clone(),
+                        }),
+                    }
+                }
+            }
+            MetaType::Enum(ref enum_meta) => {
+                check_type_name(&enum_meta.name, &mut errors);
+
+                if enum_meta.values.is_empty() {
+                    errors.push(SchemaError::EmptyEnum {
+                        name: enum_meta.name.to_string(),
+                    });
+                }
+
+                let mut seen = HashSet::new();
+                for value in &enum_meta.values {
+                    let path = format!("{}.{}", enum_meta.name, value.name);
+
+                    if !seen.insert(&value.name[..]) {
+                        errors.push(SchemaError::DuplicateEnumValue { path: path.clone() });
+                    }
+                    if value.name == "true" || value.name == "false" || value.name == "null" {
+                        errors.push(SchemaError::InvalidEnumValue { path: path.clone() });
+                    }
+                    if value.name.starts_with("__") {
+                        errors.push(SchemaError::ReservedName { path: path });
+                    }
+                }
+            }
+            MetaType::InputObject(ref input) => {
+                check_type_name(&input.name, &mut errors);
+
+                if input.input_fields.is_empty() {
+                    errors.push(SchemaError::EmptyFields {
+                        name: input.name.to_string(),
+                    });
+                }
+
+                check_input_values(schema, &input.name, &input.input_fields, false, &mut errors);
+
+                if input.is_one_of {
+                    for field in &input.input_fields {
+                        if field.arg_type.is_non_null() || field.default_value.is_some() {
+                            errors.push(SchemaError::InvalidOneOfField {
+                                path: format!("{}.{}", input.name, field.name),
+                            });
+                        }
+                    }
+                }
+            }
+            MetaType::List(_) | MetaType::Nullable(_) => {}
+        }
+    }
+
+    errors
+}
+
+fn check_type_name(name: &str, errors: &mut Vec<SchemaError>) {
+    if name.starts_with("__") && !INTROSPECTION_TYPES.contains(&name) {
+        errors.push(SchemaError::ReservedName {
+            path: name.to_owned(),
+        });
+    }
+}
+
+/// Meta fields like `__typename` are added to every type, so they don't
+/// count as fields here
+fn check_not_empty(type_name: &str, fields: &[Field], errors: &mut Vec<SchemaError>) {
+    if fields.iter().all(|f| f.name.starts_with("__")) {
+        errors.push(SchemaError::EmptyFields {
+            name: type_name.to_owned(),
+        });
+    }
+}
+
+fn check_fields(
+    schema: &SchemaType,
+    type_name: &str,
+    fields: &[Field],
+    is_query: bool,
+    errors: &mut Vec<SchemaError>,
+) {
+    let mut seen = HashSet::new();
+
+    for field in fields {
+        let path = format!("{}.{}", type_name, field.name);
+
+        if !seen.insert(&field.name[..]) {
+            errors.push(SchemaError::DuplicateField { path: path.clone() });
+        }
+
+        // Every type gets the `__typename` meta field, and the query type
+        // gets `__schema` and `__type` as well.
+        let is_meta_field = field.name == "__typename"
+            || is_query && (field.name == "__schema" || field.name == "__type");
+        if field.name.starts_with("__") && !is_meta_field
+            && !INTROSPECTION_TYPES.contains(&type_name)
+        {
+            errors.push(SchemaError::ReservedName { path: path.clone() });
+        }
+
+        let field_type_name = field.field_type.innermost_name();
+        match schema.concrete_type_by_name(field_type_name) {
+            None => errors.push(SchemaError::UnknownType {
+                path: path.clone(),
+                name: field_type_name.to_owned(),
+            }),
+            Some(&MetaType::InputObject(_)) => {
+                errors.push(SchemaError::InputTypeInOutputPosition {
+                    path: path.clone(),
+                    name: field_type_name.to_owned(),
+                })
+            }
+            Some(_) => {}
+        }
+
+        if let Some(ref arguments) = field.arguments {
+            check_input_values(schema, &path, arguments, true, errors);
+        }
+    }
+}
+
+fn check_input_values(
+    schema: &SchemaType,
+    owner: &str,
+    values: &[Argument],
+    is_arguments: bool,
+    errors: &mut Vec<SchemaError>,
+) {
+    let mut seen = HashSet::new();
+
+    for value in values {
+        let path = if is_arguments {
+            argument_path(owner, &value.name)
+        } else {
+            format!("{}.{}", owner, value.name)
+        };
+
+        if !seen.insert(&value.name[..]) {
+            errors.push(SchemaError::DuplicateArgument { path: path.clone() });
+        }
+
+        if value.name.starts_with("__") && !owner.starts_with("__") {
+            errors.push(SchemaError::ReservedName { path: path.clone() });
+        }
+
+        let value_type_name = value.arg_type.innermost_name();
+        match schema.concrete_type_by_name(value_type_name) {
+            None => errors.push(SchemaError::UnknownType {
+                path: path,
+                name: value_type_name.to_owned(),
+            }),
+            Some(t) if !t.is_input() => errors.push(SchemaError::OutputTypeInInputPosition {
+                path: path,
+                name: value_type_name.to_owned(),
+            }),
+            Some(_) => {}
+        }
+    }
+}
+
+fn check_interfaces(
+    schema: &SchemaType,
+    type_name: &str,
+    fields: &[Field],
+    interface_names: &[String],
+    errors: &mut Vec<SchemaError>,
+) {
+    for interface_name in interface_names {
+        if interface_name == type_name {
+            errors.push(SchemaError::SelfImplementation {
+                name: type_name.to_owned(),
+            });
+            continue;
+        }
+
+        match schema.concrete_type_by_name(interface_name) {
+            None => errors.push(SchemaError::UnknownType {
+                path: type_name.to_owned(),
+                name: interface_name.clone(),
+            }),
+            Some(&MetaType::Interface(ref interface)) => {
+                for transitive in &interface.interface_names {
+                    if transitive == type_name {
+                        errors.push(SchemaError::SelfImplementation {
+                            name: type_name.to_owned(),
+                        });
+                    } else if !interface_names.contains(transitive) {
+                        errors.push(SchemaError::MissingTransitiveInterface {
+                            name: type_name.to_owned(),
+                            interface: transitive.clone(),
+                            via: interface_name.clone(),
+                        });
+                    }
+                }
+
+                check_implementation(schema, type_name, fields, interface, errors)
+            }
+            Some(_) => errors.push(SchemaError::NotAnInterface {
+                object: type_name.to_owned(),
+                interface: interface_name.clone(),
+            }),
+        }
+    }
+}
+
+fn check_implementation(
+    schema: &SchemaType,
+    type_name: &str,
+    fields: &[Field],
+    interface: &InterfaceMeta,
+    errors: &mut Vec<SchemaError>,
+) {
+    for interface_field in &interface.fields {
+        let path = format!("{}.{}", type_name, interface_field.name);
+        let object_field = match fields.iter().find(|f| f.name == interface_field.name) {
+            Some(field) => field,
+            None => {
+                errors.push(SchemaError::MissingInterfaceField {
+                    interface: interface.name.to_string(),
+                    path: path,
+                });
+                continue;
+            }
+        };
+
+        if !schema.is_subtype(&object_field.field_type, &interface_field.field_type) {
+            errors.push(SchemaError::InvalidInterfaceFieldType {
+                interface: interface.name.to_string(),
+                path: path.clone(),
+                expected: interface_field.field_type.to_string(),
+                found: object_field.field_type.to_string(),
+            });
+        }
+
+        let no_arguments = Vec::new();
+        let interface_args = interface_field.arguments.as_ref().unwrap_or(&no_arguments);
+        let object_args = object_field.arguments.as_ref().unwrap_or(&no_arguments);
+
+        for interface_arg in interface_args {
+            match object_args.iter().find(|a| a.name == interface_arg.name) {
+                None => errors.push(SchemaError::MissingInterfaceArgument {
+                    interface: interface.name.to_string(),
+                    path: argument_path(&path, &interface_arg.name),
+                }),
+                Some(object_arg) if object_arg.arg_type != interface_arg.arg_type => {
+                    errors.push(SchemaError::InvalidInterfaceArgumentType {
+                        interface: interface.name.to_string(),
+                        path: argument_path(&path, &interface_arg.name),
+                        expected: interface_arg.arg_type.to_string(),
+                        found: object_arg.arg_type.to_string(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        for object_arg in object_args {
+            let in_interface = interface_args.iter().any(|a| a.name == object_arg.name);
+
+            let is_required =
+                object_arg.arg_type.is_non_null() && object_arg.default_value.is_none();
+
+            if !in_interface && is_required {
+                errors.push(SchemaError::RequiredArgumentNotInInterface {
+                    interface: interface.name.to_string(),
+                    path: argument_path(&path, &object_arg.name),
+                });
+            }
+        }
+    }
+}
+
+fn argument_path(field_path: &str, name: &str) -> String {
+    format!("{}({}:)", field_path, name)
+}
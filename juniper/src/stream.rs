@@ -0,0 +1,219 @@
+//! Streaming serialization of query results
+//!
+//! Instead of building a complete `Value` tree, streaming execution writes
+//! the JSON response while fields are resolved. Since a `null` in a non-null
+//! position propagates to the nearest nullable parent, output that might
+//! still have to be replaced with `null` is kept in a byte buffer until the
+//! value is complete. Values that can never become `null` after being
+//! started, e.g. objects without any selected non-null fields or lists of
+//! nullable items, are written straight through.
+
+use std::io;
+use std::sync::Mutex;
+
+use serde::Serialize;
+#[cfg(feature = "json")]
+use serde_json;
+
+use executor::Executor;
+use schema::meta::MetaType;
+use schema::model::TypeType;
+use types::base::collect_fields;
+use value::Value;
+
+#[doc(hidden)]
+pub trait ResponseOutput {
+    fn write_all(&self, bytes: &[u8]) -> io::Result<()>;
+}
+
+impl<W: io::Write> ResponseOutput for Mutex<W> {
+    fn write_all(&self, bytes: &[u8]) -> io::Result<()> {
+        self.lock().unwrap().write_all(bytes)
+    }
+}
+
+/// The destination of a response written during execution
+#[doc(hidden)]
+pub struct ResponseStream<'a> {
+    output: &'a (ResponseOutput + Sync + 'a),
+    state: Mutex<StreamState>,
+}
+
+struct StreamState {
+    buffers: Vec<Vec<u8>>,
+    streamed: Option<bool>,
+    suspended: usize,
+    error: Option<io::Error>,
+}
+
+impl<'a> ResponseStream<'a> {
+    #[doc(hidden)]
+    #[cfg(feature = "json")]
+    pub fn new(output: &'a (ResponseOutput + Sync + 'a)) -> ResponseStream<'a> {
+        ResponseStream {
+            output: output,
+            state: Mutex::new(StreamState {
+                buffers: Vec::new(),
+                streamed: None,
+                suspended: 0,
+                error: None,
+            }),
+        }
+    }
+
+    /// Append raw bytes to the innermost buffer, or the output if there is
+    /// no open buffer
+    #[doc(hidden)]
+    pub fn write(&self, bytes: &[u8]) {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(buffer) = state.buffers.last_mut() {
+            buffer.extend_from_slice(bytes);
+            return;
+        }
+
+        if state.error.is_none() {
+            if let Err(e) = self.output.write_all(bytes) {
+                state.error = Some(e);
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[cfg(feature = "json")]
+    pub fn write_json<T: Serialize + ?Sized>(&self, value: &T) {
+        self.write(&serde_json::to_vec(value).expect("Response could not be serialized"));
+    }
+
+    // Streams are only created by `execute_to_writer`, which needs the
+    // `json` feature
+    #[doc(hidden)]
+    #[cfg(not(feature = "json"))]
+    pub fn write_json<T: Serialize + ?Sized>(&self, _: &T) {
+        unreachable!("Response streams require the json feature")
+    }
+
+    /// Write an object key, preceded by a comma unless it's the first one
+    #[doc(hidden)]
+    pub fn write_key(&self, key: &str, first: bool) {
+        if !first {
+            self.write(b",");
+        }
+        self.write_json(key);
+        self.write(b":");
+    }
+
+    /// Write a resolved value
+    ///
+    /// Returns `false` without writing anything if the value is `null`.
+    #[doc(hidden)]
+    pub fn write_value(&self, value: &Value) -> bool {
+        if value.is_null() {
+            false
+        } else {
+            self.write_json(value);
+            true
+        }
+    }
+
+    /// Start buffering the output of a value that might become `null`
+    #[doc(hidden)]
+    pub fn begin_buffer(&self) {
+        self.state.lock().unwrap().buffers.push(Vec::new());
+    }
+
+    /// Stop buffering, writing the buffered output if `keep` is set and
+    /// discarding it otherwise
+    #[doc(hidden)]
+    pub fn end_buffer(&self, keep: bool) {
+        let buffer = self.state
+            .lock()
+            .unwrap()
+            .buffers
+            .pop()
+            .expect("No buffer to end");
+
+        if keep {
+            self.write(&buffer);
+        }
+    }
+
+    /// Record that a field value was written to the stream instead of
+    /// being returned
+    #[doc(hidden)]
+    pub fn set_streamed(&self, written: bool) {
+        self.state.lock().unwrap().streamed = Some(written);
+    }
+
+    #[doc(hidden)]
+    pub fn take_streamed(&self) -> Option<bool> {
+        self.state.lock().unwrap().streamed.take()
+    }
+
+    /// Whether field values should be written to the stream
+    ///
+    /// Field values are returned as usual while a value is resolved with
+    /// `suspend`.
+    #[doc(hidden)]
+    pub fn is_active(&self) -> bool {
+        self.state.lock().unwrap().suspended == 0
+    }
+
+    /// Resolve a complete `Value` without writing field values to the
+    /// stream
+    #[doc(hidden)]
+    pub fn suspend<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        self.state.lock().unwrap().suspended += 1;
+        let result = f();
+        self.state.lock().unwrap().suspended -= 1;
+        result
+    }
+
+    #[doc(hidden)]
+    pub fn finish(self) -> io::Result<()> {
+        match self.state.into_inner().unwrap().error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Whether a value of the given type can turn into `null` after it has
+/// started to be written, and thus has to be buffered in nullable positions
+///
+/// Objects are checked against the fields the executor's selection set
+/// selects, since a `null` in any other non-null field can't reach them.
+#[doc(hidden)]
+pub fn may_become_null<CtxT>(t: &TypeType, executor: &Executor<CtxT>) -> bool {
+    match *t {
+        TypeType::NonNull(ref inner) => may_become_null(inner, executor),
+        TypeType::List(ref inner) => inner.is_non_null(),
+        TypeType::Concrete(&MetaType::Object(ref object)) => {
+            let selection_set = match executor.current_selection_set() {
+                Some(selection_set) => selection_set,
+                None => return false,
+            };
+
+            // Selection sets that can't be streamed field by field are
+            // resolved completely before anything is written
+            let mut fields = Vec::new();
+            if !collect_fields(&object.name, selection_set, executor, &mut fields) {
+                return false;
+            }
+
+            fields.iter().any(|f| {
+                object
+                    .fields
+                    .iter()
+                    .find(|meta_field| meta_field.name == f.item.name.item)
+                    .map_or(false, |meta_field| meta_field.field_type.is_non_null())
+            })
+        }
+        TypeType::Concrete(&MetaType::Interface(_)) |
+        TypeType::Concrete(&MetaType::Union(_)) => true,
+        TypeType::Concrete(_) => false,
+    }
+}
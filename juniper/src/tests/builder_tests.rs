@@ -0,0 +1,132 @@
+use ast::InputValue;
+use builder::{DirectiveBuilder, FieldBuilder, FragmentBuilder, InlineFragmentBuilder,
+              OperationBuilder};
+use executor::Variables;
+use schema::model::RootNode;
+use tests::model::Database;
+use types::scalars::EmptyMutation;
+use GraphQLError;
+
+fn hero_query<'a>() -> OperationBuilder<'a> {
+    OperationBuilder::query()
+        .name("HeroQuery")
+        .variable_with_default("episode", "Episode", InputValue::enum_value("EMPIRE"))
+        .variable("withFriends", "Boolean!")
+        .field(
+            FieldBuilder::new("hero")
+                .argument("episode", InputValue::variable("episode"))
+                .field("name")
+                .field(
+                    FieldBuilder::new("friends")
+                        .alias("pals")
+                        .directive(DirectiveBuilder::include(InputValue::variable("withFriends")))
+                        .field("name"),
+                )
+                .spread("Ids")
+                .inline_fragment(InlineFragmentBuilder::on("Droid").field("primaryFunction")),
+        )
+        .fragment(FragmentBuilder::new("Ids", "Character").field("id"))
+}
+
+#[test]
+fn prints_built_queries() {
+    assert_eq!(
+        hero_query().to_string(),
+        r#"query HeroQuery($episode: Episode = EMPIRE, $withFriends: Boolean!) {
+  hero(episode: $episode) {
+    name
+    pals: friends @include(if: $withFriends) {
+      name
+    }
+    ...Ids
+    ... on Droid {
+      primaryFunction
+    }
+  }
+}
+
+fragment Ids on Character {
+  id
+}
+"#
+    );
+}
+
+#[test]
+fn executes_like_the_printed_query() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, EmptyMutation::<Database>::new());
+    let vars = vec![("withFriends".to_owned(), InputValue::boolean(true))]
+        .into_iter()
+        .collect();
+
+    let query = hero_query();
+    let printed = query.to_string();
+
+    let result = query.execute(&schema, &vars, &database).unwrap();
+
+    assert_eq!(
+        result,
+        ::execute(&printed, None, &schema, &vars, &database).unwrap()
+    );
+    assert_eq!(
+        result.0,
+        graphql_value!({
+            "hero": {
+                "name": "Luke Skywalker",
+                "pals": [
+                    { "name": "Han Solo" },
+                    { "name": "Leia Organa" },
+                    { "name": "C-3PO" },
+                    { "name": "R2-D2" },
+                ],
+                "id": "1000",
+            },
+        })
+    );
+}
+
+#[test]
+fn escapes_string_arguments() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, EmptyMutation::<Database>::new());
+
+    let query = OperationBuilder::query()
+        .field(FieldBuilder::new("human").argument("id", "10\"00\n").field("name"));
+
+    assert_eq!(
+        query.to_string(),
+        "{\n  human(id: \"10\\\"00\\n\") {\n    name\n  }\n}\n"
+    );
+    assert_eq!(
+        query.execute(&schema, &Variables::new(), &database),
+        Ok((graphql_value!({ "human": None }), vec![]))
+    );
+}
+
+#[test]
+fn validates_built_queries() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, EmptyMutation::<Database>::new());
+
+    let result = OperationBuilder::query()
+        .field(FieldBuilder::new("hero").field("unknownField"))
+        .execute(&schema, &Variables::new(), &database);
+
+    match result {
+        Err(GraphQLError::ValidationError(ref errors)) => assert_eq!(errors.len(), 1),
+        _ => panic!("Expected a validation error, got {:?}", result),
+    }
+}
+
+#[test]
+#[should_panic(expected = "Invalid GraphQL name: \"not a name\"")]
+fn rejects_invalid_names() {
+    FieldBuilder::new("not a name");
+}
+
+#[test]
+#[should_panic(expected = "Invalid type literal \"[Int\"")]
+fn rejects_invalid_type_literals() {
+    OperationBuilder::query().variable("ids", "[Int");
+}
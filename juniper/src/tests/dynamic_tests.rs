@@ -0,0 +1,374 @@
+use ast::InputValue;
+use executor::{Context, Variables};
+use schema::meta::EnumValue;
+use schema::validation::SchemaError;
+use types::dynamic::{DynamicRootNode, EnumType, FieldDef, InputObjectType, InputValueDef,
+                     InterfaceType, ObjectType, SchemaBuilder, UnionType};
+use value::Value;
+
+struct Tenant {
+    name: String,
+}
+
+impl Context for Tenant {}
+
+fn library_schema() -> DynamicRootNode<'static, Tenant> {
+    let books = graphql_value!([
+        { "__typename": "Book", "title": "Dune", "pages": 412, "genre": "SCIENCE_FICTION" },
+        { "__typename": "Magazine", "title": "Byte", "issue": 7 },
+    ]);
+
+    SchemaBuilder::new("Query")
+        .mutation("Mutation")
+        .enum_type(EnumType::new(
+            "Genre",
+            &[EnumValue::new("SCIENCE_FICTION"), EnumValue::new("POETRY")],
+        ))
+        .input_object(
+            InputObjectType::new("Filter")
+                .field(InputValueDef::new("title", "String"))
+                .field(InputValueDef::new("limit", "Int").default_value(InputValue::int(10))),
+        )
+        .interface(
+            InterfaceType::new("Publication")
+                .description("Anything with a title")
+                .field(FieldDef::new("title", "String!")),
+        )
+        .object(
+            ObjectType::new("Book")
+                .interface("Publication")
+                .field(FieldDef::new("title", "String!"))
+                .field(FieldDef::new("pages", "Int"))
+                .field(FieldDef::new("genre", "Genre!")),
+        )
+        .object(
+            ObjectType::new("Magazine")
+                .interface("Publication")
+                .field(FieldDef::new("title", "String!"))
+                .field(FieldDef::new("issue", "Int!")),
+        )
+        .union(
+            UnionType::new("SearchResult", &["Book", "Magazine"]).resolve_type(|value, _| {
+                if value.as_object_value().unwrap().contains_key("pages") {
+                    Some("Book".to_owned())
+                } else {
+                    Some("Magazine".to_owned())
+                }
+            }),
+        )
+        .object(
+            ObjectType::new("Query")
+                .field(
+                    FieldDef::new("tenant", "String!")
+                        .resolve(|_, _, tenant: &Tenant| Ok(Value::string(&tenant.name))),
+                )
+                .field(FieldDef::new("publications", "[Publication!]!"))
+                .field(
+                    FieldDef::new("search", "[SearchResult]")
+                        .argument(InputValueDef::new("filter", "Filter!"))
+                        .resolve(|root, args, _| {
+                            let filter = args.get::<InputValue>("filter").unwrap();
+                            let filter = filter.to_object_value().unwrap();
+                            let title = filter["title"].as_string_value().unwrap();
+                            let limit = filter
+                                .get("limit")
+                                .and_then(|l| l.as_int_value())
+                                .unwrap_or(10) as usize;
+
+                            let items = root.as_object_value().unwrap()["publications"]
+                                .as_list_value()
+                                .unwrap()
+                                .iter()
+                                .filter(|p| {
+                                    p.as_object_value().unwrap()["title"].as_string_value()
+                                        == Some(title)
+                                })
+                                .take(limit)
+                                .cloned()
+                                .collect();
+
+                            Ok(Value::list(items))
+                        }),
+                )
+                .field(
+                    FieldDef::new("byGenre", "[Book!]!")
+                        .argument(InputValueDef::new("genre", "Genre!"))
+                        .resolve(|_, args, _| {
+                            let genre = args.get::<InputValue>("genre").unwrap();
+                            assert_eq!(genre, InputValue::enum_value("SCIENCE_FICTION"));
+
+                            Ok(graphql_value!([{ "title": "Dune", "genre": "SCIENCE_FICTION" }]))
+                        }),
+                )
+                .field(FieldDef::new("broken", "Genre").resolve(|_, _, _| {
+                    Ok(Value::string("ROMANCE"))
+                })),
+        )
+        .object(
+            ObjectType::new("Mutation").field(
+                FieldDef::new("echo", "String!")
+                    .argument(InputValueDef::new("text", "String!"))
+                    .resolve(|_, args, _| Ok(Value::string(args.get::<String>("text").unwrap()))),
+            ),
+        )
+        .build(Value::object(
+            vec![("publications", books)].into_iter().collect(),
+        ))
+        .unwrap()
+}
+
+fn run(query: &str) -> (Value, Vec<::ExecutionError>) {
+    let schema = library_schema();
+    let tenant = Tenant {
+        name: "acme".to_owned(),
+    };
+
+    ::execute(query, None, &schema, &Variables::new(), &tenant).unwrap()
+}
+
+#[test]
+fn resolves_fields_with_resolvers_and_from_the_parent_value() {
+    assert_eq!(
+        run("{ tenant publications { title } }"),
+        (
+            graphql_value!({
+                "tenant": "acme",
+                "publications": [{ "title": "Dune" }, { "title": "Byte" }],
+            }),
+            vec![]
+        )
+    );
+}
+
+#[test]
+fn resolves_interfaces_by_typename() {
+    let query = r#"
+        {
+            publications {
+                __typename
+                ... on Book { pages genre }
+                ... on Magazine { issue }
+            }
+        }"#;
+
+    assert_eq!(
+        run(query),
+        (
+            graphql_value!({
+                "publications": [
+                    { "__typename": "Book", "pages": 412, "genre": "SCIENCE_FICTION" },
+                    { "__typename": "Magazine", "issue": 7 },
+                ],
+            }),
+            vec![]
+        )
+    );
+}
+
+#[test]
+fn resolves_unions_with_input_object_arguments() {
+    let query = r#"
+        {
+            search(filter: { title: "Byte" }) {
+                ... on Publication { __typename title }
+            }
+        }"#;
+
+    assert_eq!(
+        run(query),
+        (
+            graphql_value!({
+                "search": [{ "__typename": "Magazine", "title": "Byte" }],
+            }),
+            vec![]
+        )
+    );
+}
+
+#[test]
+fn accepts_enum_arguments() {
+    assert_eq!(
+        run("{ byGenre(genre: SCIENCE_FICTION) { title genre } }"),
+        (
+            graphql_value!({
+                "byGenre": [{ "title": "Dune", "genre": "SCIENCE_FICTION" }],
+            }),
+            vec![]
+        )
+    );
+}
+
+#[test]
+fn rejects_invalid_enum_values() {
+    let (result, errors) = run("{ broken }");
+
+    assert_eq!(result, graphql_value!({ "broken": None }));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].error().message(),
+        "Invalid value for enum \"Genre\""
+    );
+}
+
+#[test]
+fn executes_mutations() {
+    assert_eq!(
+        run(r#"mutation { echo(text: "hello") }"#),
+        (graphql_value!({ "echo": "hello" }), vec![])
+    );
+}
+
+#[test]
+fn supports_introspection() {
+    let query = r#"
+        {
+            __type(name: "Publication") {
+                kind
+                description
+                fields { name }
+                possibleTypes { name }
+            }
+        }"#;
+
+    assert_eq!(
+        run(query),
+        (
+            graphql_value!({
+                "__type": {
+                    "kind": "INTERFACE",
+                    "description": "Anything with a title",
+                    "fields": [{ "name": "title" }],
+                    "possibleTypes": [{ "name": "Book" }, { "name": "Magazine" }],
+                },
+            }),
+            vec![]
+        )
+    );
+}
+
+#[test]
+fn reports_invalid_definitions() {
+    let errors = SchemaBuilder::<()>::new("Query")
+        .mutation("Genre")
+        .enum_type(EnumType::new("Genre", &[EnumValue::new("POETRY")]))
+        .object(
+            ObjectType::new("Query")
+                .field(FieldDef::new("broken", "[String"))
+                .field(FieldDef::new("genre", "Genre")),
+        )
+        .object(ObjectType::new("Genre"))
+        .build(Value::null())
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        errors,
+        vec![
+            SchemaError::DuplicateTypeName {
+                name: "Genre".to_owned(),
+                first: "dynamic enum".to_owned(),
+                second: "dynamic object".to_owned(),
+            },
+            SchemaError::InvalidTypeReference {
+                path: "Query.broken".to_owned(),
+                type_ref: "[String".to_owned(),
+            },
+            SchemaError::InvalidRootType {
+                operation: "mutation".to_owned(),
+                name: "Genre".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn reports_unknown_types() {
+    let errors = SchemaBuilder::<()>::new("Query")
+        .object(ObjectType::new("Query").field(FieldDef::new("author", "Author!")))
+        .build(Value::null())
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        errors,
+        vec![
+            SchemaError::UnresolvedPlaceholder {
+                name: "Author".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn supports_interfaces_implementing_interfaces() {
+    let schema = SchemaBuilder::<()>::new("Query")
+        .interface(InterfaceType::new("Node").field(FieldDef::new("id", "ID!")))
+        .interface(
+            InterfaceType::new("Entity")
+                .interface("Node")
+                .field(FieldDef::new("id", "ID!"))
+                .field(FieldDef::new("owner", "String")),
+        )
+        .object(
+            ObjectType::new("Thing")
+                .interface("Node")
+                .field(FieldDef::new("id", "ID!")),
+        )
+        .object(
+            ObjectType::new("Query")
+                .field(FieldDef::new("node", "Node"))
+                .field(FieldDef::new("entity", "Entity"))
+                .field(FieldDef::new("thing", "Thing")),
+        )
+        .build(graphql_value!({ "node": { "__typename": "Thing", "id": "1" } }))
+        .unwrap();
+
+    let query = r#"
+        {
+            node { id ... on Entity { owner } }
+            __type(name: "Entity") { interfaces { name } }
+        }"#;
+
+    assert_eq!(
+        ::execute(query, None, &schema, &Variables::new(), &()).unwrap(),
+        (
+            graphql_value!({
+                "node": { "id": "1" },
+                "__type": { "interfaces": [{ "name": "Node" }] },
+            }),
+            vec![]
+        )
+    );
+}
+
+#[test]
+fn reports_invalid_one_of_fields() {
+    let errors = SchemaBuilder::<()>::new("Query")
+        .input_object(
+            InputObjectType::new("UserBy")
+                .one_of()
+                .field(InputValueDef::new("id", "ID!"))
+                .field(InputValueDef::new("name", "String").default_value(InputValue::string("ada")))
+                .field(InputValueDef::new("email", "String")),
+        )
+        .object(
+            ObjectType::new("Query").field(
+                FieldDef::new("user", "String").argument(InputValueDef::new("by", "UserBy!")),
+            ),
+        )
+        .build(Value::null())
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        errors,
+        vec![
+            SchemaError::InvalidOneOfField {
+                path: "UserBy.id".to_owned(),
+            },
+            SchemaError::InvalidOneOfField {
+                path: "UserBy.name".to_owned(),
+            },
+        ]
+    );
+}
@@ -0,0 +1,477 @@
+use serde_json;
+use serde_json::Value as Json;
+
+use ast::InputValue;
+use http::{GraphQLRequest, GraphQLRequestError, ResponseContentType, UploadLimits};
+use http::tests::{run_http_test_suite, HTTPIntegration, TestResponse};
+use schema::model::RootNode;
+use types::scalars::{EmptyMutation, Upload};
+use tests::model::Database;
+use value::Value;
+
+/// Serves requests without a server, to run the HTTP test suite against the
+/// request parsing in `http`
+struct InMemoryIntegration {
+    database: Database,
+}
+
+impl InMemoryIntegration {
+    fn respond(
+        &self,
+        request: Result<GraphQLRequest, GraphQLRequestError>,
+    ) -> TestResponse {
+        let schema = RootNode::new(&self.database, EmptyMutation::<Database>::new());
+
+        let (status_code, content_type, body) = match request {
+            Ok(request) => {
+                let response = request.execute(&schema, &self.database);
+                let (status_code, content_type) = response.status_and_content_type(None);
+                (status_code, content_type, serde_json::to_string(&response))
+            }
+            Err(error) => (
+                error.status_code(),
+                "application/json",
+                serde_json::to_string(&error),
+            ),
+        };
+
+        TestResponse {
+            status_code: status_code as i32,
+            body: Some(body.expect("Could not serialize response")),
+            content_type: content_type.to_owned(),
+        }
+    }
+}
+
+impl HTTPIntegration for InMemoryIntegration {
+    fn get(&self, url: &str) -> TestResponse {
+        let query_string = url.splitn(2, '?').nth(1).unwrap_or("");
+        self.respond(GraphQLRequest::from_query_string(query_string))
+    }
+
+    fn post(&self, _: &str, body: &str) -> TestResponse {
+        self.respond(GraphQLRequest::from_post_body(Some("application/json"), body))
+    }
+
+    fn post_graphql(&self, _: &str, body: &str) -> Option<TestResponse> {
+        Some(self.respond(GraphQLRequest::from_post_body(Some("application/graphql"), body)))
+    }
+}
+
+#[test]
+fn passes_the_http_test_suite() {
+    run_http_test_suite(&InMemoryIntegration {
+        database: Database::new(),
+    });
+}
+
+#[test]
+fn decodes_query_strings() {
+    assert_eq!(
+        serde_json::to_value(
+            GraphQLRequest::from_query_string(
+                "query=query+Hero($id%3AString)%7Bhero%7Bname%7D%7D&operationName=Hero&variables=%7B%22id%22%3A%221%22%7D&extra",
+            ).unwrap()
+        ).unwrap(),
+        serde_json::from_str::<Json>(
+            r#"{
+                "query": "query Hero($id:String){hero{name}}",
+                "operationName": "Hero",
+                "variables": {"id": "1"}
+            }"#
+        ).unwrap()
+    );
+}
+
+#[test]
+fn rejects_malformed_query_strings() {
+    assert_eq!(
+        GraphQLRequest::from_query_string("query={hero{name}}&query={hero{id}}").err(),
+        Some(GraphQLRequestError::DuplicateParameter("query".to_owned()))
+    );
+    assert_eq!(
+        GraphQLRequest::from_query_string("query=%7Bhero%zz").err(),
+        Some(GraphQLRequestError::InvalidEncoding)
+    );
+    assert_eq!(
+        GraphQLRequest::from_query_string("query=%FF").err(),
+        Some(GraphQLRequestError::InvalidEncoding)
+    );
+    assert_eq!(
+        GraphQLRequest::from_query_string("query=").err(),
+        Some(GraphQLRequestError::MissingQuery)
+    );
+}
+
+#[test]
+fn allows_mutations_that_are_not_executed_over_get() {
+    let query_string = "query=query+Q%7Bhero%7Bname%7D%7D+mutation+M%7Bhero%7Bname%7D%7D";
+
+    assert!(GraphQLRequest::from_query_string(&format!("{}&operationName=Q", query_string)).is_ok());
+    assert_eq!(
+        GraphQLRequest::from_query_string(&format!("{}&operationName=M", query_string)).err(),
+        Some(GraphQLRequestError::MutationOverGet)
+    );
+}
+
+#[test]
+fn dispatches_post_bodies_on_the_content_type() {
+    assert!(
+        GraphQLRequest::from_post_body(
+            Some("Application/JSON; charset=utf-8"),
+            r#"{"query": "{hero{name}}", "variables": null}"#,
+        ).is_ok()
+    );
+    assert!(GraphQLRequest::from_post_body(Some("application/graphql"), "{hero{name}}").is_ok());
+    assert_eq!(
+        GraphQLRequest::from_post_body(Some("text/plain"), "{hero{name}}").err(),
+        Some(GraphQLRequestError::UnsupportedContentType(Some("text/plain".to_owned())))
+    );
+    assert_eq!(
+        GraphQLRequest::from_post_body(None, "{hero{name}}").err(),
+        Some(GraphQLRequestError::UnsupportedContentType(None))
+    );
+    assert_eq!(
+        GraphQLRequest::from_post_body(
+            Some("application/json"),
+            r#"{"query": "{hero{name}}", "variables": "{}"}"#,
+        ).err(),
+        Some(GraphQLRequestError::InvalidVariables("Variables must be an object".to_owned()))
+    );
+}
+
+#[test]
+fn negotiates_the_response_content_type() {
+    use self::ResponseContentType::*;
+
+    let cases = [
+        (None, Some(Json)),
+        (Some(""), Some(Json)),
+        (Some("*/*"), Some(Json)),
+        (Some("application/*"), Some(Json)),
+        (Some("application/json"), Some(Json)),
+        (Some("application/graphql-response+json"), Some(GraphQLResponseJson)),
+        (
+            Some("application/graphql-response+json, application/json;q=0.9"),
+            Some(GraphQLResponseJson),
+        ),
+        (
+            Some("application/graphql-response+json;q=0.5, application/json"),
+            Some(Json),
+        ),
+        (Some("application/json, application/graphql-response+json"), Some(GraphQLResponseJson)),
+        (Some("Application/GraphQL-Response+JSON; charset=utf-8"), Some(GraphQLResponseJson)),
+        (Some("text/html, */*;q=0.1"), Some(Json)),
+        (Some("*/*, application/json;q=0"), Some(GraphQLResponseJson)),
+        (Some("text/html"), None),
+        (Some("application/json;q=0"), None),
+    ];
+
+    for &(accept, expected) in &cases {
+        assert_eq!(ResponseContentType::negotiate(accept), expected, "{:?}", accept);
+    }
+}
+
+#[test]
+fn recommends_status_codes_for_responses() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, EmptyMutation::<Database>::new());
+    let graphql_response = Some("application/graphql-response+json");
+
+    let invalid = GraphQLRequest::new("{ unknown }".to_owned(), None, None);
+    let response = invalid.execute(&schema, &database);
+    assert_eq!(
+        response.status_and_content_type(graphql_response),
+        (400, "application/graphql-response+json")
+    );
+    assert_eq!(response.status_and_content_type(None), (200, "application/json"));
+    assert_eq!(response.status_and_content_type(Some("text/html")), (406, "application/json"));
+
+    let unknown_operation = GraphQLRequest::new("{ hero { name } }".to_owned(), Some("Other".to_owned()), None);
+    assert_eq!(
+        unknown_operation.execute(&schema, &database).status_code(ResponseContentType::GraphQLResponseJson),
+        400
+    );
+
+    let valid = GraphQLRequest::new("{ hero { name } }".to_owned(), None, None);
+    assert_eq!(
+        valid.execute(&schema, &database).status_and_content_type(graphql_response),
+        (200, "application/graphql-response+json")
+    );
+}
+
+#[test]
+fn serializes_request_errors() {
+    assert_eq!(
+        serde_json::to_value(&GraphQLRequestError::MissingQuery).unwrap(),
+        serde_json::from_str::<Json>(r#"{"errors": [{"message": "Missing query"}]}"#).unwrap()
+    );
+    assert_eq!(GraphQLRequestError::MissingQuery.status_code(), 400);
+    assert_eq!(GraphQLRequestError::MutationOverGet.status_code(), 405);
+}
+
+struct UploadQuery;
+struct UploadMutation;
+
+graphql_object!(UploadQuery: () |&self| {
+    field size(file: Upload) -> i32 {
+        file.content().len() as i32
+    }
+});
+
+graphql_object!(UploadMutation: () |&self| {
+    field upload(file: Upload) -> String {
+        format!(
+            "{} ({}): {}",
+            file.filename().unwrap_or("unnamed"),
+            file.content_type().unwrap_or("unknown"),
+            String::from_utf8_lossy(file.content())
+        )
+    }
+
+    field upload_many(files: Vec<Upload>) -> i32 {
+        files.iter().map(|f| f.content().len() as i32).sum()
+    }
+});
+
+const BOUNDARY: &str = "------------------------abc123";
+
+/// Builds a multipart body from parts of a name, an optional file name, and
+/// the content
+fn multipart_body(parts: &[(&str, Option<&str>, &str)]) -> Vec<u8> {
+    let mut body = String::from("preamble\r\n");
+
+    for &(name, filename, content) in parts {
+        body.push_str(&format!("--{}\r\n", BOUNDARY));
+        match filename {
+            Some(filename) => body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+                 Content-Type: text/plain\r\n",
+                name, filename
+            )),
+            None => body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{}\"\r\n",
+                name
+            )),
+        }
+        body.push_str(&format!("\r\n{}\r\n", content));
+    }
+
+    body.push_str(&format!("--{}--\r\n", BOUNDARY));
+    body.into_bytes()
+}
+
+fn parse_multipart(
+    parts: &[(&str, Option<&str>, &str)],
+    limits: &UploadLimits,
+) -> Result<GraphQLRequest, GraphQLRequestError> {
+    GraphQLRequest::from_multipart_body(
+        &format!("multipart/form-data; boundary=\"{}\"", BOUNDARY),
+        &multipart_body(parts),
+        limits,
+    )
+}
+
+fn execute_upload(request: &GraphQLRequest) -> Json {
+    let schema = RootNode::new(UploadQuery, UploadMutation);
+    serde_json::to_value(&request.execute(&schema, &())).unwrap()
+}
+
+#[test]
+fn substitutes_uploads_into_variables() {
+    let request = parse_multipart(
+        &[
+            (
+                "operations",
+                None,
+                r#"{
+                    "query": "mutation($file: Upload!, $files: [Upload!]!) { upload(file: $file) uploadMany(files: $files) }",
+                    "variables": {"file": null, "files": [null, null]}
+                }"#,
+            ),
+            ("map", None, r#"{"0": ["variables.file"], "1": ["variables.files.0", "variables.files.1"]}"#),
+            ("0", Some("a.txt"), "Alpha\r\nwith a line break"),
+            ("1", Some("b.txt"), "Beta"),
+        ],
+        &UploadLimits::default(),
+    ).unwrap();
+
+    assert_eq!(
+        execute_upload(&request),
+        serde_json::from_str::<Json>(
+            r#"{"data": {
+                "upload": "a.txt (text/plain): Alpha\r\nwith a line break",
+                "uploadMany": 8
+            }}"#
+        ).unwrap()
+    );
+}
+
+#[test]
+fn rejects_uploads_for_other_types() {
+    let request = parse_multipart(
+        &[
+            ("operations", None, r#"{"query": "mutation($file: Upload!) { upload(file: $file) }", "variables": {"file": null}}"#),
+            ("map", None, r#"{"0": ["variables.file"]}"#),
+            ("0", Some("a.txt"), "Alpha"),
+        ],
+        &UploadLimits::default(),
+    ).unwrap();
+    assert!(execute_upload(&request).get("data").is_some());
+
+    let request = parse_multipart(
+        &[
+            ("operations", None, r#"{"query": "mutation($file: String!) { upload(file: $file) }", "variables": {"file": null}}"#),
+            ("map", None, r#"{"0": ["variables.file"]}"#),
+            ("0", Some("a.txt"), "Alpha"),
+        ],
+        &UploadLimits::default(),
+    ).unwrap();
+    assert!(execute_upload(&request).get("data").is_none());
+}
+
+#[test]
+fn rejects_malformed_multipart_requests() {
+    let operations = ("operations", None, r#"{"query": "mutation($file: Upload) { upload(file: $file) }", "variables": {"file": null}}"#);
+    let file = ("0", Some("a.txt"), "Alpha");
+    let limits = UploadLimits::default();
+
+    assert_eq!(
+        parse_multipart(&[operations, file], &limits).err(),
+        Some(GraphQLRequestError::InvalidMultipart("Missing map".to_owned()))
+    );
+    assert_eq!(
+        parse_multipart(&[operations, ("map", None, r#"{"1": ["variables.file"]}"#), file], &limits).err(),
+        Some(GraphQLRequestError::InvalidMultipart(r#"Missing file "1""#.to_owned()))
+    );
+    assert_eq!(
+        parse_multipart(&[operations, ("map", None, r#"{"0": ["variables.other"]}"#), file], &limits).err(),
+        Some(GraphQLRequestError::InvalidMultipart(r#"Invalid path "variables.other""#.to_owned()))
+    );
+    assert_eq!(
+        parse_multipart(&[("operations", None, "[]"), ("map", None, "{}")], &limits).err(),
+        Some(GraphQLRequestError::InvalidMultipart("Batched operations are not supported".to_owned()))
+    );
+    assert_eq!(
+        GraphQLRequest::from_multipart_body("multipart/form-data", b"", &limits).err(),
+        Some(GraphQLRequestError::InvalidMultipart("Missing boundary".to_owned()))
+    );
+    assert_eq!(
+        GraphQLRequest::from_multipart_body("application/json", b"{}", &limits).err(),
+        Some(GraphQLRequestError::UnsupportedContentType(Some("application/json".to_owned())))
+    );
+
+    let mut truncated = multipart_body(&[operations]);
+    let len = truncated.len();
+    truncated.truncate(len - 10);
+    assert_eq!(
+        GraphQLRequest::from_multipart_body(
+            &format!("multipart/form-data; boundary={}", BOUNDARY),
+            &truncated,
+            &limits,
+        ).err(),
+        Some(GraphQLRequestError::InvalidMultipart("Missing closing boundary".to_owned()))
+    );
+}
+
+#[test]
+fn enforces_upload_limits() {
+    let parts = [
+        ("operations", None, r#"{"query": "mutation($files: [Upload!]!) { uploadMany(files: $files) }", "variables": {"files": [null, null]}}"#),
+        ("map", None, r#"{"0": ["variables.files.0"], "1": ["variables.files.1"]}"#),
+        ("0", Some("a.txt"), "Alpha"),
+        ("1", Some("b.txt"), "Beta"),
+    ];
+
+    let limits = |max_files, max_file_size| UploadLimits {
+        max_files: max_files,
+        max_file_size: max_file_size,
+        ..UploadLimits::default()
+    };
+
+    let too_many = parse_multipart(&parts, &limits(1, 100));
+    assert_eq!(too_many.clone().err(), Some(GraphQLRequestError::TooManyFiles));
+    assert_eq!(too_many.err().unwrap().status_code(), 413);
+
+    assert_eq!(
+        parse_multipart(&parts, &limits(2, 4)).err(),
+        Some(GraphQLRequestError::FileTooLarge("0".to_owned()))
+    );
+
+    assert!(parse_multipart(&parts, &limits(2, 5)).is_ok());
+
+    // Parts the map doesn't refer to aren't files
+    let mut unmapped = parts.to_vec();
+    unmapped.push(("extra", Some("c.txt"), "A much longer part"));
+    assert!(parse_multipart(&unmapped, &limits(2, 5)).is_ok());
+}
+
+#[test]
+fn enforces_the_request_size_limit() {
+    let parts = [
+        ("operations", None, r#"{"query": "mutation($file: Upload!) { upload(file: $file) }", "variables": {"file": null}}"#),
+        ("map", None, r#"{"0": ["variables.file"]}"#),
+        ("0", Some("a.txt"), "Alpha"),
+    ];
+    let size = multipart_body(&parts).len();
+    let limits = |max_request_size| UploadLimits {
+        max_request_size: max_request_size,
+        ..UploadLimits::default()
+    };
+
+    let too_large = parse_multipart(&parts, &limits(size - 1));
+    assert_eq!(too_large.clone().err(), Some(GraphQLRequestError::RequestTooLarge));
+    assert_eq!(too_large.err().unwrap().status_code(), 413);
+
+    assert!(parse_multipart(&parts, &limits(size)).is_ok());
+}
+
+#[test]
+fn rejects_uploads_forged_in_json_variables() {
+    // Only the file mapped to `file` may be resolved, not by `forged` as well
+    let request = parse_multipart(
+        &[
+            (
+                "operations",
+                None,
+                r#"{
+                    "query": "mutation($file: Upload!, $forged: Upload!) { a: upload(file: $file) b: upload(file: $forged) }",
+                    "variables": {"file": null, "forged": {"__upload": 0}}
+                }"#,
+            ),
+            ("map", None, r#"{"0": ["variables.file"]}"#),
+            ("0", Some("a.txt"), "Alpha"),
+        ],
+        &UploadLimits::default(),
+    ).unwrap();
+
+    assert!(execute_upload(&request).get("data").is_none());
+}
+
+#[test]
+fn resolves_uploads_passed_as_variables() {
+    let schema = RootNode::new(UploadQuery, UploadMutation);
+    let file = Upload::new(None, None, b"Alpha".to_vec());
+    let variables = vec![("file".to_owned(), InputValue::upload(file))]
+        .into_iter()
+        .collect();
+
+    // Query fields may be resolved on other threads
+    let (result, errs) = ::execute(
+        "query($file: Upload!) { a: size(file: $file) b: size(file: $file) }",
+        None,
+        &schema,
+        &variables,
+        &(),
+    ).expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(
+            vec![("a", Value::int(5)), ("b", Value::int(5))]
+                .into_iter()
+                .collect()
+        )
+    );
+}
@@ -8,3 +8,17 @@ mod query_tests;
 mod introspection_tests;
 #[cfg(test)]
 mod type_info_tests;
+#[cfg(test)]
+mod schema_validation_tests;
+#[cfg(test)]
+mod schema_diff_tests;
+#[cfg(test)]
+mod schema_introspection_tests;
+#[cfg(test)]
+mod dynamic_tests;
+#[cfg(test)]
+mod builder_tests;
+#[cfg(all(test, feature = "json"))]
+mod http_tests;
+#[cfg(all(test, feature = "json"))]
+mod websocket_tests;
@@ -0,0 +1,387 @@
+use ast::ToInputValue;
+use executor::Variables;
+use schema::diff::{diff_introspection, diff_schemas, ChangeKind, Criticality, SchemaChange};
+use schema::model::{RootNode, SchemaType};
+use tests::model::Database;
+use types::base::GraphQLType;
+use types::scalars::EmptyMutation;
+
+pub const INTROSPECTION_QUERY: &str = r#"
+  query IntrospectionQuery {
+    __schema {
+      queryType { name }
+      mutationType { name }
+      subscriptionType { name }
+      types {
+        ...FullType
+      }
+      directives {
+        name
+        description
+        locations
+        args {
+          ...InputValue
+        }
+      }
+    }
+  }
+
+  fragment FullType on __Type {
+    kind
+    name
+    description
+    fields(includeDeprecated: true) {
+      name
+      description
+      args {
+        ...InputValue
+      }
+      type {
+        ...TypeRef
+      }
+      isDeprecated
+      deprecationReason
+    }
+    inputFields {
+      ...InputValue
+    }
+    interfaces {
+      ...TypeRef
+    }
+    enumValues(includeDeprecated: true) {
+      name
+      description
+      isDeprecated
+      deprecationReason
+    }
+    possibleTypes {
+      ...TypeRef
+    }
+  }
+
+  fragment InputValue on __InputValue {
+    name
+    description
+    type { ...TypeRef }
+    defaultValue
+  }
+
+  fragment TypeRef on __Type {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType {
+                kind
+                name
+                ofType {
+                  kind
+                  name
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+"#;
+
+fn schema<QueryT: GraphQLType<TypeInfo = ()>>() -> SchemaType<'static> {
+    SchemaType::new::<QueryT, EmptyMutation<()>>(&(), &())
+}
+
+fn introspect<QueryT: GraphQLType<TypeInfo = (), Context = ()>>(query: QueryT) -> ::InputValue {
+    let root = RootNode::new(query, EmptyMutation::<()>::new());
+    let (result, errors) =
+        ::execute(INTROSPECTION_QUERY, None, &root, &Variables::new(), &()).unwrap();
+
+    assert_eq!(errors, vec![]);
+    result.to_input_value()
+}
+
+#[test]
+fn identical_schemas_have_no_changes() {
+    let old = SchemaType::new::<Database, EmptyMutation<Database>>(&(), &());
+    let new = SchemaType::new::<Database, EmptyMutation<Database>>(&(), &());
+
+    assert_eq!(diff_schemas(&old, &new), vec![]);
+}
+
+#[test]
+fn introspection_of_the_same_schema_has_no_changes() {
+    let database = Database::new();
+    let root = RootNode::new(&database, EmptyMutation::<Database>::new());
+    let (result, errors) =
+        ::execute(INTROSPECTION_QUERY, None, &root, &Variables::new(), &database).unwrap();
+    assert_eq!(errors, vec![]);
+
+    let json = format!(r#"{{"data": {}}}"#, ::serde_json::to_string(&result).unwrap());
+    let introspection = ::serde_json::from_str(&json).unwrap();
+    let schema = SchemaType::new::<Database, EmptyMutation<Database>>(&(), &());
+
+    assert_eq!(diff_introspection(&introspection, &schema), Ok(vec![]));
+}
+
+mod v1 {
+    pub struct Query;
+    pub struct Legacy;
+
+    pub enum Color {
+        Red,
+        Blue,
+    }
+
+    graphql_enum!(Color {
+        Color::Red => "RED",
+        Color::Blue => "BLUE",
+    });
+
+    graphql_input_object!(
+        pub struct Filter {
+            name: String,
+            limit: Option<i32>,
+        }
+    );
+
+    graphql_object!(Legacy: () |&self| {
+        field id() -> i32 { 0 }
+    });
+
+    graphql_object!(Query: () |&self| {
+        field name() -> Option<String> { None }
+        field count() -> i32 { 0 }
+        field color(filter: Filter) -> Color { Color::Red }
+        field search(term: String, exact: bool, page = 1: i32) -> Vec<i32> { vec![] }
+        field legacy() -> Legacy { Legacy }
+    });
+}
+
+mod v2 {
+    pub struct Query;
+    pub struct Fresh;
+
+    pub enum Color {
+        Red,
+        Green,
+    }
+
+    graphql_enum!(Color {
+        Color::Red => "RED",
+        Color::Green => "GREEN",
+    });
+
+    graphql_input_object!(
+        pub struct Filter {
+            name: Option<String>,
+            strict: bool,
+        }
+    );
+
+    graphql_object!(Fresh: () |&self| {
+        field id() -> i32 { 0 }
+    });
+
+    graphql_object!(Query: () |&self| {
+        field name() -> String { String::new() }
+        field count() -> Option<i32> { None }
+        field color(filter: Filter) -> Color { Color::Red }
+        field search(term: Option<String>, page = 2: i32, limit: Option<i32>, sort: String)
+            -> Option<Vec<i32>> { None }
+        field fresh() -> Fresh { Fresh }
+    });
+}
+
+fn change(criticality: Criticality, kind: ChangeKind) -> SchemaChange {
+    SchemaChange {
+        criticality: criticality,
+        kind: kind,
+    }
+}
+
+fn expected_changes() -> Vec<SchemaChange> {
+    use schema::diff::ChangeKind::*;
+    use schema::diff::Criticality::*;
+
+    vec![
+        change(Breaking, EnumValueRemoved { path: "Color.BLUE".to_owned() }),
+        change(Dangerous, EnumValueAdded { path: "Color.GREEN".to_owned() }),
+        change(
+            Safe,
+            InputFieldTypeChanged {
+                path: "Filter.name".to_owned(),
+                from: "String!".to_owned(),
+                to: "String".to_owned(),
+            },
+        ),
+        change(Breaking, InputFieldRemoved { path: "Filter.limit".to_owned() }),
+        change(
+            Breaking,
+            InputFieldAdded {
+                path: "Filter.strict".to_owned(),
+                required: true,
+            },
+        ),
+        change(Safe, TypeAdded { name: "Fresh".to_owned() }),
+        change(Breaking, TypeRemoved { name: "Legacy".to_owned() }),
+        change(
+            Safe,
+            FieldTypeChanged {
+                path: "Query.name".to_owned(),
+                from: "String".to_owned(),
+                to: "String!".to_owned(),
+            },
+        ),
+        change(
+            Breaking,
+            FieldTypeChanged {
+                path: "Query.count".to_owned(),
+                from: "Int!".to_owned(),
+                to: "Int".to_owned(),
+            },
+        ),
+        change(
+            Breaking,
+            FieldTypeChanged {
+                path: "Query.search".to_owned(),
+                from: "[Int!]!".to_owned(),
+                to: "[Int!]".to_owned(),
+            },
+        ),
+        change(
+            Safe,
+            ArgumentTypeChanged {
+                path: "Query.search(term:)".to_owned(),
+                from: "String!".to_owned(),
+                to: "String".to_owned(),
+            },
+        ),
+        change(Breaking, ArgumentRemoved { path: "Query.search(exact:)".to_owned() }),
+        change(
+            Dangerous,
+            ArgumentDefaultChanged {
+                path: "Query.search(page:)".to_owned(),
+                from: Some("1".to_owned()),
+                to: Some("2".to_owned()),
+            },
+        ),
+        change(
+            Dangerous,
+            ArgumentAdded {
+                path: "Query.search(limit:)".to_owned(),
+                required: false,
+            },
+        ),
+        change(
+            Breaking,
+            ArgumentAdded {
+                path: "Query.search(sort:)".to_owned(),
+                required: true,
+            },
+        ),
+        change(Breaking, FieldRemoved { path: "Query.legacy".to_owned() }),
+        change(Safe, FieldAdded { path: "Query.fresh".to_owned() }),
+    ]
+}
+
+#[test]
+fn reports_changes_between_schemas() {
+    let changes = diff_schemas(&schema::<v1::Query>(), &schema::<v2::Query>());
+
+    assert_eq!(changes, expected_changes());
+}
+
+#[test]
+fn reports_changes_from_introspection() {
+    let changes = diff_introspection(&introspect(v1::Query), &schema::<v2::Query>());
+
+    assert_eq!(changes, Ok(expected_changes()));
+}
+
+#[test]
+fn reports_changes_in_kind_and_abstract_types() {
+    mod before {
+        pub struct Query;
+        pub struct Droid;
+        pub struct Human;
+
+        graphql_interface!(Human: () as "Character" |&self| {
+            field id() -> i32 { 0 }
+
+            instance_resolvers: |_| {
+                &Droid => None::<&Droid>,
+            }
+        });
+
+        graphql_object!(Droid: () |&self| {
+            field id() -> i32 { 0 }
+
+            interfaces: [Human]
+        });
+
+        graphql_object!(Query: () |&self| {
+            field character() -> Human { Human }
+            field droid() -> Droid { Droid }
+        });
+    }
+
+    mod after {
+        pub struct Query;
+        pub struct Droid;
+        pub struct Human;
+
+        graphql_object!(Human: () as "Character" |&self| {
+            field id() -> i32 { 0 }
+        });
+
+        graphql_object!(Droid: () |&self| {
+            field id() -> i32 { 0 }
+        });
+
+        graphql_object!(Query: () |&self| {
+            field character() -> Human { Human }
+            field droid() -> Droid { Droid }
+        });
+    }
+
+    let changes = diff_schemas(&schema::<before::Query>(), &schema::<after::Query>());
+
+    assert_eq!(
+        changes
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>(),
+        vec![
+            "[breaking] Type \"Character\" changed from INTERFACE to OBJECT",
+            "[breaking] Type \"Droid\" no longer implements interface \"Character\"",
+        ]
+    );
+    assert!(changes.iter().all(SchemaChange::is_breaking));
+}
+
+#[test]
+fn rejects_malformed_introspection() {
+    let introspection = ::serde_json::from_str(
+        r#"{"__schema": {"types": [{"kind": "OBJECT", "name": "Query", "fields": [{}]}]}}"#,
+    ).unwrap();
+
+    let error = diff_introspection(&introspection, &schema::<v1::Query>()).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "__schema.types[0].fields[0]: Missing field \"name\""
+    );
+}
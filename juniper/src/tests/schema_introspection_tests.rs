@@ -0,0 +1,222 @@
+use ast::{InputValue, ToInputValue};
+use executor::Variables;
+use schema::diff::{diff_schemas, IntrospectionError};
+use schema::model::{DirectiveLocation, RootNode, SchemaType};
+use tests::model::Database;
+use tests::schema_diff_tests::INTROSPECTION_QUERY;
+use types::scalars::EmptyMutation;
+use {validate_query, GraphQLError};
+
+struct Query;
+
+enum Status {
+    Active,
+    Retired,
+}
+
+graphql_enum!(Status {
+    Status::Active => "ACTIVE",
+    Status::Retired => "RETIRED" deprecated "Nothing retires",
+});
+
+graphql_object!(Query: () |&self| {
+    description: "The root of all queries"
+
+    field status(since: Option<i32> as "A year") -> Status as "The current status" {
+        Status::Active
+    }
+
+    field deprecated "Use status"
+        active() -> bool { true }
+});
+
+fn introspect_database() -> InputValue {
+    let database = Database::new();
+    let root = RootNode::new(&database, EmptyMutation::<Database>::new());
+    let (result, errors) =
+        ::execute(INTROSPECTION_QUERY, None, &root, &Variables::new(), &database).unwrap();
+    assert_eq!(errors, vec![]);
+
+    result.to_input_value()
+}
+
+fn parse_json(json: &str) -> InputValue {
+    ::serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn reads_back_introspected_schemas() {
+    let schema = SchemaType::from_introspection(&introspect_database()).unwrap();
+    let original = SchemaType::new::<Database, EmptyMutation<Database>>(&(), &());
+
+    assert_eq!(diff_schemas(&original, &schema), vec![]);
+    assert_eq!(schema.concrete_query_type().name(), Some("Query"));
+    assert!(schema.concrete_mutation_type().is_none());
+    assert_eq!(
+        schema.directive_by_name("include").unwrap().locations,
+        vec![
+            DirectiveLocation::Field,
+            DirectiveLocation::FragmentSpread,
+            DirectiveLocation::InlineFragment,
+        ]
+    );
+}
+
+#[test]
+fn keeps_descriptions_and_deprecations() {
+    let root = RootNode::new(Query, EmptyMutation::<()>::new());
+    let (result, errors) =
+        ::execute(INTROSPECTION_QUERY, None, &root, &Variables::new(), &()).unwrap();
+    assert_eq!(errors, vec![]);
+
+    let schema = SchemaType::from_introspection(&result.to_input_value()).unwrap();
+    let query = schema.concrete_type_by_name("Query").unwrap();
+    assert_eq!(
+        query.description(),
+        Some(&"The root of all queries".to_owned())
+    );
+
+    let status = query.field_by_name("status").unwrap();
+    assert_eq!(status.description, Some("The current status".to_owned()));
+    assert_eq!(
+        status.arguments.as_ref().unwrap()[0].description,
+        Some("A year".to_owned())
+    );
+    assert_eq!(
+        query.field_by_name("active").unwrap().deprecation_reason,
+        Some("Use status".to_owned())
+    );
+
+    match *schema.concrete_type_by_name("Status").unwrap() {
+        ::schema::meta::MetaType::Enum(ref meta) => assert_eq!(
+            meta.values[1].deprecation_reason,
+            Some("Nothing retires".to_owned())
+        ),
+        _ => panic!("Status is not an enum"),
+    }
+}
+
+#[test]
+fn validates_queries_offline() {
+    let schema = SchemaType::from_introspection(&introspect_database()).unwrap();
+
+    assert_eq!(
+        validate_query(
+            r#"query ($id: String!) { hero(episode: EMPIRE) { name ... on Droid { primaryFunction } }
+                 human(id: $id) { homePlanet } }"#,
+            &schema,
+        ),
+        Ok(())
+    );
+    assert_eq!(validate_query(INTROSPECTION_QUERY, &schema), Ok(()));
+
+    for query in &[
+        "{ hero { unknownField } }",
+        "{ hero(episode: SITH) { name } }",
+        "{ human(id: 1000) { name } }",
+        "{ hero { name @skip(if: 1) } }",
+    ] {
+        match validate_query(query, &schema) {
+            Err(GraphQLError::ValidationError(ref errors)) => assert_eq!(errors.len(), 1),
+            result => panic!("Expected {} to be invalid, got {:?}", query, result),
+        }
+    }
+}
+
+#[test]
+fn adds_missing_builtin_directives() {
+    let schema = SchemaType::from_introspection(&parse_json(
+        r#"{
+            "__schema": {
+                "queryType": { "name": "Query" },
+                "types": [
+                    {
+                        "kind": "OBJECT",
+                        "name": "Query",
+                        "fields": [
+                            {
+                                "name": "name",
+                                "args": [],
+                                "type": { "kind": "SCALAR", "name": "String", "ofType": null }
+                            }
+                        ],
+                        "interfaces": []
+                    },
+                    { "kind": "SCALAR", "name": "String" },
+                    { "kind": "SCALAR", "name": "Boolean" }
+                ]
+            }
+        }"#,
+    )).unwrap();
+
+    assert_eq!(
+        validate_query("{ name @skip(if: true) }", &schema),
+        Ok(())
+    );
+}
+
+#[test]
+fn rejects_unknown_type_references() {
+    assert_eq!(
+        SchemaType::from_introspection(&parse_json(
+            r#"{
+                "data": {
+                    "__schema": {
+                        "queryType": { "name": "Query" },
+                        "types": [
+                            {
+                                "kind": "OBJECT",
+                                "name": "Query",
+                                "fields": [
+                                    {
+                                        "name": "user",
+                                        "args": [],
+                                        "type": {
+                                            "kind": "NON_NULL",
+                                            "name": null,
+                                            "ofType": { "kind": "OBJECT", "name": "User", "ofType": null }
+                                        }
+                                    }
+                                ],
+                                "interfaces": []
+                            }
+                        ]
+                    }
+                }
+            }"#,
+        )).err(),
+        Some(IntrospectionError {
+            path: "data.__schema.types[0].fields[0].type".to_owned(),
+            message: "Unknown type \"User\"".to_owned(),
+        })
+    );
+
+    assert_eq!(
+        SchemaType::from_introspection(&parse_json(r#"{ "__schema": { "types": [] } }"#)).err(),
+        Some(IntrospectionError {
+            path: "__schema".to_owned(),
+            message: "Missing field \"queryType\"".to_owned(),
+        })
+    );
+
+    assert_eq!(
+        SchemaType::from_introspection(&parse_json(
+            r#"{
+                "data": {
+                    "__schema": {
+                        "queryType": { "name": "Query" },
+                        "types": [
+                            { "kind": "SCALAR", "name": "Query" },
+                            { "kind": "SCALAR", "name": "Not-A-Name" }
+                        ]
+                    }
+                }
+            }"#,
+        )).err(),
+        Some(IntrospectionError {
+            path: "data.__schema.types[1].name".to_owned(),
+            message: "Names must match /^[_a-zA-Z][_a-zA-Z0-9]*$/ but \"Not-A-Name\" does not"
+                .to_owned(),
+        })
+    );
+}
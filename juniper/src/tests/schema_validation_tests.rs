@@ -0,0 +1,354 @@
+use std::borrow::Cow;
+
+use ast::Type;
+use executor::Registry;
+use schema::meta::{MetaType, PlaceholderMeta};
+use schema::model::{build_schema_type, RootNode, SchemaType};
+use schema::validation::SchemaError;
+use tests::model::Database;
+use types::base::GraphQLType;
+use types::scalars::EmptyMutation;
+
+fn validate<QueryT: GraphQLType<TypeInfo = ()>>() -> Vec<SchemaError> {
+    build_schema_type::<QueryT, EmptyMutation<()>>(&(), &()).validate()
+}
+
+#[test]
+fn star_wars_schema_is_valid() {
+    let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+
+    assert_eq!(schema.validate(), vec![]);
+}
+
+mod duplicate_names {
+    use super::validate;
+    use schema::validation::SchemaError;
+
+    struct First;
+    struct Second;
+    struct Query;
+
+    graphql_object!(First: () as "Thing" |&self| {
+        field first() -> i32 { 1 }
+    });
+
+    graphql_object!(Second: () as "Thing" |&self| {
+        field second() -> i32 { 2 }
+    });
+
+    graphql_object!(Query: () |&self| {
+        field first() -> First { First }
+        field second() -> Second { Second }
+        field boxed() -> Box<First> { Box::new(First) }
+    });
+
+    #[test]
+    fn reports_types_sharing_a_name() {
+        let errors = validate::<Query>();
+
+        assert_eq!(errors.len(), 1);
+        match errors[0] {
+            SchemaError::DuplicateTypeName {
+                ref name,
+                ref first,
+                ref second,
+            } => {
+                assert_eq!(name, "Thing");
+                assert!(first.ends_with("::First"));
+                assert!(second.ends_with("::Second"));
+            }
+            ref e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+}
+
+mod interfaces {
+    use super::validate;
+    use schema::validation::SchemaError;
+
+    struct Node;
+    struct Broken;
+    struct NotNode;
+    struct Query;
+
+    graphql_interface!(Node: () |&self| {
+        field id() -> i32 { 0 }
+        field name(upper: bool) -> Option<String> { None }
+
+        instance_resolvers: |_| {
+            &Broken => None::<&Broken>,
+        }
+    });
+
+    graphql_object!(Broken: () |&self| {
+        field name(upper: i32, required: bool, defaulted = false: bool) -> String {
+            String::new()
+        }
+
+        interfaces: [Node]
+    });
+
+    graphql_object!(NotNode: () |&self| {
+        field id() -> i32 { 0 }
+
+        interfaces: [Query]
+    });
+
+    graphql_object!(Query: () |&self| {
+        field node() -> Node { Node }
+        field broken() -> Broken { Broken }
+        field not_node() -> NotNode { NotNode }
+    });
+
+    #[test]
+    fn reports_invalid_implementations() {
+        let errors = validate::<Query>();
+
+        assert_eq!(
+            errors,
+            vec![
+                SchemaError::MissingInterfaceField {
+                    interface: "Node".to_owned(),
+                    path: "Broken.id".to_owned(),
+                },
+                SchemaError::InvalidInterfaceArgumentType {
+                    interface: "Node".to_owned(),
+                    path: "Broken.name(upper:)".to_owned(),
+                    expected: "Boolean!".to_owned(),
+                    found: "Int!".to_owned(),
+                },
+                SchemaError::RequiredArgumentNotInInterface {
+                    interface: "Node".to_owned(),
+                    path: "Broken.name(required:)".to_owned(),
+                },
+                SchemaError::NotAnInterface {
+                    object: "NotNode".to_owned(),
+                    interface: "Query".to_owned(),
+                },
+            ]
+        );
+    }
+}
+
+mod interface_implementations {
+    use super::validate;
+    use schema::validation::SchemaError;
+
+    struct Node;
+    struct Resource;
+    struct Image;
+    struct Loop;
+    struct Query;
+
+    graphql_interface!(Node: () |&self| {
+        field id() -> i32 { 0 }
+
+        instance_resolvers: |_| {
+            &Image => None::<&Image>,
+        }
+    });
+
+    graphql_interface!(Resource: () |&self| {
+        field id() -> i32 { 0 }
+        field url() -> String { String::new() }
+
+        interfaces: [Node]
+
+        instance_resolvers: |_| {
+            &Image => None::<&Image>,
+        }
+    });
+
+    graphql_interface!(Loop: () |&self| {
+        field id() -> i32 { 0 }
+
+        interfaces: [Loop]
+
+        instance_resolvers: |_| {
+            &Image => None::<&Image>,
+        }
+    });
+
+    graphql_object!(Image: () |&self| {
+        field id() -> i32 { 0 }
+        field url() -> String { String::new() }
+
+        interfaces: [Resource]
+    });
+
+    graphql_object!(Query: () |&self| {
+        field node() -> Node { Node }
+        field resource() -> Resource { Resource }
+        field image() -> Image { Image }
+        field loop_() -> Loop { Loop }
+    });
+
+    #[test]
+    fn reports_invalid_interface_hierarchies() {
+        let errors = validate::<Query>();
+
+        assert_eq!(
+            errors,
+            vec![
+                SchemaError::MissingTransitiveInterface {
+                    name: "Image".to_owned(),
+                    interface: "Node".to_owned(),
+                    via: "Resource".to_owned(),
+                },
+                SchemaError::SelfImplementation {
+                    name: "Loop".to_owned(),
+                },
+            ]
+        );
+    }
+}
+
+mod field_types {
+    use super::validate;
+    use schema::validation::SchemaError;
+
+    graphql_input_object!(
+        struct Point {
+            x: i32,
+        }
+    );
+
+    pub struct Query;
+
+    graphql_object!(Query: () |&self| {
+        field point() -> Point { Point { x: 0 } }
+    });
+
+    #[test]
+    fn reports_input_object_fields() {
+        let errors = validate::<Query>();
+
+        assert_eq!(
+            errors,
+            vec![
+                SchemaError::InputTypeInOutputPosition {
+                    path: "Query.point".to_owned(),
+                    name: "Point".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "Field \"Query.point\" has input object type \"Point\", but fields must have output \
+             types"
+        );
+    }
+}
+
+mod empty_types {
+    use super::validate;
+    use ast::{FromInputValue, InputValue};
+    use executor::Registry;
+    use schema::meta::MetaType;
+    use schema::validation::SchemaError;
+    use types::base::GraphQLType;
+
+    struct Nothing;
+    struct Shape;
+    struct Query;
+    struct Blank;
+
+    impl GraphQLType for Blank {
+        type Context = ();
+        type TypeInfo = ();
+
+        fn name(_: &()) -> Option<&str> {
+            Some("Blank")
+        }
+
+        fn meta<'r>(_: &(), registry: &mut Registry<'r>) -> MetaType<'r> {
+            registry.build_input_object_type::<Self>(&(), &[]).into_meta()
+        }
+    }
+
+    impl FromInputValue for Blank {
+        fn from_input_value(_: &InputValue) -> Option<Blank> {
+            Some(Blank)
+        }
+    }
+
+    graphql_object!(Nothing: () |&self| {
+    });
+
+    graphql_interface!(Shape: () |&self| {
+        instance_resolvers: |_| {
+            &Nothing => None::<&Nothing>,
+        }
+    });
+
+    graphql_object!(Query: () |&self| {
+        field nothing(blank: Option<Blank>) -> Nothing { Nothing }
+        field shape() -> Shape { Shape }
+    });
+
+    #[test]
+    fn reports_types_without_fields() {
+        assert_eq!(
+            validate::<Query>(),
+            vec![
+                SchemaError::EmptyFields {
+                    name: "Blank".to_owned(),
+                },
+                SchemaError::EmptyFields {
+                    name: "Nothing".to_owned(),
+                },
+                SchemaError::EmptyFields {
+                    name: "Shape".to_owned(),
+                },
+            ]
+        );
+    }
+}
+
+struct Unresolved;
+
+impl GraphQLType for Unresolved {
+    type Context = ();
+    type TypeInfo = ();
+
+    fn name(_: &()) -> Option<&str> {
+        Some("Unresolved")
+    }
+
+    fn meta<'r>(_: &(), registry: &mut Registry<'r>) -> MetaType<'r> {
+        registry.types.insert(
+            "Ghost".parse().unwrap(),
+            MetaType::Placeholder(PlaceholderMeta {
+                of_type: Type::NonNullNamed(Cow::Borrowed("Ghost")),
+            }),
+        );
+
+        let fields = &[registry.field::<i32>("value", &())];
+        registry.build_object_type::<Self>(&(), fields).into_meta()
+    }
+}
+
+#[test]
+fn reports_unresolved_placeholders() {
+    assert_eq!(
+        validate::<Unresolved>(),
+        vec![
+            SchemaError::UnresolvedPlaceholder {
+                name: "Ghost".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Type NonNullNamed(\"Ghost\") is still a placeholder type")]
+fn schema_type_rejects_unresolved_placeholders() {
+    SchemaType::new::<Unresolved, EmptyMutation<()>>(&(), &());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Invalid schema")]
+fn root_node_rejects_invalid_schemas() {
+    RootNode::new(field_types::Query, EmptyMutation::<()>::new());
+}
@@ -0,0 +1,228 @@
+use std::sync::mpsc::channel;
+
+use serde_json;
+use serde_json::Value as Json;
+
+use ast::InputValue;
+use schema::model::RootNode;
+use tests::model::Database;
+use types::scalars::EmptyMutation;
+use websocket::{Connection, Frame, Protocol};
+
+/// Sends the messages over an in-memory channel to a connection, and returns
+/// the frames it sent back, with text frames parsed as JSON
+fn exchange(protocol: Protocol, messages: &[&str]) -> Vec<Result<Json, (u16, String)>> {
+    let database = Database::new();
+    let schema = RootNode::new(&database, EmptyMutation::<Database>::new());
+    let mut connection = Connection::new(protocol, &schema, |payload: Option<InputValue>| {
+        let token = payload
+            .as_ref()
+            .and_then(|p| p.to_object_value())
+            .and_then(|p| p.get("token").and_then(|t| t.as_string_value()).map(|t| t.to_owned()));
+
+        match token.as_ref().map(|t| t.as_str()) {
+            Some("invalid") => Err("Invalid token".to_owned()),
+            _ => Ok(Database::new()),
+        }
+    });
+
+    let (client, server) = channel::<String>();
+    let (server_out, client_in) = channel::<Frame>();
+
+    for message in messages {
+        client.send(message.to_string()).unwrap();
+    }
+    drop(client);
+
+    for text in server.iter() {
+        for frame in connection.handle(&text) {
+            server_out.send(frame).unwrap();
+        }
+    }
+    drop(server_out);
+
+    client_in
+        .iter()
+        .map(|frame| match frame {
+            Frame::Text(text) => Ok(serde_json::from_str(&text).expect("Invalid JSON message")),
+            Frame::Close(code, reason) => Err((code, reason)),
+        })
+        .collect()
+}
+
+fn json(text: &str) -> Result<Json, (u16, String)> {
+    Ok(serde_json::from_str(text).expect("Invalid JSON constant in test"))
+}
+
+fn close(code: u16, reason: &str) -> Result<Json, (u16, String)> {
+    Err((code, reason.to_owned()))
+}
+
+#[test]
+fn transport_ws_executes_operations() {
+    assert_eq!(
+        exchange(
+            Protocol::GraphQLTransportWs,
+            &[
+                r#"{"type": "connection_init", "payload": {"token": "valid"}}"#,
+                r#"{"type": "ping"}"#,
+                r#"{"type": "subscribe", "id": "1", "payload": {"query": "{ hero { name } }"}}"#,
+                r#"{"type": "subscribe", "id": "2", "payload": {
+                    "query": "query($id: String!) { human(id: $id) { name } }",
+                    "variables": {"id": "1000"}
+                }}"#,
+                r#"{"type": "complete", "id": "1"}"#,
+                r#"{"type": "pong"}"#,
+            ]
+        ),
+        vec![
+            json(r#"{"type": "connection_ack"}"#),
+            json(r#"{"type": "pong"}"#),
+            json(r#"{"type": "next", "id": "1", "payload": {"data": {"hero": {"name": "R2-D2"}}}}"#),
+            json(r#"{"type": "complete", "id": "1"}"#),
+            json(r#"{"type": "next", "id": "2", "payload": {"data": {"human": {"name": "Luke Skywalker"}}}}"#),
+            json(r#"{"type": "complete", "id": "2"}"#),
+        ]
+    );
+}
+
+#[test]
+fn transport_ws_reports_request_errors() {
+    assert_eq!(
+        exchange(
+            Protocol::GraphQLTransportWs,
+            &[
+                r#"{"type": "connection_init"}"#,
+                r#"{"type": "subscribe", "id": "1", "payload": {"query": "{ unknown }"}}"#,
+                r#"{"type": "subscribe", "id": "2", "payload": {"query": ""}}"#,
+            ]
+        ),
+        vec![
+            json(r#"{"type": "connection_ack"}"#),
+            json(
+                r#"{"type": "error", "id": "1", "payload": [{
+                    "message": "Unknown field \"unknown\" on type \"Query\"",
+                    "locations": [{"line": 1, "column": 3}]
+                }]}"#
+            ),
+            json(r#"{"type": "error", "id": "2", "payload": [{"message": "Missing query"}]}"#),
+        ]
+    );
+}
+
+#[test]
+fn transport_ws_closes_on_protocol_violations() {
+    let subscribe = r#"{"type": "subscribe", "id": "1", "payload": {"query": "{ hero { name } }"}}"#;
+
+    assert_eq!(
+        exchange(Protocol::GraphQLTransportWs, &[subscribe]),
+        vec![close(4401, "Unauthorized")]
+    );
+    assert_eq!(
+        exchange(
+            Protocol::GraphQLTransportWs,
+            &[r#"{"type": "connection_init"}"#, r#"{"type": "connection_init"}"#]
+        ),
+        vec![
+            json(r#"{"type": "connection_ack"}"#),
+            close(4429, "Too many initialisation requests"),
+        ]
+    );
+    assert_eq!(
+        exchange(
+            Protocol::GraphQLTransportWs,
+            &[r#"{"type": "connection_init", "payload": {"token": "invalid"}}"#]
+        ),
+        vec![close(4403, "Forbidden")]
+    );
+    assert_eq!(
+        exchange(Protocol::GraphQLTransportWs, &["not json", r#"{"type": "start"}"#]),
+        vec![
+            close(4400, "Invalid message"),
+            close(4400, "Invalid message type \"start\""),
+        ]
+    );
+    assert_eq!(
+        exchange(
+            Protocol::GraphQLTransportWs,
+            &[r#"{"type": "connection_init"}"#, r#"{"type": "subscribe", "id": "1"}"#]
+        ),
+        vec![
+            json(r#"{"type": "connection_ack"}"#),
+            close(4400, "Invalid message"),
+        ]
+    );
+}
+
+#[test]
+fn legacy_ws_executes_operations() {
+    assert_eq!(
+        exchange(
+            Protocol::GraphQLWs,
+            &[
+                r#"{"type": "connection_init", "payload": {}}"#,
+                r#"{"type": "start", "id": "1", "payload": {"query": "{ hero { name } }"}}"#,
+                r#"{"type": "start", "id": "2", "payload": {"query": "{ unknown }"}}"#,
+                r#"{"type": "stop", "id": "1"}"#,
+                r#"{"type": "connection_terminate"}"#,
+            ]
+        ),
+        vec![
+            json(r#"{"type": "connection_ack"}"#),
+            json(r#"{"type": "ka"}"#),
+            json(r#"{"type": "data", "id": "1", "payload": {"data": {"hero": {"name": "R2-D2"}}}}"#),
+            json(r#"{"type": "complete", "id": "1"}"#),
+            json(
+                r#"{"type": "data", "id": "2", "payload": {"errors": [{
+                    "message": "Unknown field \"unknown\" on type \"Query\"",
+                    "locations": [{"line": 1, "column": 3}]
+                }]}}"#
+            ),
+            json(r#"{"type": "complete", "id": "2"}"#),
+            close(1000, ""),
+        ]
+    );
+}
+
+#[test]
+fn legacy_ws_reports_connection_errors() {
+    assert_eq!(
+        exchange(
+            Protocol::GraphQLWs,
+            &[
+                r#"{"type": "start", "id": "1", "payload": {"query": "{ hero { name } }"}}"#,
+                r#"{"type": "connection_init", "payload": {"token": "invalid"}}"#,
+            ]
+        ),
+        vec![
+            json(r#"{"type": "error", "id": "1", "payload": {"message": "Connection not initialized"}}"#),
+            json(r#"{"type": "connection_error", "payload": {"message": "Invalid token"}}"#),
+            close(1011, "Invalid token"),
+        ]
+    );
+}
+
+#[test]
+fn negotiates_protocols() {
+    assert_eq!(
+        Protocol::from_subprotocol("graphql-transport-ws"),
+        Some(Protocol::GraphQLTransportWs)
+    );
+    assert_eq!(Protocol::from_subprotocol("graphql-ws"), Some(Protocol::GraphQLWs));
+    assert_eq!(Protocol::from_subprotocol("graphql"), None);
+    assert_eq!(Protocol::GraphQLWs.subprotocol(), "graphql-ws");
+}
+
+#[test]
+fn sends_keep_alive_messages() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, EmptyMutation::<Database>::new());
+
+    let transport_ws = Connection::new(Protocol::GraphQLTransportWs, &schema, |_| Ok(Database::new()));
+    assert_eq!(transport_ws.keep_alive(), Some(Frame::Text(r#"{"type":"ping"}"#.to_owned())));
+
+    let mut legacy = Connection::new(Protocol::GraphQLWs, &schema, |_| Ok(Database::new()));
+    assert_eq!(legacy.keep_alive(), None);
+    legacy.handle(r#"{"type": "connection_init"}"#);
+    assert_eq!(legacy.keep_alive(), Some(Frame::Text(r#"{"type":"ka"}"#.to_owned())));
+}
@@ -1,13 +1,16 @@
 use ordermap::OrderMap;
 use ordermap::Entry;
 
-use ast::{Directive, FromInputValue, InputValue, Selection};
+use ast::{Directive, Field, FromInputValue, InlineFragment, InputValue, InputValueError, Selection};
 use executor::Variables;
 use value::Value;
 
 use schema::meta::{Argument, MetaType};
-use executor::{ExecutionResult, Executor, Registry};
-use parser::Spanning;
+use schema::model::SchemaType;
+use executor::{ExecutionResult, Executor, FieldError, Registry};
+use parser::{SourcePosition, Spanning};
+use stream::may_become_null;
+use types::utilities::validate_argument_value;
 
 /// GraphQL type kind
 ///
@@ -99,19 +102,54 @@ impl<'a> Arguments<'a> {
     /// If the argument is found, or a default argument has been provided,
     /// the `InputValue` will be converted into the type `T`.
     ///
-    /// Returns `Some` if the argument is present _and_ type conversion
-    /// succeeeds.
+    /// Returns `None` if the argument is not present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value can't be converted to `T`. Validation rules this
+    /// out for arguments of the type the field declares; use `try_get` for
+    /// any other type.
     pub fn get<T>(&self, key: &str) -> Option<T>
+    where
+        T: FromInputValue,
+    {
+        self.try_get(key)
+            .unwrap_or_else(|e| panic!(r#"Invalid value for argument "{}": {}"#, key, e))
+    }
+
+    /// Get and convert an argument, describing why the conversion failed.
+    ///
+    /// Returns `Ok(None)` if the argument is not present.
+    pub fn try_get<T>(&self, key: &str) -> Result<Option<T>, InputValueError>
     where
         T: FromInputValue,
     {
         match self.args {
             Some(ref args) => match args.get(key) {
-                Some(v) => Some(v.convert().unwrap()),
-                None => None,
+                Some(v) => v.try_convert().map(Some),
+                None => Ok(None),
             },
-            None => None,
+            None => Ok(None),
+        }
+    }
+
+    fn validate(
+        &self,
+        schema: &SchemaType,
+        meta_args: &Option<Vec<Argument>>,
+    ) -> Result<(), FieldError> {
+        if let (&Some(ref args), &Some(ref meta_args)) = (&self.args, meta_args) {
+            for meta_arg in meta_args {
+                if let Some(value) = args.get(meta_arg.name.as_str()) {
+                    try!(
+                        validate_argument_value(schema, meta_arg, value)
+                            .map_err(|e| e.into_argument_error(&meta_arg.name))
+                    );
+                }
+            }
         }
+
+        Ok(())
     }
 }
 
@@ -241,6 +279,26 @@ pub trait GraphQLType: Sized {
     /// The meta type representing this GraphQL type.
     fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r>;
 
+    /// The name of this type in the names of generic types using it as a
+    /// type parameter
+    ///
+    /// Named types use their name, while lists and nullable types spell out
+    /// their wrappers, e.g. `ListOfNullableString` for `Vec<Option<String>>`.
+    #[doc(hidden)]
+    fn type_param_name(info: &Self::TypeInfo) -> Option<String> {
+        Self::name(info).map(|name| name.to_owned())
+    }
+
+    /// The name of the Rust type backing this GraphQL type
+    ///
+    /// Used to detect different Rust types registering the same GraphQL
+    /// type name. Types exposing another type as-is, like `Box<T>`, return
+    /// the name of the type they wrap.
+    #[doc(hidden)]
+    fn rust_type_name() -> &'static str {
+        ::std::any::type_name::<Self>()
+    }
+
     /// Resolve the value of a single field on this type.
     ///
     /// The arguments object contain all specified arguments, with default
@@ -316,6 +374,29 @@ pub trait GraphQLType: Sized {
             panic!("resolve() must be implemented by non-object output types");
         }
     }
+
+    /// Resolve the provided selection set, writing the result to the
+    /// executor's response stream
+    ///
+    /// This is used instead of `resolve` when streaming the response with
+    /// `execute_to_writer`. Returns `false` if the value is `null`, in which
+    /// case nothing, or an incomplete value that will be discarded, has been
+    /// written.
+    ///
+    /// The default implementation writes the value returned by `resolve`.
+    /// Object types can use `stream_selection_set` instead, to write fields
+    /// as they are resolved.
+    #[doc(hidden)]
+    fn stream(
+        &self,
+        info: &Self::TypeInfo,
+        selection_set: Option<&[Selection]>,
+        executor: &Executor<Self::Context>,
+    ) -> bool {
+        let stream = executor.response_stream();
+        let value = stream.suspend(|| self.resolve(info, selection_set, executor));
+        stream.write_value(&value)
+    }
 }
 
 fn resolve_selection_set_into<T, CtxT>(
@@ -338,71 +419,13 @@ fn resolve_selection_set_into<T, CtxT>(
 
     for selection in selection_set {
         match *selection {
-            Selection::Field(Spanning {
-                item: ref f,
-                start: ref start_pos,
-                ..
-            }) => {
-                if is_excluded(&f.directives, executor.variables()) {
+            Selection::Field(ref field) => {
+                if is_excluded(&field.item.directives, executor.variables()) {
                     continue;
                 }
 
-                let response_name = &f.alias.as_ref().unwrap_or(&f.name).item;
-
-                if f.name.item == "__typename" {
-                    result.insert(
-                        (*response_name).to_owned(),
-                        Value::string(instance.concrete_type_name(executor.context())),
-                    );
-                    continue;
-                }
-
-                let meta_field = meta_type.field_by_name(f.name.item).unwrap_or_else(|| {
-                    panic!(format!(
-                        "Field {} not found on type {:?}",
-                        f.name.item,
-                        meta_type.name()
-                    ))
-                });
-
-                let exec_vars = executor.variables();
-
-                let sub_exec = executor.field_sub_executor(
-                    response_name,
-                    &f.name.item,
-                    start_pos.clone(),
-                    f.selection_set.as_ref().map(|v| &v[..]),
-                );
-
-                let field_result = instance.resolve_field(
-                    info,
-                    f.name.item,
-                    &Arguments::new(
-                        f.arguments.as_ref().map(|m| {
-                            m.item
-                                .iter()
-                                .map(|&(ref k, ref v)| {
-                                    (k.item, v.item.clone().into_const(exec_vars))
-                                })
-                                .collect()
-                        }),
-                        &meta_field.arguments,
-                    ),
-                    &sub_exec,
-                );
-
-                match field_result {
-                    Ok(Value::Null) if meta_field.field_type.is_non_null() => return false,
-                    Ok(v) => merge_key_into(result, response_name, v),
-                    Err(e) => {
-                        sub_exec.push_error_at(e, start_pos.clone());
-
-                        if meta_field.field_type.is_non_null() {
-                            return false;
-                        }
-
-                        result.insert((*response_name).to_owned(), Value::null());
-                    }
+                if !merge_resolved(result, resolve_field(instance, info, meta_type, field, executor)) {
+                    return false;
                 }
             }
             Selection::FragmentSpread(Spanning {
@@ -435,26 +458,14 @@ fn resolve_selection_set_into<T, CtxT>(
                     continue;
                 }
 
-                let sub_exec = executor.type_sub_executor(
-                    fragment.type_condition.as_ref().map(|c| c.item),
-                    Some(&fragment.selection_set[..]));
-
-                if let Some(ref type_condition) = fragment.type_condition {
-                    let sub_result = instance.resolve_into_type(
-                        info,
-                        type_condition.item,
-                        Some(&fragment.selection_set[..]),
-                        &sub_exec,
+                if fragment.type_condition.is_some() {
+                    merge_resolved(
+                        result,
+                        resolve_inline_fragment(instance, info, fragment, start_pos, executor),
                     );
-
-                    if let Ok(Value::Object(mut hash_map)) = sub_result {
-                        for (k, v) in hash_map.drain(..) {
-                            result.insert(k, v);
-                        }
-                    } else if let Err(e) = sub_result {
-                        sub_exec.push_error_at(e, start_pos.clone());
-                    }
                 } else {
+                    let sub_exec = executor.type_sub_executor(None, Some(&fragment.selection_set[..]));
+
                     if !resolve_selection_set_into(
                         instance,
                         info,
@@ -472,6 +483,424 @@ fn resolve_selection_set_into<T, CtxT>(
     true
 }
 
+/// Resolve the fields of an object on the thread pool
+///
+/// Fragments on the object's own type are flattened first, so that all
+/// fields resolve concurrently. Each field and everything below it is then
+/// resolved on a single thread. The results are merged in the order of the
+/// selection set, which keeps the order of keys in the response the same as
+/// in serial execution.
+#[cfg(feature = "parallel")]
+pub fn resolve_selection_set_in_parallel<T, CtxT>(
+    instance: &T,
+    info: &T::TypeInfo,
+    selection_set: &[Selection],
+    executor: &Executor<CtxT>,
+) -> Value where
+    T: GraphQLType<Context = CtxT> + Sync,
+    T::TypeInfo: Sync,
+    CtxT: Sync,
+{
+    use rayon::prelude::*;
+
+    let meta_type = executor
+        .schema()
+        .concrete_type_by_name(
+            T::name(info)
+                .expect("Resolving named type's selection set")
+                .as_ref(),
+        )
+        .expect("Type not found in schema");
+
+    let mut selections = Vec::new();
+    collect_selections(selection_set, executor, &mut selections);
+
+    let resolved = selections
+        .par_iter()
+        .map(|selection| match **selection {
+            Selection::Field(ref field) => resolve_field(instance, info, meta_type, field, executor),
+            Selection::InlineFragment(Spanning {
+                item: ref fragment,
+                start: ref start_pos,
+                ..
+            }) => resolve_inline_fragment(instance, info, fragment, start_pos, executor),
+            Selection::FragmentSpread(_) => unreachable!("Fragment spreads are flattened"),
+        })
+        .collect::<Vec<_>>();
+
+    let mut result = OrderMap::new();
+    if resolved
+        .into_iter()
+        .all(|resolved| merge_resolved(&mut result, resolved))
+    {
+        Value::object(result)
+    } else {
+        Value::null()
+    }
+}
+
+/// Collect the fields and the inline fragments with a type condition of a
+/// selection set, flattening fragment spreads and other inline fragments
+#[cfg(feature = "parallel")]
+fn collect_selections<'a, CtxT>(
+    selection_set: &'a [Selection<'a>],
+    executor: &Executor<'a, CtxT>,
+    selections: &mut Vec<&'a Selection<'a>>,
+) {
+    for selection in selection_set {
+        match *selection {
+            Selection::Field(ref field) => {
+                if !is_excluded(&field.item.directives, executor.variables()) {
+                    selections.push(selection);
+                }
+            }
+            Selection::FragmentSpread(Spanning {
+                item: ref spread, ..
+            }) => {
+                if is_excluded(&spread.directives, executor.variables()) {
+                    continue;
+                }
+
+                let fragment = executor
+                    .fragment_by_name(spread.name.item)
+                    .expect("Fragment could not be found");
+
+                collect_selections(&fragment.selection_set[..], executor, selections);
+            }
+            Selection::InlineFragment(Spanning {
+                item: ref fragment,
+                ..
+            }) => {
+                if is_excluded(&fragment.directives, executor.variables()) {
+                    continue;
+                }
+
+                if fragment.type_condition.is_some() {
+                    selections.push(selection);
+                } else {
+                    collect_selections(&fragment.selection_set[..], executor, selections);
+                }
+            }
+        }
+    }
+}
+
+/// The outcome of resolving a single selection of an object
+enum Resolved<'a> {
+    /// The value of a field, merged with earlier fields of the same name
+    Field(&'a str, Value),
+    /// A nullable field that failed and has already reported its error
+    Failed(&'a str),
+    /// The fields of a fragment on a more specific type
+    Fragment(OrderMap<String, Value>),
+    /// A non-null field that resolved to null, which nulls the object
+    Null,
+}
+
+fn resolve_field<'a, T, CtxT>(
+    instance: &T,
+    info: &T::TypeInfo,
+    meta_type: &MetaType,
+    field: &'a Spanning<Field<'a>>,
+    executor: &Executor<CtxT>,
+) -> Resolved<'a> where
+    T: GraphQLType<Context = CtxT>,
+{
+    let f = &field.item;
+    let start_pos = &field.start;
+    let response_name = response_name(f);
+
+    if f.name.item == "__typename" {
+        return Resolved::Field(
+            response_name,
+            Value::string(instance.concrete_type_name(executor.context())),
+        );
+    }
+
+    with_field_resolver(instance, info, meta_type, field, executor, |sub_exec, is_non_null, resolve| {
+        // A null in a non-null field makes the whole object null, which
+        // in turn propagates to the nearest nullable parent
+        let field_result = if is_non_null {
+            sub_exec.resolve_non_null(resolve)
+        } else {
+            resolve()
+        };
+
+        match field_result {
+            Ok(Value::Null) if is_non_null => Resolved::Null,
+            Ok(v) => Resolved::Field(response_name, v),
+            Err(e) => {
+                sub_exec.push_error_at(e, start_pos.clone());
+
+                if is_non_null {
+                    Resolved::Null
+                } else {
+                    Resolved::Failed(response_name)
+                }
+            }
+        }
+    })
+}
+
+/// Set up the sub-executor and the arguments of a field, other than
+/// `__typename`, and pass them to `complete`
+///
+/// `complete` receives the sub-executor, whether the field is non-null, and
+/// a function that validates the arguments and calls the field's resolver.
+/// Both the value and the streaming paths resolve fields through this.
+fn with_field_resolver<'a, T, CtxT, R, F>(
+    instance: &T,
+    info: &T::TypeInfo,
+    meta_type: &MetaType,
+    field: &'a Spanning<Field<'a>>,
+    executor: &Executor<CtxT>,
+    complete: F,
+) -> R where
+    T: GraphQLType<Context = CtxT>,
+    F: FnOnce(&Executor<CtxT>, bool, &Fn() -> ExecutionResult) -> R,
+{
+    let f = &field.item;
+
+    let meta_field = meta_type.field_by_name(f.name.item).unwrap_or_else(|| {
+        panic!(format!(
+            "Field {} not found on type {:?}",
+            f.name.item,
+            meta_type.name()
+        ))
+    });
+
+    let exec_vars = executor.variables();
+
+    let sub_exec = executor.field_sub_executor(
+        response_name(f),
+        &f.name.item,
+        field.start.clone(),
+        f.selection_set.as_ref().map(|v| &v[..]),
+    );
+
+    let args = Arguments::new(
+        f.arguments.as_ref().map(|m| {
+            m.item
+                .iter()
+                .map(|&(ref k, ref v)| (k.item, v.item.clone().into_const(exec_vars)))
+                .collect()
+        }),
+        &meta_field.arguments,
+    );
+
+    let resolve = || match args.validate(executor.schema(), &meta_field.arguments) {
+        Ok(()) => instance.resolve_field(info, f.name.item, &args, &sub_exec),
+        Err(e) => Err(e),
+    };
+
+    complete(&sub_exec, meta_field.field_type.is_non_null(), &resolve)
+}
+
+fn resolve_inline_fragment<'a, T, CtxT>(
+    instance: &T,
+    info: &T::TypeInfo,
+    fragment: &'a InlineFragment<'a>,
+    start_pos: &SourcePosition,
+    executor: &Executor<'a, CtxT>,
+) -> Resolved<'a> where
+    T: GraphQLType<Context = CtxT>,
+{
+    let type_condition = fragment
+        .type_condition
+        .as_ref()
+        .expect("Inline fragment without a type condition");
+
+    let sub_exec = executor.type_sub_executor(
+        Some(type_condition.item),
+        Some(&fragment.selection_set[..]));
+
+    let sub_result = instance.resolve_into_type(
+        info,
+        type_condition.item,
+        Some(&fragment.selection_set[..]),
+        &sub_exec,
+    );
+
+    match sub_result {
+        Ok(Value::Object(fields)) => Resolved::Fragment(fields),
+        Ok(_) => Resolved::Fragment(OrderMap::new()),
+        Err(e) => {
+            sub_exec.push_error_at(e, start_pos.clone());
+            Resolved::Fragment(OrderMap::new())
+        }
+    }
+}
+
+fn merge_resolved(result: &mut OrderMap<String, Value>, resolved: Resolved) -> bool {
+    match resolved {
+        Resolved::Field(response_name, value) => merge_key_into(result, response_name, value),
+        Resolved::Failed(response_name) => {
+            result.insert(response_name.to_owned(), Value::null());
+        }
+        Resolved::Fragment(fields) => for (k, v) in fields {
+            result.insert(k, v);
+        },
+        Resolved::Null => return false,
+    }
+
+    true
+}
+
+/// Write the fields in the selection set of an object to the response
+/// stream as they are resolved
+#[doc(hidden)]
+pub fn stream_selection_set<T, CtxT>(
+    instance: &T,
+    info: &T::TypeInfo,
+    selection_set: Option<&[Selection]>,
+    executor: &Executor<CtxT>,
+) -> bool
+where
+    T: GraphQLType<Context = CtxT>,
+{
+    let stream = executor.response_stream();
+    let selection_set = selection_set.expect("Streaming object without a selection set");
+    let type_name = T::name(info).expect("Streaming named type's selection set");
+
+    let mut fields = Vec::new();
+    if !collect_fields(type_name, selection_set, executor, &mut fields) {
+        // Fields that have to be merged, or fragments on other types, need
+        // the complete value of the object
+        let value = stream.suspend(|| instance.resolve(info, Some(selection_set), executor));
+        return stream.write_value(&value);
+    }
+
+    let meta_type = executor
+        .schema()
+        .concrete_type_by_name(type_name)
+        .expect("Type not found in schema");
+
+    stream.write(b"{");
+
+    for (i, field) in fields.into_iter().enumerate() {
+        let f = &field.item;
+        let start_pos = &field.start;
+
+        stream.write_key(response_name(f), i == 0);
+
+        if f.name.item == "__typename" {
+            stream.write_json(&instance.concrete_type_name(executor.context()));
+            continue;
+        }
+
+        let is_complete = with_field_resolver(instance, info, meta_type, field, executor, |sub_exec, is_non_null, resolve| {
+            // Output that might still turn into null is buffered, unless a
+            // null would propagate to the parent anyway
+            let is_buffered = !is_non_null && may_become_null(sub_exec.current_type(), sub_exec);
+            if is_buffered {
+                stream.begin_buffer();
+            }
+
+            let error_count = executor.error_count();
+            stream.take_streamed();
+
+            let written = match resolve() {
+                Ok(v) => stream.take_streamed().unwrap_or_else(|| stream.write_value(&v)),
+                Err(e) => {
+                    sub_exec.push_error_at(e, start_pos.clone());
+                    false
+                }
+            };
+
+            if is_non_null {
+                if !written && !sub_exec.has_errors_since(error_count) {
+                    sub_exec.push_error_at(sub_exec.non_null_error(), start_pos.clone());
+                }
+                written
+            } else {
+                if is_buffered {
+                    stream.end_buffer(written);
+                }
+                if !written {
+                    stream.write(b"null");
+                }
+                true
+            }
+        });
+
+        if !is_complete {
+            return false;
+        }
+    }
+
+    stream.write(b"}");
+
+    true
+}
+
+/// Collect the fields of a selection set in response order
+///
+/// Returns `false` if a response name occurs more than once, or if an inline
+/// fragment applies to another type.
+#[doc(hidden)]
+pub fn collect_fields<'a, CtxT>(
+    type_name: &str,
+    selection_set: &'a [Selection<'a>],
+    executor: &Executor<'a, CtxT>,
+    fields: &mut Vec<&'a Spanning<Field<'a>>>,
+) -> bool {
+    for selection in selection_set {
+        match *selection {
+            Selection::Field(ref field) => {
+                if is_excluded(&field.item.directives, executor.variables()) {
+                    continue;
+                }
+
+                let name = response_name(&field.item);
+                if fields.iter().any(|f| response_name(&f.item) == name) {
+                    return false;
+                }
+
+                fields.push(field);
+            }
+            Selection::FragmentSpread(Spanning {
+                item: ref spread, ..
+            }) => {
+                if is_excluded(&spread.directives, executor.variables()) {
+                    continue;
+                }
+
+                let fragment = executor
+                    .fragment_by_name(spread.name.item)
+                    .expect("Fragment could not be found");
+
+                if !collect_fields(type_name, &fragment.selection_set[..], executor, fields) {
+                    return false;
+                }
+            }
+            Selection::InlineFragment(Spanning {
+                item: ref fragment,
+                ..
+            }) => {
+                if is_excluded(&fragment.directives, executor.variables()) {
+                    continue;
+                }
+
+                if let Some(ref type_condition) = fragment.type_condition {
+                    if type_condition.item != type_name {
+                        return false;
+                    }
+                }
+
+                if !collect_fields(type_name, &fragment.selection_set[..], executor, fields) {
+                    return false;
+                }
+            }
+        }
+    }
+
+    true
+}
+
+fn response_name<'a>(field: &Field<'a>) -> &'a str {
+    field.alias.as_ref().unwrap_or(&field.name).item
+}
+
 fn is_excluded(directives: &Option<Vec<Spanning<Directive>>>, vars: &Variables) -> bool {
     if let Some(ref directives) = *directives {
         for &Spanning {
@@ -1,9 +1,10 @@
-use ast::{FromInputValue, InputValue, Selection, ToInputValue};
+use ast::{FromInputValue, InputValue, InputValueError, Selection, ToInputValue};
 use value::Value;
 use schema::meta::MetaType;
 
 use executor::{Executor, Registry};
 use types::base::GraphQLType;
+use stream::may_become_null;
 
 impl<T, CtxT> GraphQLType for Option<T>
 where
@@ -16,6 +17,10 @@ where
         None
     }
 
+    fn type_param_name(info: &T::TypeInfo) -> Option<String> {
+        T::type_param_name(info).map(|name| format!("Nullable{}", name))
+    }
+
     fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r> {
         registry.build_nullable_type::<T>(info).into_meta()
     }
@@ -31,6 +36,18 @@ where
             None => Value::null(),
         }
     }
+
+    fn stream(
+        &self,
+        info: &T::TypeInfo,
+        _: Option<&[Selection]>,
+        executor: &Executor<CtxT>,
+    ) -> bool {
+        match *self {
+            Some(ref obj) => executor.stream(info, obj),
+            None => false,
+        }
+    }
 }
 
 impl<T> FromInputValue for Option<T>
@@ -46,6 +63,13 @@ where
             },
         }
     }
+
+    fn try_from_input_value(v: &InputValue) -> Result<Option<T>, InputValueError> {
+        match v {
+            &InputValue::Null => Ok(None),
+            v => v.try_convert().map(Some),
+        }
+    }
 }
 
 impl<T> ToInputValue for Option<T>
@@ -71,6 +95,10 @@ where
         None
     }
 
+    fn type_param_name(info: &T::TypeInfo) -> Option<String> {
+        T::type_param_name(info).map(|name| format!("ListOf{}", name))
+    }
+
     fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r> {
         registry.build_list_type::<T>(info).into_meta()
     }
@@ -83,6 +111,15 @@ where
     ) -> Value {
         resolve_into_list(executor, info, self.iter())
     }
+
+    fn stream(
+        &self,
+        info: &T::TypeInfo,
+        _: Option<&[Selection]>,
+        executor: &Executor<CtxT>,
+    ) -> bool {
+        stream_list(executor, info, self.iter())
+    }
 }
 
 impl<T> FromInputValue for Vec<T>
@@ -107,6 +144,16 @@ where
             },
         }
     }
+
+    fn try_from_input_value(v: &InputValue) -> Result<Vec<T>, InputValueError> {
+        match *v {
+            InputValue::List(ref ls) => ls.iter()
+                .enumerate()
+                .map(|(i, item)| item.item.try_convert().map_err(|e| e.in_element(i)))
+                .collect(),
+            ref other => other.try_convert().map(|e| vec![e]),
+        }
+    }
 }
 
 impl<T> ToInputValue for Vec<T>
@@ -129,6 +176,10 @@ where
         None
     }
 
+    fn type_param_name(info: &T::TypeInfo) -> Option<String> {
+        T::type_param_name(info).map(|name| format!("ListOf{}", name))
+    }
+
     fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r> {
         registry.build_list_type::<T>(info).into_meta()
     }
@@ -141,6 +192,15 @@ where
     ) -> Value {
         resolve_into_list(executor, info, self.iter())
     }
+
+    fn stream(
+        &self,
+        info: &T::TypeInfo,
+        _: Option<&[Selection]>,
+        executor: &Executor<CtxT>,
+    ) -> bool {
+        stream_list(executor, info, self.iter())
+    }
 }
 
 impl<'a, T> ToInputValue for &'a [T]
@@ -159,14 +219,77 @@ fn resolve_into_list<T: GraphQLType, I: Iterator<Item=T>>(executor: &Executor<T:
 
     let mut result = Vec::new();
 
-    for o in iter {
-        let value = executor.resolve_into_value(info, &o);
-        if stop_on_null && value.is_null() {
-            return value;
+    for (i, o) in iter.enumerate() {
+        match resolve_list_item(executor, info, i, &o, stop_on_null) {
+            Some(value) => result.push(value),
+            None => return Value::null(),
         }
-
-        result.push(value);
     }
 
     Value::list(result)
-}
\ No newline at end of file
+}
+
+/// Resolve a single item of a list, returning `None` if the item makes the
+/// whole list null
+fn resolve_list_item<T: GraphQLType>(executor: &Executor<T::Context>, info: &T::TypeInfo, index: usize, item: &T, stop_on_null: bool) -> Option<Value> {
+    let executor = &executor.index_sub_executor(index);
+
+    if stop_on_null {
+        // A null item in a list of non-null items makes the list null
+        match executor.resolve_non_null(|| executor.resolve(info, item)) {
+            Ok(Value::Null) => None,
+            Ok(value) => Some(value),
+            Err(e) => {
+                executor.push_error(e);
+                None
+            }
+        }
+    } else {
+        Some(executor.resolve_into_value(info, item))
+    }
+}
+
+fn stream_list<T: GraphQLType, I: Iterator<Item=T>>(executor: &Executor<T::Context>, info: &T::TypeInfo, iter: I) -> bool {
+    let stream = executor.response_stream();
+    let item_type = executor.current_type()
+        .list_contents().expect("Current type is not a list type");
+    let stop_on_null = item_type.is_non_null();
+    let is_buffered = !stop_on_null && may_become_null(item_type, executor);
+
+    stream.write(b"[");
+
+    for (i, o) in iter.enumerate() {
+        if i > 0 {
+            stream.write(b",");
+        }
+
+        if is_buffered {
+            stream.begin_buffer();
+        }
+
+        let executor = &executor.index_sub_executor(i);
+        let error_count = executor.error_count();
+        let written = executor.stream(info, &o);
+
+        if stop_on_null {
+            // A null item in a list of non-null items makes the list null
+            if !written {
+                if !executor.has_errors_since(error_count) {
+                    executor.push_error(executor.non_null_error());
+                }
+                return false;
+            }
+        } else {
+            if is_buffered {
+                stream.end_buffer(written);
+            }
+            if !written {
+                stream.write(b"null");
+            }
+        }
+    }
+
+    stream.write(b"]");
+
+    true
+}
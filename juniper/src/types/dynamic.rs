@@ -0,0 +1,1054 @@
+/*!
+Schemas defined at runtime
+
+Instead of exposing a Rust type for every GraphQL type, a `SchemaBuilder`
+collects type definitions at runtime, e.g. from a configuration file, and
+builds a `RootNode` that can be used with `juniper::execute` like any other.
+
+All values in a dynamic schema are `Value`s. A field is resolved by calling
+its resolver with the value of the parent object, or, if it has no resolver,
+by looking up the field's name in the parent value. Interfaces and unions
+determine the concrete type of a value with their `resolve_type` function,
+or by looking up the `__typename` key of the value.
+
+```rust
+use juniper::{execute, Value, Variables};
+use juniper::dynamic::{FieldDef, InputValueDef, ObjectType, SchemaBuilder};
+
+# fn main() {
+let schema = SchemaBuilder::<()>::new("Query")
+    .object(
+        ObjectType::new("Query")
+            .field(FieldDef::new("user", "User").resolve(|_, _, _| {
+                Ok(graphql_value!({ "name": "Alice", "age": 34 }))
+            }))
+            .field(
+                FieldDef::new("double", "Int!")
+                    .argument(InputValueDef::new("value", "Int!"))
+                    .resolve(|_, args, _| {
+                        let value: i32 = args.get("value").unwrap();
+                        Ok(Value::int(value * 2))
+                    }),
+            ),
+    )
+    .object(
+        ObjectType::new("User")
+            .field(FieldDef::new("name", "String!"))
+            .field(FieldDef::new("age", "Int")),
+    )
+    .build(Value::null())
+    .unwrap();
+
+let (result, errors) = execute(
+    "{ user { name } double(value: 21) }",
+    None,
+    &schema,
+    &Variables::new(),
+    &(),
+).unwrap();
+
+assert!(errors.is_empty());
+assert_eq!(result, graphql_value!({ "user": { "name": "Alice" }, "double": 42 }));
+# }
+# #[macro_use] extern crate juniper;
+```
+*/
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use ordermap::OrderMap;
+
+use ast::{InputValue, InputValueError, Selection, Type};
+use executor::{ExecutionResult, Executor, FieldResult, Registry};
+use parser::parse_type_source;
+use schema::meta::{Argument, EnumMeta, EnumValue, Field, InputObjectMeta, MetaType,
+                   PlaceholderMeta, ScalarMeta, Validator};
+use schema::model::{build_schema_type, RootNode};
+use schema::validation::SchemaError;
+use types::base::{Arguments, GraphQLType};
+use types::scalars::ID;
+use value::Value;
+
+/// The name `SchemaType` recognizes as "no mutation type"
+const EMPTY_MUTATION: &str = "_EmptyMutation";
+
+type FieldResolver<CtxT> = Fn(&Value, &Arguments, &CtxT) -> FieldResult<Value> + Send + Sync;
+type TypeResolver<CtxT> = Fn(&Value, &CtxT) -> Option<String> + Send + Sync;
+type ParseFn = Fn(&InputValue) -> bool + Send + Sync;
+
+/// A root node for a schema built by a `SchemaBuilder`
+pub type DynamicRootNode<'a, CtxT> = RootNode<'a, DynamicValue<CtxT>, DynamicValue<CtxT>>;
+
+/// Collects type definitions and builds a schema from them
+pub struct SchemaBuilder<CtxT> {
+    query_type: String,
+    mutation_type: Option<String>,
+    types: OrderMap<String, TypeDef<CtxT>>,
+    errors: Vec<SchemaError>,
+}
+
+enum TypeDef<CtxT> {
+    Scalar(ScalarType),
+    Object(ObjectType<CtxT>),
+    Interface(InterfaceType<CtxT>),
+    Union(UnionType<CtxT>),
+    Enum(EnumType),
+    InputObject(InputObjectType),
+}
+
+/// A custom scalar type
+pub struct ScalarType {
+    name: String,
+    description: Option<String>,
+    parse: Arc<ParseFn>,
+}
+
+/// An object type
+pub struct ObjectType<CtxT> {
+    name: String,
+    description: Option<String>,
+    fields: Vec<FieldDef<CtxT>>,
+    interfaces: Vec<String>,
+}
+
+/// An interface type
+pub struct InterfaceType<CtxT> {
+    name: String,
+    description: Option<String>,
+    fields: Vec<FieldDef<CtxT>>,
+    interfaces: Vec<String>,
+    resolve_type: Option<Box<TypeResolver<CtxT>>>,
+}
+
+/// A union type
+pub struct UnionType<CtxT> {
+    name: String,
+    description: Option<String>,
+    members: Vec<String>,
+    resolve_type: Option<Box<TypeResolver<CtxT>>>,
+}
+
+/// An enum type
+pub struct EnumType {
+    name: String,
+    description: Option<String>,
+    values: Vec<EnumValue>,
+}
+
+/// An input object type
+pub struct InputObjectType {
+    name: String,
+    description: Option<String>,
+    fields: Vec<InputValueDef>,
+    one_of: bool,
+}
+
+/// A field of an object or interface type
+pub struct FieldDef<CtxT> {
+    name: String,
+    field_type: TypeRef,
+    description: Option<String>,
+    deprecation_reason: Option<String>,
+    arguments: Vec<InputValueDef>,
+    resolver: Option<Box<FieldResolver<CtxT>>>,
+}
+
+/// An argument of a field, or a field of an input object type
+pub struct InputValueDef {
+    name: String,
+    value_type: TypeRef,
+    description: Option<String>,
+    default_value: Option<InputValue>,
+    validators: Vec<Validator>,
+}
+
+/// A type reference in GraphQL syntax, e.g. `[String!]`
+struct TypeRef {
+    source: String,
+    parsed: Option<Type<'static>>,
+}
+
+/// The type info of a `DynamicValue`, naming its type in the schema
+pub struct DynamicTypeInfo<CtxT> {
+    name: String,
+    types: Arc<OrderMap<String, TypeDef<CtxT>>>,
+}
+
+/// A value of an object type in a dynamic schema
+pub struct DynamicValue<CtxT> {
+    type_name: String,
+    value: Value,
+    phantom: PhantomData<CtxT>,
+}
+
+impl<CtxT> SchemaBuilder<CtxT> {
+    /// Start a schema with the named object type as its query root
+    pub fn new(query_type: &str) -> SchemaBuilder<CtxT> {
+        SchemaBuilder {
+            query_type: query_type.to_owned(),
+            mutation_type: None,
+            types: OrderMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Use the named object type as the mutation root
+    pub fn mutation(mut self, mutation_type: &str) -> SchemaBuilder<CtxT> {
+        self.mutation_type = Some(mutation_type.to_owned());
+        self
+    }
+
+    /// Add a custom scalar type
+    pub fn scalar(self, scalar: ScalarType) -> SchemaBuilder<CtxT> {
+        let name = scalar.name.clone();
+        self.add(name, TypeDef::Scalar(scalar))
+    }
+
+    /// Add an object type
+    pub fn object(self, object: ObjectType<CtxT>) -> SchemaBuilder<CtxT> {
+        let name = object.name.clone();
+        self.add(name, TypeDef::Object(object))
+    }
+
+    /// Add an interface type
+    pub fn interface(self, interface: InterfaceType<CtxT>) -> SchemaBuilder<CtxT> {
+        let name = interface.name.clone();
+        self.add(name, TypeDef::Interface(interface))
+    }
+
+    /// Add a union type
+    pub fn union(self, union: UnionType<CtxT>) -> SchemaBuilder<CtxT> {
+        let name = union.name.clone();
+        self.add(name, TypeDef::Union(union))
+    }
+
+    /// Add an enum type
+    pub fn enum_type(self, enum_type: EnumType) -> SchemaBuilder<CtxT> {
+        let name = enum_type.name.clone();
+        self.add(name, TypeDef::Enum(enum_type))
+    }
+
+    /// Add an input object type
+    pub fn input_object(self, input_object: InputObjectType) -> SchemaBuilder<CtxT> {
+        let name = input_object.name.clone();
+        self.add(name, TypeDef::InputObject(input_object))
+    }
+
+    fn add(mut self, name: String, type_def: TypeDef<CtxT>) -> SchemaBuilder<CtxT> {
+        if let Some(existing) = self.types.get(&name) {
+            self.errors.push(SchemaError::DuplicateTypeName {
+                name: name,
+                first: existing.describe().to_owned(),
+                second: type_def.describe().to_owned(),
+            });
+            return self;
+        }
+
+        self.types.insert(name, type_def);
+        self
+    }
+
+    /// Build the schema
+    ///
+    /// `root_value` is the parent value of the fields of the query and
+    /// mutation root types. Returns all errors if the schema is invalid,
+    /// see `RootNode::validate`.
+    pub fn build(self, root_value: Value) -> Result<DynamicRootNode<'static, CtxT>, Vec<SchemaError>> {
+        let mut errors = self.errors;
+
+        for (name, type_def) in &self.types {
+            type_def.check_type_refs(name, &mut errors);
+        }
+
+        let mut roots = vec![("query", &self.query_type)];
+        roots.extend(self.mutation_type.iter().map(|name| ("mutation", name)));
+
+        for (operation, name) in roots {
+            match self.types.get(name) {
+                Some(&TypeDef::Object(_)) => {}
+                _ => errors.push(SchemaError::InvalidRootType {
+                    operation: operation.to_owned(),
+                    name: name.clone(),
+                }),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mutation_type = self.mutation_type
+            .unwrap_or_else(|| EMPTY_MUTATION.to_owned());
+        let types = Arc::new(self.types);
+        let query_info = DynamicTypeInfo {
+            name: self.query_type.clone(),
+            types: types.clone(),
+        };
+        let mutation_info = DynamicTypeInfo {
+            name: mutation_type.clone(),
+            types: types,
+        };
+
+        let schema = build_schema_type::<DynamicValue<CtxT>, DynamicValue<CtxT>>(
+            &query_info,
+            &mutation_info,
+        );
+
+        let errors = schema.validate();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(RootNode {
+            query_type: DynamicValue::new(self.query_type, root_value.clone()),
+            query_info: query_info,
+            mutation_type: DynamicValue::new(mutation_type, root_value),
+            mutation_info: mutation_info,
+            schema: schema,
+        })
+    }
+}
+
+impl<CtxT> TypeDef<CtxT> {
+    fn describe(&self) -> &'static str {
+        match *self {
+            TypeDef::Scalar(_) => "dynamic scalar",
+            TypeDef::Object(_) => "dynamic object",
+            TypeDef::Interface(_) => "dynamic interface",
+            TypeDef::Union(_) => "dynamic union",
+            TypeDef::Enum(_) => "dynamic enum",
+            TypeDef::InputObject(_) => "dynamic input object",
+        }
+    }
+
+    fn fields(&self) -> &[FieldDef<CtxT>] {
+        match *self {
+            TypeDef::Object(ObjectType { ref fields, .. }) |
+            TypeDef::Interface(InterfaceType { ref fields, .. }) => fields,
+            _ => &[],
+        }
+    }
+
+    fn check_type_refs(&self, type_name: &str, errors: &mut Vec<SchemaError>) {
+        for field in self.fields() {
+            let path = format!("{}.{}", type_name, field.name);
+            field.field_type.check(&path, errors);
+
+            for argument in &field.arguments {
+                let path = format!("{}({}:)", path, argument.name);
+                argument.value_type.check(&path, errors);
+            }
+        }
+
+        if let TypeDef::InputObject(ref input_object) = *self {
+            for field in &input_object.fields {
+                let path = format!("{}.{}", type_name, field.name);
+                field.value_type.check(&path, errors);
+            }
+        }
+    }
+}
+
+impl ScalarType {
+    /// Define a scalar type accepting any input value
+    pub fn new(name: &str) -> ScalarType {
+        ScalarType {
+            name: name.to_owned(),
+            description: None,
+            parse: Arc::new(|_: &InputValue| true),
+        }
+    }
+
+    /// Set the description of the type
+    ///
+    /// If a description was provided prior to calling this method, it will be overwritten.
+    pub fn description(mut self, description: &str) -> ScalarType {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Restrict the input values accepted for this scalar
+    ///
+    /// The function returns `true` if the provided value is valid.
+    pub fn parse<F>(mut self, parse: F) -> ScalarType
+    where
+        F: Fn(&InputValue) -> bool + Send + Sync + 'static,
+    {
+        self.parse = Arc::new(parse);
+        self
+    }
+}
+
+impl<CtxT> ObjectType<CtxT> {
+    /// Define an object type without fields
+    pub fn new(name: &str) -> ObjectType<CtxT> {
+        ObjectType {
+            name: name.to_owned(),
+            description: None,
+            fields: Vec::new(),
+            interfaces: Vec::new(),
+        }
+    }
+
+    /// Set the description of the type
+    ///
+    /// If a description was provided prior to calling this method, it will be overwritten.
+    pub fn description(mut self, description: &str) -> ObjectType<CtxT> {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Add a field to the type
+    pub fn field(mut self, field: FieldDef<CtxT>) -> ObjectType<CtxT> {
+        self.fields.push(field);
+        self
+    }
+
+    /// Declare that the type implements the named interface
+    pub fn interface(mut self, interface: &str) -> ObjectType<CtxT> {
+        self.interfaces.push(interface.to_owned());
+        self
+    }
+}
+
+impl<CtxT> InterfaceType<CtxT> {
+    /// Define an interface type without fields
+    pub fn new(name: &str) -> InterfaceType<CtxT> {
+        InterfaceType {
+            name: name.to_owned(),
+            description: None,
+            fields: Vec::new(),
+            interfaces: Vec::new(),
+            resolve_type: None,
+        }
+    }
+
+    /// Set the description of the type
+    ///
+    /// If a description was provided prior to calling this method, it will be overwritten.
+    pub fn description(mut self, description: &str) -> InterfaceType<CtxT> {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Add a field to the type
+    pub fn field(mut self, field: FieldDef<CtxT>) -> InterfaceType<CtxT> {
+        self.fields.push(field);
+        self
+    }
+
+    /// Declare that the interface implements the named interface
+    pub fn interface(mut self, interface: &str) -> InterfaceType<CtxT> {
+        self.interfaces.push(interface.to_owned());
+        self
+    }
+
+    /// Set the function determining the name of the object type of a value
+    ///
+    /// Without one, the `__typename` key of the value is used.
+    pub fn resolve_type<F>(mut self, resolve_type: F) -> InterfaceType<CtxT>
+    where
+        F: Fn(&Value, &CtxT) -> Option<String> + Send + Sync + 'static,
+    {
+        self.resolve_type = Some(Box::new(resolve_type));
+        self
+    }
+}
+
+impl<CtxT> UnionType<CtxT> {
+    /// Define a union of the named object types
+    pub fn new(name: &str, members: &[&str]) -> UnionType<CtxT> {
+        UnionType {
+            name: name.to_owned(),
+            description: None,
+            members: members.iter().map(|m| (*m).to_owned()).collect(),
+            resolve_type: None,
+        }
+    }
+
+    /// Set the description of the type
+    ///
+    /// If a description was provided prior to calling this method, it will be overwritten.
+    pub fn description(mut self, description: &str) -> UnionType<CtxT> {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Set the function determining the name of the object type of a value
+    ///
+    /// Without one, the `__typename` key of the value is used.
+    pub fn resolve_type<F>(mut self, resolve_type: F) -> UnionType<CtxT>
+    where
+        F: Fn(&Value, &CtxT) -> Option<String> + Send + Sync + 'static,
+    {
+        self.resolve_type = Some(Box::new(resolve_type));
+        self
+    }
+}
+
+impl EnumType {
+    /// Define an enum type with the provided values
+    pub fn new(name: &str, values: &[EnumValue]) -> EnumType {
+        EnumType {
+            name: name.to_owned(),
+            description: None,
+            values: values.to_vec(),
+        }
+    }
+
+    /// Set the description of the type
+    ///
+    /// If a description was provided prior to calling this method, it will be overwritten.
+    pub fn description(mut self, description: &str) -> EnumType {
+        self.description = Some(description.to_owned());
+        self
+    }
+}
+
+impl InputObjectType {
+    /// Define an input object type without fields
+    pub fn new(name: &str) -> InputObjectType {
+        InputObjectType {
+            name: name.to_owned(),
+            description: None,
+            fields: Vec::new(),
+            one_of: false,
+        }
+    }
+
+    /// Set the description of the type
+    ///
+    /// If a description was provided prior to calling this method, it will be overwritten.
+    pub fn description(mut self, description: &str) -> InputObjectType {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Add a field to the type
+    pub fn field(mut self, field: InputValueDef) -> InputObjectType {
+        self.fields.push(field);
+        self
+    }
+
+    /// Mark the type as a `@oneOf` input object, accepting exactly one field
+    pub fn one_of(mut self) -> InputObjectType {
+        self.one_of = true;
+        self
+    }
+}
+
+impl<CtxT> FieldDef<CtxT> {
+    /// Define a field of the provided type, e.g. `[String!]`
+    pub fn new(name: &str, field_type: &str) -> FieldDef<CtxT> {
+        FieldDef {
+            name: name.to_owned(),
+            field_type: TypeRef::new(field_type),
+            description: None,
+            deprecation_reason: None,
+            arguments: Vec::new(),
+            resolver: None,
+        }
+    }
+
+    /// Set the description of the field
+    ///
+    /// This overwrites the description if any was previously set.
+    pub fn description(mut self, description: &str) -> FieldDef<CtxT> {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Set the deprecation reason
+    ///
+    /// This overwrites the deprecation reason if any was previously set.
+    pub fn deprecated(mut self, reason: &str) -> FieldDef<CtxT> {
+        self.deprecation_reason = Some(reason.to_owned());
+        self
+    }
+
+    /// Add an argument to the field
+    pub fn argument(mut self, argument: InputValueDef) -> FieldDef<CtxT> {
+        self.arguments.push(argument);
+        self
+    }
+
+    /// Set the function resolving the field
+    ///
+    /// The function is called with the value of the parent object, the
+    /// arguments of the field, and the context. Without one, the field's
+    /// name is looked up in the parent value.
+    pub fn resolve<F>(mut self, resolver: F) -> FieldDef<CtxT>
+    where
+        F: Fn(&Value, &Arguments, &CtxT) -> FieldResult<Value> + Send + Sync + 'static,
+    {
+        self.resolver = Some(Box::new(resolver));
+        self
+    }
+
+    fn meta<'r>(&self, info: &DynamicTypeInfo<CtxT>, registry: &mut Registry<'r>) -> Field<'r> {
+        let arguments = self.arguments
+            .iter()
+            .map(|a| a.meta(info, registry))
+            .collect::<Vec<_>>();
+
+        Field {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            arguments: if arguments.is_empty() {
+                None
+            } else {
+                Some(arguments)
+            },
+            field_type: self.field_type.register(info, registry),
+            deprecation_reason: self.deprecation_reason.clone(),
+        }
+    }
+}
+
+impl InputValueDef {
+    /// Define an argument or input field of the provided type, e.g. `[Int!]`
+    pub fn new(name: &str, value_type: &str) -> InputValueDef {
+        InputValueDef {
+            name: name.to_owned(),
+            value_type: TypeRef::new(value_type),
+            description: None,
+            default_value: None,
+            validators: Vec::new(),
+        }
+    }
+
+    /// Set the description of the argument
+    ///
+    /// This overwrites the description if any was previously set.
+    pub fn description(mut self, description: &str) -> InputValueDef {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Set the default value of the argument
+    ///
+    /// This overwrites the default value if any was previously set.
+    pub fn default_value(mut self, default_value: InputValue) -> InputValueDef {
+        self.default_value = Some(default_value);
+        self
+    }
+
+    /// Add a validator to the argument
+    pub fn validator(mut self, validator: Validator) -> InputValueDef {
+        self.validators.push(validator);
+        self
+    }
+
+    fn meta<'r, CtxT>(
+        &self,
+        info: &DynamicTypeInfo<CtxT>,
+        registry: &mut Registry<'r>,
+    ) -> Argument<'r> {
+        Argument {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            arg_type: self.value_type.register(info, registry),
+            default_value: self.default_value.clone(),
+            validators: self.validators.clone(),
+        }
+    }
+}
+
+impl TypeRef {
+    fn new(source: &str) -> TypeRef {
+        TypeRef {
+            source: source.to_owned(),
+            parsed: parse_type_source(source).ok().map(|t| into_owned(&t)),
+        }
+    }
+
+    fn check(&self, path: &str, errors: &mut Vec<SchemaError>) {
+        if self.parsed.is_none() {
+            errors.push(SchemaError::InvalidTypeReference {
+                path: path.to_owned(),
+                type_ref: self.source.clone(),
+            });
+        }
+    }
+
+    fn get(&self) -> &Type<'static> {
+        self.parsed
+            .as_ref()
+            .expect("Type references are checked when building the schema")
+    }
+
+    /// Register the referenced type, returning the reference itself
+    fn register<'r, CtxT>(
+        &self,
+        info: &DynamicTypeInfo<CtxT>,
+        registry: &mut Registry<'r>,
+    ) -> Type<'r> {
+        let type_ref = self.get();
+        register_type(type_ref.innermost_name(), info, registry);
+        type_ref.clone()
+    }
+}
+
+fn into_owned(t: &Type) -> Type<'static> {
+    match *t {
+        Type::Named(ref name) => Type::Named(Cow::Owned(name.to_string())),
+        Type::NonNullNamed(ref name) => Type::NonNullNamed(Cow::Owned(name.to_string())),
+        Type::List(ref inner) => Type::List(Box::new(into_owned(inner))),
+        Type::NonNullList(ref inner) => Type::NonNullList(Box::new(into_owned(inner))),
+    }
+}
+
+/// Register a named type, using the built-in scalars unless they have been
+/// redefined
+fn register_type<'r, CtxT>(name: &str, info: &DynamicTypeInfo<CtxT>, registry: &mut Registry<'r>) {
+    if info.types.contains_key(name) {
+        registry.get_type::<DynamicValue<CtxT>>(&info.with_name(name));
+        return;
+    }
+
+    match name {
+        "Int" => registry.get_type::<i32>(&()),
+        "Float" => registry.get_type::<f64>(&()),
+        "String" => registry.get_type::<String>(&()),
+        "Boolean" => registry.get_type::<bool>(&()),
+        "ID" => registry.get_type::<ID>(&()),
+        // Left as a placeholder, which is reported by schema validation
+        _ => registry.get_type::<DynamicValue<CtxT>>(&info.with_name(name)),
+    };
+}
+
+impl<CtxT> DynamicTypeInfo<CtxT> {
+    fn with_name(&self, name: &str) -> DynamicTypeInfo<CtxT> {
+        DynamicTypeInfo {
+            name: name.to_owned(),
+            types: self.types.clone(),
+        }
+    }
+}
+
+impl<CtxT> DynamicValue<CtxT> {
+    fn new(type_name: String, value: Value) -> DynamicValue<CtxT> {
+        DynamicValue {
+            type_name: type_name,
+            value: value,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<CtxT> GraphQLType for DynamicValue<CtxT> {
+    type Context = CtxT;
+    type TypeInfo = DynamicTypeInfo<CtxT>;
+
+    fn name(info: &DynamicTypeInfo<CtxT>) -> Option<&str> {
+        Some(&info.name)
+    }
+
+    fn meta<'r>(info: &DynamicTypeInfo<CtxT>, registry: &mut Registry<'r>) -> MetaType<'r> {
+        let type_def = match info.types.get(&info.name) {
+            Some(type_def) => type_def,
+            None if info.name == EMPTY_MUTATION => {
+                return registry.build_object_type::<Self>(info, &[]).into_meta();
+            }
+            None => {
+                return MetaType::Placeholder(PlaceholderMeta {
+                    of_type: Type::NonNullNamed(Cow::Owned(info.name.clone())),
+                });
+            }
+        };
+
+        match *type_def {
+            TypeDef::Scalar(ref scalar) => {
+                let parse = scalar.parse.clone();
+                let name = info.name.clone();
+
+                ScalarMeta {
+                    name: Cow::Owned(info.name.clone()),
+                    description: scalar.description.clone(),
+                    try_parse_fn: Box::new(move |v: &InputValue| if parse(v) {
+                        Ok(())
+                    } else {
+                        Err(InputValueError::expected(&name, v))
+                    }),
+                    accepts_lists_and_objects: false,
+                }.into_meta()
+            }
+            TypeDef::Object(ref object) => {
+                let fields = object
+                    .fields
+                    .iter()
+                    .map(|f| f.meta(info, registry))
+                    .collect::<Vec<_>>();
+                let interfaces = object
+                    .interfaces
+                    .iter()
+                    .map(|name| registry.get_type::<Self>(&info.with_name(name)))
+                    .collect::<Vec<_>>();
+
+                let mut meta = registry
+                    .build_object_type::<Self>(info, &fields)
+                    .interfaces(&interfaces);
+                meta.description = object.description.clone();
+                meta.into_meta()
+            }
+            TypeDef::Interface(ref interface) => {
+                let fields = interface
+                    .fields
+                    .iter()
+                    .map(|f| f.meta(info, registry))
+                    .collect::<Vec<_>>();
+                let interfaces = interface
+                    .interfaces
+                    .iter()
+                    .map(|name| registry.get_type::<Self>(&info.with_name(name)))
+                    .collect::<Vec<_>>();
+
+                let mut meta = registry
+                    .build_interface_type::<Self>(info, &fields)
+                    .interfaces(&interfaces);
+                meta.description = interface.description.clone();
+                meta.into_meta()
+            }
+            TypeDef::Union(ref union) => {
+                let members = union
+                    .members
+                    .iter()
+                    .map(|name| registry.get_type::<Self>(&info.with_name(name)))
+                    .collect::<Vec<_>>();
+
+                let mut meta = registry.build_union_type::<Self>(info, &members);
+                meta.description = union.description.clone();
+                meta.into_meta()
+            }
+            TypeDef::Enum(ref enum_type) => {
+                let names = enum_type
+                    .values
+                    .iter()
+                    .map(|v| v.name.clone())
+                    .collect::<Vec<_>>();
+                let name = info.name.clone();
+
+                EnumMeta {
+                    name: Cow::Owned(info.name.clone()),
+                    description: enum_type.description.clone(),
+                    values: enum_type.values.clone(),
+                    try_parse_fn: Box::new(move |v: &InputValue| {
+                        match v.as_enum_value().or_else(|| v.as_string_value()) {
+                            Some(value) if names.iter().any(|n| n == value) => Ok(()),
+                            _ => Err(InputValueError::expected(&name, v)),
+                        }
+                    }),
+                }.into_meta()
+            }
+            TypeDef::InputObject(ref input_object) => {
+                let input_fields = input_object
+                    .fields
+                    .iter()
+                    .map(|f| f.meta(info, registry))
+                    .collect::<Vec<_>>();
+                let name = info.name.clone();
+
+                InputObjectMeta {
+                    name: Cow::Owned(info.name.clone()),
+                    description: input_object.description.clone(),
+                    input_fields: input_fields,
+                    is_one_of: input_object.one_of,
+                    try_parse_fn: Box::new(move |v: &InputValue| match v.to_object_value() {
+                        Some(_) => Ok(()),
+                        None => Err(InputValueError::expected(&name, v)),
+                    }),
+                }.into_meta()
+            }
+        }
+    }
+
+    fn resolve_field(
+        &self,
+        info: &DynamicTypeInfo<CtxT>,
+        field_name: &str,
+        arguments: &Arguments,
+        executor: &Executor<CtxT>,
+    ) -> ExecutionResult {
+        let field = info.types
+            .get(&info.name)
+            .and_then(|t| t.fields().iter().find(|f| f.name == field_name))
+            .unwrap_or_else(|| panic!("Field {} not found on type {}", field_name, info.name));
+
+        let value = match field.resolver {
+            Some(ref resolver) => try!(resolver(&self.value, arguments, executor.context())),
+            None => self.value
+                .as_object_value()
+                .and_then(|o| o.get(field_name))
+                .cloned()
+                .unwrap_or_else(Value::null),
+        };
+
+        resolve_value(value, field.field_type.get(), info, executor)
+    }
+
+    fn resolve_into_type(
+        &self,
+        info: &DynamicTypeInfo<CtxT>,
+        type_name: &str,
+        selection_set: Option<&[Selection]>,
+        executor: &Executor<CtxT>,
+    ) -> ExecutionResult {
+        let schema = executor.schema();
+        let matches = type_name == self.type_name || match (
+            schema.concrete_type_by_name(type_name),
+            schema.concrete_type_by_name(&self.type_name),
+        ) {
+            (Some(condition), Some(concrete)) => {
+                condition.is_abstract() && schema.is_possible_type(condition, concrete)
+            }
+            _ => false,
+        };
+
+        if matches {
+            Ok(self.resolve(info, selection_set, executor))
+        } else {
+            Ok(Value::null())
+        }
+    }
+
+    fn concrete_type_name(&self, _: &CtxT) -> String {
+        self.type_name.clone()
+    }
+}
+
+/// Resolve a field's value according to the field's type
+fn resolve_value<CtxT>(
+    value: Value,
+    field_type: &Type,
+    info: &DynamicTypeInfo<CtxT>,
+    executor: &Executor<CtxT>,
+) -> ExecutionResult {
+    if value.is_null() {
+        return Ok(value);
+    }
+
+    match *field_type {
+        Type::Named(ref name) | Type::NonNullNamed(ref name) => {
+            resolve_named(value, name, info, executor)
+        }
+        Type::List(ref inner) | Type::NonNullList(ref inner) => match value {
+            Value::List(items) => {
+                let mut result = Vec::new();
+
+                for (i, item) in items.into_iter().enumerate() {
+                    let executor = &executor.index_sub_executor(i);
+
+                    if inner.is_non_null() {
+                        // A null item in a list of non-null items makes the
+                        // list null
+                        match executor
+                            .resolve_non_null(|| resolve_value(item, inner, info, executor))
+                        {
+                            Ok(Value::Null) => return Ok(Value::null()),
+                            Ok(v) => result.push(v),
+                            Err(e) => {
+                                executor.push_error(e);
+                                return Ok(Value::null());
+                            }
+                        }
+                    } else {
+                        result.push(match resolve_value(item, inner, info, executor) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                executor.push_error(e);
+                                Value::null()
+                            }
+                        });
+                    }
+                }
+
+                Ok(Value::list(result))
+            }
+            _ => Err(format!("Expected a list for type \"{}\"", field_type).into()),
+        },
+    }
+}
+
+fn resolve_named<CtxT>(
+    value: Value,
+    type_name: &str,
+    info: &DynamicTypeInfo<CtxT>,
+    executor: &Executor<CtxT>,
+) -> ExecutionResult {
+    let concrete_type = match info.types.get(type_name) {
+        Some(&TypeDef::Object(_)) => type_name.to_owned(),
+        Some(&TypeDef::Interface(InterfaceType {
+            ref resolve_type, ..
+        })) |
+        Some(&TypeDef::Union(UnionType {
+            ref resolve_type, ..
+        })) => try!(resolve_concrete_type(
+            &value,
+            type_name,
+            resolve_type,
+            executor
+        )),
+        Some(&TypeDef::Enum(ref enum_type)) => {
+            let is_value = value
+                .as_string_value()
+                .map_or(false, |v| enum_type.values.iter().any(|e| e.name == v));
+
+            return if is_value {
+                Ok(value)
+            } else {
+                Err(format!("Invalid value for enum \"{}\"", type_name).into())
+            };
+        }
+        // Scalars are resolved as they are
+        _ => return Ok(value),
+    };
+
+    executor.resolve(
+        &info.with_name(&concrete_type),
+        &DynamicValue::new(concrete_type, value),
+    )
+}
+
+fn resolve_concrete_type<CtxT>(
+    value: &Value,
+    abstract_type: &str,
+    resolve_type: &Option<Box<TypeResolver<CtxT>>>,
+    executor: &Executor<CtxT>,
+) -> FieldResult<String> {
+    let concrete_type = match *resolve_type {
+        Some(ref resolve_type) => resolve_type(value, executor.context()),
+        None => value
+            .as_object_value()
+            .and_then(|o| o.get("__typename"))
+            .and_then(|t| t.as_string_value())
+            .map(str::to_owned),
+    };
+
+    let concrete_type = match concrete_type {
+        Some(concrete_type) => concrete_type,
+        None => {
+            return Err(format!("Could not determine the concrete type of \"{}\"", abstract_type).into())
+        }
+    };
+
+    let schema = executor.schema();
+    let is_possible = match (
+        schema.concrete_type_by_name(abstract_type),
+        schema.concrete_type_by_name(&concrete_type),
+    ) {
+        (Some(a), Some(c @ &MetaType::Object(_))) => schema.is_possible_type(a, c),
+        _ => false,
+    };
+
+    if is_possible {
+        Ok(concrete_type)
+    } else {
+        Err(format!(
+            "\"{}\" is not a possible type of \"{}\"",
+            concrete_type,
+            abstract_type
+        ).into())
+    }
+}
@@ -4,3 +4,4 @@ pub mod pointers;
 pub mod containers;
 pub mod utilities;
 pub mod name;
+pub mod dynamic;
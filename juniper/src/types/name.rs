@@ -1,7 +1,13 @@
+use std::any::type_name;
 use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::str::FromStr;
+use std::sync::Mutex;
+
+use fnv::FnvHashMap;
 
 // Helper functions until the corresponding AsciiExt methods
 // stabilise (https://github.com/rust-lang/rust/issues/39658).
@@ -75,6 +81,59 @@ impl Borrow<str> for Name {
     }
 }
 
+lazy_static! {
+    static ref INTERNED_NAMES: Mutex<HashSet<&'static str>> = Mutex::new(HashSet::new());
+}
+
+thread_local! {
+    /// The names already computed on this thread, by the Rust type they
+    /// belong to
+    static CACHED_TYPE_NAMES: RefCell<FnvHashMap<&'static str, &'static str>> =
+        RefCell::new(FnvHashMap::default());
+}
+
+/// Get the name of the generic type instantiation `T`, calling `make_name`
+/// only the first time it's requested on each thread
+///
+/// A `static` in a generic impl is shared by all instantiations, so names
+/// are cached by the Rust name of `T` in a thread local instead. This keeps
+/// `GraphQLType::name`, which is called for every resolved field, free of
+/// formatting and locks after the first call.
+#[doc(hidden)]
+pub fn cached_type_name<T: ?Sized, F>(make_name: F) -> &'static str
+where
+    F: FnOnce() -> String,
+{
+    let key = type_name::<T>();
+
+    if let Some(name) = CACHED_TYPE_NAMES.with(|names| names.borrow().get(key).cloned()) {
+        return name;
+    }
+
+    // The names of type parameters are computed without borrowing the cache,
+    // since they might be cached themselves
+    let name = intern_type_name(make_name());
+    CACHED_TYPE_NAMES.with(|names| names.borrow_mut().insert(key, name));
+    name
+}
+
+/// Get a `'static` reference to a type name computed at runtime
+///
+/// `GraphQLType::name` must return a reference that outlives the type info,
+/// which rules out names built from the names of generic parameters. Each
+/// distinct name is leaked exactly once and shared between all callers.
+fn intern_type_name(name: String) -> &'static str {
+    let mut names = INTERNED_NAMES.lock().unwrap();
+
+    if let Some(interned) = names.get(name.as_str()) {
+        return interned;
+    }
+
+    let interned: &'static str = Box::leak(name.into_boxed_str());
+    names.insert(interned);
+    interned
+}
+
 #[test]
 fn test_name_is_valid() {
     assert!(Name::is_valid("Foo"));
@@ -88,3 +147,25 @@ fn test_name_is_valid() {
     assert!(!Name::is_valid("Foo-42"));
     assert!(!Name::is_valid("Foo???"));
 }
+
+#[test]
+fn test_intern_type_name() {
+    let first = intern_type_name("PageOfString".to_owned());
+    let second = intern_type_name(format!("PageOf{}", "String"));
+
+    assert_eq!(first, "PageOfString");
+    assert_eq!(first.as_ptr(), second.as_ptr());
+}
+
+#[test]
+fn test_cached_type_name() {
+    struct Page<T>(T);
+
+    let first = cached_type_name::<Page<i32>, _>(|| "PageOfInt".to_owned());
+    let second = cached_type_name::<Page<i32>, _>(|| panic!("Name computed twice"));
+    let other = cached_type_name::<Page<String>, _>(|| "PageOfString".to_owned());
+
+    assert_eq!(first, "PageOfInt");
+    assert_eq!(first.as_ptr(), second.as_ptr());
+    assert_eq!(other, "PageOfString");
+}
@@ -1,4 +1,4 @@
-use ast::{FromInputValue, InputValue, Selection, ToInputValue};
+use ast::{FromInputValue, InputValue, InputValueError, Selection, ToInputValue};
 use value::Value;
 
 use schema::meta::MetaType;
@@ -16,10 +16,18 @@ where
         T::name(info)
     }
 
+    fn type_param_name(info: &T::TypeInfo) -> Option<String> {
+        T::type_param_name(info)
+    }
+
     fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r> {
         T::meta(info, registry)
     }
 
+    fn rust_type_name() -> &'static str {
+        T::rust_type_name()
+    }
+
     fn resolve_into_type(
         &self,
         info: &T::TypeInfo,
@@ -48,6 +56,15 @@ where
     ) -> Value {
         (**self).resolve(info, selection_set, executor)
     }
+
+    fn stream(
+        &self,
+        info: &T::TypeInfo,
+        selection_set: Option<&[Selection]>,
+        executor: &Executor<CtxT>,
+    ) -> bool {
+        (**self).stream(info, selection_set, executor)
+    }
 }
 
 impl<T> FromInputValue for Box<T>
@@ -60,6 +77,10 @@ where
             None => None,
         }
     }
+
+    fn try_from_input_value(v: &InputValue) -> Result<Box<T>, InputValueError> {
+        <T as FromInputValue>::try_from_input_value(v).map(Box::new)
+    }
 }
 
 impl<T> ToInputValue for Box<T>
@@ -82,10 +103,18 @@ where
         T::name(info)
     }
 
+    fn type_param_name(info: &T::TypeInfo) -> Option<String> {
+        T::type_param_name(info)
+    }
+
     fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r> {
         T::meta(info, registry)
     }
 
+    fn rust_type_name() -> &'static str {
+        T::rust_type_name()
+    }
+
     fn resolve_into_type(
         &self,
         info: &T::TypeInfo,
@@ -114,6 +143,15 @@ where
     ) -> Value {
         (**self).resolve(info, selection_set, executor)
     }
+
+    fn stream(
+        &self,
+        info: &T::TypeInfo,
+        selection_set: Option<&[Selection]>,
+        executor: &Executor<CtxT>,
+    ) -> bool {
+        (**self).stream(info, selection_set, executor)
+    }
 }
 
 impl<'a, T> ToInputValue for &'a T
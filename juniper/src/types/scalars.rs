@@ -1,6 +1,7 @@
 use std::convert::From;
 use std::marker::PhantomData;
 use std::ops::Deref;
+use std::sync::Arc;
 
 use ast::{FromInputValue, InputValue, Selection, ToInputValue};
 use value::Value;
@@ -44,6 +45,62 @@ graphql_scalar!(ID as "ID" {
     }
 });
 
+/// A file uploaded along with a request, as defined by the GraphQL multipart
+/// request specification
+///
+/// Uploads are parsed from `multipart/form-data` requests by
+/// `GraphQLRequest::from_multipart_body`, which puts them into the variables
+/// of the request as `InputValue::Upload`. When executing a query directly,
+/// pass them as variables the same way. Uploads can only be used as input;
+/// resolving one as output yields null.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Upload {
+    filename: Option<String>,
+    content_type: Option<String>,
+    content: Arc<Vec<u8>>,
+}
+
+impl Upload {
+    /// Construct an upload from the contents of a file part
+    pub fn new(filename: Option<String>, content_type: Option<String>, content: Vec<u8>) -> Upload {
+        Upload {
+            filename: filename,
+            content_type: content_type,
+            content: Arc::new(content),
+        }
+    }
+
+    /// The file name sent by the client, if any
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_ref().map(|s| s.as_str())
+    }
+
+    /// The content type of the file part, if any
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_ref().map(|s| s.as_str())
+    }
+
+    /// The contents of the file
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+}
+
+graphql_scalar!(Upload {
+    description: "A file sent in a multipart request"
+
+    resolve(&self) -> Value {
+        Value::null()
+    }
+
+    from_input_value(v: &InputValue) -> Option<Upload> {
+        match *v {
+            InputValue::Upload(ref u) => Some(u.clone()),
+            _ => None,
+        }
+    }
+});
+
 
 graphql_scalar!(String as "String" {
     resolve(&self) -> Value {
@@ -71,6 +128,10 @@ impl<'a> GraphQLType for &'a str {
         registry.build_scalar_type::<String>(&()).into_meta()
     }
 
+    fn rust_type_name() -> &'static str {
+        String::rust_type_name()
+    }
+
     fn resolve(&self, _: &(), _: Option<&[Selection]>, _: &Executor<Self::Context>) -> Value {
         Value::string(self)
     }
@@ -1,78 +1,206 @@
-use std::collections::HashSet;
-use ast::InputValue;
+use ast::{InputValue, InputValueError, Type};
+use executor::Variables;
 use schema::model::{SchemaType, TypeType};
-use schema::meta::{EnumMeta, InputObjectMeta, MetaType};
+use schema::meta::{Argument, EnumMeta, InputObjectMeta, MetaType, ScalarMeta};
 
 pub fn is_valid_literal_value(
     schema: &SchemaType,
     arg_type: &TypeType,
     arg_value: &InputValue,
 ) -> bool {
+    validate_literal_value(schema, arg_type, arg_value).is_ok()
+}
+
+/// Check that a literal can be coerced into the given type, returning the
+/// reason and the path to the offending value if it can't
+pub fn validate_literal_value(
+    schema: &SchemaType,
+    arg_type: &TypeType,
+    arg_value: &InputValue,
+) -> Result<(), InputValueError> {
     match *arg_type {
         TypeType::NonNull(ref inner) => if arg_value.is_null() {
-            false
+            Err(InputValueError::expected(&format!("{}", arg_type), arg_value))
         } else {
-            is_valid_literal_value(schema, inner, arg_value)
+            validate_literal_value(schema, inner, arg_value)
         },
         TypeType::List(ref inner) => match *arg_value {
-            InputValue::List(ref items) => items
-                .iter()
-                .all(|i| is_valid_literal_value(schema, inner, &i.item)),
-            ref v => is_valid_literal_value(schema, inner, v),
+            InputValue::List(ref items) => {
+                for (i, item) in items.iter().enumerate() {
+                    try!(
+                        validate_literal_value(schema, inner, &item.item)
+                            .map_err(|e| e.in_element(i))
+                    );
+                }
+                Ok(())
+            }
+            ref v => validate_literal_value(schema, inner, v),
         },
         TypeType::Concrete(t) => {
+            let type_name = t.name().unwrap_or("");
+
             // Even though InputValue::String can be parsed into an enum, they
             // are not valid as enum *literals* in a GraphQL query.
             if let (&InputValue::String(_), Some(&MetaType::Enum(EnumMeta { .. }))) =
                 (arg_value, arg_type.to_concrete())
             {
-                return false;
+                return Err(InputValueError::expected(type_name, arg_value));
             }
 
             match *arg_value {
-                InputValue::Null | InputValue::Variable(_) => true,
+                InputValue::Null | InputValue::Variable(_) => Ok(()),
                 ref v @ InputValue::Int(_) |
                 ref v @ InputValue::Float(_) |
                 ref v @ InputValue::String(_) |
                 ref v @ InputValue::Boolean(_) |
-                ref v @ InputValue::Enum(_) => if let Some(parse_fn) = t.input_value_parse_fn() {
+                ref v @ InputValue::Enum(_) |
+                ref v @ InputValue::Upload(_) => if let Some(parse_fn) = t.input_value_parse_fn() {
                     parse_fn(v)
                 } else {
-                    false
+                    Err(InputValueError::expected(type_name, v))
+                },
+                // Scalars like JSON can accept lists and objects
+                ref v @ InputValue::List(_) => if let MetaType::Scalar(ScalarMeta {
+                    ref try_parse_fn,
+                    accepts_lists_and_objects: true,
+                    ..
+                }) = *t
+                {
+                    validate_scalar_literal(try_parse_fn, v)
+                } else {
+                    Err(InputValueError::expected(type_name, v))
                 },
-                InputValue::List(_) => false,
                 InputValue::Object(ref obj) => if let MetaType::InputObject(InputObjectMeta {
-                    ref input_fields, ..
+                    ref input_fields,
+                    is_one_of,
+                    ..
                 }) = *t
                 {
+                    // A oneOf input object takes exactly one field, which may
+                    // not be null
+                    if is_one_of && (obj.len() != 1 || obj[0].1.item.is_null()) {
+                        return Err(InputValueError::new(format!(
+                            r#"Expected exactly one non-null field for "{}""#,
+                            type_name
+                        )));
+                    }
+
                     let mut remaining_required_fields = input_fields
                         .iter()
                         .filter_map(|f| if f.arg_type.is_non_null() {
-                            Some(&f.name)
+                            Some(f)
                         } else {
                             None
                         })
-                        .collect::<HashSet<_>>();
+                        .collect::<Vec<_>>();
 
-                    let all_types_ok = obj.iter().all(|&(ref key, ref value)| {
-                        remaining_required_fields.remove(&key.item);
-                        if let Some(ref arg_type) = input_fields
-                            .iter()
-                            .filter(|f| f.name == key.item)
-                            .map(|f| schema.make_type(&f.arg_type))
-                            .next()
-                        {
-                            is_valid_literal_value(schema, arg_type, &value.item)
-                        } else {
-                            false
-                        }
-                    });
+                    for &(ref key, ref value) in obj.iter() {
+                        remaining_required_fields.retain(|f| f.name != key.item);
+
+                        let arg_type = try!(
+                            input_fields
+                                .iter()
+                                .find(|f| f.name == key.item)
+                                .map(|f| schema.make_type(&f.arg_type))
+                                .ok_or_else(|| InputValueError::new("Unknown field").in_field(&key.item))
+                        );
 
-                    all_types_ok && remaining_required_fields.is_empty()
+                        try!(
+                            validate_literal_value(schema, &arg_type, &value.item)
+                                .map_err(|e| e.in_field(&key.item))
+                        );
+                    }
+
+                    match remaining_required_fields.first() {
+                        Some(f) => Err(
+                            InputValueError::expected(&format!("{}", f.arg_type), &InputValue::null())
+                                .in_field(&f.name),
+                        ),
+                        None => Ok(()),
+                    }
+                } else if let MetaType::Scalar(ScalarMeta {
+                    ref try_parse_fn,
+                    accepts_lists_and_objects: true,
+                    ..
+                }) = *t
+                {
+                    validate_scalar_literal(try_parse_fn, arg_value)
                 } else {
-                    false
+                    Err(InputValueError::expected(type_name, arg_value))
                 },
             }
         }
     }
 }
+
+/// Parse a list or object literal of a scalar, treating the variables nested
+/// inside of it as valid
+///
+/// The variables are only substituted before execution, so they are replaced
+/// with `null` here, which scalars that accept lists and objects are expected
+/// to take in any position.
+fn validate_scalar_literal(
+    try_parse_fn: &Fn(&InputValue) -> Result<(), InputValueError>,
+    value: &InputValue,
+) -> Result<(), InputValueError> {
+    if value.referenced_variables().is_empty() {
+        try_parse_fn(value)
+    } else {
+        try_parse_fn(&value.clone().into_const(&Variables::new()))
+    }
+}
+
+/// Check the validators of an argument, and of all input object fields
+/// nested inside of its value
+pub fn validate_argument_value(
+    schema: &SchemaType,
+    argument: &Argument,
+    value: &InputValue,
+) -> Result<(), InputValueError> {
+    for validator in &argument.validators {
+        try!(validator.validate(value));
+    }
+
+    validate_nested_values(schema, &argument.arg_type, value)
+}
+
+fn validate_nested_values(
+    schema: &SchemaType,
+    arg_type: &Type,
+    value: &InputValue,
+) -> Result<(), InputValueError> {
+    match (arg_type, value) {
+        (_, &InputValue::Null) | (_, &InputValue::Variable(_)) => Ok(()),
+        (&Type::List(ref inner), &InputValue::List(ref items)) |
+        (&Type::NonNullList(ref inner), &InputValue::List(ref items)) => {
+            for (i, item) in items.iter().enumerate() {
+                try!(
+                    validate_nested_values(schema, inner, &item.item)
+                        .map_err(|e| e.in_element(i))
+                );
+            }
+            Ok(())
+        }
+        (&Type::List(ref inner), v) | (&Type::NonNullList(ref inner), v) => {
+            validate_nested_values(schema, inner, v)
+        }
+        (&Type::Named(ref name), &InputValue::Object(ref fields)) |
+        (&Type::NonNullNamed(ref name), &InputValue::Object(ref fields)) => {
+            if let Some(&MetaType::InputObject(InputObjectMeta {
+                ref input_fields, ..
+            })) = schema.concrete_type_by_name(name)
+            {
+                for &(ref key, ref field_value) in fields {
+                    if let Some(field) = input_fields.iter().find(|f| f.name == key.item) {
+                        try!(
+                            validate_argument_value(schema, field, &field_value.item)
+                                .map_err(|e| e.in_field(&key.item))
+                        );
+                    }
+                }
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
@@ -140,7 +140,7 @@ fn unify_scalar<'a>(
     errors: &mut Vec<RuleError>,
     path: &Path<'a>,
 ) {
-    if !(meta.try_parse_fn)(value) {
+    if (meta.try_parse_fn)(value).is_err() {
         push_unification_error(
             errors,
             var_name,
@@ -151,6 +151,10 @@ fn unify_scalar<'a>(
         return;
     }
 
+    if meta.accepts_lists_and_objects {
+        return;
+    }
+
     match *value {
         InputValue::List(_) => push_unification_error(
             errors,
@@ -252,6 +256,19 @@ fn unify_input_object<'a>(
                 "Unknown field",
             );
         }
+
+        if meta.is_one_of && (obj.len() != 1 || obj.values().any(|v| v.is_null())) {
+            push_unification_error(
+                errors,
+                var_name,
+                var_pos,
+                path,
+                &format!(
+                    r#"Expected exactly one non-null field for "{}""#,
+                    meta.name
+                ),
+            );
+        }
     } else {
         push_unification_error(
             errors,
@@ -1,6 +1,6 @@
 use ast::{Directive, Field, InputValue};
 use schema::meta::Argument;
-use types::utilities::is_valid_literal_value;
+use types::utilities::{validate_argument_value, validate_literal_value};
 use parser::Spanning;
 use validation::{ValidatorContext, Visitor};
 
@@ -47,9 +47,12 @@ impl<'a> Visitor<'a> for ArgumentsOfCorrectType<'a> {
         {
             let meta_type = ctx.schema.make_type(&argument_meta.arg_type);
 
-            if !is_valid_literal_value(ctx.schema, &meta_type, &arg_value.item) {
+            let result = validate_literal_value(ctx.schema, &meta_type, &arg_value.item)
+                .and_then(|()| validate_argument_value(ctx.schema, argument_meta, &arg_value.item));
+
+            if let Err(e) = result {
                 ctx.report_error(
-                    &error_message(arg_name.item, &format!("{}", argument_meta.arg_type)),
+                    &error_message(arg_name.item, &e.to_string()),
                     &[arg_value.start.clone()],
                 );
             }
@@ -57,12 +60,8 @@ impl<'a> Visitor<'a> for ArgumentsOfCorrectType<'a> {
     }
 }
 
-fn error_message(arg_name: &str, type_name: &str) -> String {
-    format!(
-        "Invalid value for argument \"{}\", expected type \"{}\"",
-        arg_name,
-        type_name
-    )
+fn error_message(arg_name: &str, reason: &str) -> String {
+    format!("Invalid value for argument \"{}\": {}", arg_name, reason)
 }
 
 
@@ -100,7 +99,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("nonNullIntArg", "Int!"),
+                    &error_message("nonNullIntArg", r#"Expected "Int!", found null"#),
                     &[SourcePosition::new(97, 3, 50)],
                 ),
             ],
@@ -232,7 +231,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("stringArg", "String"),
+                    &error_message("stringArg", r#"Expected "String", found 1"#),
                     &[SourcePosition::new(89, 3, 42)],
                 ),
             ],
@@ -252,7 +251,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("stringArg", "String"),
+                    &error_message("stringArg", r#"Expected "String", found 1"#),
                     &[SourcePosition::new(89, 3, 42)],
                 ),
             ],
@@ -272,7 +271,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("stringArg", "String"),
+                    &error_message("stringArg", r#"Expected "String", found true"#),
                     &[SourcePosition::new(89, 3, 42)],
                 ),
             ],
@@ -292,7 +291,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("stringArg", "String"),
+                    &error_message("stringArg", r#"Expected "String", found BAR"#),
                     &[SourcePosition::new(89, 3, 42)],
                 ),
             ],
@@ -312,7 +311,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("intArg", "Int"),
+                    &error_message("intArg", r#"Expected "Int", found "3""#),
                     &[SourcePosition::new(83, 3, 36)],
                 ),
             ],
@@ -332,7 +331,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("intArg", "Int"),
+                    &error_message("intArg", r#"Expected "Int", found FOO"#),
                     &[SourcePosition::new(83, 3, 36)],
                 ),
             ],
@@ -352,7 +351,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("intArg", "Int"),
+                    &error_message("intArg", r#"Expected "Int", found 3"#),
                     &[SourcePosition::new(83, 3, 36)],
                 ),
             ],
@@ -372,7 +371,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("intArg", "Int"),
+                    &error_message("intArg", r#"Expected "Int", found 3.333"#),
                     &[SourcePosition::new(83, 3, 36)],
                 ),
             ],
@@ -392,7 +391,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("floatArg", "Float"),
+                    &error_message("floatArg", r#"Expected "Float", found "3.333""#),
                     &[SourcePosition::new(87, 3, 40)],
                 ),
             ],
@@ -412,7 +411,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("floatArg", "Float"),
+                    &error_message("floatArg", r#"Expected "Float", found true"#),
                     &[SourcePosition::new(87, 3, 40)],
                 ),
             ],
@@ -432,7 +431,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("floatArg", "Float"),
+                    &error_message("floatArg", r#"Expected "Float", found FOO"#),
                     &[SourcePosition::new(87, 3, 40)],
                 ),
             ],
@@ -452,7 +451,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("booleanArg", "Boolean"),
+                    &error_message("booleanArg", r#"Expected "Boolean", found 2"#),
                     &[SourcePosition::new(91, 3, 44)],
                 ),
             ],
@@ -472,7 +471,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("booleanArg", "Boolean"),
+                    &error_message("booleanArg", r#"Expected "Boolean", found 1"#),
                     &[SourcePosition::new(91, 3, 44)],
                 ),
             ],
@@ -492,7 +491,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("booleanArg", "Boolean"),
+                    &error_message("booleanArg", r#"Expected "Boolean", found "true""#),
                     &[SourcePosition::new(91, 3, 44)],
                 ),
             ],
@@ -512,7 +511,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("booleanArg", "Boolean"),
+                    &error_message("booleanArg", r#"Expected "Boolean", found TRUE"#),
                     &[SourcePosition::new(91, 3, 44)],
                 ),
             ],
@@ -532,7 +531,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("idArg", "ID"),
+                    &error_message("idArg", r#"Expected "ID", found 1"#),
                     &[SourcePosition::new(81, 3, 34)],
                 ),
             ],
@@ -552,7 +551,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("idArg", "ID"),
+                    &error_message("idArg", r#"Expected "ID", found true"#),
                     &[SourcePosition::new(81, 3, 34)],
                 ),
             ],
@@ -572,7 +571,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("idArg", "ID"),
+                    &error_message("idArg", r#"Expected "ID", found SOMETHING"#),
                     &[SourcePosition::new(81, 3, 34)],
                 ),
             ],
@@ -592,7 +591,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("dogCommand", "DogCommand"),
+                    &error_message("dogCommand", r#"Expected "DogCommand", found 2"#),
                     &[SourcePosition::new(79, 3, 44)],
                 ),
             ],
@@ -612,7 +611,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("dogCommand", "DogCommand"),
+                    &error_message("dogCommand", r#"Expected "DogCommand", found 1"#),
                     &[SourcePosition::new(79, 3, 44)],
                 ),
             ],
@@ -632,7 +631,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("dogCommand", "DogCommand"),
+                    &error_message("dogCommand", r#"Expected "DogCommand", found "SIT""#),
                     &[SourcePosition::new(79, 3, 44)],
                 ),
             ],
@@ -652,7 +651,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("dogCommand", "DogCommand"),
+                    &error_message("dogCommand", r#"Expected "DogCommand", found true"#),
                     &[SourcePosition::new(79, 3, 44)],
                 ),
             ],
@@ -672,7 +671,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("dogCommand", "DogCommand"),
+                    &error_message("dogCommand", r#"Expected "DogCommand", found JUGGLE"#),
                     &[SourcePosition::new(79, 3, 44)],
                 ),
             ],
@@ -692,7 +691,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("dogCommand", "DogCommand"),
+                    &error_message("dogCommand", r#"Expected "DogCommand", found sit"#),
                     &[SourcePosition::new(79, 3, 44)],
                 ),
             ],
@@ -754,7 +753,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("stringListArg", "[String]"),
+                    &error_message("stringListArg", r#"In element #1: Expected "String", found 2"#),
                     &[SourcePosition::new(97, 3, 50)],
                 ),
             ],
@@ -774,7 +773,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("stringListArg", "[String]"),
+                    &error_message("stringListArg", r#"Expected "String", found 1"#),
                     &[SourcePosition::new(97, 3, 50)],
                 ),
             ],
@@ -934,11 +933,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("req2", "Int!"),
+                    &error_message("req2", r#"Expected "Int", found "two""#),
                     &[SourcePosition::new(82, 3, 35)],
                 ),
                 RuleError::new(
-                    &error_message("req1", "Int!"),
+                    &error_message("req1", r#"Expected "Int", found "one""#),
                     &[SourcePosition::new(95, 3, 48)],
                 ),
             ],
@@ -958,7 +957,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("req1", "Int!"),
+                    &error_message("req1", r#"Expected "Int", found "one""#),
                     &[SourcePosition::new(82, 3, 35)],
                 ),
             ],
@@ -1074,7 +1073,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("complexArg", "ComplexInput"),
+                    &error_message("complexArg", r#"In field "requiredField": Expected "Boolean!", found null"#),
                     &[SourcePosition::new(91, 3, 44)],
                 ),
             ],
@@ -1097,7 +1096,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("complexArg", "ComplexInput"),
+                    &error_message("complexArg", r#"In field "stringListField": In element #1: Expected "String", found 2"#),
                     &[SourcePosition::new(91, 3, 44)],
                 ),
             ],
@@ -1120,7 +1119,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("complexArg", "ComplexInput"),
+                    &error_message("complexArg", r#"In field "unknownField": Unknown field"#),
                     &[SourcePosition::new(91, 3, 44)],
                 ),
             ],
@@ -1157,14 +1156,94 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("if", "Boolean!"),
+                    &error_message("if", r#"Expected "Boolean", found "yes""#),
                     &[SourcePosition::new(38, 2, 27)],
                 ),
                 RuleError::new(
-                    &error_message("if", "Boolean!"),
+                    &error_message("if", r#"Expected "Boolean", found ENUM"#),
                     &[SourcePosition::new(74, 3, 27)],
                 ),
             ],
         );
     }
+
+    #[test]
+    fn values_passing_validators() {
+        expect_passes_rule(
+            factory,
+            r#"
+            query Query($rating: Int) {
+              complicatedArgs {
+                validatedArgField(rating: 5, tags: ["a", "bc"])
+                withVariable: validatedArgField(rating: $rating, tags: null)
+              }
+            }
+        "#,
+        );
+    }
+
+    #[test]
+    fn value_out_of_range() {
+        expect_fails_rule(
+            factory,
+            r#"
+            {
+              complicatedArgs {
+                validatedArgField(rating: 0)
+              }
+            }
+        "#,
+            &[
+                RuleError::new(
+                    r#"Invalid value for argument "rating": Expected a value of at least 1, found 0"#,
+                    &[SourcePosition::new(89, 3, 42)],
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn list_violating_validators() {
+        expect_fails_rule(
+            factory,
+            r#"
+            {
+              complicatedArgs {
+                tooLong: validatedArgField(tags: ["a", "b", "c"])
+                mismatch: validatedArgField(tags: ["ok", "NO"])
+              }
+            }
+        "#,
+            &[
+                RuleError::new(
+                    r#"Invalid value for argument "tags": Expected a length of at most 2, found 3"#,
+                    &[SourcePosition::new(96, 3, 49)],
+                ),
+                RuleError::new(
+                    r#"Invalid value for argument "tags": In element #1: Expected a value matching "^[a-z]+$", found "NO""#,
+                    &[SourcePosition::new(163, 4, 50)],
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn input_field_violating_validators() {
+        expect_fails_rule(
+            factory,
+            r#"
+            {
+              complicatedArgs {
+                complexArgField(complexArg: { requiredField: true, stringField: "toolong" })
+              }
+            }
+        "#,
+            &[
+                RuleError::new(
+                    r#"Invalid value for argument "complexArg": In field "stringField": Expected a length of at most 5, found 7"#,
+                    &[SourcePosition::new(91, 3, 44)],
+                ),
+            ],
+        );
+    }
 }
@@ -207,6 +207,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn interface_into_implemented_interface() {
+        expect_passes_rule(
+            factory,
+            r#"
+          fragment interfaceWithinInterface on Canine { ...petFragment }
+          fragment petFragment on Pet { name }
+        "#,
+        );
+    }
+
     #[test]
     fn interface_into_overlapping_union() {
         expect_passes_rule(
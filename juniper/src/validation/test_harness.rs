@@ -4,7 +4,7 @@ use types::base::GraphQLType;
 use executor::Registry;
 use types::scalars::{EmptyMutation, ID};
 use schema::model::{DirectiveLocation, DirectiveType, RootNode};
-use schema::meta::{EnumValue, MetaType};
+use schema::meta::{EnumValue, MetaType, Validator};
 use validation::{visit, MultiVisitor, MultiVisitorNil, RuleError, ValidatorContext, Visitor};
 
 struct Being;
@@ -104,7 +104,10 @@ impl GraphQLType for Canine {
                 .argument(registry.arg::<Option<bool>>("surname", i)),
         ];
 
-        registry.build_interface_type::<Self>(i, fields).into_meta()
+        registry
+            .build_interface_type::<Self>(i, fields)
+            .interfaces(&[registry.get_type::<Being>(i), registry.get_type::<Pet>(i)])
+            .into_meta()
     }
 }
 
@@ -370,7 +373,9 @@ impl GraphQLType for ComplexInput {
         let fields = &[
             registry.arg::<bool>("requiredField", i),
             registry.arg::<Option<i32>>("intField", i),
-            registry.arg::<Option<String>>("stringField", i),
+            registry
+                .arg::<Option<String>>("stringField", i)
+                .validator(Validator::max_length(5)),
             registry.arg::<Option<bool>>("booleanField", i),
             registry.arg::<Option<Vec<Option<String>>>>("stringListField", i),
         ];
@@ -440,6 +445,20 @@ impl GraphQLType for ComplicatedArgs {
             registry
                 .field::<Option<String>>("complexArgField", i)
                 .argument(registry.arg::<Option<ComplexInput>>("complexArg", i)),
+            registry
+                .field::<Option<String>>("validatedArgField", i)
+                .argument(
+                    registry
+                        .arg::<Option<i32>>("rating", i)
+                        .validator(Validator::min(1))
+                        .validator(Validator::max(5)),
+                )
+                .argument(
+                    registry
+                        .arg::<Option<Vec<Option<String>>>>("tags", i)
+                        .validator(Validator::max_length(2))
+                        .validator(Validator::pattern("^[a-z]+$")),
+                ),
             registry
                 .field::<Option<String>>("multipleReqs", i)
                 .argument(registry.arg::<i32>("req1", i))
@@ -3,7 +3,7 @@ use std::borrow::Cow;
 use ast::{Arguments, Definition, Directive, Document, Field, Fragment, FragmentSpread,
           InlineFragment, InputValue, Operation, OperationType, Selection, Type,
           VariableDefinitions};
-use schema::meta::Argument;
+use schema::meta::{Argument, InputObjectMeta, MetaType};
 use parser::Spanning;
 use validation::{ValidatorContext, Visitor};
 
@@ -260,17 +260,25 @@ fn visit_input_value<'a, V: Visitor<'a>>(
 
     match input_value.item {
         InputValue::Object(ref fields) => for field in fields {
-            let inner_type = ctx.current_input_type_literal()
-                .and_then(|t| match *t {
-                    Type::NonNullNamed(ref name) | Type::Named(ref name) => {
-                        ctx.schema.concrete_type_by_name(name)
-                    }
-                    _ => None,
-                })
+            let object_type = ctx.current_input_type_literal().and_then(|t| match *t {
+                Type::NonNullNamed(ref name) | Type::Named(ref name) => {
+                    ctx.schema.concrete_type_by_name(name)
+                }
+                _ => None,
+            });
+
+            // The single field of a oneOf input object may not be null, so
+            // variables used for it have to be non-null
+            let inner_type = object_type
                 .and_then(|ct| ct.input_field_by_name(&field.0.item))
-                .map(|f| &f.arg_type);
+                .map(|f| match object_type {
+                    Some(&MetaType::InputObject(InputObjectMeta { is_one_of: true, .. })) => {
+                        f.arg_type.clone().into_non_null()
+                    }
+                    _ => f.arg_type.clone(),
+                });
 
-            ctx.with_pushed_input_type(inner_type, |ctx| {
+            ctx.with_pushed_input_type(inner_type.as_ref(), |ctx| {
                 v.enter_object_field(ctx, field);
                 visit_input_value(v, ctx, &field.1);
                 v.exit_object_field(ctx, field);
@@ -314,6 +322,8 @@ fn enter_input_value<'a, V: Visitor<'a>>(
         Variable(ref s) => v.enter_variable_value(ctx, Spanning::start_end(start, end, s)),
         List(ref l) => v.enter_list_value(ctx, Spanning::start_end(start, end, l)),
         Object(ref o) => v.enter_object_value(ctx, Spanning::start_end(start, end, o)),
+        // Uploads only occur in variables, never in documents
+        Upload(_) => (),
     }
 }
 
@@ -337,5 +347,7 @@ fn exit_input_value<'a, V: Visitor<'a>>(
         Variable(ref s) => v.exit_variable_value(ctx, Spanning::start_end(start, end, s)),
         List(ref l) => v.exit_list_value(ctx, Spanning::start_end(start, end, l)),
         Object(ref o) => v.exit_object_value(ctx, Spanning::start_end(start, end, o)),
+        // Uploads only occur in variables, never in documents
+        Upload(_) => (),
     }
 }
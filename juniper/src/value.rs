@@ -13,7 +13,7 @@ use ast::{InputValue, ToInputValue};
 /// values or variables. Also, lists and objects do not contain any location
 /// information since they are generated by resolving fields and values rather
 /// than parsing a source query.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 #[allow(missing_docs)]
 pub enum Value {
     Null,
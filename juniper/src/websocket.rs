@@ -0,0 +1,330 @@
+//! Executing operations over WebSockets
+//!
+//! A `Connection` implements the server side of the `graphql-transport-ws`
+//! protocol and the legacy `graphql-ws` protocol of
+//! `subscriptions-transport-ws`. It consumes the text frames sent by the
+//! client and returns the frames to send back, so a web framework only has to
+//! pass frames between the socket and the connection:
+//!
+//! ```rust
+//! # use juniper::{EmptyMutation, RootNode};
+//! # use juniper::websocket::{Connection, Frame, Protocol};
+//! # struct Query;
+//! # graphql_object!(Query: () |&self| { field ok() -> bool { true } });
+//! # #[macro_use] extern crate juniper;
+//! # fn main() {
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+//!
+//! // Use the protocol the client asked for in `Sec-WebSocket-Protocol`
+//! let protocol = Protocol::from_subprotocol("graphql-transport-ws").unwrap();
+//! let mut connection = Connection::new(protocol, &schema, |_payload| Ok(()));
+//!
+//! let incoming = vec![
+//!     r#"{"type": "connection_init"}"#,
+//!     r#"{"type": "subscribe", "id": "1", "payload": {"query": "{ ok }"}}"#,
+//! ];
+//!
+//! for text in incoming {
+//!     for frame in connection.handle(text) {
+//!         match frame {
+//!             Frame::Text(text) => println!("send {}", text),
+//!             Frame::Close(code, reason) => println!("close {} {}", code, reason),
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! Operations are executed as soon as they arrive, and produce a single
+//! result followed by `complete`. Juniper doesn't support subscription
+//! operations, which are reported as GraphQL errors.
+
+use serde_json::{self, Map, Value as Json};
+
+use {GraphQLType, InputValue, RootNode};
+use http::GraphQLRequest;
+
+/// The WebSocket subprotocols a `Connection` can speak
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// The `graphql-transport-ws` protocol of the `graphql-ws` library
+    GraphQLTransportWs,
+    /// The legacy `graphql-ws` protocol of the `subscriptions-transport-ws`
+    /// library
+    GraphQLWs,
+}
+
+impl Protocol {
+    /// Look up the protocol by its name in the `Sec-WebSocket-Protocol`
+    /// header
+    pub fn from_subprotocol(name: &str) -> Option<Protocol> {
+        match name.trim() {
+            "graphql-transport-ws" => Some(Protocol::GraphQLTransportWs),
+            "graphql-ws" => Some(Protocol::GraphQLWs),
+            _ => None,
+        }
+    }
+
+    /// The name of the protocol in the `Sec-WebSocket-Protocol` header
+    pub fn subprotocol(&self) -> &'static str {
+        match *self {
+            Protocol::GraphQLTransportWs => "graphql-transport-ws",
+            Protocol::GraphQLWs => "graphql-ws",
+        }
+    }
+}
+
+/// A frame to send to the client
+#[derive(Debug, Clone, PartialEq)]
+pub enum Frame {
+    /// A text frame holding a JSON message
+    Text(String),
+    /// Close the socket with the status code and reason
+    Close(u16, String),
+}
+
+/// The server side of a WebSocket connection
+///
+/// The context of the connection is created by the `init` function from the
+/// payload of the `connection_init` message. Returning an error rejects the
+/// connection.
+pub struct Connection<'a, QueryT, MutationT, CtxT>
+where
+    QueryT: GraphQLType<Context = CtxT> + 'a,
+    MutationT: GraphQLType<Context = CtxT> + 'a,
+{
+    protocol: Protocol,
+    root_node: &'a RootNode<'a, QueryT, MutationT>,
+    init: Box<FnMut(Option<InputValue>) -> Result<CtxT, String> + 'a>,
+    context: Option<CtxT>,
+}
+
+struct Message {
+    message_type: String,
+    id: Option<String>,
+    payload: Option<Json>,
+}
+
+impl<'a, QueryT, MutationT, CtxT> Connection<'a, QueryT, MutationT, CtxT>
+where
+    QueryT: GraphQLType<Context = CtxT> + 'a,
+    MutationT: GraphQLType<Context = CtxT> + 'a,
+{
+    /// Start a connection speaking the protocol
+    pub fn new<F>(
+        protocol: Protocol,
+        root_node: &'a RootNode<'a, QueryT, MutationT>,
+        init: F,
+    ) -> Connection<'a, QueryT, MutationT, CtxT>
+    where
+        F: FnMut(Option<InputValue>) -> Result<CtxT, String> + 'a,
+    {
+        Connection {
+            protocol: protocol,
+            root_node: root_node,
+            init: Box::new(init),
+            context: None,
+        }
+    }
+
+    /// The protocol of the connection
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    /// Has the client been acknowledged?
+    pub fn is_initialized(&self) -> bool {
+        self.context.is_some()
+    }
+
+    /// Handle a text frame sent by the client
+    pub fn handle(&mut self, text: &str) -> Vec<Frame> {
+        match self.protocol {
+            Protocol::GraphQLTransportWs => self.handle_transport_ws(text),
+            Protocol::GraphQLWs => self.handle_legacy(text),
+        }
+    }
+
+    /// The message to send periodically to keep the connection alive, if
+    /// the protocol has one at this point
+    pub fn keep_alive(&self) -> Option<Frame> {
+        match self.protocol {
+            Protocol::GraphQLTransportWs => Some(message("ping", None, None)),
+            Protocol::GraphQLWs if self.is_initialized() => Some(message("ka", None, None)),
+            Protocol::GraphQLWs => None,
+        }
+    }
+
+    fn handle_transport_ws(&mut self, text: &str) -> Vec<Frame> {
+        let msg = match parse_message(text) {
+            Some(msg) => msg,
+            None => return vec![Frame::Close(4400, "Invalid message".to_owned())],
+        };
+
+        match msg.message_type.as_str() {
+            "connection_init" if self.is_initialized() => vec![
+                Frame::Close(4429, "Too many initialisation requests".to_owned()),
+            ],
+            "connection_init" => match self.initialize(msg.payload) {
+                Ok(()) => vec![message("connection_ack", None, None)],
+                Err(_) => vec![Frame::Close(4403, "Forbidden".to_owned())],
+            },
+            "ping" => vec![message("pong", None, None)],
+            "pong" => vec![],
+            "subscribe" | "complete" if !self.is_initialized() => {
+                vec![Frame::Close(4401, "Unauthorized".to_owned())]
+            }
+            "subscribe" => match (msg.id, msg.payload) {
+                (Some(id), Some(payload)) => match self.execute(&payload) {
+                    Ok(response) => vec![
+                        message("next", Some(&id), Some(response)),
+                        message("complete", Some(&id), None),
+                    ],
+                    Err(errors) => vec![message("error", Some(&id), Some(errors))],
+                },
+                _ => vec![Frame::Close(4400, "Invalid message".to_owned())],
+            },
+            // Operations complete right away, so there's nothing to stop
+            "complete" => vec![],
+            t => vec![Frame::Close(4400, format!("Invalid message type \"{}\"", t))],
+        }
+    }
+
+    fn handle_legacy(&mut self, text: &str) -> Vec<Frame> {
+        let msg = match parse_message(text) {
+            Some(msg) => msg,
+            None => return vec![message("error", None, Some(error_payload("Invalid message")))],
+        };
+        let id = msg.id.as_ref().map(|id| id.as_str());
+
+        match msg.message_type.as_str() {
+            "connection_init" if self.is_initialized() => vec![
+                message(
+                    "connection_error",
+                    None,
+                    Some(error_payload("Connection already initialized")),
+                ),
+            ],
+            "connection_init" => match self.initialize(msg.payload) {
+                Ok(()) => vec![
+                    message("connection_ack", None, None),
+                    message("ka", None, None),
+                ],
+                Err(reason) => vec![
+                    message("connection_error", None, Some(error_payload(&reason))),
+                    Frame::Close(1011, reason),
+                ],
+            },
+            "connection_terminate" => vec![Frame::Close(1000, String::new())],
+            "start" if !self.is_initialized() => vec![
+                message("error", id, Some(error_payload("Connection not initialized"))),
+            ],
+            "start" => match (id, msg.payload.as_ref()) {
+                (Some(id), Some(payload)) => {
+                    // Request errors are sent as the result of the operation
+                    let response = self.execute(payload).unwrap_or_else(|errors| {
+                        let mut response = Map::new();
+                        response.insert("errors".to_owned(), errors);
+                        Json::Object(response)
+                    });
+
+                    vec![
+                        message("data", Some(id), Some(response)),
+                        message("complete", Some(id), None),
+                    ]
+                }
+                _ => vec![message("error", id, Some(error_payload("Invalid message")))],
+            },
+            "stop" => vec![],
+            t => vec![
+                message(
+                    "error",
+                    id,
+                    Some(error_payload(&format!("Invalid message type \"{}\"", t))),
+                ),
+            ],
+        }
+    }
+
+    fn initialize(&mut self, payload: Option<Json>) -> Result<(), String> {
+        let payload = match payload {
+            Some(Json::Null) | None => None,
+            Some(payload) => Some(try!(
+                serde_json::from_value::<InputValue>(payload).map_err(|e| e.to_string())
+            )),
+        };
+
+        self.context = Some(try!((self.init)(payload)));
+        Ok(())
+    }
+
+    /// Execute the request in the payload, returning the response or the
+    /// errors that prevented execution
+    fn execute(&self, payload: &Json) -> Result<Json, Json> {
+        let context = self.context.as_ref().expect("Connection is initialized");
+
+        let request = match GraphQLRequest::from_json_body(&payload.to_string()) {
+            Ok(request) => request,
+            Err(error) => return Err(take_errors(serde_json::to_value(&error))),
+        };
+
+        let response = request.execute(self.root_node, context);
+        let serialized = serde_json::to_value(&response);
+
+        if response.is_ok() {
+            Ok(serialized.expect("Response could not be serialized"))
+        } else {
+            Err(take_errors(serialized))
+        }
+    }
+}
+
+fn parse_message(text: &str) -> Option<Message> {
+    let mut object = match serde_json::from_str::<Json>(text) {
+        Ok(Json::Object(object)) => object,
+        _ => return None,
+    };
+
+    let message_type = match object.remove("type") {
+        Some(Json::String(t)) => t,
+        _ => return None,
+    };
+    let id = match object.remove("id") {
+        Some(Json::String(id)) => Some(id),
+        Some(Json::Number(id)) => Some(id.to_string()),
+        _ => None,
+    };
+
+    Some(Message {
+        message_type: message_type,
+        id: id,
+        payload: object.remove("payload"),
+    })
+}
+
+fn message(message_type: &str, id: Option<&str>, payload: Option<Json>) -> Frame {
+    let mut message = Map::new();
+    message.insert("type".to_owned(), Json::String(message_type.to_owned()));
+
+    if let Some(id) = id {
+        message.insert("id".to_owned(), Json::String(id.to_owned()));
+    }
+    if let Some(payload) = payload {
+        message.insert("payload".to_owned(), payload);
+    }
+
+    Frame::Text(Json::Object(message).to_string())
+}
+
+fn error_payload(reason: &str) -> Json {
+    let mut payload = Map::new();
+    payload.insert("message".to_owned(), Json::String(reason.to_owned()));
+    Json::Object(payload)
+}
+
+fn take_errors(response: Result<Json, serde_json::Error>) -> Json {
+    match response.expect("Errors could not be serialized") {
+        Json::Object(mut response) => response.remove("errors").unwrap_or(Json::Null),
+        _ => Json::Null,
+    }
+}
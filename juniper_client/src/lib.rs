@@ -0,0 +1,837 @@
+//! Typed clients for GraphQL operations
+//!
+//! `generate_client` turns a query document into Rust source code for
+//! calling its operations from another service. The document is validated
+//! against the schema with the same rules `execute` uses, so a query that
+//! doesn't match the schema fails the build instead of the request.
+//!
+//! The generator lives apart from `juniper`, since it is usually only
+//! needed in `[build-dependencies]`.
+//!
+//! Each operation gets a module, named after the operation in snake case,
+//! containing:
+//!
+//! * `QUERY` and `OPERATION_NAME`, the source of the operation along with
+//!   the fragments it uses, and its name,
+//! * `Variables`, a struct with a field for each variable,
+//! * `ResponseData`, a struct for the selection set of the operation, with
+//!   nested structs named after the path to each composite field,
+//! * `Request` and `Response`, the serialized request and response bodies,
+//! * an enum or struct for each enum and input object type in use.
+//!
+//! Fields that might be missing from the response, because they're selected
+//! in a fragment on a narrower type or have an `@include` or `@skip`
+//! directive, are wrapped in an `Option`.
+//!
+//! For a schema served by another service, read it from the result of an
+//! introspection query with `SchemaType::from_introspection`.
+//!
+//! The generated code derives `Serialize` and `Deserialize`, so the crate
+//! including it needs `#[macro_use] extern crate serde_derive;`. The usual
+//! place to run the generator is a build script:
+//!
+//! ```rust,ignore
+//! // build.rs
+//! let schema = SchemaType::new::<Query, Mutation>(&(), &());
+//! let source = fs::read_to_string("src/queries.graphql").unwrap();
+//! let code = ClientGenerator::new(&schema)
+//!     .scalar("DateTime", "String")
+//!     .generate(&source)
+//!     .unwrap();
+//! fs::write(Path::new(&env::var("OUT_DIR").unwrap()).join("queries.rs"), code).unwrap();
+//!
+//! // lib.rs
+//! include!(concat!(env!("OUT_DIR"), "/queries.rs"));
+//! ```
+//!
+//! ```rust
+//! # #[macro_use] extern crate juniper;
+//! # extern crate juniper_client;
+//! use juniper::{EmptyMutation, SchemaType};
+//! use juniper_client::generate_client;
+//!
+//! struct Query;
+//!
+//! graphql_object!(Query: () |&self| {
+//!     field version() -> i32 { 1 }
+//! });
+//!
+//! # fn main() {
+//! let schema = SchemaType::new::<Query, EmptyMutation<()>>(&(), &());
+//! let code = generate_client(&schema, "query Version { version }").unwrap();
+//!
+//! assert!(code.contains("pub mod version {"));
+//! assert!(code.contains("pub version: i32,"));
+//!
+//! assert!(generate_client(&schema, "query Version { name }").is_err());
+//! # }
+//! ```
+
+#[cfg_attr(test, macro_use)]
+extern crate juniper;
+extern crate ordermap;
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Write};
+
+use ordermap::OrderMap;
+
+use juniper::meta::MetaType;
+use juniper::parser::{parse_document_source, print_document, Definition, Directive, Document,
+                      Fragment, Operation, OperationType, SourcePosition, Spanning};
+use juniper::{validate_query, GraphQLError, RuleError, SchemaType, Selection, Type};
+
+#[cfg(test)]
+mod tests;
+
+/// A query document no client could be generated for
+#[derive(Debug, PartialEq)]
+pub enum ClientError {
+    /// The document could not be parsed
+    Parse {
+        /// A description of the syntax error
+        message: String,
+        /// Where the error was found
+        position: SourcePosition,
+    },
+    /// The document is not valid for the schema
+    Validation(Vec<RuleError>),
+    /// Two operations would be generated into the same module
+    DuplicateOperation(String),
+    /// No Rust type was given for a custom scalar used by the document
+    UnmappedScalar(String),
+}
+
+/// Generates typed clients for query documents
+///
+/// The built-in scalars are mapped to `i32`, `f64`, `String`, and `bool`,
+/// and `ID` to `String`. Other scalars need a Rust type that implements
+/// `Serialize` and `Deserialize`, given with `scalar`.
+pub struct ClientGenerator<'a> {
+    schema: &'a SchemaType<'a>,
+    scalars: HashMap<String, String>,
+}
+
+/// Generate a typed client for the operations in a query document
+///
+/// This is `ClientGenerator::new(schema).generate(source)`, for schemas that
+/// only use the built-in scalars.
+pub fn generate_client<'a>(schema: &'a SchemaType<'a>, source: &str) -> Result<String, ClientError> {
+    ClientGenerator::new(schema).generate(source)
+}
+
+impl<'a> ClientGenerator<'a> {
+    /// Create a generator for queries against a schema
+    pub fn new(schema: &'a SchemaType<'a>) -> ClientGenerator<'a> {
+        ClientGenerator {
+            schema: schema,
+            scalars: [
+                ("Int", "i32"),
+                ("Float", "f64"),
+                ("String", "String"),
+                ("Boolean", "bool"),
+                ("ID", "String"),
+            ].iter()
+                .map(|&(name, rust_type)| (name.to_owned(), rust_type.to_owned()))
+                .collect(),
+        }
+    }
+
+    /// Use a Rust type, e.g. `chrono::DateTime<chrono::Utc>`, for a scalar
+    ///
+    /// Paths are resolved in the module that includes the generated code.
+    pub fn scalar(mut self, name: &str, rust_type: &str) -> ClientGenerator<'a> {
+        self.scalars.insert(name.to_owned(), rust_type.to_owned());
+        self
+    }
+
+    /// Generate the source code for a query document
+    pub fn generate(&self, source: &str) -> Result<String, ClientError> {
+        try!(validate_query(source, self.schema).map_err(|e| match e {
+            GraphQLError::ParseError(e) => ClientError::Parse {
+                message: e.item.to_string(),
+                position: e.start,
+            },
+            GraphQLError::ValidationError(errors) => ClientError::Validation(errors),
+            e => unreachable!("Unexpected error from validation: {:?}", e),
+        }));
+        let document = parse_document_source(source).expect("Validated document failed to parse");
+
+        let fragments = document
+            .iter()
+            .filter_map(|d| match *d {
+                Definition::Fragment(ref f) => Some((f.item.name.item, &f.item)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+        let operations = document
+            .iter()
+            .filter_map(|d| match *d {
+                Definition::Operation(ref op) => Some(&op.item),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut out = String::from("// Generated by juniper::client, do not edit\n");
+        let mut modules = HashSet::new();
+
+        for op in &operations {
+            let module = match op.name {
+                Some(ref name) => field_name(name.item),
+                // Validation makes sure an anonymous operation is the only one
+                None => match op.operation_type {
+                    OperationType::Query => "query".to_owned(),
+                    OperationType::Mutation => "mutation".to_owned(),
+                },
+            };
+
+            if !modules.insert(module.clone()) {
+                return Err(ClientError::DuplicateOperation(module));
+            }
+
+            let mut writer = OperationWriter {
+                generator: self,
+                fragments: &fragments,
+                type_names: self.scalars.values().cloned().collect(),
+                schema_types: HashMap::new(),
+                pending: Vec::new(),
+                items: Vec::new(),
+            };
+
+            out.push('\n');
+            try!(writer.write(
+                &mut out,
+                &module,
+                op,
+                &operation_document(&document, op, &fragments)
+            ));
+        }
+
+        Ok(out)
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ClientError::Parse {
+                ref message,
+                ref position,
+            } => write!(
+                f,
+                "{} at line {}, column {}",
+                message,
+                position.line() + 1,
+                position.column() + 1
+            ),
+            ClientError::Validation(ref errors) => {
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        try!(write!(f, "\n"));
+                    }
+                    try!(write!(f, "{}", error.message()));
+                }
+                Ok(())
+            }
+            ClientError::DuplicateOperation(ref module) => {
+                write!(f, "Several operations would be generated into module {}", module)
+            }
+            ClientError::UnmappedScalar(ref name) => {
+                write!(f, "No Rust type was given for the scalar {}", name)
+            }
+        }
+    }
+}
+
+struct OperationWriter<'g, 'a: 'g, 'd: 'g> {
+    generator: &'g ClientGenerator<'a>,
+    fragments: &'g HashMap<&'d str, &'d Fragment<'d>>,
+    type_names: HashSet<String>,
+    schema_types: HashMap<String, String>,
+    pending: Vec<String>,
+    items: Vec<String>,
+}
+
+struct SelectedField<'a, 'd> {
+    field_type: Type<'a>,
+    description: Option<&'a str>,
+    optional: bool,
+    selection_sets: Vec<&'d [Selection<'d>]>,
+}
+
+impl<'g, 'a, 'd> OperationWriter<'g, 'a, 'd> {
+    fn write(
+        &mut self,
+        out: &mut String,
+        module: &str,
+        op: &'d Operation<'d>,
+        document: &Document,
+    ) -> Result<(), ClientError> {
+        for name in &["Variables", "Request", "Response", "ResponseError", "Location",
+                      "Option", "Vec", "Box", "String"]
+        {
+            self.type_names.insert((*name).to_owned());
+        }
+
+        let schema = self.generator.schema;
+        let root_type = match op.operation_type {
+            OperationType::Query => schema.concrete_query_type(),
+            OperationType::Mutation => schema
+                .concrete_mutation_type()
+                .expect("Validated mutation without a mutation type"),
+        };
+        try!(self.selection_struct(
+            "ResponseData".to_owned(),
+            "",
+            root_type,
+            &[&op.selection_set]
+        ));
+        try!(self.variables(op));
+
+        let mut i = 0;
+        while i < self.pending.len() {
+            let name = self.pending[i].clone();
+            try!(self.schema_type(&name));
+            i += 1;
+        }
+
+        let operation_name = match op.name {
+            Some(ref name) => format!("Some({:?})", name.item),
+            None => "None".to_owned(),
+        };
+        let kind = match op.operation_type {
+            OperationType::Query => "query",
+            OperationType::Mutation => "mutation",
+        };
+
+        writeln!(
+            out,
+            "/// Types for the `{}` {}",
+            op.name.as_ref().map(|n| n.item).unwrap_or("anonymous"),
+            kind
+        ).unwrap();
+        out.push_str(
+            "#[allow(dead_code, missing_docs, non_camel_case_types, unused_imports)]\n",
+        );
+        writeln!(out, "pub mod {} {{", module).unwrap();
+        out.push_str("    use super::*;\n\n");
+        writeln!(
+            out,
+            "    pub const OPERATION_NAME: Option<&'static str> = {};",
+            operation_name
+        ).unwrap();
+        writeln!(
+            out,
+            "    pub const QUERY: &'static str = {:?};\n",
+            print_document(document)
+        ).unwrap();
+        out.push_str(REQUEST_ITEMS);
+
+        for item in &self.items {
+            out.push('\n');
+            out.push_str(item);
+        }
+
+        out.push_str("}\n");
+
+        Ok(())
+    }
+
+    fn variables(&mut self, op: &'d Operation<'d>) -> Result<(), ClientError> {
+        let mut body = String::new();
+        let mut field_names = HashSet::new();
+
+        if let Some(ref defs) = op.variable_definitions {
+            for &(ref name, ref def) in &defs.item.items {
+                let rust_type = try!(self.input_type(&def.var_type.item, None));
+                try!(self.input_field(
+                    &mut body,
+                    &mut field_names,
+                    name.item,
+                    None,
+                    &def.var_type.item,
+                    &rust_type
+                ));
+            }
+        }
+
+        self.items.insert(0, format!(
+            "    #[derive(Debug, Clone, PartialEq, Serialize)]\n    pub struct Variables {{\n{}    }}\n",
+            body
+        ));
+
+        Ok(())
+    }
+
+    fn selection_struct(
+        &mut self,
+        base_name: String,
+        prefix: &str,
+        parent: &'a MetaType<'a>,
+        selection_sets: &[&'d [Selection<'d>]],
+    ) -> Result<String, ClientError> {
+        let name = unique_name(&mut self.type_names, base_name);
+        let index = self.items.len();
+        self.items.push(String::new());
+
+        let mut fields = OrderMap::new();
+        for selection_set in selection_sets {
+            self.collect_fields(selection_set, parent, false, &mut fields);
+        }
+
+        let mut body = String::new();
+        let mut field_names = HashSet::new();
+
+        for (key, field) in fields {
+            let rust_name = unique_name(&mut field_names, field_name(key));
+            let mut rust_type = try!(self.output_type(&field, &format!("{}{}", prefix, type_name(key))));
+            if field.optional && field.field_type.is_non_null() {
+                rust_type = format!("Option<{}>", rust_type);
+            }
+
+            write_description(&mut body, field.description, "        ");
+            if rust_name != key {
+                writeln!(body, "        #[serde(rename = {:?})]", key).unwrap();
+            }
+            writeln!(body, "        pub {}: {},", rust_name, rust_type).unwrap();
+        }
+
+        self.items[index] = format!(
+            "    #[derive(Debug, Clone, PartialEq, Deserialize)]\n    pub struct {} {{\n{}    }}\n",
+            name,
+            body
+        );
+
+        Ok(name)
+    }
+
+    fn collect_fields(
+        &self,
+        selection_set: &'d [Selection<'d>],
+        parent: &'a MetaType<'a>,
+        optional: bool,
+        fields: &mut OrderMap<&'d str, SelectedField<'a, 'd>>,
+    ) {
+        let schema = self.generator.schema;
+
+        for selection in selection_set {
+            match *selection {
+                Selection::Field(Spanning { item: ref field, .. }) => {
+                    let key = field.alias.as_ref().unwrap_or(&field.name).item;
+                    let optional = optional || is_conditional(&field.directives);
+
+                    let (field_type, description) = if field.name.item == "__typename" {
+                        (Type::NonNullNamed(Cow::Borrowed("String")), None)
+                    } else {
+                        let meta = parent
+                            .field_by_name(field.name.item)
+                            .expect("Validated field not found");
+                        (
+                            meta.field_type.clone(),
+                            meta.description.as_ref().map(|d| d.as_str()),
+                        )
+                    };
+
+                    let entry = fields.entry(key).or_insert_with(|| SelectedField {
+                        field_type: field_type,
+                        description: description,
+                        optional: optional,
+                        selection_sets: Vec::new(),
+                    });
+                    entry.optional = entry.optional && optional;
+
+                    if let Some(ref selection_set) = field.selection_set {
+                        entry.selection_sets.push(selection_set);
+                    }
+                }
+                Selection::FragmentSpread(Spanning { item: ref spread, .. }) => {
+                    let fragment = self.fragments[spread.name.item];
+                    let type_condition = schema
+                        .concrete_type_by_name(fragment.type_condition.item)
+                        .expect("Validated type condition not found");
+                    let optional = optional || is_conditional(&spread.directives) ||
+                        !self.always_applies(type_condition, parent);
+
+                    self.collect_fields(&fragment.selection_set, type_condition, optional, fields);
+                }
+                Selection::InlineFragment(Spanning { item: ref fragment, .. }) => {
+                    let type_condition = fragment
+                        .type_condition
+                        .as_ref()
+                        .map(|c| {
+                            schema
+                                .concrete_type_by_name(c.item)
+                                .expect("Validated type condition not found")
+                        })
+                        .unwrap_or(parent);
+                    let optional = optional || is_conditional(&fragment.directives) ||
+                        !self.always_applies(type_condition, parent);
+
+                    self.collect_fields(&fragment.selection_set, type_condition, optional, fields);
+                }
+            }
+        }
+    }
+
+    /// Whether every value of the parent type matches a type condition
+    fn always_applies(&self, type_condition: &MetaType, parent: &MetaType) -> bool {
+        type_condition.name() == parent.name() ||
+            type_condition.is_abstract() && !parent.is_abstract() &&
+                self.generator.schema.is_possible_type(type_condition, parent)
+    }
+
+    fn output_type(
+        &mut self,
+        field: &SelectedField<'a, 'd>,
+        struct_name: &str,
+    ) -> Result<String, ClientError> {
+        let schema = self.generator.schema;
+        let name = field.field_type.innermost_name();
+        let meta = schema
+            .concrete_type_by_name(name)
+            .expect("Field type not found");
+
+        let leaf = match *meta {
+            MetaType::Scalar(_) => try!(self.scalar(name)),
+            MetaType::Enum(_) => self.schema_type_name(name),
+            _ => try!(self.selection_struct(
+                struct_name.to_owned(),
+                struct_name,
+                meta,
+                &field.selection_sets
+            )),
+        };
+
+        Ok(wrap_type(&field.field_type, &leaf, false))
+    }
+
+    fn input_type(&mut self, t: &Type, container: Option<&str>) -> Result<String, ClientError> {
+        let name = t.innermost_name();
+        let meta = self.generator
+            .schema
+            .concrete_type_by_name(name)
+            .expect("Input type not found");
+
+        let (leaf, boxed) = match *meta {
+            MetaType::Scalar(_) => (try!(self.scalar(name)), false),
+            _ => (
+                self.schema_type_name(name),
+                container.map_or(false, |c| self.contains_input(name, c, &mut vec![])),
+            ),
+        };
+
+        Ok(wrap_type(t, &leaf, boxed))
+    }
+
+    fn input_field(
+        &mut self,
+        body: &mut String,
+        field_names: &mut HashSet<String>,
+        name: &str,
+        description: Option<&str>,
+        t: &Type,
+        rust_type: &str,
+    ) -> Result<(), ClientError> {
+        let rust_name = unique_name(field_names, field_name(name));
+
+        write_description(body, description, "        ");
+        let mut attributes = Vec::new();
+        if rust_name != name {
+            attributes.push(format!("rename = {:?}", name));
+        }
+        if !t.is_non_null() {
+            attributes.push("skip_serializing_if = \"Option::is_none\"".to_owned());
+        }
+        if !attributes.is_empty() {
+            writeln!(body, "        #[serde({})]", attributes.join(", ")).unwrap();
+        }
+        writeln!(body, "        pub {}: {},", rust_name, rust_type).unwrap();
+
+        Ok(())
+    }
+
+    /// Whether an input object contains another one without a list in
+    /// between, which needs a `Box` to have a finite size
+    fn contains_input(&self, name: &str, target: &str, visited: &mut Vec<String>) -> bool {
+        if name == target {
+            return true;
+        }
+        if visited.iter().any(|v| v == name) {
+            return false;
+        }
+        visited.push(name.to_owned());
+
+        match self.generator.schema.concrete_type_by_name(name) {
+            Some(&MetaType::InputObject(ref input)) => input.input_fields.iter().any(|f| {
+                f.arg_type.name().map_or(false, |n| self.contains_input(n, target, visited))
+            }),
+            _ => false,
+        }
+    }
+
+    fn scalar(&self, name: &str) -> Result<String, ClientError> {
+        self.generator
+            .scalars
+            .get(name)
+            .cloned()
+            .ok_or_else(|| ClientError::UnmappedScalar(name.to_owned()))
+    }
+
+    fn schema_type_name(&mut self, name: &str) -> String {
+        if let Some(rust_name) = self.schema_types.get(name) {
+            return rust_name.clone();
+        }
+
+        let rust_name = unique_name(&mut self.type_names, type_name(name));
+        self.schema_types
+            .insert(name.to_owned(), rust_name.clone());
+        self.pending.push(name.to_owned());
+        rust_name
+    }
+
+    fn schema_type(&mut self, name: &str) -> Result<(), ClientError> {
+        let schema = self.generator.schema;
+        let rust_name = self.schema_types[name].clone();
+        let mut item = String::new();
+
+        match *schema.concrete_type_by_name(name).expect("Type not found") {
+            MetaType::Enum(ref meta) => {
+                write_description(&mut item, meta.description.as_ref().map(|d| d.as_str()), "    ");
+                item.push_str(
+                    "    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]\n",
+                );
+                writeln!(item, "    pub enum {} {{", rust_name).unwrap();
+
+                let mut variant_names = HashSet::new();
+                for value in &meta.values {
+                    write_description(
+                        &mut item,
+                        value.description.as_ref().map(|d| d.as_str()),
+                        "        ",
+                    );
+                    let variant = unique_name(&mut variant_names, variant_name(&value.name));
+                    if variant != value.name {
+                        writeln!(item, "        #[serde(rename = {:?})]", value.name).unwrap();
+                    }
+                    writeln!(item, "        {},", variant).unwrap();
+                }
+
+                item.push_str("    }\n");
+            }
+            MetaType::InputObject(ref meta) => {
+                let mut body = String::new();
+                let mut field_names = HashSet::new();
+
+                for field in &meta.input_fields {
+                    let rust_type = try!(self.input_type(&field.arg_type, Some(name)));
+                    try!(self.input_field(
+                        &mut body,
+                        &mut field_names,
+                        &field.name,
+                        field.description.as_ref().map(|d| d.as_str()),
+                        &field.arg_type,
+                        &rust_type
+                    ));
+                }
+
+                write_description(&mut item, meta.description.as_ref().map(|d| d.as_str()), "    ");
+                item.push_str("    #[derive(Debug, Clone, PartialEq, Serialize)]\n");
+                write!(item, "    pub struct {} {{\n{}    }}\n", rust_name, body).unwrap();
+            }
+            _ => unreachable!("Only enums and input objects are generated from the schema"),
+        }
+
+        self.items.push(item);
+        Ok(())
+    }
+}
+
+const REQUEST_ITEMS: &'static str = r#"    #[derive(Debug, Clone, PartialEq, Serialize)]
+    pub struct Request {
+        pub query: &'static str,
+        #[serde(rename = "operationName")]
+        pub operation_name: Option<&'static str>,
+        pub variables: Variables,
+    }
+
+    impl Request {
+        pub fn new(variables: Variables) -> Request {
+            Request {
+                query: QUERY,
+                operation_name: OPERATION_NAME,
+                variables: variables,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    pub struct Response {
+        pub data: Option<ResponseData>,
+        #[serde(default)]
+        pub errors: Vec<ResponseError>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    pub struct ResponseError {
+        pub message: String,
+        #[serde(default)]
+        pub locations: Vec<Location>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    pub struct Location {
+        pub line: i32,
+        pub column: i32,
+    }
+"#;
+
+/// The operation along with the fragments it uses, in document order
+fn operation_document<'d>(
+    document: &Document<'d>,
+    op: &Operation<'d>,
+    fragments: &HashMap<&'d str, &Fragment<'d>>,
+) -> Document<'d> {
+    let mut used = Vec::new();
+    collect_fragment_names(&op.selection_set, fragments, &mut used);
+
+    document
+        .iter()
+        .filter(|d| match **d {
+            Definition::Operation(ref o) => &o.item as *const _ == op as *const _,
+            Definition::Fragment(ref f) => used.contains(&f.item.name.item),
+        })
+        .cloned()
+        .collect()
+}
+
+fn collect_fragment_names<'d>(
+    selection_set: &[Selection<'d>],
+    fragments: &HashMap<&'d str, &Fragment<'d>>,
+    used: &mut Vec<&'d str>,
+) {
+    for selection in selection_set {
+        match *selection {
+            Selection::Field(ref field) => if let Some(ref s) = field.item.selection_set {
+                collect_fragment_names(s, fragments, used);
+            },
+            Selection::InlineFragment(ref fragment) => {
+                collect_fragment_names(&fragment.item.selection_set, fragments, used);
+            }
+            Selection::FragmentSpread(ref spread) => {
+                let name = spread.item.name.item;
+                if !used.contains(&name) {
+                    used.push(name);
+                    collect_fragment_names(&fragments[name].selection_set, fragments, used);
+                }
+            }
+        }
+    }
+}
+
+fn is_conditional(directives: &Option<Vec<Spanning<Directive>>>) -> bool {
+    directives.as_ref().map_or(false, |directives| {
+        directives
+            .iter()
+            .any(|d| d.item.name.item == "include" || d.item.name.item == "skip")
+    })
+}
+
+fn wrap_type(t: &Type, leaf: &str, boxed: bool) -> String {
+    let named = if boxed {
+        format!("Box<{}>", leaf)
+    } else {
+        leaf.to_owned()
+    };
+
+    match *t {
+        Type::NonNullNamed(_) => named,
+        Type::Named(_) => format!("Option<{}>", named),
+        Type::NonNullList(ref inner) => format!("Vec<{}>", wrap_type(inner, leaf, false)),
+        Type::List(ref inner) => format!("Option<Vec<{}>>", wrap_type(inner, leaf, false)),
+    }
+}
+
+fn write_description(out: &mut String, description: Option<&str>, indent: &str) {
+    if let Some(description) = description {
+        for line in description.lines() {
+            writeln!(out, "{}/// {}", indent, line).unwrap();
+        }
+    }
+}
+
+fn unique_name(names: &mut HashSet<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut i = 2;
+
+    while names.contains(&candidate) {
+        candidate = format!("{}{}", name, i);
+        i += 1;
+    }
+
+    names.insert(candidate.clone());
+    candidate
+}
+
+const KEYWORDS: &'static [&'static str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// `appearsIn` becomes `appears_in`, and keywords get a trailing underscore
+fn field_name(name: &str) -> String {
+    let chars = name.trim_left_matches('_').chars().collect::<Vec<_>>();
+    let mut result = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map_or(false, |n| n.is_lowercase());
+
+            if prev.is_lowercase() || prev.is_numeric() || prev.is_uppercase() && next_is_lower {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+
+    if result.is_empty() || KEYWORDS.contains(&result.as_str()) {
+        result.push('_');
+    }
+    result
+}
+
+/// `appearsIn` becomes `AppearsIn`
+fn type_name(name: &str) -> String {
+    let name = name.trim_left_matches('_');
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) if name != "Self" => first.to_uppercase().chain(chars).collect(),
+        Some(_) => "Self_".to_owned(),
+        None => "Type".to_owned(),
+    }
+}
+
+/// `NEW_HOPE` becomes `NewHope`
+fn variant_name(value: &str) -> String {
+    let mut result = String::new();
+
+    for part in value.split('_').filter(|p| !p.is_empty()) {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            result.extend(first.to_uppercase());
+            result.extend(chars.flat_map(|c| c.to_lowercase()));
+        }
+    }
+
+    if result.is_empty() || result.starts_with(|c: char| c.is_numeric()) {
+        result.insert(0, '_');
+    } else if result == "Self" {
+        result.push('_');
+    }
+    result
+}
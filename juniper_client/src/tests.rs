@@ -0,0 +1,230 @@
+use juniper::tests::model::Database;
+use juniper::{EmptyMutation, SchemaType};
+
+use {generate_client, ClientError, ClientGenerator};
+
+fn star_wars_schema<'a>() -> SchemaType<'a> {
+    SchemaType::new::<Database, EmptyMutation<Database>>(&(), &())
+}
+
+mod inputs {
+    use juniper::Value;
+
+    pub struct Query;
+    pub struct Timestamp;
+
+    graphql_scalar!(Timestamp {
+        resolve(&self) -> Value {
+            Value::int(0)
+        }
+
+        from_input_value(v: &InputValue) -> Option<Timestamp> {
+            v.as_int_value().map(|_| Timestamp)
+        }
+    });
+
+    graphql_input_object!(
+        pub struct Filter {
+            name_prefix: Option<String>,
+            since: Option<Timestamp>,
+            and: Option<Box<Filter>>,
+            any: Option<Vec<Filter>>,
+        }
+    );
+
+    graphql_object!(Query: () |&self| {
+        field search(filter: Filter, limit: Option<i32>) -> Vec<i32> { vec![] }
+        field type_() -> Option<Timestamp> as "When the index was updated" { None }
+    });
+}
+
+fn inputs_schema<'a>() -> SchemaType<'a> {
+    SchemaType::new::<inputs::Query, EmptyMutation<()>>(&(), &())
+}
+
+#[test]
+fn generates_structs_for_selections() {
+    let schema = star_wars_schema();
+    let code = generate_client(
+        &schema,
+        r#"
+        query HeroQuery($episode: Episode, $withFriends: Boolean!) {
+            hero(episode: $episode) {
+                __typename
+                name
+                friends @include(if: $withFriends) { ...Ids }
+                ... on Droid { primaryFunction }
+                appearsIn
+            }
+        }
+
+        query Luke { human(id: "1000") { ...Ids homePlanet } }
+
+        fragment Ids on Character { id }
+        "#,
+    ).unwrap();
+
+    assert!(code.contains(
+        r#"/// Types for the `HeroQuery` query
+#[allow(dead_code, missing_docs, non_camel_case_types, unused_imports)]
+pub mod hero_query {
+    use super::*;
+
+    pub const OPERATION_NAME: Option<&'static str> = Some("HeroQuery");
+    pub const QUERY: &'static str = "query HeroQuery($episode: Episode, $withFriends: Boolean!) {\n  hero(episode: $episode) {\n    __typename\n    name\n    friends @include(if: $withFriends) {\n      ...Ids\n    }\n    ... on Droid {\n      primaryFunction\n    }\n    appearsIn\n  }\n}\n\nfragment Ids on Character {\n  id\n}\n";
+"#
+    ));
+
+    assert!(code.contains(
+        r#"
+    #[derive(Debug, Clone, PartialEq, Serialize)]
+    pub struct Variables {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub episode: Option<Episode>,
+        #[serde(rename = "withFriends")]
+        pub with_friends: bool,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    pub struct ResponseData {
+        pub hero: Option<Hero>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    pub struct Hero {
+        #[serde(rename = "__typename")]
+        pub typename: String,
+        /// The name of the character
+        pub name: Option<String>,
+        /// The friends of the character
+        pub friends: Option<Vec<HeroFriends>>,
+        /// The primary function of the droid
+        #[serde(rename = "primaryFunction")]
+        pub primary_function: Option<String>,
+        /// Which movies they appear in
+        #[serde(rename = "appearsIn")]
+        pub appears_in: Vec<Episode>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    pub struct HeroFriends {
+        /// The id of the character
+        pub id: String,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum Episode {
+        #[serde(rename = "NEW_HOPE")]
+        NewHope,
+        #[serde(rename = "EMPIRE")]
+        Empire,
+        #[serde(rename = "JEDI")]
+        Jedi,
+    }
+}
+"#
+    ));
+
+    // Fragments on the same type are always present, and each module only
+    // contains the fragments its operation uses
+    assert!(code.contains(
+        r#"
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    pub struct Human {
+        /// The id of the character
+        pub id: String,
+        /// The home planet of the human
+        #[serde(rename = "homePlanet")]
+        pub home_planet: Option<String>,
+    }
+"#
+    ));
+    assert!(code.contains(
+        r#"pub const QUERY: &'static str = "query Luke {\n  human(id: \"1000\") {\n    ...Ids\n    homePlanet\n  }\n}\n\nfragment Ids on Character {\n  id\n}\n";"#
+    ));
+    assert_eq!(code.matches("pub struct Variables {").count(), 2);
+}
+
+#[test]
+fn generates_input_objects() {
+    let schema = inputs_schema();
+    let code = ClientGenerator::new(&schema)
+        .scalar("Timestamp", "i64")
+        .generate("query Search($filter: Filter!) { search(filter: $filter) type }")
+        .unwrap();
+
+    assert!(code.contains(
+        r#"
+    #[derive(Debug, Clone, PartialEq, Serialize)]
+    pub struct Variables {
+        pub filter: Filter,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    pub struct ResponseData {
+        pub search: Vec<i32>,
+        /// When the index was updated
+        #[serde(rename = "type")]
+        pub type_: Option<i64>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize)]
+    pub struct Filter {
+        #[serde(rename = "namePrefix", skip_serializing_if = "Option::is_none")]
+        pub name_prefix: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub since: Option<i64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub and: Option<Box<Filter>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub any: Option<Vec<Filter>>,
+    }
+}
+"#
+    ));
+}
+
+#[test]
+fn names_anonymous_operations_after_their_type() {
+    let schema = star_wars_schema();
+    let code = generate_client(&schema, "{ hero { id } }").unwrap();
+
+    assert!(code.contains("pub mod query {"));
+    assert!(code.contains("pub const OPERATION_NAME: Option<&'static str> = None;"));
+}
+
+#[test]
+fn rejects_invalid_documents() {
+    let schema = star_wars_schema();
+
+    match generate_client(&schema, "query Q { hero { unknownField } }") {
+        Err(ClientError::Validation(ref errors)) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(
+                errors[0].message(),
+                r#"Unknown field "unknownField" on type "Character""#
+            );
+        }
+        result => panic!("Expected a validation error, got {:?}", result),
+    }
+
+    match generate_client(&schema, "query Q { hero ") {
+        Err(ClientError::Parse { .. }) => (),
+        result => panic!("Expected a parse error, got {:?}", result),
+    }
+
+    assert_eq!(
+        generate_client(&schema, "query heroQuery { hero { id } } query HeroQuery { hero { name } }"),
+        Err(ClientError::DuplicateOperation("hero_query".to_owned()))
+    );
+}
+
+#[test]
+fn requires_types_for_custom_scalars() {
+    let schema = inputs_schema();
+
+    assert_eq!(
+        generate_client(&schema, "query Q { type }"),
+        Err(ClientError::UnmappedScalar("Timestamp".to_owned()))
+    );
+}
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+
 use syn;
 use syn::*;
 use quote::Tokens;
 
+use diagnostic::{Error, Location, Result};
 use util::*;
 
 
@@ -9,30 +12,32 @@ use util::*;
 struct EnumAttrs {
     name: Option<String>,
     description: Option<String>,
+    rename_all: Option<RenameRule>,
 }
 
 impl EnumAttrs {
-    fn from_input(input: &DeriveInput) -> EnumAttrs {
+    fn from_input(input: &DeriveInput) -> Result<EnumAttrs> {
         let mut res = EnumAttrs::default();
 
-        // Check attributes for name and description.
+        // Check attributes for name, description, and naming policy.
         if let Some(items) = get_graphl_attr(&input.attrs) {
             for item in items {
-                if let Some(val) = keyed_item_value(item, "name", true) {
+                if let Some(val) = try!(keyed_item_value(item, "name", true)) {
                     res.name = Some(val);
                     continue;
                 }
-                if let Some(val) = keyed_item_value(item, "description", true) {
+                if let Some(val) = try!(keyed_item_value(item, "description", true)) {
                     res.description = Some(val);
                     continue;
                 }
-                panic!(format!(
-                    "Unknown attribute for #[derive(GraphQLEnum)]: {:?}",
-                    item
-                ));
+                if let Some(val) = try!(keyed_item_value(item, "rename_all", true)) {
+                    res.rename_all = Some(try!(rename_rule_from_name(&val)));
+                    continue;
+                }
+                return Err(unknown_attr(item, "GraphQLEnum"));
             }
         }
-        res
+        Ok(res)
     }
 }
 
@@ -44,47 +49,52 @@ struct EnumVariantAttrs {
 }
 
 impl EnumVariantAttrs {
-    fn from_input(variant: &Variant) -> EnumVariantAttrs {
+    fn from_input(variant: &Variant) -> Result<EnumVariantAttrs> {
         let mut res = EnumVariantAttrs::default();
 
         // Check attributes for name and description.
         if let Some(items) = get_graphl_attr(&variant.attrs) {
             for item in items {
-                if let Some(val) = keyed_item_value(item, "name", true) {
+                if let Some(val) = try!(keyed_item_value(item, "name", true)) {
                     res.name = Some(val);
                     continue;
                 }
-                if let Some(val) = keyed_item_value(item, "description", true) {
+                if let Some(val) = try!(keyed_item_value(item, "description", true)) {
                     res.description = Some(val);
                     continue;
                 }
-                if let Some(val) = keyed_item_value(item, "deprecated", true) {
+                if let Some(val) = try!(keyed_item_value(item, "deprecated", true)) {
                     res.deprecation = Some(val);
                     continue;
                 }
-                panic!(format!(
-                    "Unknown attribute for #[derive(GraphQLEnum)]: {:?}",
-                    item
-                ));
+                return Err(unknown_attr(item, "GraphQLEnum"));
             }
         }
-        res
+        Ok(res)
     }
 }
 
 
-pub fn impl_enum(ast: &syn::DeriveInput) -> Tokens {
+pub fn impl_enum(ast: &syn::DeriveInput) -> Result<Tokens> {
     let variants = match ast.body {
         Body::Enum(ref var) => var,
         Body::Struct(_) => {
-            panic!("#[derive(GraphlQLEnum)] may only be applied to enums, not to structs");
+            return Err(Error::new(
+                Location::TypeName,
+                "#[derive(GraphQLEnum)] may only be applied to enums, not to structs",
+            ));
         }
     };
 
     // Parse attributes.
     let ident = &ast.ident;
-    let attrs = EnumAttrs::from_input(ast);
-    let name = attrs.name.unwrap_or(ast.ident.to_string());
+    let attrs = try!(EnumAttrs::from_input(ast));
+    let name = try!(type_name_expr(attrs.name.as_ref().map(|n| &n[..]), ast));
+    let generics = add_type_param_bounds(
+        &ast.generics,
+        &["::juniper::GraphQLType<TypeInfo = ()>"],
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let meta_description = match attrs.description {
         Some(descr) => quote!{ let meta = meta.description(#descr); },
@@ -95,21 +105,42 @@ pub fn impl_enum(ast: &syn::DeriveInput) -> Tokens {
     let mut resolves = Vec::<Tokens>::new();
     let mut from_inputs = Vec::<Tokens>::new();
     let mut to_inputs = Vec::<Tokens>::new();
+    let mut value_names = HashMap::new();
 
     for variant in variants {
+        let var_ident = &variant.ident;
+
         if variant.data != VariantData::Unit {
-            panic!(format!(
-                "Invalid enum variant {}.\nGraphQL enums may only contain unit variants.",
-                variant.ident
+            return Err(Error::new(
+                Location::Item(var_ident.to_string()),
+                format!(
+                    "Invalid enum variant {}, GraphQL enums may only contain unit variants",
+                    var_ident
+                ),
             ));
         }
-        let var_attrs = EnumVariantAttrs::from_input(variant);
-        let var_ident = &variant.ident;
+        let var_attrs =
+            try!(EnumVariantAttrs::from_input(variant).map_err(|e| e.within(var_ident.as_ref())));
 
         // Build value.
-        let name = var_attrs
-            .name
-            .unwrap_or(variant.ident.as_ref().to_uppercase());
+        let default = match attrs.rename_all {
+            Some(rule) => rule.apply_to_variant(var_ident.as_ref()).into_owned(),
+            // Note: uppercasing when neither a custom name nor a naming
+            // policy is specified.
+            None => var_ident.as_ref().to_uppercase(),
+        };
+        let name = try!(item_name(
+            var_ident.as_ref(),
+            var_attrs.name.clone(),
+            default,
+            &mut value_names,
+        ));
+        if name == "true" || name == "false" || name == "null" {
+            return Err(Error::new(
+                Location::Item(var_ident.to_string()),
+                format!("\"{}\" can not be used as the name of an enum value", name),
+            ));
+        }
         let descr = match var_attrs.description {
             Some(s) => quote!{ Some(#s.to_string())  },
             None => quote!{ None },
@@ -147,17 +178,17 @@ pub fn impl_enum(ast: &syn::DeriveInput) -> Tokens {
         to_inputs.push(to_input);
     }
 
-    quote! {
-        impl ::juniper::GraphQLType for #ident {
+    Ok(quote! {
+        impl #impl_generics ::juniper::GraphQLType for #ident #ty_generics #where_clause {
             type Context = ();
             type TypeInfo = ();
 
-            fn name(_: &()) -> Option<&'static str> {
+            fn name(_: &()) -> Option<&str> {
                 Some(#name)
             }
 
             fn meta<'r>(_: &(), registry: &mut ::juniper::Registry<'r>) -> ::juniper::meta::MetaType<'r> {
-                let meta = registry.build_enum_type::<#ident>(&(), &[
+                let meta = registry.build_enum_type::<#ident #ty_generics>(&(), &[
                     #(#values)*
                 ]);
                 #meta_description
@@ -171,21 +202,28 @@ pub fn impl_enum(ast: &syn::DeriveInput) -> Tokens {
             }
         }
 
-        impl ::juniper::FromInputValue for #ident {
-            fn from_input_value(v: &::juniper::InputValue) -> Option<#ident> {
+        impl #impl_generics ::juniper::FromInputValue for #ident #ty_generics #where_clause {
+            fn from_input_value(v: &::juniper::InputValue) -> Option<#ident #ty_generics> {
                 match v.as_enum_value().or_else(|| v.as_string_value()) {
                     #(#from_inputs)*
                     _ => None,
                 }
             }
+
+            fn try_from_input_value(
+                v: &::juniper::InputValue,
+            ) -> Result<#ident #ty_generics, ::juniper::InputValueError> {
+                <Self as ::juniper::FromInputValue>::from_input_value(v)
+                    .ok_or_else(|| ::juniper::InputValueError::expected(#name, v))
+            }
         }
 
-        impl ::juniper::ToInputValue for #ident {
+        impl #impl_generics ::juniper::ToInputValue for #ident #ty_generics #where_clause {
             fn to_input_value(&self) -> ::juniper::InputValue {
                 match self {
                     #(#to_inputs)*
                 }
             }
         }
-    }
+    })
 }
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+
 use syn;
 use syn::*;
 use quote::Tokens;
 
+use diagnostic::{Error, Location, Result};
 use util::*;
 
 
@@ -9,30 +12,32 @@ use util::*;
 struct ObjAttrs {
     name: Option<String>,
     description: Option<String>,
+    rename_all: Option<RenameRule>,
 }
 
 impl ObjAttrs {
-    fn from_input(input: &DeriveInput) -> ObjAttrs {
+    fn from_input(input: &DeriveInput) -> Result<ObjAttrs> {
         let mut res = ObjAttrs::default();
 
-        // Check attributes for name and description.
+        // Check attributes for name, description, and naming policy.
         if let Some(items) = get_graphl_attr(&input.attrs) {
             for item in items {
-                if let Some(val) = keyed_item_value(item, "name", true) {
+                if let Some(val) = try!(keyed_item_value(item, "name", true)) {
                     res.name = Some(val);
                     continue;
                 }
-                if let Some(val) = keyed_item_value(item, "description", true) {
+                if let Some(val) = try!(keyed_item_value(item, "description", true)) {
                     res.description = Some(val);
                     continue;
                 }
-                panic!(format!(
-                    "Unknown attribute for #[derive(GraphQLInputObject)]: {:?}",
-                    item
-                ));
+                if let Some(val) = try!(keyed_item_value(item, "rename_all", true)) {
+                    res.rename_all = Some(try!(rename_rule_from_name(&val)));
+                    continue;
+                }
+                return Err(unknown_attr(item, "GraphQLInputObject"));
             }
         }
-        res
+        Ok(res)
     }
 }
 
@@ -41,56 +46,142 @@ struct ObjFieldAttrs {
     name: Option<String>,
     description: Option<String>,
     default: Option<String>,
+    validators: Vec<Tokens>,
 }
 
 impl ObjFieldAttrs {
-    fn from_input(variant: &Field) -> ObjFieldAttrs {
+    fn from_input(variant: &Field) -> Result<ObjFieldAttrs> {
         let mut res = ObjFieldAttrs::default();
 
         // Check attributes for name and description.
         if let Some(items) = get_graphl_attr(&variant.attrs) {
             for item in items {
-                if let Some(val) = keyed_item_value(item, "name", true) {
+                if let Some(val) = try!(keyed_item_value(item, "name", true)) {
                     res.name = Some(val);
                     continue;
                 }
-                if let Some(val) = keyed_item_value(item, "description", true) {
+                if let Some(val) = try!(keyed_item_value(item, "description", true)) {
                     res.description = Some(val);
                     continue;
                 }
-                if let Some(val) = keyed_item_value(item, "default", true) {
+                if let Some(val) = try!(keyed_item_value(item, "default", true)) {
                     res.default = Some(val);
                     continue;
                 }
-                panic!(format!(
-                    "Unknown attribute for #[derive(GraphQLInputObject)]: {:?}",
-                    item
-                ));
+                if let Some(validator) = try!(validator_attr(item)) {
+                    res.validators.push(validator);
+                    continue;
+                }
+                return Err(unknown_attr(item, "GraphQLInputObject"));
             }
         }
-        res
+        Ok(res)
     }
 }
 
-pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
+/// Parse a validator attribute, e.g. `min = "1"` or `pattern = "^[a-z]+$"`
+fn validator_attr(item: &NestedMetaItem) -> Result<Option<Tokens>> {
+    for &key in &["min", "max"] {
+        if let Some(val) = try!(keyed_item_value(item, key, true)) {
+            let bound = try!(val.parse::<f64>().map_err(|_| {
+                Error::new(
+                    Location::Attr(key.to_owned()),
+                    format!("Invalid value for attribute \"{}\": expected a number", key),
+                )
+            }));
+
+            return Ok(Some(if key == "min" {
+                quote!{ ::juniper::meta::Validator::Min(#bound) }
+            } else {
+                quote!{ ::juniper::meta::Validator::Max(#bound) }
+            }));
+        }
+    }
+
+    for &key in &["min_length", "max_length"] {
+        if let Some(val) = try!(keyed_item_value(item, key, true)) {
+            let length = try!(val.parse::<usize>().map_err(|_| {
+                Error::new(
+                    Location::Attr(key.to_owned()),
+                    format!(
+                        "Invalid value for attribute \"{}\": expected a non-negative integer",
+                        key
+                    ),
+                )
+            }));
+
+            return Ok(Some(if key == "min_length" {
+                quote!{ ::juniper::meta::Validator::MinLength(#length) }
+            } else {
+                quote!{ ::juniper::meta::Validator::MaxLength(#length) }
+            }));
+        }
+    }
+
+    if let Some(val) = try!(keyed_item_value(item, "pattern", true)) {
+        return Ok(Some(quote!{ ::juniper::meta::Validator::pattern(#val) }));
+    }
+
+    Ok(None)
+}
+
+#[derive(Default)]
+struct OneOfVariantAttrs {
+    name: Option<String>,
+    description: Option<String>,
+}
+
+impl OneOfVariantAttrs {
+    fn from_input(variant: &Variant) -> Result<OneOfVariantAttrs> {
+        let mut res = OneOfVariantAttrs::default();
+
+        // Check attributes for name and description.
+        if let Some(items) = get_graphl_attr(&variant.attrs) {
+            for item in items {
+                if let Some(val) = try!(keyed_item_value(item, "name", true)) {
+                    res.name = Some(val);
+                    continue;
+                }
+                if let Some(val) = try!(keyed_item_value(item, "description", true)) {
+                    res.description = Some(val);
+                    continue;
+                }
+                return Err(unknown_attr(item, "GraphQLInputObject"));
+            }
+        }
+        Ok(res)
+    }
+}
+
+pub fn impl_input_object(ast: &syn::DeriveInput) -> Result<Tokens> {
     let fields = match ast.body {
         Body::Struct(ref data) => match data {
             &VariantData::Struct(ref fields) => fields,
             _ => {
-                panic!(
-                    "#[derive(GraphQLInputObject)] may only be used on regular structs with fields"
-                );
+                return Err(Error::new(
+                    Location::TypeName,
+                    "#[derive(GraphQLInputObject)] may only be used on regular structs with fields",
+                ));
             }
         },
-        Body::Enum(_) => {
-            panic!("#[derive(GraphlQLInputObject)] may only be applied to structs, not to enums");
-        }
+        Body::Enum(ref variants) => return impl_one_of(ast, variants),
     };
 
     // Parse attributes.
     let ident = &ast.ident;
-    let attrs = ObjAttrs::from_input(ast);
-    let name = attrs.name.unwrap_or(ast.ident.to_string());
+    let attrs = try!(ObjAttrs::from_input(ast));
+    let name = try!(type_name_expr(attrs.name.as_ref().map(|n| &n[..]), ast));
+    let rename_all = attrs.rename_all.unwrap_or(RenameRule::CamelCase);
+
+    let generics = add_type_param_bounds(
+        &ast.generics,
+        &[
+            "::juniper::GraphQLType<TypeInfo = ()>",
+            "::juniper::FromInputValue",
+            "::juniper::ToInputValue",
+        ],
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let meta_description = match attrs.description {
         Some(descr) => quote!{ let meta = meta.description(#descr); },
@@ -100,23 +191,23 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
     let mut meta_fields = Vec::<Tokens>::new();
     let mut from_inputs = Vec::<Tokens>::new();
     let mut to_inputs = Vec::<Tokens>::new();
+    let mut field_names = HashMap::new();
 
     for field in fields {
         let field_ty = &field.ty;
-        let field_attrs = ObjFieldAttrs::from_input(field);
         let field_ident = field.ident.as_ref().unwrap();
+        let field_attrs =
+            try!(ObjFieldAttrs::from_input(field).map_err(|e| e.within(field_ident.as_ref())));
 
         // Build value.
-        let name = match field_attrs.name {
-            Some(ref name) => {
-                // Custom name specified.
-                name.to_string()
-            }
-            None => {
-                // Note: auto camel casing when no custom name specified.
-                ::util::to_camel_case(field_ident.as_ref())
-            }
-        };
+        // Note: auto camel casing when neither a custom name nor a naming
+        // policy is specified.
+        let name = try!(item_name(
+            field_ident.as_ref(),
+            field_attrs.name.clone(),
+            rename_all.apply(field_ident.as_ref()).into_owned(),
+            &mut field_names,
+        ));
         let field_description = match field_attrs.description {
             Some(s) => quote!{ let field = field.description(#s); },
             None => quote!{ let field = field; },
@@ -126,7 +217,10 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
             Some(ref def) => match syn::parse_token_trees(def) {
                 Ok(t) => Some(quote!{ #(#t)* }),
                 Err(_) => {
-                    panic!("#graphql(default = ?) must be a valid Rust expression inside a string");
+                    return Err(Error::new(
+                        Location::ItemAttr(field_ident.to_string(), "default".to_owned()),
+                        "#[graphql(default = ?)] must be a valid Rust expression inside a string",
+                    ));
                 }
             },
             None => None,
@@ -144,10 +238,12 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
                 }
             }
         };
+        let validators = &field_attrs.validators;
         let meta_field = quote!{
             {
                 #create_meta_field
                 #field_description
+                #( let field = field.validator(#validators); )*
                 field
             },
         };
@@ -158,7 +254,7 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
         let from_input_default = match default {
             Some(ref def) => {
                 quote!{
-                    Some(&&::juniper::InputValue::Null) | None if true => #def,
+                    Some(&&::juniper::InputValue::Null) | None if true => Ok(#def),
                 }
             }
             None => quote!{},
@@ -166,12 +262,13 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
 
         let from_input = quote!{
             #field_ident: {
-                // TODO: investigate the unwraps here, they seem dangerous!
-                match obj.get(#name) {
+                let value = match obj.get(#name) {
                     #from_input_default
-                    Some(v) => ::juniper::FromInputValue::from_input_value(v).unwrap(),
-                    _ => ::juniper::FromInputValue::from_input_value(&::juniper::InputValue::null()).unwrap()
-                }
+                    Some(v) => ::juniper::FromInputValue::try_from_input_value(v),
+                    _ => ::juniper::FromInputValue::try_from_input_value(&::juniper::InputValue::null())
+                };
+
+                try!(value.map_err(|e: ::juniper::InputValueError| e.in_field(#name)))
             },
         };
         from_inputs.push(from_input);
@@ -183,12 +280,12 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
         to_inputs.push(to_input);
     }
 
-    quote! {
-        impl ::juniper::GraphQLType for #ident {
+    Ok(quote! {
+        impl #impl_generics ::juniper::GraphQLType for #ident #ty_generics #where_clause {
             type Context = ();
             type TypeInfo = ();
 
-            fn name(_: &()) -> Option<&'static str> {
+            fn name(_: &()) -> Option<&str> {
                 Some(#name)
             }
 
@@ -196,32 +293,179 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
                 let fields = &[
                     #(#meta_fields)*
                 ];
-                let meta = registry.build_input_object_type::<#ident>(&(), fields);
+                let meta = registry.build_input_object_type::<#ident #ty_generics>(&(), fields);
                 #meta_description
                 meta.into_meta()
             }
         }
 
-        impl ::juniper::FromInputValue for #ident {
-            fn from_input_value(value: &::juniper::InputValue) -> Option<#ident> {
+        impl #impl_generics ::juniper::FromInputValue for #ident #ty_generics #where_clause {
+            fn from_input_value(value: &::juniper::InputValue) -> Option<#ident #ty_generics> {
+                <Self as ::juniper::FromInputValue>::try_from_input_value(value).ok()
+            }
+
+            fn try_from_input_value(
+                value: &::juniper::InputValue,
+            ) -> Result<#ident #ty_generics, ::juniper::InputValueError> {
                 if let Some(obj) = value.to_object_value() {
                     let item = #ident {
                         #(#from_inputs)*
                     };
-                    Some(item)
+                    Ok(item)
                 }
                 else {
-                    None
+                    Err(::juniper::InputValueError::expected(#name, value))
                 }
             }
         }
 
-        impl ::juniper::ToInputValue for #ident {
+        impl #impl_generics ::juniper::ToInputValue for #ident #ty_generics #where_clause {
             fn to_input_value(&self) -> ::juniper::InputValue {
                 ::juniper::InputValue::object(vec![
                     #(#to_inputs)*
                 ].into_iter().collect())
             }
         }
+    })
+}
+
+/// Derive a oneOf input object for an enum with one single-field variant per
+/// input field
+fn impl_one_of(ast: &syn::DeriveInput, variants: &[Variant]) -> Result<Tokens> {
+    // Parse attributes.
+    let ident = &ast.ident;
+    let attrs = try!(ObjAttrs::from_input(ast));
+    let name = try!(type_name_expr(attrs.name.as_ref().map(|n| &n[..]), ast));
+    let rename_all = attrs.rename_all.unwrap_or(RenameRule::CamelCase);
+
+    let generics = add_type_param_bounds(
+        &ast.generics,
+        &[
+            "::juniper::GraphQLType<TypeInfo = ()>",
+            "::juniper::FromInputValue",
+            "::juniper::ToInputValue",
+        ],
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let meta_description = match attrs.description {
+        Some(descr) => quote!{ let meta = meta.description(#descr); },
+        None => quote!{ let meta = meta; },
+    };
+
+    let mut meta_fields = Vec::<Tokens>::new();
+    let mut from_inputs = Vec::<Tokens>::new();
+    let mut to_inputs = Vec::<Tokens>::new();
+    let mut field_names = HashMap::new();
+
+    for variant in variants {
+        let var_ident = &variant.ident;
+        let var_ty = match variant.data {
+            VariantData::Tuple(ref fields) if fields.len() == 1 => &fields[0].ty,
+            _ => {
+                return Err(Error::new(
+                    Location::Item(var_ident.to_string()),
+                    format!(
+                        "Invalid enum variant {}, GraphQL oneOf input objects may only \
+                         contain variants with a single unnamed field",
+                        var_ident
+                    ),
+                ));
+            }
+        };
+        let var_attrs =
+            try!(OneOfVariantAttrs::from_input(variant).map_err(|e| e.within(var_ident.as_ref())));
+
+        // Build value.
+        let name = try!(item_name(
+            var_ident.as_ref(),
+            var_attrs.name.clone(),
+            rename_all.apply_to_variant(var_ident.as_ref()).into_owned(),
+            &mut field_names,
+        ));
+        let field_description = match var_attrs.description {
+            Some(s) => quote!{ let field = field.description(#s); },
+            None => quote!{ let field = field; },
+        };
+
+        // All fields of a oneOf input object are nullable.
+        meta_fields.push(quote!{
+            {
+                let field = registry.arg::<Option<#var_ty>>(#name, &());
+                #field_description
+                field
+            },
+        });
+
+        // Build from_input clause.
+        from_inputs.push(quote!{
+            #name => ::juniper::FromInputValue::try_from_input_value(value)
+                .map(#ident::#var_ident)
+                .map_err(|e: ::juniper::InputValueError| e.in_field(#name)),
+        });
+
+        // Build to_input clause.
+        to_inputs.push(quote!{
+            &#ident::#var_ident(ref value) => ::juniper::InputValue::object(vec![
+                (#name, ::juniper::ToInputValue::to_input_value(value)),
+            ].into_iter().collect()),
+        });
     }
+
+    Ok(quote! {
+        impl #impl_generics ::juniper::GraphQLType for #ident #ty_generics #where_clause {
+            type Context = ();
+            type TypeInfo = ();
+
+            fn name(_: &()) -> Option<&str> {
+                Some(#name)
+            }
+
+            fn meta<'r>(_: &(), registry: &mut ::juniper::Registry<'r>) -> ::juniper::meta::MetaType<'r> {
+                let fields = &[
+                    #(#meta_fields)*
+                ];
+                let meta = registry
+                    .build_input_object_type::<#ident #ty_generics>(&(), fields)
+                    .one_of();
+                #meta_description
+                meta.into_meta()
+            }
+        }
+
+        impl #impl_generics ::juniper::FromInputValue for #ident #ty_generics #where_clause {
+            fn from_input_value(value: &::juniper::InputValue) -> Option<#ident #ty_generics> {
+                <Self as ::juniper::FromInputValue>::try_from_input_value(value).ok()
+            }
+
+            fn try_from_input_value(
+                value: &::juniper::InputValue,
+            ) -> Result<#ident #ty_generics, ::juniper::InputValueError> {
+                let obj = match value.to_object_value() {
+                    Some(obj) => obj,
+                    None => return Err(::juniper::InputValueError::expected(#name, value)),
+                };
+
+                let len = obj.len();
+
+                match obj.into_iter().next() {
+                    Some((key, value)) if len == 1 && !value.is_null() => match key {
+                        #(#from_inputs)*
+                        _ => Err(::juniper::InputValueError::new(
+                            format!("Unknown field \"{}\"", key))),
+                    },
+                    _ => Err(::juniper::InputValueError::new(
+                        format!("Expected exactly one non-null field for \"{}\"", #name))),
+                }
+            }
+        }
+
+        impl #impl_generics ::juniper::ToInputValue for #ident #ty_generics #where_clause {
+            fn to_input_value(&self) -> ::juniper::InputValue {
+                match self {
+                    #(#to_inputs)*
+                }
+            }
+        }
+    })
 }
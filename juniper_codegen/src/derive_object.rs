@@ -1,37 +1,42 @@
+use std::collections::HashMap;
+
 use syn;
 use syn::*;
 use quote::Tokens;
 
+use diagnostic::{Error, Location, Result};
 use util::*;
 
 #[derive(Default, Debug)]
 struct ObjAttrs {
     name: Option<String>,
     description: Option<String>,
+    rename_all: Option<RenameRule>,
 }
 
 impl ObjAttrs {
-    fn from_input(input: &DeriveInput) -> ObjAttrs {
+    fn from_input(input: &DeriveInput) -> Result<ObjAttrs> {
         let mut res = ObjAttrs::default();
 
-        // Check attributes for name and description.
+        // Check attributes for name, description, and naming policy.
         if let Some(items) = get_graphl_attr(&input.attrs) {
             for item in items {
-                if let Some(val) = keyed_item_value(item, "name", true) {
+                if let Some(val) = try!(keyed_item_value(item, "name", true)) {
                     res.name = Some(val);
                     continue;
                 }
-                if let Some(val) = keyed_item_value(item, "description", true) {
+                if let Some(val) = try!(keyed_item_value(item, "description", true)) {
                     res.description = Some(val);
                     continue;
                 }
-                panic!(format!(
-                    "Unknown attribute for #[derive(GraphQLObject)]: {:?}",
-                    item
-                ));
+                if let Some(val) = try!(keyed_item_value(item, "rename_all", true)) {
+                    res.rename_all = Some(try!(rename_rule_from_name(&val)));
+                    continue;
+                }
+                return Err(unknown_attr(item, "GraphQLObject"));
             }
         }
-        res
+        Ok(res)
     }
 }
 
@@ -43,52 +48,64 @@ struct ObjFieldAttrs {
 }
 
 impl ObjFieldAttrs {
-    fn from_input(variant: &Field) -> ObjFieldAttrs {
+    fn from_input(variant: &Field) -> Result<ObjFieldAttrs> {
         let mut res = ObjFieldAttrs::default();
 
         // Check attributes for name and description.
         if let Some(items) = get_graphl_attr(&variant.attrs) {
             for item in items {
-                if let Some(val) = keyed_item_value(item, "name", true) {
+                if let Some(val) = try!(keyed_item_value(item, "name", true)) {
                     res.name = Some(val);
                     continue;
                 }
-                if let Some(val) = keyed_item_value(item, "description", true) {
+                if let Some(val) = try!(keyed_item_value(item, "description", true)) {
                     res.description = Some(val);
                     continue;
                 }
-                if let Some(val) = keyed_item_value(item, "deprecation", true) {
+                if let Some(val) = try!(keyed_item_value(item, "deprecation", true)) {
                     res.deprecation = Some(val);
                     continue;
                 }
-                panic!(format!(
-                    "Unknown attribute for #[derive(GraphQLObject)]: {:?}",
-                    item
-                ));
+                return Err(unknown_attr(item, "GraphQLObject"));
             }
         }
-        res
+        Ok(res)
     }
 }
 
-pub fn impl_object(ast: &syn::DeriveInput) -> Tokens {
+pub fn impl_object(ast: &syn::DeriveInput) -> Result<Tokens> {
     let fields = match ast.body {
         Body::Struct(ref data) => match data {
             &VariantData::Struct(ref fields) => fields,
             _ => {
-                panic!("#[derive(GraphQLObject)] may only be used on regular structs with fields");
+                return Err(Error::new(
+                    Location::TypeName,
+                    "#[derive(GraphQLObject)] may only be used on regular structs with fields",
+                ));
             }
         },
         Body::Enum(_) => {
-            panic!("#[derive(GraphlQLObject)] may only be applied to structs, not to enums");
+            return Err(Error::new(
+                Location::TypeName,
+                "#[derive(GraphQLObject)] may only be applied to structs, not to enums",
+            ));
         }
     };
 
     // Parse attributes.
     let ident = &ast.ident;
     let ident_name = ident.to_string();
-    let attrs = ObjAttrs::from_input(ast);
-    let name = attrs.name.unwrap_or(ast.ident.to_string());
+    let attrs = try!(ObjAttrs::from_input(ast));
+    let name = try!(type_name_expr(attrs.name.as_ref().map(|n| &n[..]), ast));
+    let rename_all = attrs.rename_all.unwrap_or(RenameRule::CamelCase);
+
+    // Fields are resolved without a context, so type parameters must be
+    // context-free as well.
+    let generics = add_type_param_bounds(
+        &ast.generics,
+        &["::juniper::GraphQLType<Context = (), TypeInfo = ()>"],
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let build_description = match attrs.description {
         Some(s) => quote!{ builder.description(#s)  },
         None => quote!{ builder },
@@ -96,23 +113,23 @@ pub fn impl_object(ast: &syn::DeriveInput) -> Tokens {
 
     let mut meta_fields = Vec::<Tokens>::new();
     let mut resolvers = Vec::<Tokens>::new();
+    let mut field_names = HashMap::new();
 
     for field in fields {
         let field_ty = &field.ty;
-        let field_attrs = ObjFieldAttrs::from_input(field);
         let field_ident = field.ident.as_ref().unwrap();
+        let field_attrs =
+            try!(ObjFieldAttrs::from_input(field).map_err(|e| e.within(field_ident.as_ref())));
 
         // Build value.
-        let name = match field_attrs.name {
-            Some(ref name) => {
-                // Custom name specified.
-                name.to_string()
-            }
-            None => {
-                // Note: auto camel casing when no custom name specified.
-                ::util::to_camel_case(field_ident.as_ref())
-            }
-        };
+        // Note: auto camel casing when neither a custom name nor a naming
+        // policy is specified.
+        let name = try!(item_name(
+            field_ident.as_ref(),
+            field_attrs.name.clone(),
+            rename_all.apply(field_ident.as_ref()).into_owned(),
+            &mut field_names,
+        ));
         let build_description = match field_attrs.description {
             Some(s) => quote!{ field.description(#s)  },
             None => quote!{ field },
@@ -137,13 +154,13 @@ pub fn impl_object(ast: &syn::DeriveInput) -> Tokens {
 
 
         let resolver = quote!{
-            #name => executor.resolve_with_ctx(&(), &self.#field_ident),
+            #name => executor.resolve_field_value_with_ctx(&(), &self.#field_ident),
         };
         resolvers.push(resolver);
     }
 
-    let toks = quote! {
-        impl ::juniper::GraphQLType for #ident {
+    Ok(quote! {
+        impl #impl_generics ::juniper::GraphQLType for #ident #ty_generics #where_clause {
             type Context = ();
             type TypeInfo = ();
 
@@ -159,7 +176,7 @@ pub fn impl_object(ast: &syn::DeriveInput) -> Tokens {
                 let fields = &[
                     #(#meta_fields)*
                 ];
-                let builder = registry.build_object_type::<#ident>(&(), fields);
+                let builder = registry.build_object_type::<#ident #ty_generics>(&(), fields);
                 let builder = #build_description;
                 builder.into_meta()
             }
@@ -174,8 +191,10 @@ pub fn impl_object(ast: &syn::DeriveInput) -> Tokens {
                 }
 
             }
-        }
-    };
 
-    toks
+            fn stream(&self, info: &(), selection_set: Option<&[::juniper::Selection]>, executor: &::juniper::Executor<Self::Context>) -> bool {
+                ::juniper::stream_selection_set(self, info, selection_set, executor)
+            }
+        }
+    })
 }
@@ -0,0 +1,203 @@
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// The part of the derive input that an error should point at
+///
+/// `syn` does not keep track of spans, so errors refer to the input by name
+/// instead. The names are resolved against the original token stream when
+/// the error is reported.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Location {
+    /// The whole derive input
+    CallSite,
+    /// The name of the deriving type
+    TypeName,
+    /// A key in the type's `#[graphql(...)]` attribute
+    Attr(String),
+    /// A field or enum variant
+    Item(String),
+    /// A key in the `#[graphql(...)]` attribute of a field or enum variant
+    ItemAttr(String, String),
+}
+
+/// A compile error raised by one of the derives
+#[derive(Debug)]
+pub struct Error {
+    pub location: Location,
+    pub message: String,
+}
+
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+impl Error {
+    pub fn new<S: Into<String>>(location: Location, message: S) -> Error {
+        Error {
+            location: location,
+            message: message.into(),
+        }
+    }
+
+    /// Move an error about a type level attribute to the attribute of the
+    /// field or variant `item`.
+    pub fn within(self, item: &str) -> Error {
+        let location = match self.location {
+            Location::Attr(key) => Location::ItemAttr(item.to_owned(), key),
+            other => other,
+        };
+
+        Error {
+            location: location,
+            message: self.message,
+        }
+    }
+
+    /// Turn the error into a `compile_error!` invocation spanning the
+    /// offending part of `input`.
+    pub fn into_compile_error(self, input: TokenStream) -> TokenStream {
+        let tokens = input.into_iter().collect::<Vec<_>>();
+        let span = find_span(&tokens, &self.location).unwrap_or_else(Span::call_site);
+
+        let mut message = Literal::string(&self.message);
+        message.set_span(span);
+
+        let mut args = Group::new(Delimiter::Parenthesis, TokenTree::Literal(message).into());
+        args.set_span(span);
+
+        let mut bang = Punct::new('!', Spacing::Alone);
+        bang.set_span(span);
+
+        let mut semi = Punct::new(';', Spacing::Alone);
+        semi.set_span(span);
+
+        vec![
+            TokenTree::Ident(Ident::new("compile_error", span)),
+            TokenTree::Punct(bang),
+            TokenTree::Group(args),
+            TokenTree::Punct(semi),
+        ].into_iter()
+            .collect()
+    }
+}
+
+fn find_span(tokens: &[TokenTree], location: &Location) -> Option<Span> {
+    let keyword = match tokens.iter().position(|t| is_ident(t, "struct") || is_ident(t, "enum")) {
+        Some(keyword) => keyword,
+        None => return None,
+    };
+    let is_struct = is_ident(&tokens[keyword], "struct");
+    let body = tokens[keyword..].iter().rev().find(|t| match **t {
+        TokenTree::Group(ref g) => {
+            g.delimiter() == Delimiter::Brace || g.delimiter() == Delimiter::Parenthesis
+        }
+        _ => false,
+    });
+    let type_name = tokens.get(keyword + 1).map(TokenTree::span);
+
+    match *location {
+        Location::CallSite => None,
+        Location::TypeName => type_name,
+        Location::Attr(ref key) => {
+            find_attr_key(&attrs_of(&tokens[..keyword]), key).or(type_name)
+        }
+        Location::Item(ref name) | Location::ItemAttr(ref name, _) => {
+            let body = match body {
+                Some(&TokenTree::Group(ref g)) => g.stream().into_iter().collect::<Vec<_>>(),
+                _ => return type_name,
+            };
+            let (item, attrs) = match find_item(&body, name, is_struct) {
+                Some(found) => found,
+                None => return type_name,
+            };
+
+            match *location {
+                Location::ItemAttr(_, ref key) => find_attr_key(&attrs, key).or(Some(item)),
+                _ => Some(item),
+            }
+        }
+    }
+}
+
+/// Find the field or variant `name` in the body of the deriving type,
+/// returning its span along with the attributes attached to it.
+fn find_item(body: &[TokenTree], name: &str, is_struct: bool) -> Option<(Span, Vec<Group>)> {
+    let mut attrs = Vec::new();
+    let mut depth = 0;
+    let mut i = 0;
+
+    while i < body.len() {
+        match body[i] {
+            TokenTree::Punct(ref p) if p.as_char() == '#' => {
+                if let Some(&TokenTree::Group(ref g)) = body.get(i + 1) {
+                    attrs.push(g.clone());
+                    i += 2;
+                    continue;
+                }
+            }
+            // Commas inside of generic arguments do not separate items.
+            TokenTree::Punct(ref p) if p.as_char() == '<' => depth += 1,
+            TokenTree::Punct(ref p) if p.as_char() == '>' && depth > 0 => depth -= 1,
+            TokenTree::Punct(ref p) if p.as_char() == ',' && depth == 0 => attrs.clear(),
+            TokenTree::Ident(ref ident) if depth == 0 && ident.to_string() == name => {
+                let is_field = match body.get(i + 1) {
+                    Some(&TokenTree::Punct(ref p)) => {
+                        p.as_char() == ':' && p.spacing() == Spacing::Alone
+                    }
+                    _ => false,
+                };
+
+                if is_field == is_struct {
+                    return Some((ident.span(), attrs));
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Collect the bracketed attribute groups from a list of tokens.
+fn attrs_of(tokens: &[TokenTree]) -> Vec<Group> {
+    tokens
+        .windows(2)
+        .filter_map(|w| match (&w[0], &w[1]) {
+            (&TokenTree::Punct(ref p), &TokenTree::Group(ref g))
+                if p.as_char() == '#' && g.delimiter() == Delimiter::Bracket =>
+            {
+                Some(g.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Find `key` within the `graphql(...)` attributes among `attrs`, falling
+/// back to the first `graphql` identifier.
+fn find_attr_key(attrs: &[Group], key: &str) -> Option<Span> {
+    let mut fallback = None;
+
+    for attr in attrs {
+        let tokens = attr.stream().into_iter().collect::<Vec<_>>();
+
+        if let (Some(name), Some(&TokenTree::Group(ref args))) = (tokens.get(0), tokens.get(1)) {
+            if !is_ident(name, "graphql") {
+                continue;
+            }
+
+            if let Some(found) = args.stream().into_iter().find(|t| is_ident(t, key)) {
+                return Some(found.span());
+            }
+
+            fallback = fallback.or_else(|| Some(name.span()));
+        }
+    }
+
+    fallback
+}
+
+fn is_ident(token: &TokenTree, name: &str) -> bool {
+    match *token {
+        TokenTree::Ident(ref ident) => ident.to_string() == name,
+        _ => false,
+    }
+}
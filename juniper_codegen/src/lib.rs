@@ -1,37 +1,49 @@
 #![recursion_limit = "1024"]
 
+extern crate juniper;
 extern crate proc_macro;
 extern crate syn;
 #[macro_use]
 extern crate quote;
 
+mod diagnostic;
 mod util;
 mod derive_enum;
 mod derive_input_object;
 mod derive_object;
 
 use proc_macro::TokenStream;
+use quote::Tokens;
+
+use diagnostic::{Error, Location};
 
 #[proc_macro_derive(GraphQLEnum, attributes(graphql))]
 pub fn derive_enum(input: TokenStream) -> TokenStream {
-    let s = input.to_string();
-    let ast = syn::parse_derive_input(&s).unwrap();
-    let gen = derive_enum::impl_enum(&ast);
-    gen.parse().unwrap()
+    expand(input, derive_enum::impl_enum)
 }
 
 #[proc_macro_derive(GraphQLInputObject, attributes(graphql))]
 pub fn derive_input_object(input: TokenStream) -> TokenStream {
-    let s = input.to_string();
-    let ast = syn::parse_derive_input(&s).unwrap();
-    let gen = derive_input_object::impl_input_object(&ast);
-    gen.parse().unwrap()
+    expand(input, derive_input_object::impl_input_object)
 }
 
 #[proc_macro_derive(GraphQLObject, attributes(graphql))]
 pub fn derive_object(input: TokenStream) -> TokenStream {
+    expand(input, derive_object::impl_object)
+}
+
+/// Run a derive, reporting any errors as compile errors rather than panics
+fn expand(
+    input: TokenStream,
+    derive: fn(&syn::DeriveInput) -> diagnostic::Result<Tokens>,
+) -> TokenStream {
     let s = input.to_string();
-    let ast = syn::parse_derive_input(&s).unwrap();
-    let gen = derive_object::impl_object(&ast);
-    gen.parse().unwrap()
+    let gen = syn::parse_derive_input(&s)
+        .map_err(|e| Error::new(Location::CallSite, e))
+        .and_then(|ast| derive(&ast));
+
+    match gen {
+        Ok(gen) => gen.parse().unwrap(),
+        Err(err) => err.into_compile_error(input),
+    }
 }
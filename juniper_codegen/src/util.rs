@@ -1,4 +1,11 @@
+use std::collections::HashMap;
+
 use syn::*;
+use quote::Tokens;
+
+use diagnostic::{Error, Location, Result};
+
+pub use juniper::RenameRule;
 
 pub fn get_graphl_attr(attrs: &Vec<Attribute>) -> Option<&Vec<NestedMetaItem>> {
     for attr in attrs {
@@ -12,71 +19,244 @@ pub fn get_graphl_attr(attrs: &Vec<Attribute>) -> Option<&Vec<NestedMetaItem>> {
     None
 }
 
-pub fn keyed_item_value(item: &NestedMetaItem, name: &str, must_be_string: bool) -> Option<String> {
+pub fn keyed_item_value(
+    item: &NestedMetaItem,
+    name: &str,
+    must_be_string: bool,
+) -> Result<Option<String>> {
     let item = match item {
         &NestedMetaItem::MetaItem(ref item) => item,
         _ => {
-            return None;
+            return Ok(None);
         }
     };
     let lit = match item {
         &MetaItem::NameValue(ref ident, ref lit) => if ident == name {
             lit
         } else {
-            return None;
+            return Ok(None);
         },
         _ => {
-            return None;
+            return Ok(None);
         }
     };
     match lit {
-        &Lit::Str(ref val, _) => Some(val.clone()),
+        &Lit::Str(ref val, _) => Ok(Some(val.clone())),
         _ => if must_be_string {
-            panic!(format!(
-                "Invalid format for attribute \"{:?}\": expected a string",
-                item
-            ));
+            Err(Error::new(
+                Location::Attr(name.to_owned()),
+                format!("Invalid format for attribute \"{}\": expected a string", name),
+            ))
         } else {
-            None
+            Ok(None)
         },
     }
 }
 
-// Note: duplicated from juniper crate!
-#[doc(hidden)]
-pub fn to_camel_case(s: &str) -> String {
-    let mut dest = String::new();
-
-    for (i, part) in s.split('_').enumerate() {
-        if i > 0 && part.len() == 1 {
-            dest.push_str(&part.to_uppercase());
-        } else if i > 0 && part.len() > 1 {
-            let first = part.chars()
-                .next()
-                .unwrap()
-                .to_uppercase()
-                .collect::<String>();
-            let second = &part[1..];
-
-            dest.push_str(&first);
-            dest.push_str(second);
-        } else if i == 0 {
-            dest.push_str(part);
+/// Build the error for an attribute item not supported by a derive
+pub fn unknown_attr(item: &NestedMetaItem, derive: &str) -> Error {
+    match item {
+        &NestedMetaItem::MetaItem(ref item) => Error::new(
+            Location::Attr(item.name().to_owned()),
+            format!("Unknown attribute for #[derive({})]: {}", derive, item.name()),
+        ),
+        &NestedMetaItem::Literal(_) => Error::new(
+            Location::Attr("graphql".to_owned()),
+            format!("Unexpected literal in #[derive({})] attribute", derive),
+        ),
+    }
+}
+
+/// Check that `name` is a valid GraphQL name
+///
+/// Names must match `/[_A-Za-z][_0-9A-Za-z]*/`, and names starting with two
+/// underscores are reserved for the introspection system.
+pub fn validate_name(name: &str) -> ::std::result::Result<(), String> {
+    let valid = name.chars().enumerate().all(|(i, c)| {
+        c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit())
+    });
+
+    if name.is_empty() || !valid {
+        Err(format!(
+            "\"{}\" is not a valid GraphQL name, names must match /[_A-Za-z][_0-9A-Za-z]*/",
+            name
+        ))
+    } else if name.starts_with("__") {
+        Err(format!(
+            "\"{}\" is not a valid GraphQL name, names starting with \"__\" are reserved \
+             for introspection",
+            name
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Pick the GraphQL name of a field or enum variant
+///
+/// Uses the custom `name` if given and `default` otherwise. The name is
+/// checked for validity and against the names of the other items of the
+/// type, collected in `seen`.
+pub fn item_name(
+    ident: &str,
+    name: Option<String>,
+    default: String,
+    seen: &mut HashMap<String, String>,
+) -> Result<String> {
+    let location = match name {
+        Some(_) => Location::ItemAttr(ident.to_owned(), "name".to_owned()),
+        None => Location::Item(ident.to_owned()),
+    };
+    let name = name.unwrap_or(default);
+
+    try!(validate_name(&name).map_err(|msg| Error::new(location.clone(), msg)));
+
+    if let Some(other) = seen.insert(name.clone(), ident.to_owned()) {
+        return Err(Error::new(
+            location,
+            format!("Duplicate GraphQL name \"{}\", already used by `{}`", name, other),
+        ));
+    }
+
+    Ok(name)
+}
+
+/// Add trait bounds to every type parameter of the deriving type
+///
+/// Each bound is a string like `::juniper::GraphQLType<TypeInfo = ()>`.
+pub fn add_type_param_bounds(generics: &Generics, bounds: &[&str]) -> Generics {
+    let mut generics = generics.clone();
+
+    for param in &mut generics.ty_params {
+        for bound in bounds {
+            param
+                .bounds
+                .push(parse_ty_param_bound(bound).expect("Invalid generated type bound"));
+        }
+    }
+
+    generics
+}
+
+/// Build an expression evaluating to the GraphQL name of the deriving type
+///
+/// Names can refer to the type parameters of the deriving type using
+/// placeholders, e.g. `Page{T}`. Each placeholder is replaced with the
+/// GraphQL name of the type the parameter is instantiated with, giving
+/// every instantiation its own name. Generic types without an explicit name
+/// get one placeholder per type parameter appended to their Rust name.
+pub fn type_name_expr(name: Option<&str>, ast: &DeriveInput) -> Result<Tokens> {
+    let location = match name {
+        Some(_) => Location::Attr("name".to_owned()),
+        None => Location::TypeName,
+    };
+    let name = match name {
+        Some(name) => name.to_owned(),
+        None => {
+            let mut name = ast.ident.to_string();
+            for param in &ast.generics.ty_params {
+                name.push_str(&format!("{{{}}}", param.ident));
+            }
+            name
         }
+    };
+
+    let mut format_str = String::new();
+    let mut args = Vec::<Tokens>::new();
+    let mut rest = &name[..];
+
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => {
+                return Err(Error::new(
+                    location,
+                    format!(
+                        "Unterminated type parameter placeholder in GraphQL name \"{}\"",
+                        name
+                    ),
+                ))
+            }
+        };
+
+        let param_name = &rest[start + 1..end];
+        let param = match ast.generics.ty_params.iter().find(|p| p.ident == param_name) {
+            Some(param) => &param.ident,
+            None => {
+                return Err(Error::new(
+                    location,
+                    format!(
+                        "Unknown type parameter {{{}}} in GraphQL name \"{}\"",
+                        param_name,
+                        name
+                    ),
+                ))
+            }
+        };
+
+        format_str.push_str(&rest[..start]);
+        format_str.push_str("{}");
+        args.push(quote!{
+            <#param as ::juniper::GraphQLType>::type_param_name(&())
+                .expect("Type parameters of generic types must have a GraphQL name")
+        });
+
+        rest = &rest[end + 1..];
     }
 
-    dest
+    format_str.push_str(rest);
+
+    // Placeholders are always replaced with valid names, so only the text
+    // around them needs checking.
+    try!(validate_name(&format_str.replace("{}", "T")).map_err(|msg| Error::new(location, msg)));
+
+    if args.is_empty() {
+        return Ok(quote!{ #name });
+    }
+
+    Ok(quote!{
+        ::juniper::cached_type_name::<Self, _>(|| format!(#format_str, #(#args),*))
+    })
+}
+
+/// Parse the naming policy given in a `rename_all` attribute
+pub fn rename_rule_from_name(rule: &str) -> Result<RenameRule> {
+    RenameRule::from_name(rule).ok_or_else(|| {
+        Error::new(
+            Location::Attr("rename_all".to_owned()),
+            format!(
+                "Unknown rename_all rule \"{}\", expected one of \"none\", \"camelCase\", \
+                 \"snake_case\", or \"SCREAMING_SNAKE_CASE\"",
+                rule
+            ),
+        )
+    })
+}
+
+#[test]
+fn test_rename_rule() {
+    let rule = |name| rename_rule_from_name(name).unwrap();
+
+    assert_eq!(rule("none").apply("user_name"), "user_name");
+    assert_eq!(rule("camelCase").apply("user_name"), "userName");
+    assert_eq!(rule("camelCase").apply("HTTPServer"), "HTTPServer");
+    assert_eq!(rule("camelCase").apply_to_variant("NewHope"), "newHope");
+    assert_eq!(rule("none").apply_to_variant("NewHope"), "NewHope");
+    assert_eq!(rule("snake_case").apply("NewHope"), "new_hope");
+    assert_eq!(rule("snake_case").apply("HTTPServer"), "http_server");
+    assert_eq!(rule("SCREAMING_SNAKE_CASE").apply("user_name"), "USER_NAME");
+    assert_eq!(rule("SCREAMING_SNAKE_CASE").apply("NewHope"), "NEW_HOPE");
+    assert!(rename_rule_from_name("kebab-case").is_err());
 }
 
 #[test]
-fn test_to_camel_case() {
-    assert_eq!(&to_camel_case("test")[..], "test");
-    assert_eq!(&to_camel_case("_test")[..], "Test");
-    assert_eq!(&to_camel_case("first_second")[..], "firstSecond");
-    assert_eq!(&to_camel_case("first_")[..], "first");
-    assert_eq!(&to_camel_case("a_b_c")[..], "aBC");
-    assert_eq!(&to_camel_case("a_bc")[..], "aBc");
-    assert_eq!(&to_camel_case("a_b")[..], "aB");
-    assert_eq!(&to_camel_case("a")[..], "a");
-    assert_eq!(&to_camel_case("")[..], "");
+fn test_validate_name() {
+    assert!(validate_name("name").is_ok());
+    assert!(validate_name("_name2").is_ok());
+    assert!(validate_name("SCREAMING_NAME").is_ok());
+    assert!(validate_name("").is_err());
+    assert!(validate_name("2name").is_err());
+    assert!(validate_name("my-name").is_err());
+    assert!(validate_name("näme").is_err());
+    assert!(validate_name("__name").is_err());
 }
@@ -0,0 +1,389 @@
+/*!
+
+[Juniper] handlers for the [Hyper] HTTP server
+
+The `graphql` function handles GraphQL requests sent as GET requests with a
+query string, or as POST requests with an `application/json`,
+`application/graphql`, or `multipart/form-data` body. `graphiql` and
+`playground` serve pages to explore the schema from a browser.
+
+Hyper's handlers are shared between threads, so the schema is kept in an
+`Arc`, while the context can be created per request:
+
+```rust,no_run
+# extern crate hyper;
+# #[macro_use] extern crate juniper;
+# extern crate juniper_hyper;
+use std::sync::Arc;
+
+use hyper::server::{Request, Response, Server};
+use juniper::{EmptyMutation, RootNode};
+
+# struct Database;
+# impl juniper::Context for Database {}
+struct Query;
+
+graphql_object!(Query: Database |&self| {
+    field api_version() -> &str { "1.0" }
+});
+
+# fn main() {
+let schema = Arc::new(RootNode::new(Query, EmptyMutation::<Database>::new()));
+
+Server::http("127.0.0.1:8080").unwrap().handle(move |req: Request, res: Response| {
+    if req.uri.to_string().starts_with("/graphiql") {
+        juniper_hyper::graphiql("/graphql", res)
+    } else {
+        juniper_hyper::graphql(&schema, &Database, req, res)
+    }
+}).unwrap();
+# }
+```
+
+[Juniper]: https://github.com/graphql-rust/juniper
+[Hyper]: https://hyper.rs
+
+*/
+
+extern crate hyper;
+extern crate juniper;
+extern crate serde;
+extern crate serde_json;
+
+use std::io::Read;
+
+use serde::Serialize;
+
+use hyper::header::{Allow, Connection, ContentType};
+use hyper::method::Method;
+use hyper::server::{Request, Response};
+use hyper::status::StatusCode;
+use hyper::uri::RequestUri;
+
+use juniper::{GraphQLType, RootNode};
+use juniper::http::{GraphQLRequest, GraphQLRequestError, UploadLimits};
+
+/// Handle a GraphQL request, executing it against the schema
+///
+/// The status code and content type of the response depend on the `Accept`
+/// header of the request, see `GraphQLResponse::status_and_content_type`.
+/// Requests with methods other than GET and POST are refused with `405 Method
+/// Not Allowed`. POST bodies are limited by the default `UploadLimits`.
+pub fn graphql<QueryT, MutationT, CtxT>(
+    root_node: &RootNode<QueryT, MutationT>,
+    context: &CtxT,
+    req: Request,
+    res: Response,
+) where
+    QueryT: GraphQLType<Context = CtxT>,
+    MutationT: GraphQLType<Context = CtxT>,
+{
+    graphql_with_limits(root_node, context, &UploadLimits::default(), req, res)
+}
+
+/// Handle a GraphQL request like `graphql`, with custom limits for the body
+///
+/// POST bodies larger than `limits.max_request_size` are refused with `413
+/// Payload Too Large` without reading the rest of the body.
+pub fn graphql_with_limits<QueryT, MutationT, CtxT>(
+    root_node: &RootNode<QueryT, MutationT>,
+    context: &CtxT,
+    limits: &UploadLimits,
+    mut req: Request,
+    mut res: Response,
+) where
+    QueryT: GraphQLType<Context = CtxT>,
+    MutationT: GraphQLType<Context = CtxT>,
+{
+    let request = match req.method {
+        Method::Get => {
+            let query_string = match req.uri {
+                RequestUri::AbsolutePath(ref path) => path.splitn(2, '?').nth(1).unwrap_or(""),
+                _ => "",
+            };
+
+            GraphQLRequest::from_query_string(query_string)
+        }
+        Method::Post => {
+            let content_type = header_value(&req, "content-type");
+            let mut body = Vec::new();
+
+            // Reading one byte more than allowed tells whether the body is
+            // too large without reading all of it
+            let max_size = limits.max_request_size as u64;
+            if let Err(e) = req.by_ref().take(max_size + 1).read_to_end(&mut body) {
+                let error = GraphQLRequestError::UnreadableBody(e.to_string());
+                send_json(res, error.status_code(), "application/json", &error);
+                return;
+            }
+
+            if body.len() as u64 > max_size {
+                // The rest of the body is left unread, so the connection
+                // can't be reused
+                res.headers_mut().set(Connection::close());
+                let error = GraphQLRequestError::RequestTooLarge;
+                send_json(res, error.status_code(), "application/json", &error);
+                return;
+            }
+
+            match content_type {
+                Some(ref t) if t.to_ascii_lowercase().starts_with("multipart/form-data") => {
+                    GraphQLRequest::from_multipart_body(t, &body, limits)
+                }
+                ref t => match String::from_utf8(body) {
+                    Ok(body) => GraphQLRequest::from_post_body(t.as_ref().map(|t| t.as_str()), &body),
+                    Err(_) => Err(GraphQLRequestError::InvalidBodyEncoding),
+                },
+            }
+        }
+        _ => {
+            *res.status_mut() = StatusCode::MethodNotAllowed;
+            res.headers_mut().set(Allow(vec![Method::Get, Method::Post]));
+            send(res, b"");
+            return;
+        }
+    };
+
+    match request {
+        Ok(request) => {
+            let accept = header_value(&req, "accept");
+            let response = request.execute(root_node, context);
+            let (status_code, content_type) =
+                response.status_and_content_type(accept.as_ref().map(|a| a.as_str()));
+
+            send_json(res, status_code, content_type, &response);
+        }
+        Err(error) => send_json(res, error.status_code(), "application/json", &error),
+    }
+}
+
+/// Serve a GraphiQL page sending queries to the endpoint
+pub fn graphiql(graphql_endpoint: &str, res: Response) {
+    send_html(res, juniper::graphiql::graphiql_source(graphql_endpoint));
+}
+
+/// Serve a GraphQL Playground page sending queries to the endpoint
+pub fn playground(graphql_endpoint: &str, res: Response) {
+    send_html(res, juniper::graphiql::playground_source(graphql_endpoint, None));
+}
+
+fn header_value(req: &Request, name: &str) -> Option<String> {
+    req.headers
+        .get_raw(name)
+        .and_then(|values| values.first())
+        .map(|value| String::from_utf8_lossy(value).into_owned())
+}
+
+fn send_json<T: Serialize>(
+    mut res: Response,
+    status_code: u16,
+    content_type: &str,
+    body: &T,
+) {
+    *res.status_mut() = StatusCode::from_u16(status_code);
+    res.headers_mut()
+        .set_raw("content-type", vec![content_type.as_bytes().to_vec()]);
+
+    let body = serde_json::to_vec(body).expect("Response could not be serialized");
+    send(res, &body);
+}
+
+fn send_html(mut res: Response, source: String) {
+    res.headers_mut().set(ContentType::html());
+    send(res, source.as_bytes());
+}
+
+fn send(res: Response, body: &[u8]) {
+    // The client has disconnected if the body can't be written, so there's
+    // nobody to report the error to
+    let _ = res.send(body);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use hyper::client::{Client, Response as ClientResponse};
+    use hyper::header::ContentType;
+    use hyper::server::{Listening, Request, Response, Server};
+
+    use juniper::{EmptyMutation, RootNode};
+    use juniper::http::UploadLimits;
+    use juniper::http::tests as http_tests;
+    use juniper::tests::model::Database;
+
+    struct TestHyperIntegration {
+        client: Client,
+        base_url: String,
+    }
+
+    impl TestHyperIntegration {
+        fn start() -> (Listening, TestHyperIntegration) {
+            let listening = Server::http("127.0.0.1:0")
+                .expect("Could not bind to localhost")
+                .handle(|req: Request, res: Response| {
+                    let database = Database::new();
+                    let schema = RootNode::new(&database, EmptyMutation::<Database>::new());
+
+                    if req.uri.to_string().starts_with("/graphiql") {
+                        super::graphiql("/", res)
+                    } else if req.uri.to_string().starts_with("/limited") {
+                        let limits = UploadLimits {
+                            max_request_size: 64,
+                            ..UploadLimits::default()
+                        };
+                        super::graphql_with_limits(&schema, &database, &limits, req, res)
+                    } else {
+                        super::graphql(&schema, &database, req, res)
+                    }
+                })
+                .expect("Could not start the server");
+
+            let base_url = format!("http://{}", listening.socket);
+
+            (
+                listening,
+                TestHyperIntegration {
+                    client: Client::new(),
+                    base_url: base_url,
+                },
+            )
+        }
+
+        fn post_with_content_type(
+            &self,
+            url: &str,
+            body: &str,
+            content_type: &str,
+        ) -> http_tests::TestResponse {
+            make_test_response(
+                self.client
+                    .post(&format!("{}{}", self.base_url, url))
+                    .header(ContentType(content_type.parse().unwrap()))
+                    .body(body)
+                    .send()
+                    .expect("Request failed"),
+            )
+        }
+    }
+
+    impl http_tests::HTTPIntegration for TestHyperIntegration {
+        fn get(&self, url: &str) -> http_tests::TestResponse {
+            make_test_response(
+                self.client
+                    .get(&format!("{}{}", self.base_url, url))
+                    .send()
+                    .expect("Request failed"),
+            )
+        }
+
+        fn post(&self, url: &str, body: &str) -> http_tests::TestResponse {
+            self.post_with_content_type(url, body, "application/json")
+        }
+
+        fn post_graphql(&self, url: &str, body: &str) -> Option<http_tests::TestResponse> {
+            Some(self.post_with_content_type(url, body, "application/graphql"))
+        }
+    }
+
+    fn make_test_response(mut response: ClientResponse) -> http_tests::TestResponse {
+        let mut body = String::new();
+        response
+            .read_to_string(&mut body)
+            .expect("Could not read the response body");
+
+        let content_type = response
+            .headers
+            .get::<ContentType>()
+            .map(|t| t.to_string())
+            .expect("No content type header from the server");
+
+        http_tests::TestResponse {
+            status_code: response.status.to_u16() as i32,
+            body: Some(body),
+            content_type: content_type,
+        }
+    }
+
+    #[test]
+    fn test_hyper_integration() {
+        let (mut listening, integration) = TestHyperIntegration::start();
+
+        http_tests::run_http_test_suite(&integration);
+
+        listening.close().unwrap();
+    }
+
+    #[test]
+    fn test_hyper_protocol_details() {
+        let (mut listening, integration) = TestHyperIntegration::start();
+
+        let response = integration
+            .client
+            .delete(&integration.base_url)
+            .send()
+            .expect("Request failed");
+        assert_eq!(response.status.to_u16(), 405);
+
+        let response = integration
+            .client
+            .get(&format!("{}/?query={{unknown}}", integration.base_url))
+            .header(::hyper::header::Accept(vec![
+                "application/graphql-response+json".parse().unwrap(),
+            ]))
+            .send()
+            .expect("Request failed");
+        let response = make_test_response(response);
+        assert_eq!(response.status_code, 400);
+        assert_eq!(response.content_type, "application/graphql-response+json");
+
+        let response = make_test_response(
+            integration
+                .client
+                .get(&format!("{}/graphiql", integration.base_url))
+                .send()
+                .expect("Request failed"),
+        );
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "text/html; charset=utf-8");
+        assert!(response.body.unwrap().contains("GraphiQL.createFetcher"));
+
+        listening.close().unwrap();
+    }
+
+    #[test]
+    fn test_hyper_body_limits() {
+        let (mut listening, integration) = TestHyperIntegration::start();
+
+        let query = r#"{"query": "{ hero { name } }"}"#;
+        let response = integration.post_with_content_type("/limited", query, "application/json");
+        assert_eq!(response.status_code, 200);
+
+        let padded = format!("{}{}", query, " ".repeat(64));
+        let response = integration.post_with_content_type("/limited", &padded, "application/json");
+        assert_eq!(response.status_code, 413);
+        assert_eq!(response.content_type, "application/json");
+        assert_eq!(
+            response.body.as_ref().map(|b| &b[..]),
+            Some(r#"{"errors":[{"message":"Request body is too large"}]}"#)
+        );
+
+        let response = make_test_response(
+            integration
+                .client
+                .post(&integration.base_url)
+                .header(ContentType("application/json".parse().unwrap()))
+                .body(&b"{\"query\": \"\xff\"}"[..])
+                .send()
+                .expect("Request failed"),
+        );
+        assert_eq!(response.status_code, 400);
+        assert_eq!(response.content_type, "application/json");
+        assert_eq!(
+            response.body.as_ref().map(|b| &b[..]),
+            Some(r#"{"errors":[{"message":"The request body must be UTF-8"}]}"#)
+        );
+
+        listening.close().unwrap();
+    }
+}
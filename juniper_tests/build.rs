@@ -0,0 +1,29 @@
+extern crate juniper;
+extern crate juniper_client;
+
+use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use juniper::tests::model::Database;
+use juniper::{EmptyMutation, SchemaType};
+use juniper_client::generate_client;
+
+fn main() {
+    let source_path = "src/client/star_wars.graphql";
+    println!("cargo:rerun-if-changed={}", source_path);
+
+    let mut source = String::new();
+    File::open(source_path)
+        .and_then(|mut f| f.read_to_string(&mut source))
+        .unwrap();
+
+    let schema = SchemaType::new::<Database, EmptyMutation<Database>>(&(), &());
+    let code = generate_client(&schema, &source).unwrap_or_else(|e| panic!("{}", e));
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("star_wars_client.rs");
+    File::create(out_path)
+        .and_then(|mut f| f.write_all(code.as_bytes()))
+        .unwrap();
+}
@@ -0,0 +1,74 @@
+use juniper::http::GraphQLRequest;
+use juniper::tests::model::Database;
+use juniper::{EmptyMutation, RootNode};
+use serde_json;
+
+include!(concat!(env!("OUT_DIR"), "/star_wars_client.rs"));
+
+fn send<Req, Resp>(request: &Req) -> Resp
+where
+    Req: ::serde::Serialize,
+    Resp: ::serde::de::DeserializeOwned,
+{
+    let database = Database::new();
+    let schema = RootNode::new(&database, EmptyMutation::<Database>::new());
+
+    let request: GraphQLRequest =
+        serde_json::from_str(&serde_json::to_string(request).unwrap()).unwrap();
+    let response = request.execute(&schema, &database);
+
+    serde_json::from_str(&serde_json::to_string(&response).unwrap()).unwrap()
+}
+
+#[test]
+fn round_trips_through_the_schema() {
+    let response: hero_query::Response = send(&hero_query::Request::new(hero_query::Variables {
+        episode: Some(hero_query::Episode::Empire),
+        with_friends: true,
+    }));
+
+    assert_eq!(response.errors, vec![]);
+    assert_eq!(
+        response.data,
+        Some(hero_query::ResponseData {
+            hero: Some(hero_query::Hero {
+                typename: "Human".to_owned(),
+                name: Some("Luke Skywalker".to_owned()),
+                friends: Some(
+                    ["1002", "1003", "2000", "2001"]
+                        .iter()
+                        .map(|id| hero_query::HeroFriends { id: (*id).to_owned() })
+                        .collect(),
+                ),
+                primary_function: None,
+                appears_in: vec![
+                    hero_query::Episode::NewHope,
+                    hero_query::Episode::Empire,
+                    hero_query::Episode::Jedi,
+                ],
+            }),
+        })
+    );
+}
+
+#[test]
+fn leaves_out_conditional_fields() {
+    let response: hero_query::Response = send(&hero_query::Request::new(hero_query::Variables {
+        episode: None,
+        with_friends: false,
+    }));
+
+    let hero = response.data.unwrap().hero.unwrap();
+    assert_eq!(hero.typename, "Droid");
+    assert_eq!(hero.friends, None);
+    assert_eq!(hero.primary_function, Some("Astromech".to_owned()));
+}
+
+#[test]
+fn reads_nullable_results() {
+    let response: human::Response = send(&human::Request::new(human::Variables {
+        id: "nobody".to_owned(),
+    }));
+
+    assert_eq!(response.data, Some(human::ResponseData { human: None }));
+}
@@ -4,6 +4,7 @@ use fnv::FnvHashMap;
 #[cfg(test)]
 use juniper::{self, FromInputValue, GraphQLType, InputValue, ToInputValue};
 
+#[allow(dead_code)]
 #[derive(GraphQLEnum, Debug, PartialEq)]
 #[graphql(name = "Some", description = "enum descr")]
 enum SomeEnum {
@@ -13,6 +14,15 @@ enum SomeEnum {
     Full,
 }
 
+#[allow(dead_code)]
+#[derive(GraphQLEnum, Debug, PartialEq)]
+#[graphql(rename_all = "snake_case")]
+enum Ordering {
+    NewestFirst,
+    #[graphql(name = "OLDEST")]
+    OldestFirst,
+}
+
 #[test]
 fn test_derived_enum() {
     // Ensure that rename works.
@@ -39,3 +49,18 @@ fn test_derived_enum() {
         Some(SomeEnum::Full)
     );
 }
+
+#[test]
+fn test_derived_enum_rename_all() {
+    assert_eq!(Ordering::NewestFirst.to_input_value(), InputValue::String("newest_first".into()));
+    assert_eq!(
+        FromInputValue::from_input_value(&InputValue::String("newest_first".into())),
+        Some(Ordering::NewestFirst)
+    );
+
+    assert_eq!(Ordering::OldestFirst.to_input_value(), InputValue::String("OLDEST".into()));
+    assert_eq!(
+        FromInputValue::from_input_value(&InputValue::String("OLDEST".into())),
+        Some(Ordering::OldestFirst)
+    );
+}
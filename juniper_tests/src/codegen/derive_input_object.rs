@@ -2,8 +2,12 @@
 use fnv::FnvHashMap;
 
 #[cfg(test)]
-use juniper::{self, FromInputValue, GraphQLType, ToInputValue};
+use juniper::{self, execute, EmptyMutation, FromInputValue, GraphQLError, GraphQLType,
+              InputValue, RootNode, ToInputValue, Value, Variables};
+#[cfg(test)]
+use juniper::meta::MetaType;
 
+#[allow(dead_code)]
 #[derive(GraphQLInputObject, Debug, PartialEq)]
 #[graphql(name = "MyInput", description = "input descr")]
 struct Input {
@@ -12,6 +16,59 @@ struct Input {
     c: i32,
 }
 
+#[allow(dead_code)]
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+#[graphql(name = "RangeOf{T}")]
+struct Range<T> {
+    from: T,
+    to: T,
+}
+
+#[allow(dead_code)]
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+#[graphql(rename_all = "SCREAMING_SNAKE_CASE")]
+struct ScreamingInput {
+    regular_field: String,
+    #[graphql(name = "customName")]
+    c: i32,
+}
+
+#[allow(dead_code)]
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+struct Review {
+    #[graphql(min = "1", max = "5")]
+    stars: i32,
+    #[graphql(description = "A short summary", max_length = "20", pattern = "^[A-Za-z ]*$")]
+    summary: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+#[graphql(description = "Exactly one way to find a user")]
+enum UserBy {
+    ById(i32),
+    ByName(String),
+    #[graphql(name = "email", description = "The primary email address")]
+    ByEmail(String),
+}
+
+#[allow(dead_code)]
+struct Query;
+
+graphql_object!(Query: () |&self| {
+    field user(by: UserBy) -> String {
+        match by {
+            UserBy::ById(id) => format!("id {}", id),
+            UserBy::ByName(name) => format!("name {}", name),
+            UserBy::ByEmail(email) => format!("email {}", email),
+        }
+    }
+
+    field review(review: Review) -> String {
+        format!("{} stars", review.stars)
+    }
+});
+
 #[test]
 fn test_derived_input_object() {
     assert_eq!(Input::name(&()), Some("MyInput"));
@@ -29,3 +86,260 @@ fn test_derived_input_object() {
     let restored: Input = FromInputValue::from_input_value(&obj.to_input_value()).unwrap();
     assert_eq!(obj, restored);
 }
+
+#[test]
+fn test_derived_generic_input_object() {
+    assert_eq!(Range::<i32>::name(&()), Some("RangeOfInt"));
+    assert_eq!(Range::<f64>::name(&()), Some("RangeOfFloat"));
+
+    let mut registry = juniper::Registry::new(FnvHashMap::default());
+    let meta = Range::<i32>::meta(&(), &mut registry);
+    assert_eq!(meta.name(), Some("RangeOfInt"));
+
+    let obj = Range { from: 1, to: 10 };
+    let restored: Range<i32> = FromInputValue::from_input_value(&obj.to_input_value()).unwrap();
+    assert_eq!(obj, restored);
+}
+
+#[test]
+fn test_derived_input_object_rename_all() {
+    let obj = ScreamingInput {
+        regular_field: "a".to_string(),
+        c: 33,
+    };
+
+    assert_eq!(
+        obj.to_input_value(),
+        InputValue::object(
+            vec![
+                ("REGULAR_FIELD", InputValue::string("a")),
+                ("customName", InputValue::int(33)),
+            ].into_iter()
+                .collect()
+        )
+    );
+
+    let restored: ScreamingInput = FromInputValue::from_input_value(&obj.to_input_value()).unwrap();
+    assert_eq!(obj, restored);
+}
+
+#[test]
+fn test_derived_input_object_validators() {
+    let mut registry = juniper::Registry::new(FnvHashMap::default());
+    match Review::meta(&(), &mut registry) {
+        MetaType::InputObject(ref meta) => assert_eq!(
+            meta.input_fields
+                .iter()
+                .map(|f| (f.name.as_str(), f.validators.len()))
+                .collect::<Vec<_>>(),
+            vec![("stars", 2), ("summary", 2)]
+        ),
+        ref meta => panic!("Unexpected meta type: {:?}", meta.name()),
+    }
+
+    let invalid = InputValue::object(vec![("stars", InputValue::string("many"))].into_iter().collect());
+    assert_eq!(Review::from_input_value(&invalid), None);
+    assert_eq!(
+        Review::try_from_input_value(&invalid).unwrap_err().to_string(),
+        r#"In field "stars": Expected "Int", found "many""#
+    );
+
+    let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+    let run = |doc| execute(doc, None, &schema, &Variables::new(), &());
+
+    assert_eq!(
+        run(r#"{ review(review: { stars: 4, summary: "Great read" }) }"#),
+        Ok((
+            Value::object(vec![("review", Value::string("4 stars"))].into_iter().collect()),
+            vec![]
+        ))
+    );
+
+    match run(r#"{ review(review: { stars: 6, summary: "Great read!" }) }"#) {
+        Err(GraphQLError::ValidationError(errors)) => assert_eq!(
+            errors.iter().map(|e| e.message()).collect::<Vec<_>>(),
+            vec![r#"Invalid value for argument "review": In field "stars": Expected a value of at most 5, found 6"#]
+        ),
+        r => panic!("Expected a validation error, found {:?}", r),
+    }
+}
+
+#[test]
+fn test_derived_one_of_input_object() {
+    let mut registry = juniper::Registry::new(FnvHashMap::default());
+    match UserBy::meta(&(), &mut registry) {
+        MetaType::InputObject(ref meta) => {
+            assert!(meta.is_one_of);
+            assert_eq!(
+                meta.input_fields
+                    .iter()
+                    .map(|f| (f.name.as_str(), f.arg_type.to_string()))
+                    .collect::<Vec<_>>(),
+                vec![("byId", "Int".to_owned()), ("byName", "String".to_owned()), ("email", "String".to_owned())]
+            );
+        }
+        ref meta => panic!("Unexpected meta type: {:?}", meta.name()),
+    }
+
+    let obj = UserBy::ByEmail("ada@example.com".to_owned());
+    assert_eq!(
+        obj.to_input_value(),
+        InputValue::object(
+            vec![("email", InputValue::string("ada@example.com"))]
+                .into_iter()
+                .collect()
+        )
+    );
+    let restored: UserBy = FromInputValue::from_input_value(&obj.to_input_value()).unwrap();
+    assert_eq!(obj, restored);
+
+    let two_fields = InputValue::object(
+        vec![("byId", InputValue::int(1)), ("byName", InputValue::string("ada"))]
+            .into_iter()
+            .collect(),
+    );
+    let null_field = InputValue::object(vec![("byId", InputValue::null())].into_iter().collect());
+    assert_eq!(UserBy::from_input_value(&two_fields), None);
+    assert_eq!(UserBy::from_input_value(&null_field), None);
+    assert_eq!(UserBy::from_input_value(&InputValue::object::<String>(Default::default())), None);
+    assert_eq!(
+        UserBy::try_from_input_value(&two_fields).unwrap_err().to_string(),
+        r#"Expected exactly one non-null field for "UserBy""#
+    );
+    assert_eq!(
+        UserBy::try_from_input_value(&InputValue::object(
+            vec![("byId", InputValue::string("one"))].into_iter().collect(),
+        )).unwrap_err()
+            .to_string(),
+        r#"In field "byId": Expected "Int", found "one""#
+    );
+}
+
+#[test]
+fn test_one_of_input_object_in_queries() {
+    let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+    let run = |doc, vars: &Variables| execute(doc, None, &schema, vars, &());
+
+    assert_eq!(
+        run(r#"{ user(by: { byName: "ada" }) }"#, &Variables::new()),
+        Ok((
+            Value::object(vec![("user", Value::string("name ada"))].into_iter().collect()),
+            vec![]
+        ))
+    );
+
+    let vars = vec![("by".to_owned(), InputValue::object(
+        vec![("byId", InputValue::int(7))].into_iter().collect(),
+    ))].into_iter().collect();
+    assert_eq!(
+        run("query ($by: UserBy!) { user(by: $by) }", &vars),
+        Ok((
+            Value::object(vec![("user", Value::string("id 7"))].into_iter().collect()),
+            vec![]
+        ))
+    );
+
+    let messages = |result| match result {
+        Err(GraphQLError::ValidationError(errors)) => errors
+            .iter()
+            .map(|e: &juniper::RuleError| e.message().to_owned())
+            .collect::<Vec<_>>(),
+        r => panic!("Expected a validation error, found {:?}", r),
+    };
+
+    assert_eq!(
+        messages(run(r#"{ user(by: { byId: 1, byName: "ada" }) }"#, &Variables::new())),
+        vec![r#"Invalid value for argument "by": Expected exactly one non-null field for "UserBy""#.to_owned()]
+    );
+    assert_eq!(
+        messages(run(r#"{ user(by: { byId: null }) }"#, &Variables::new())),
+        vec![r#"Invalid value for argument "by": Expected exactly one non-null field for "UserBy""#.to_owned()]
+    );
+
+    let vars = vec![("by".to_owned(), InputValue::object(
+        vec![("byId", InputValue::int(7)), ("email", InputValue::string("ada@example.com"))]
+            .into_iter()
+            .collect(),
+    ))].into_iter().collect();
+    assert_eq!(
+        messages(run("query ($by: UserBy!) { user(by: $by) }", &vars)),
+        vec![
+            r#"Variable "$by" got invalid value. Expected exactly one non-null field for "UserBy"."#
+                .to_owned(),
+        ]
+    );
+
+    let vars = vec![("id".to_owned(), InputValue::int(7))].into_iter().collect();
+    assert_eq!(
+        run("query ($id: Int!) { user(by: { byId: $id }) }", &vars),
+        Ok((
+            Value::object(vec![("user", Value::string("id 7"))].into_iter().collect()),
+            vec![]
+        ))
+    );
+    assert_eq!(
+        messages(run("query ($id: Int) { user(by: { byId: $id }) }", &vars)),
+        vec![
+            r#"Variable "id" of type "Int" used in position expecting type "Int!""#.to_owned(),
+        ]
+    );
+}
+
+#[test]
+fn test_one_of_introspection() {
+    let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+    let doc = r#"
+        {
+            userBy: __type(name: "UserBy") { isOneOf }
+            query: __type(name: "Query") { isOneOf }
+        }"#;
+
+    assert_eq!(
+        execute(doc, None, &schema, &Variables::new(), &()),
+        Ok((
+            Value::object(
+                vec![
+                    ("userBy", Value::object(
+                        vec![("isOneOf", Value::boolean(true))].into_iter().collect(),
+                    )),
+                    ("query", Value::object(
+                        vec![("isOneOf", Value::null())].into_iter().collect(),
+                    )),
+                ].into_iter()
+                    .collect()
+            ),
+            vec![]
+        ))
+    );
+}
+
+#[test]
+fn test_one_of_directive() {
+    let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+    let doc = r#"
+        {
+            __schema {
+                directives { name locations args { name } }
+            }
+        }"#;
+
+    let (result, errors) = execute(doc, None, &schema, &Variables::new(), &()).unwrap();
+    assert_eq!(errors, vec![]);
+
+    let directives = result
+        .as_object_value()
+        .and_then(|o| o.get("__schema"))
+        .and_then(|s| s.as_object_value())
+        .and_then(|s| s.get("directives"))
+        .and_then(|d| d.as_list_value())
+        .unwrap();
+
+    assert!(directives.contains(&Value::object(
+        vec![
+            ("name", Value::string("oneOf")),
+            ("locations", Value::list(vec![Value::string("INPUT_OBJECT")])),
+            ("args", Value::list(vec![])),
+        ].into_iter()
+            .collect(),
+    )));
+}
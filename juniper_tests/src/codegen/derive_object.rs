@@ -4,6 +4,7 @@ use fnv::FnvHashMap;
 #[cfg(test)]
 use juniper::{self, execute, EmptyMutation, GraphQLType, RootNode, Value, Variables};
 
+#[allow(dead_code)]
 #[derive(GraphQLObject, Debug, PartialEq)]
 #[graphql(name = "MyObj", description = "obj descr")]
 struct Obj {
@@ -12,6 +13,36 @@ struct Obj {
     c: i32,
 }
 
+#[allow(dead_code)]
+#[derive(GraphQLObject, Debug, PartialEq)]
+struct Page<T> {
+    items: Vec<T>,
+}
+
+#[allow(dead_code)]
+#[derive(GraphQLObject, Debug, PartialEq)]
+#[graphql(name = "Pair{A}And{B}")]
+struct Pair<A, B> {
+    first: A,
+    second: B,
+}
+
+#[allow(dead_code)]
+#[derive(GraphQLObject, Debug, PartialEq)]
+struct Borrowed<'a> {
+    text: &'a str,
+}
+
+#[allow(dead_code)]
+#[derive(GraphQLObject, Debug, PartialEq)]
+#[graphql(rename_all = "SCREAMING_SNAKE_CASE")]
+struct Screaming {
+    regular_field: bool,
+    #[graphql(name = "customName")]
+    c: i32,
+}
+
+#[allow(dead_code)]
 struct Query;
 
 graphql_object!(Query: () |&self| {
@@ -21,6 +52,26 @@ graphql_object!(Query: () |&self| {
         c: 22,
       }
     }
+
+    field numbers() -> Page<i32> {
+        Page { items: vec![1, 2] }
+    }
+
+    field words() -> Page<String> {
+        Page { items: vec!["a".to_owned()] }
+    }
+
+    field pair() -> Pair<i32, bool> {
+        Pair { first: 1, second: true }
+    }
+
+    field borrowed() -> Borrowed<'static> {
+        Borrowed { text: "static" }
+    }
+
+    field screaming() -> Screaming {
+        Screaming { regular_field: true, c: 22 }
+    }
 });
 
 #[test]
@@ -54,3 +105,110 @@ fn test_derived_object() {
     ].into_iter().collect()),
         vec![])));
 }
+
+#[test]
+fn test_derived_generic_object() {
+    assert_eq!(Page::<i32>::name(&()), Some("PageInt"));
+    assert_eq!(Page::<String>::name(&()), Some("PageString"));
+    assert_eq!(Pair::<i32, bool>::name(&()), Some("PairIntAndBoolean"));
+    assert_eq!(Borrowed::name(&()), Some("Borrowed"));
+
+    // Wrapped type parameters are spelled out in the name
+    assert_eq!(Page::<Vec<i32>>::name(&()), Some("PageListOfInt"));
+    assert_eq!(Page::<Option<String>>::name(&()), Some("PageNullableString"));
+    assert_eq!(
+        Page::<Option<Vec<Option<bool>>>>::name(&()),
+        Some("PageNullableListOfNullableBoolean")
+    );
+    assert_eq!(
+        Pair::<Box<i32>, &[String]>::name(&()),
+        Some("PairIntAndListOfString")
+    );
+
+    let doc = r#"
+        {
+            numbers { items }
+            words { items }
+            pair { first second }
+            borrowed { text }
+        }"#;
+
+    let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+
+    assert_eq!(
+        execute(doc, None, &schema, &Variables::new(), &()),
+        Ok((
+            Value::object(
+                vec![
+                    (
+                        "numbers",
+                        Value::object(
+                            vec![("items", Value::list(vec![Value::int(1), Value::int(2)]))]
+                                .into_iter()
+                                .collect(),
+                        ),
+                    ),
+                    (
+                        "words",
+                        Value::object(
+                            vec![("items", Value::list(vec![Value::string("a")]))]
+                                .into_iter()
+                                .collect(),
+                        ),
+                    ),
+                    (
+                        "pair",
+                        Value::object(
+                            vec![("first", Value::int(1)), ("second", Value::boolean(true))]
+                                .into_iter()
+                                .collect(),
+                        ),
+                    ),
+                    (
+                        "borrowed",
+                        Value::object(
+                            vec![("text", Value::string("static"))].into_iter().collect(),
+                        ),
+                    ),
+                ].into_iter()
+                    .collect()
+            ),
+            vec![]
+        ))
+    );
+}
+
+#[test]
+fn test_derived_object_rename_all() {
+    let doc = r#"
+        {
+            screaming {
+                REGULAR_FIELD
+                customName
+            }
+        }"#;
+
+    let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+
+    assert_eq!(
+        execute(doc, None, &schema, &Variables::new(), &()),
+        Ok((
+            Value::object(
+                vec![
+                    (
+                        "screaming",
+                        Value::object(
+                            vec![
+                                ("REGULAR_FIELD", Value::boolean(true)),
+                                ("customName", Value::int(22)),
+                            ].into_iter()
+                                .collect(),
+                        ),
+                    ),
+                ].into_iter()
+                    .collect()
+            ),
+            vec![]
+        ))
+    );
+}
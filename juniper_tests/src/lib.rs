@@ -6,5 +6,12 @@ extern crate serde_json;
 
 #[cfg(test)]
 extern crate fnv;
+#[cfg(test)]
+extern crate serde;
+#[cfg(test)]
+#[macro_use]
+extern crate serde_derive;
 
 mod codegen;
+#[cfg(test)]
+mod client;
@@ -0,0 +1,7 @@
+extern crate trybuild;
+
+#[test]
+fn codegen_compile_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/fail/*.rs");
+}
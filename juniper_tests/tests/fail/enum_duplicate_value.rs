@@ -0,0 +1,13 @@
+extern crate juniper;
+#[macro_use]
+extern crate juniper_codegen;
+
+#[derive(GraphQLEnum)]
+#[graphql(rename_all = "snake_case")]
+enum Ordering {
+    Newest,
+    #[graphql(name = "newest")]
+    Latest,
+}
+
+fn main() {}
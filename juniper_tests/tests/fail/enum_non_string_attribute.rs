@@ -0,0 +1,11 @@
+extern crate juniper;
+#[macro_use]
+extern crate juniper_codegen;
+
+#[derive(GraphQLEnum)]
+enum Episode {
+    #[graphql(description = 4)]
+    NewHope,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate juniper;
+#[macro_use]
+extern crate juniper_codegen;
+
+#[derive(GraphQLEnum)]
+#[graphql(description = "Episodes", rename_all = "kebab-case")]
+enum Episode {
+    NewHope,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate juniper;
+#[macro_use]
+extern crate juniper_codegen;
+
+#[derive(GraphQLEnum)]
+enum Episode {
+    NewHope,
+    Empire(i32),
+}
+
+fn main() {}
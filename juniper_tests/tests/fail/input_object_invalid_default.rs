@@ -0,0 +1,11 @@
+extern crate juniper;
+#[macro_use]
+extern crate juniper_codegen;
+
+#[derive(GraphQLInputObject)]
+struct Input {
+    #[graphql(default = "(1")]
+    count: i32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate juniper;
+#[macro_use]
+extern crate juniper_codegen;
+
+#[derive(GraphQLInputObject)]
+struct Input {
+    #[graphql(max_length = "ten")]
+    name: String,
+}
+
+fn main() {}
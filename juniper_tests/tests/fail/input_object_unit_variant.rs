@@ -0,0 +1,11 @@
+extern crate juniper;
+#[macro_use]
+extern crate juniper_codegen;
+
+#[derive(GraphQLInputObject)]
+enum Input {
+    First,
+    Second,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate juniper;
+
+struct Query;
+
+graphql_object!(Query: () |&self| {
+    rename_all: "kebab-case"
+
+    field user_name() -> &str { "Bob" }
+});
+
+fn main() {}
@@ -0,0 +1,12 @@
+extern crate juniper;
+#[macro_use]
+extern crate juniper_codegen;
+
+#[derive(GraphQLObject)]
+struct Object {
+    user_name: String,
+    #[graphql(name = "userName")]
+    name: String,
+}
+
+fn main() {}
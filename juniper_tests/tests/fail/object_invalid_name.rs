@@ -0,0 +1,11 @@
+extern crate juniper;
+#[macro_use]
+extern crate juniper_codegen;
+
+#[derive(GraphQLObject)]
+#[graphql(name = "My-Object")]
+struct Object {
+    field: i32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate juniper;
+#[macro_use]
+extern crate juniper_codegen;
+
+#[derive(GraphQLObject)]
+struct Object {
+    #[graphql(name = "__typename")]
+    type_name: String,
+}
+
+fn main() {}
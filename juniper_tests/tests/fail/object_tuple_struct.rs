@@ -0,0 +1,8 @@
+extern crate juniper;
+#[macro_use]
+extern crate juniper_codegen;
+
+#[derive(GraphQLObject)]
+struct Object(i32, String);
+
+fn main() {}
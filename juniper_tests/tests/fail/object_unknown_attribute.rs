@@ -0,0 +1,12 @@
+extern crate juniper;
+#[macro_use]
+extern crate juniper_codegen;
+
+#[derive(GraphQLObject)]
+struct Object {
+    regular: bool,
+    #[graphql(name = "renamed", descripton = "typo")]
+    field: i32,
+}
+
+fn main() {}